@@ -1,45 +1,162 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    io::Write,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
+use chrono::Local;
 use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
     layout::{Constraint, Layout, Rect},
     prelude::Backend,
     style::{Modifier, Style, Stylize},
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, Padding, Paragraph},
     Frame, Terminal,
 };
-use tokio::spawn;
+use tokio::{spawn, task::spawn_blocking};
+use tui_input::{backend::crossterm::EventHandler, Input};
 
 use crate::{
+    audit::{AuditEntry, AuditLog},
     client::Client,
     color::ColorTheme,
-    config::Config,
-    data::{Item, Table, TableDescription, TableInsight},
+    config::{fill_template, Config, KeyTemplate, TableWriteMode},
+    data::{
+        compare_items, parse_key, parse_raw_json_item, to_key_attributes, write_requests_summary,
+        AccountLimits, Attribute, Item, ItemsSource, LoadStats, LoadedItems, QueryOptions,
+        RegionTable, ScanEstimate, ScanOptions, Table, TableDescription, TableInsight,
+        TablePermissions, WriteOptions, WriteRequest,
+    },
     error::{AppError, AppResult},
-    event::{AppEvent, Receiver, Sender, UserEvent, UserEventMapper},
-    handle_user_events,
-    help::{prune_spans_to_fit_width, Spans},
-    view::{View, ViewStack},
-    widget::LoadingDialog,
+    event::{
+        key_event_to_string, AppEvent, PromptCallback, Receiver, Sender, UserEvent, UserEventMapper,
+    },
+    external::{edit_in_editor, open_in_pager, pipe_to_command, with_suspended_terminal},
+    handle_user_events, handle_user_events_with_default,
+    help::{
+        build_short_help_spans, prune_spans_to_fit_width, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    profile::Profile,
+    state::{SavedFilter, SavedQuery, State, TableSizeSnapshot, TableViewPreferences},
+    util::{
+        format_count, format_duration, parse_environment, paste_to_input, truncate_to_width,
+        DisplayTimeZone,
+    },
+    validation::{ItemSchema, ValidationReport},
+    view::{SideBySidePane, View, ViewStack},
+    widget::{InspectorPanel, LoadingDialog, ProgressDialog, SelectDialog, SelectDialogState},
 };
 
 enum Status {
     None,
     NotificationSuccess(String),
     NotificationWarning(String),
-    NotificationError(String),
+    // with the event to dispatch again to retry the failed operation
+    NotificationError(String, Option<Box<AppEvent>>),
     Input(String, Option<u16>),
 }
 
+struct Prompt {
+    title: String,
+    input: Input,
+    on_submit: PromptCallback,
+}
+
+// The table (and the item) specified by command line arguments
+pub struct StartupTarget {
+    pub table_name: String,
+    pub key: Option<String>,
+}
+
+// The modal requests in flight, each cleared only by its own completion
+// (table descriptions and previews are not modal, tracked by the table list instead)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Loading {
+    Initialize,
+    OpenItemByKey,
+    ListMultiRegionTables,
+    ScanTableItems,
+    QueryTableItems,
+    WriteItems,
+    ExecuteStatement,
+    RefreshItem,
+    CheckItemExists,
+    UpdateContributorInsights,
+    DiffTables,
+    AccountLimits,
+    CompareItems,
+    OpenSideBySide,
+    ReferencedItems,
+}
+
+struct QuickOpen {
+    input: Input,
+    table_names: Vec<String>,
+    dialog: SelectDialogState,
+}
+
+const MAX_RECENT_VALUES_PER_TABLE: usize = 1000;
+
+// The string and number values of the items loaded recently, the oldest ones are dropped first
+#[derive(Default)]
+struct RecentValues {
+    values: VecDeque<String>,
+    seen: HashSet<String>,
+}
+
+impl RecentValues {
+    fn push(&mut self, value: String) {
+        if self.seen.contains(&value) {
+            return;
+        }
+        self.seen.insert(value.clone());
+        self.values.push_back(value);
+        if self.values.len() > MAX_RECENT_VALUES_PER_TABLE {
+            if let Some(oldest) = self.values.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+    }
+}
+
 pub struct App {
     view_stack: ViewStack,
 
     config: Config,
     theme: ColorTheme,
     mapper: UserEventMapper,
+    state: State,
+    audit_log: AuditLog,
 
     status: Status,
-    loading: bool,
+    loading: HashSet<Loading>,
+    progress: Option<(usize, usize)>,
+    // the progress is shown only as a percentage if the amount is not a count of items
+    progress_percent_only: bool,
+    write_abort: Option<Arc<AtomicBool>>,
+    prompt: Option<Prompt>,
+    dialog: Option<(SelectDialogState, Vec<AppEvent>)>,
+    dialog_short_helps: Vec<SpansWithPriority>,
+    pending_filter: Option<String>,
+    quick_open: Option<QuickOpen>,
+    table_names: Vec<String>,
+    attribute_names: BTreeMap<String, BTreeSet<String>>,
+    recent_values: BTreeMap<String, RecentValues>,
+    table_permissions: HashMap<String, TablePermissions>,
+    describing_tables: HashSet<String>,
+    print_on_exit: bool,
+    startup_target: Option<StartupTarget>,
+    dry_run: bool,
+    inspector_open: bool,
+    timezone: DisplayTimeZone,
+    output: Option<String>,
+    last_load_stats: Option<LoadStats>,
 
     client: Arc<Client>,
     tx: Sender,
@@ -50,16 +167,46 @@ impl App {
         config: Config,
         theme: ColorTheme,
         mapper: UserEventMapper,
+        state: State,
         client: Client,
+        print_on_exit: bool,
         tx: Sender,
     ) -> Self {
+        let dialog_short_helps = build_dialog_short_helps(&mapper);
+        let dry_run = config.write.dry_run;
+        let timezone = DisplayTimeZone::parse(&config.timezone).unwrap_or_else(|e| {
+            tx.send(AppEvent::NotifyError(e));
+            DisplayTimeZone::default()
+        });
         App {
             view_stack: ViewStack::new(View::of_init(theme, tx.clone())),
             config,
             theme,
             mapper,
+            state,
+            audit_log: AuditLog::new(),
             status: Status::None,
-            loading: true,
+            loading: HashSet::from([Loading::Initialize]),
+            progress: None,
+            progress_percent_only: false,
+            write_abort: None,
+            prompt: None,
+            dialog: None,
+            dialog_short_helps,
+            pending_filter: None,
+            quick_open: None,
+            table_names: Vec::new(),
+            attribute_names: BTreeMap::new(),
+            recent_values: BTreeMap::new(),
+            table_permissions: HashMap::new(),
+            describing_tables: HashSet::new(),
+            print_on_exit,
+            startup_target: None,
+            dry_run,
+            inspector_open: false,
+            timezone,
+            output: None,
+            last_load_stats: None,
             client: Arc::new(client),
             tx,
         }
@@ -67,7 +214,15 @@ impl App {
 }
 
 impl App {
-    pub fn run<B: Backend>(
+    pub fn set_startup_target(&mut self, target: StartupTarget) {
+        self.startup_target = Some(target);
+    }
+
+    pub fn output(&self) -> Option<&str> {
+        self.output.as_deref()
+    }
+
+    pub fn run<B: Backend + Write>(
         &mut self,
         terminal: &mut Terminal<B>,
         rx: Receiver,
@@ -78,18 +233,24 @@ impl App {
                 AppEvent::Key(key_event) => {
                     let user_events = self.mapper.find_events(key_event);
 
-                    handle_user_events! { user_events =>
-                        UserEvent::Quit => {
-                            return Ok(());
+                    if user_events.contains(&UserEvent::Quit) {
+                        if self.should_confirm_quit() {
+                            self.open_quit_confirm_dialog();
+                            continue;
                         }
+                        self.prepare_quit();
+                        return Ok(());
                     }
 
-                    if self.loading {
-                        // Ignore key inputs while loading (except quit)
+                    if !self.loading.is_empty() && self.dialog.is_none() {
+                        // Ignore key inputs while loading (except quit, abort and the confirmation dialog)
+                        if user_events.contains(&UserEvent::Reset) {
+                            self.abort_write_items();
+                        }
                         continue;
                     }
 
-                    match self.status {
+                    match &mut self.status {
                         Status::None | Status::Input(_, _) => {
                             // do nothing
                         }
@@ -97,20 +258,62 @@ impl App {
                             // Clear message and pass key input as is
                             self.clear_status();
                         }
-                        Status::NotificationError(_) => {
+                        Status::NotificationError(_, retry) => {
                             if matches!(self.view_stack.current_view(), View::Init(_)) {
                                 return Ok(());
                             }
+                            if user_events.contains(&UserEvent::Retry) {
+                                if let Some(event) = retry.take() {
+                                    self.tx.send(*event);
+                                }
+                            }
                             // Clear message and cancel key input
                             self.clear_status();
                             continue;
                         }
                     }
 
+                    if self.prompt.is_some() {
+                        self.handle_prompt_key_event(user_events, key_event);
+                        continue;
+                    }
+
+                    if self.dialog.is_some() {
+                        self.handle_dialog_key_event(user_events);
+                        continue;
+                    }
+
+                    if self.quick_open.is_some() {
+                        self.handle_quick_open_key_event(user_events, key_event);
+                        continue;
+                    }
+
+                    if user_events.contains(&UserEvent::QuickOpen) && !self.table_names.is_empty() {
+                        self.open_quick_open();
+                        continue;
+                    }
+
+                    if user_events.contains(&UserEvent::ToggleDryRun) {
+                        self.toggle_dry_run();
+                        continue;
+                    }
+
+                    if user_events.contains(&UserEvent::ToggleInspector) {
+                        self.inspector_open = !self.inspector_open;
+                        continue;
+                    }
+
                     self.view_stack
                         .current_view_mut()
                         .handle_user_key_event(user_events, key_event);
                 }
+                AppEvent::Quit => {
+                    self.prepare_quit();
+                    return Ok(());
+                }
+                AppEvent::Paste(text) => {
+                    self.handle_paste(text);
+                }
                 AppEvent::Resize(w, h) => {
                     let _ = (w, h);
                 }
@@ -120,33 +323,246 @@ impl App {
                 AppEvent::CompleteInitialize(result) => {
                     self.complete_initialize(result);
                 }
+                AppEvent::CompleteOpenItemByKey(result) => {
+                    self.complete_open_item_by_key(result);
+                }
+                AppEvent::ListMultiRegionTables(regions) => {
+                    self.list_multi_region_tables(regions);
+                }
+                AppEvent::CompleteListMultiRegionTables(regions, results) => {
+                    self.complete_list_multi_region_tables(regions, results);
+                }
+                AppEvent::SwitchRegion(region, table_name) => {
+                    self.switch_region(region, table_name);
+                }
+                AppEvent::CompleteSwitchRegion(client, table_name) => {
+                    self.complete_switch_region(*client, table_name);
+                }
+                AppEvent::OpenEnvironmentPicker => {
+                    self.open_environment_picker();
+                }
+                AppEvent::SwitchEnvironment(name) => {
+                    self.switch_environment(name);
+                }
+                AppEvent::CompleteSwitchEnvironment(client) => {
+                    self.complete_switch_environment(*client);
+                }
+                AppEvent::OpenProfilePicker(profiles, region) => {
+                    self.open_profile_picker(profiles, region);
+                }
+                AppEvent::SelectProfile(profile, region) => {
+                    self.select_profile(profile, region);
+                }
                 AppEvent::LoadTableDescription(table_name) => {
                     self.load_table_description(table_name);
                 }
-                AppEvent::CompleteLoadTableDescription(result) => {
-                    self.complete_load_table_description(result);
+                AppEvent::CompleteLoadTableDescription(table_name, result) => {
+                    self.complete_load_table_description(table_name, result);
                 }
-                AppEvent::LoadTableItems(desc) => {
-                    self.load_table_items(desc);
+                AppEvent::LoadTableItemsPreview(desc, count) => {
+                    self.load_table_items_preview(desc, count);
                 }
-                AppEvent::CompleteLoadTableItems(desc, result) => {
-                    self.complete_load_table_items(desc, result);
+                AppEvent::CompleteLoadTableItemsPreview(table_name, result) => {
+                    self.complete_load_table_items_preview(table_name, result);
+                }
+                AppEvent::LoadTableItems(desc, options) => {
+                    self.load_table_items(desc, options, None);
+                }
+                AppEvent::ScanTableItems(desc, options) => {
+                    let memory_limit_bytes = self.config.scan.memory_limit_bytes;
+                    self.scan_table_items(desc, options, memory_limit_bytes);
+                }
+                AppEvent::ScanTableItemsWithoutMemoryLimit(desc, options) => {
+                    self.scan_table_items(desc, options, 0);
+                }
+                AppEvent::CompleteLoadTableItems(desc, options, result) => {
+                    self.complete_load_table_items(desc, options, result);
+                }
+                AppEvent::ShowLoadedTableItems(desc, options, loaded) => {
+                    self.show_loaded_table_items(desc, options, loaded);
                 }
                 AppEvent::OpenItem(desc, item) => {
                     self.open_item(desc, item);
                 }
-                AppEvent::OpenTableInsight(insight) => {
-                    self.open_table_insight(insight);
+                AppEvent::OpenAdjacentItem(forward) => {
+                    self.open_adjacent_item(forward);
+                }
+                AppEvent::RefreshItem(desc, key) => {
+                    self.refresh_item(desc, key);
+                }
+                AppEvent::CompleteRefreshItem(desc, key, result) => {
+                    self.complete_refresh_item(desc, key, result);
+                }
+                AppEvent::CheckItemExists(desc, key) => {
+                    self.check_item_exists(desc, key);
+                }
+                AppEvent::CompleteCheckItemExists(desc, key, result) => {
+                    self.complete_check_item_exists(desc, key, result);
+                }
+                AppEvent::OpenTableInsight(desc, items) => {
+                    self.open_table_insight(desc, items);
+                }
+                AppEvent::UpdateContributorInsights(table_name, index_name, enable) => {
+                    self.update_contributor_insights(table_name, index_name, enable);
+                }
+                AppEvent::ExecuteUpdateContributorInsights(table_name, index_name, enable) => {
+                    self.execute_update_contributor_insights(table_name, index_name, enable);
+                }
+                AppEvent::CompleteUpdateContributorInsights(table_name, enable, result) => {
+                    self.complete_update_contributor_insights(table_name, enable, result);
+                }
+                AppEvent::DiffTables(left, right) => {
+                    self.diff_tables(left, right);
+                }
+                AppEvent::CompleteDiffTables(result) => {
+                    self.complete_diff_tables(result);
+                }
+                AppEvent::LoadAccountLimits => {
+                    self.load_account_limits();
+                }
+                AppEvent::CompleteLoadAccountLimits(result) => {
+                    self.complete_load_account_limits(result);
+                }
+                AppEvent::CompareItems(desc, items, statement) => {
+                    self.compare_items(desc, items, statement);
+                }
+                AppEvent::CompleteCompareItems(desc, items, statement, result) => {
+                    self.complete_compare_items(desc, items, statement, result);
+                }
+                AppEvent::OpenSideBySide(desc, loaded, source, environment) => {
+                    self.open_side_by_side(desc, loaded, source, environment);
+                }
+                AppEvent::CompleteOpenSideBySide(left, result) => {
+                    self.complete_open_side_by_side(left, result);
+                }
+                AppEvent::JumpToReference(table_name, attribute_name, value) => {
+                    self.jump_to_reference(table_name, attribute_name, value);
+                }
+                AppEvent::LoadReferencedItems(table_name, attribute_name, target, value) => {
+                    self.load_referenced_items(table_name, attribute_name, target, value);
+                }
+                AppEvent::CompleteLoadReferencedItems(result) => {
+                    self.complete_load_referenced_items(result);
+                }
+                AppEvent::ValidateItems(desc, items) => {
+                    self.validate_items(desc, items);
+                }
+                AppEvent::OpenPartiQL(desc) => {
+                    self.open_partiql(desc);
+                }
+                AppEvent::OpenFilterBuilder(desc) => {
+                    self.open_filter_builder(desc);
+                }
+                AppEvent::OpenQueryBuilder(desc) => {
+                    self.open_query_builder(desc);
+                }
+                AppEvent::QueryTableItems(desc, options) => {
+                    self.query_table_items(desc, options);
+                }
+                AppEvent::CompleteQueryTableItems(desc, options, result) => {
+                    self.complete_query_table_items(desc, options, result);
+                }
+                AppEvent::WriteItems(desc, requests) => {
+                    self.write_items(desc, requests);
+                }
+                AppEvent::ExecuteWriteItems(desc, requests) => {
+                    self.execute_write_items(desc, requests);
+                }
+                AppEvent::EditNewItem(desc, content) => {
+                    let mut edited = None;
+                    self.run_external(terminal, &rx, || {
+                        edited = Some(edit_in_editor(&content)?);
+                        Ok(())
+                    })?;
+                    if let Some(edited) = edited {
+                        self.create_item(desc, content, edited);
+                    }
+                }
+                AppEvent::ProbeTablePermissions(desc) => {
+                    self.probe_table_permissions(desc);
+                }
+                AppEvent::CompleteProbeTablePermissions(table_name, permissions) => {
+                    self.complete_probe_table_permissions(table_name, permissions);
+                }
+                AppEvent::Progress(current, total) => {
+                    self.progress = Some((current, total));
+                }
+                AppEvent::CompleteWriteItems(desc, requests, result) => {
+                    self.complete_write_items(desc, requests, result);
+                }
+                AppEvent::OpenAuditLog => {
+                    self.open_audit_log();
+                }
+                AppEvent::ExecuteStatement(desc, statement) => {
+                    self.execute_statement(desc, statement);
+                }
+                AppEvent::CompleteExecuteStatement(desc, statement, result) => {
+                    self.complete_execute_statement(desc, statement, result);
                 }
                 AppEvent::OpenHelp(helps) => {
                     self.open_help(helps);
                 }
+                AppEvent::OpenTableInConsole(table_name) => {
+                    self.open_table_in_console(table_name);
+                }
+                AppEvent::OpenLink(link) => {
+                    self.open_link(link);
+                }
+                AppEvent::OpenPrompt(title, on_submit) => {
+                    self.open_prompt(title, on_submit);
+                }
+                AppEvent::OpenSelectDialog(title, items) => {
+                    self.open_select_dialog(title, items);
+                }
+                AppEvent::JumpToTable(table_name) => {
+                    self.jump_to_table(table_name);
+                }
+                AppEvent::OpenSavedFilters(desc) => {
+                    self.open_saved_filters(desc);
+                }
+                AppEvent::OpenKeyTemplates(desc) => {
+                    self.open_key_templates(desc);
+                }
+                AppEvent::OpenTableWithFilter(desc, query) => {
+                    self.open_table_with_filter(desc, query);
+                }
+                AppEvent::SaveFilter(table_name, filter) => {
+                    self.save_filter(table_name, filter);
+                }
+                AppEvent::SaveQuery(table_name, query) => {
+                    self.save_query(table_name, query);
+                }
+                AppEvent::SaveViewPreferences(table_name, preferences) => {
+                    self.save_view_preferences(table_name, preferences);
+                }
+                AppEvent::ApplyFilter(query) => {
+                    self.apply_filter(query);
+                }
                 AppEvent::BackToBeforeView => {
                     self.back_to_before_view();
                 }
                 AppEvent::CopyToClipboard(name, content) => {
                     self.copy_to_clipboard(name, content);
                 }
+                AppEvent::QuitWithOutput(output) => {
+                    self.output = Some(output);
+                    return Ok(());
+                }
+                AppEvent::PipeToCommand(name, content) => {
+                    self.pipe_to_command(name, content);
+                }
+                AppEvent::ExecuteCommand(command, content) => {
+                    self.run_external(terminal, &rx, || pipe_to_command(&command, &content))?;
+                }
+                AppEvent::OpenInPager(content) => {
+                    self.run_external(terminal, &rx, || open_in_pager(&content))?;
+                }
+                AppEvent::SaveToFile(name, content) => {
+                    self.save_to_file(name, content);
+                }
+                AppEvent::WriteFile(name, path, content) => {
+                    self.write_file(name, path, content);
+                }
                 AppEvent::ClearStatus => {
                     self.clear_status();
                 }
@@ -162,6 +578,9 @@ impl App {
                 AppEvent::NotifyError(msg) => {
                     self.notify_error(msg);
                 }
+                AppEvent::NotifyErrorWithRetry(msg, retry) => {
+                    self.notify_error_with_retry(msg, retry);
+                }
             }
         }
     }
@@ -169,19 +588,61 @@ impl App {
 
 impl App {
     fn render(&mut self, f: &mut Frame) {
+        let capturing_status_line = self.prompt.is_some() || self.quick_open.is_some();
+        if self.view_stack.current_view().is_full_screen() && !capturing_status_line {
+            let area = f.area();
+            let view_area = self.render_inspector_panel(f, area);
+            self.view_stack.current_view_mut().render(f, view_area);
+            self.render_loading_dialog(f);
+            self.render_select_dialog(f, area);
+            return;
+        }
+
         let [view_area, status_line_area] =
             Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(f.area());
 
+        let view_area = self.render_inspector_panel(f, view_area);
         self.view_stack.current_view_mut().render(f, view_area);
         self.render_status_line(f, status_line_area);
+        // the select dialog is rendered on top so that it can be used while loading
         self.render_loading_dialog(f);
+        self.render_select_dialog(f, view_area);
     }
 
     fn render_status_line(&self, f: &mut Frame, area: Rect) {
+        if let Some(prompt) = &self.prompt {
+            self.render_prompt(f, area, &prompt.title, &prompt.input);
+            return;
+        }
+        if let Some(quick_open) = &self.quick_open {
+            self.render_prompt(f, area, "Quick open", &quick_open.input);
+            return;
+        }
+
         let text: Line = match &self.status {
             Status::None => {
-                let helps = self.view_stack.current_view().short_helps();
-                let spans = prune_spans_to_fit_width(helps, area.width as usize - 2, ", "); // -2 for padding
+                let helps = if self.dialog.is_some() {
+                    &self.dialog_short_helps
+                } else {
+                    self.view_stack.current_view().short_helps()
+                };
+                let mut info = self.status_line_info();
+                if self.dry_run {
+                    info = if info.is_empty() {
+                        "DRY RUN".into()
+                    } else {
+                        format!("DRY RUN | {info}")
+                    };
+                }
+                let info_width = console::measure_text_width(&info);
+                let max_width = (area.width as usize).saturating_sub(2 + info_width); // -2 for padding
+                let mut spans = prune_spans_to_fit_width(helps, max_width, ", ");
+                if !info.is_empty() {
+                    let helps_width: usize = spans.iter().map(Span::width).sum();
+                    let pad = max_width.saturating_sub(helps_width);
+                    spans.push(Span::raw(" ".repeat(pad)));
+                    spans.push(Span::raw(info));
+                }
                 Line::from(spans).fg(self.theme.short_help)
             }
             Status::NotificationSuccess(msg) => Line::from(
@@ -194,11 +655,18 @@ impl App {
                     .add_modifier(Modifier::BOLD)
                     .fg(self.theme.notification_warning),
             ),
-            Status::NotificationError(msg) => Line::from(
-                format!("ERROR: {msg}")
-                    .add_modifier(Modifier::BOLD)
-                    .fg(self.theme.notification_error),
-            ),
+            Status::NotificationError(msg, retry) => {
+                let mut msg = format!("ERROR: {msg}");
+                if retry.is_some() {
+                    if let Some(key) = self.mapper.find_first_key(UserEvent::Retry) {
+                        msg.push_str(&format!(" ({} to retry)", key_event_to_string(key, true)));
+                    }
+                }
+                Line::from(
+                    msg.add_modifier(Modifier::BOLD)
+                        .fg(self.theme.notification_error),
+                )
+            }
             Status::Input(msg, _) => Line::from(msg.as_str().fg(self.theme.fg)),
         };
         let paragraph = Paragraph::new(text).block(
@@ -214,16 +682,328 @@ impl App {
         }
     }
 
+    fn status_line_info(&self) -> String {
+        let format = &self.config.ui.status_line.format;
+        if format.is_empty() {
+            return String::new();
+        }
+        let view = self.view_stack.current_view();
+        let values = StatusLineValues {
+            profile: self.client.profile().unwrap_or("default"),
+            region: self.client.region(),
+            table: view.table_name(),
+            position: view.position(),
+            elapsed: self.last_load_stats.map(|stats| stats.elapsed),
+        };
+        format_status_line(format, &values)
+    }
+
+    fn render_prompt(&self, f: &mut Frame, area: Rect, title: &str, input: &Input) {
+        let title = format!("{title}: ");
+        let title_width = console::measure_text_width(&title) as u16;
+        let text = Line::from(vec![
+            title.bold().fg(self.theme.fg),
+            input.value().fg(self.theme.fg),
+        ]);
+        let paragraph = Paragraph::new(text).block(
+            Block::default()
+                .style(Style::default().bg(self.theme.bg))
+                .padding(Padding::horizontal(1)),
+        );
+        f.render_widget(paragraph, area);
+
+        let cursor_pos = title_width + input.cursor() as u16;
+        f.set_cursor_position((area.x + cursor_pos + 1, area.y));
+    }
+
+    fn render_select_dialog(&mut self, f: &mut Frame, area: Rect) {
+        if let Some((state, _)) = &mut self.dialog {
+            let dialog = SelectDialog::default().theme(self.theme);
+            f.render_stateful_widget(dialog, area, state);
+        }
+        if let Some(quick_open) = &mut self.quick_open {
+            let dialog = SelectDialog::default().theme(self.theme);
+            f.render_stateful_widget(dialog, area, &mut quick_open.dialog);
+        }
+    }
+
+    // Render the inspector panel at the bottom of the area if open, and return the rest of the area
+    fn render_inspector_panel(&self, f: &mut Frame, area: Rect) -> Rect {
+        if !self.inspector_open {
+            return area;
+        }
+        let height = self.config.ui.inspector.height;
+        let [view_area, panel_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(height)]).areas(area);
+        let records = self.client.operation_log().records();
+        let panel = InspectorPanel::new(&records).theme(&self.theme);
+        f.render_widget(panel, panel_area);
+        view_area
+    }
+
     fn render_loading_dialog(&self, f: &mut Frame) {
-        if self.loading {
-            let dialog = LoadingDialog::default().theme(self.theme);
-            f.render_widget(dialog, f.area());
+        if self.loading.is_empty() {
+            return;
+        }
+        // the progress is shown instead if the total is known
+        match self.progress {
+            Some((current, total)) => {
+                let mut dialog = ProgressDialog::new(current, total)
+                    .percent_only(self.progress_percent_only)
+                    .theme(self.theme);
+                if self.write_abort.is_some() {
+                    if let Some(key) = self.mapper.find_first_key(UserEvent::Reset) {
+                        dialog = dialog.abort_key(key_event_to_string(key, true));
+                    }
+                }
+                f.render_widget(dialog, f.area());
+            }
+            None => {
+                let dialog = LoadingDialog::default().theme(self.theme);
+                f.render_widget(dialog, f.area());
+            }
+        }
+    }
+}
+
+impl App {
+    fn handle_prompt_key_event(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        handle_user_events_with_default! { user_events =>
+            UserEvent::Confirm => {
+                self.submit_prompt();
+            }
+            UserEvent::Reset => {
+                self.prompt = None;
+            }
+            => {
+                if let Some(prompt) = &mut self.prompt {
+                    prompt.input.handle_event(&Event::Key(key_event));
+                }
+            }
+        }
+    }
+
+    fn handle_dialog_key_event(&mut self, user_events: Vec<UserEvent>) {
+        handle_user_events! { user_events =>
+            UserEvent::Down => {
+                if let Some((state, _)) = &mut self.dialog {
+                    state.select_next();
+                }
+            }
+            UserEvent::Up => {
+                if let Some((state, _)) = &mut self.dialog {
+                    state.select_prev();
+                }
+            }
+            UserEvent::Confirm => {
+                self.select_dialog_item();
+            }
+            UserEvent::Close | UserEvent::Reset => {
+                self.dialog = None;
+            }
+        }
+    }
+
+    fn handle_quick_open_key_event(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        // characters are typed into the query even if they are mapped to a movement like j/k
+        let user_events = if is_text_input_key(key_event) {
+            vec![]
+        } else {
+            user_events
+        };
+        handle_user_events_with_default! { user_events =>
+            UserEvent::Down => {
+                if let Some(quick_open) = &mut self.quick_open {
+                    quick_open.dialog.select_next();
+                }
+            }
+            UserEvent::Up => {
+                if let Some(quick_open) = &mut self.quick_open {
+                    quick_open.dialog.select_prev();
+                }
+            }
+            UserEvent::Confirm => {
+                if let Some(quick_open) = self.quick_open.take() {
+                    let i = quick_open.dialog.selected();
+                    if let Some(name) = quick_open.table_names.get(i) {
+                        self.tx.send(AppEvent::JumpToTable(name.clone()));
+                    }
+                }
+            }
+            UserEvent::Reset => {
+                self.quick_open = None;
+            }
+            => {
+                if let Some(quick_open) = &mut self.quick_open {
+                    quick_open.input.handle_event(&Event::Key(key_event));
+                    self.update_quick_open_candidates();
+                }
+            }
+        }
+    }
+
+    // Confirm only while an operation is in progress, and quit immediately if Quit is pressed again
+    fn should_confirm_quit(&self) -> bool {
+        self.config.ui.confirm_quit && !self.loading.is_empty() && self.dialog.is_none()
+    }
+
+    fn open_quit_confirm_dialog(&mut self) {
+        let title = "An operation is in progress - quit anyway?".to_string();
+        let items = vec![
+            ("Quit".into(), AppEvent::Quit),
+            ("Cancel".into(), AppEvent::ClearStatus),
+        ];
+        self.open_select_dialog(title, items);
+    }
+
+    fn prepare_quit(&mut self) {
+        if self.print_on_exit {
+            self.output = self.view_stack.current_view().selected_item_json();
+        }
+    }
+
+    fn update_quick_open_candidates(&mut self) {
+        if let Some(quick_open) = &mut self.quick_open {
+            let query = quick_open.input.value();
+            let (table_names, labels) = quick_open_candidates(
+                query,
+                &self.table_names,
+                &self.attribute_names,
+                &self.recent_values,
+            );
+            quick_open.table_names = table_names;
+            quick_open.dialog = SelectDialogState::new("Tables", labels);
+        }
+    }
+
+    fn handle_paste(&mut self, text: String) {
+        if !self.loading.is_empty() || self.dialog.is_some() {
+            return;
+        }
+        if let Some(prompt) = &mut self.prompt {
+            paste_to_input(&mut prompt.input, &text);
+            return;
+        }
+        if let Some(quick_open) = &mut self.quick_open {
+            paste_to_input(&mut quick_open.input, &text);
+            self.update_quick_open_candidates();
+            return;
+        }
+        self.view_stack.current_view_mut().handle_paste(&text);
+    }
+
+    fn submit_prompt(&mut self) {
+        if let Some(prompt) = self.prompt.take() {
+            let value = prompt.input.value().trim().to_string();
+            if value.is_empty() {
+                return;
+            }
+            self.tx.send((prompt.on_submit)(value));
+        }
+    }
+
+    fn select_dialog_item(&mut self) {
+        if let Some((state, mut events)) = self.dialog.take() {
+            let i = state.selected();
+            if i < events.len() {
+                self.tx.send(events.swap_remove(i));
+            }
         }
     }
 }
 
+fn build_dialog_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Select", 1),
+        BuildShortHelpsItem::single(UserEvent::Confirm, "Confirm", 1),
+        BuildShortHelpsItem::single(UserEvent::Close, "Cancel", 2),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+fn environment_label(client: &Client) -> String {
+    format!(
+        "{}@{}",
+        client.profile().unwrap_or("default"),
+        client.region().unwrap_or("-")
+    )
+}
+
+fn is_text_input_key(key_event: KeyEvent) -> bool {
+    matches!(key_event.code, KeyCode::Char(_))
+        && !key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+}
+
+const QUICK_OPEN_VALUE_WIDTH: usize = 40;
+
+// Search the table names, and the attribute names and the recent values of the tables loaded in this session
+fn quick_open_candidates(
+    query: &str,
+    table_names: &[String],
+    attribute_names: &BTreeMap<String, BTreeSet<String>>,
+    recent_values: &BTreeMap<String, RecentValues>,
+) -> (Vec<String>, Vec<String>) {
+    let query = query.to_lowercase();
+    table_names
+        .iter()
+        .filter_map(|name| {
+            if name.to_lowercase().contains(&query) {
+                return Some((name.clone(), name.clone()));
+            }
+            let attr = attribute_names.get(name).and_then(|names| {
+                names
+                    .iter()
+                    .find(|attr| attr.to_lowercase().contains(&query))
+            });
+            if let Some(attr) = attr {
+                return Some((name.clone(), format!("{name} (attribute: {attr})")));
+            }
+            if query.is_empty() {
+                return None;
+            }
+            // the newest value is the most likely to be the one being looked for
+            recent_values
+                .get(name)?
+                .values
+                .iter()
+                .rev()
+                .find(|value| value.to_lowercase().contains(&query))
+                .map(|value| {
+                    let value = truncate_to_width(value, QUICK_OPEN_VALUE_WIDTH);
+                    (name.clone(), format!("{name} (value: {value})"))
+                })
+        })
+        .unzip()
+}
+
+struct StatusLineValues<'a> {
+    profile: &'a str,
+    region: Option<&'a str>,
+    table: Option<&'a str>,
+    position: Option<(usize, usize)>,
+    elapsed: Option<Duration>,
+}
+
+fn format_status_line(format: &str, values: &StatusLineValues) -> String {
+    let or_dash = |s: Option<String>| s.unwrap_or_else(|| "-".into());
+    format
+        .replace("{profile}", values.profile)
+        .replace("{region}", &or_dash(values.region.map(Into::into)))
+        .replace("{table}", &or_dash(values.table.map(Into::into)))
+        .replace(
+            "{position}",
+            &or_dash(values.position.map(|(c, t)| format!("{c}/{t}"))),
+        )
+        .replace("{elapsed}", &or_dash(values.elapsed.map(format_duration)))
+}
+
 impl App {
-    fn initialize(&self) {
+    fn initialize(&mut self) {
+        self.loading.insert(Loading::Initialize);
         let client = self.client.clone();
         let tx = self.tx.clone();
         spawn(async move {
@@ -236,101 +1016,1524 @@ impl App {
         match result {
             Ok(tables) => {
                 if tables.is_empty() {
-                    self.loading = false;
+                    self.loading.remove(&Loading::Initialize);
                     self.tx
                         .send(AppEvent::NotifyWarning(AppError::msg("No tables found.")));
                 } else {
+                    self.table_names = tables.iter().map(|t| t.name.clone()).collect();
                     let view = View::of_table_list(
                         tables,
                         &self.mapper,
                         self.config.ui.table_list.clone(),
+                        self.timezone,
                         self.theme,
                         self.tx.clone(),
                     );
                     self.view_stack.pop();
                     self.view_stack.push(view);
-                    // not update loading here
+                    self.loading.remove(&Loading::Initialize);
+
+                    if let Some(target) = self.startup_target.take() {
+                        self.jump_to_table(target.table_name.clone());
+                        if let Some(key) = target.key {
+                            self.open_item_by_key(target.table_name, key);
+                        }
+                    }
                 }
             }
             Err(e) => {
-                self.tx.send(AppEvent::NotifyError(e));
-                self.loading = false;
+                // the diagnostics are shown instead of the error message to be able to retry
+                let diagnostics = self.client.diagnostics(&e);
+                let view = View::of_connection_diagnostics(
+                    diagnostics,
+                    &self.mapper,
+                    self.theme,
+                    self.tx.clone(),
+                );
+                self.view_stack.pop();
+                self.view_stack.push(view);
+                self.loading.remove(&Loading::Initialize);
             }
         }
     }
 
-    fn load_table_description(&mut self, name: String) {
-        self.loading = true;
+    fn open_item_by_key(&mut self, table_name: String, key: String) {
+        self.loading.insert(Loading::OpenItemByKey);
         let client = self.client.clone();
         let tx = self.tx.clone();
         spawn(async move {
-            let result = client.describe_table(&name).await;
-            tx.send(AppEvent::CompleteLoadTableDescription(result));
+            let result = async {
+                let desc = client.describe_table_with_ttl(&table_name).await?;
+                let key = parse_key(&desc, &key)?;
+                let item = client.get_item(&desc.table_name, &key).await?;
+                Ok((desc, item))
+            }
+            .await;
+            tx.send(AppEvent::CompleteOpenItemByKey(result));
         });
     }
 
-    fn complete_load_table_description(&mut self, result: AppResult<TableDescription>) {
+    fn complete_open_item_by_key(&mut self, result: AppResult<(TableDescription, Option<Item>)>) {
         match result {
-            Ok(desc) => {
-                if let View::TableList(view) = self.view_stack.current_view_mut() {
-                    view.set_table_description(desc);
-                }
+            Ok((desc, Some(item))) => {
+                self.add_item_attribute_names(&desc.table_name, std::slice::from_ref(&item));
+                self.open_item(desc, item);
+            }
+            Ok((desc, None)) => {
+                let msg = format!("Item not found in {}", desc.table_name);
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
             }
             Err(e) => {
                 self.tx.send(AppEvent::NotifyError(e));
             }
         }
-        self.loading = false;
+        self.loading.remove(&Loading::OpenItemByKey);
     }
 
-    fn load_table_items(&mut self, desc: TableDescription) {
-        self.loading = true;
-        let client = self.client.clone();
+    fn list_multi_region_tables(&mut self, regions: Vec<String>) {
+        self.loading.insert(Loading::ListMultiRegionTables);
+        let profile = self.client.profile().map(Into::into);
+        let default_region = self.config.default_region.clone();
+        let compat_mode = self.config.compat_mode;
+        let operation_log = self.client.operation_log().clone();
         let tx = self.tx.clone();
         spawn(async move {
-            let result = client
-                .scan_all_items(&desc.table_name, &desc.key_schema_type)
-                .await;
-            tx.send(AppEvent::CompleteLoadTableItems(desc, result));
+            let handles: Vec<_> = regions
+                .iter()
+                .map(|region| {
+                    let region = region.clone();
+                    let profile = profile.clone();
+                    let default_region = default_region.clone();
+                    let operation_log = operation_log.clone();
+                    spawn(async move {
+                        // the endpoint url is not inherited for the same reason as side-by-side
+                        let client = Client::new(
+                            Some(region),
+                            None,
+                            profile,
+                            None,
+                            default_region,
+                            compat_mode,
+                            operation_log,
+                        )
+                        .await;
+                        client.list_all_tables().await
+                    })
+                })
+                .collect();
+            let mut results = Vec::new();
+            for (region, handle) in regions.iter().zip(handles) {
+                let result = handle
+                    .await
+                    .unwrap_or_else(|e| Err(AppError::new("failed to list tables", e)));
+                results.push((region.clone(), result));
+            }
+            tx.send(AppEvent::CompleteListMultiRegionTables(regions, results));
         });
     }
 
-    fn complete_load_table_items(&mut self, desc: TableDescription, result: AppResult<Vec<Item>>) {
-        match result {
-            Ok(items) => {
-                if matches!(self.view_stack.current_view(), View::Table(_)) {
-                    // when reloading in table view, pop current table view first
-                    self.view_stack.pop();
-                }
-                if items.is_empty() {
-                    let msg = format!("Table {} has no items", desc.table_name);
-                    self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
-                } else {
-                    let view = View::of_table(
-                        desc,
-                        items,
-                        &self.mapper,
-                        self.config.ui.table.clone(),
-                        self.theme,
-                        self.tx.clone(),
-                    );
-                    self.view_stack.push(view);
-                }
-            }
-            Err(e) => {
-                self.tx.send(AppEvent::NotifyError(e));
+    fn complete_list_multi_region_tables(
+        &mut self,
+        regions: Vec<String>,
+        results: Vec<(String, AppResult<Vec<Table>>)>,
+    ) {
+        self.loading.remove(&Loading::ListMultiRegionTables);
+        let mut tables = Vec::new();
+        let mut failed_regions = Vec::new();
+        for (region, result) in results {
+            match result {
+                Ok(ts) => tables.extend(ts.into_iter().map(|t| RegionTable {
+                    region: region.clone(),
+                    name: t.name,
+                })),
+                Err(_) => failed_regions.push(region),
             }
         }
-        self.loading = false;
+        if !failed_regions.is_empty() {
+            let msg = format!("Failed to list tables in {}", failed_regions.join(", "));
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+        }
+        if tables.is_empty() {
+            if failed_regions.is_empty() {
+                let msg = "No tables found.";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+            return;
+        }
+        let view = View::of_multi_region_table_list(
+            tables,
+            regions,
+            &self.mapper,
+            self.theme,
+            self.tx.clone(),
+        );
+        self.view_stack.push(view);
+    }
+
+    fn switch_region(&mut self, region: String, table_name: String) {
+        self.loading.insert(Loading::Initialize);
+        let profile = self.client.profile().map(Into::into);
+        let default_region = self.config.default_region.clone();
+        let compat_mode = self.config.compat_mode;
+        let operation_log = self.client.operation_log().clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let client = Client::new(
+                Some(region),
+                None,
+                profile,
+                None,
+                default_region,
+                compat_mode,
+                operation_log,
+            )
+            .await;
+            tx.send(AppEvent::CompleteSwitchRegion(Box::new(client), table_name));
+        });
+    }
+
+    // Choose the environment preset to connect to on startup
+    fn open_environment_picker(&mut self) {
+        let mut names: Vec<&String> = self.config.environments.keys().collect();
+        names.sort();
+        let mut items = vec![("(default)".to_string(), AppEvent::Initialize)];
+        items.extend(
+            names
+                .into_iter()
+                .map(|name| (name.clone(), AppEvent::SwitchEnvironment(name.clone()))),
+        );
+        self.open_select_dialog("Environments".into(), items);
+    }
+
+    fn switch_environment(&mut self, name: String) {
+        let Some(preset) = self.config.environments.get(&name).cloned() else {
+            let msg = format!("Environment {name} is not defined");
+            self.tx.send(AppEvent::NotifyError(AppError::msg(msg)));
+            return;
+        };
+        let default_region = self.config.default_region.clone();
+        let compat_mode = self.config.compat_mode;
+        let operation_log = self.client.operation_log().clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let credentials = preset.credentials();
+            let client = Client::new(
+                preset.region,
+                preset.endpoint_url,
+                preset.profile,
+                credentials,
+                default_region,
+                compat_mode,
+                operation_log,
+            )
+            .await;
+            tx.send(AppEvent::CompleteSwitchEnvironment(Box::new(client)));
+        });
+    }
+
+    // Choose the profile to connect with on startup, keys are accepted while picking
+    fn open_profile_picker(&mut self, profiles: Vec<Profile>, region: Option<String>) {
+        self.loading.remove(&Loading::Initialize);
+        self.view_stack = ViewStack::new(View::of_profile_picker(
+            profiles,
+            region,
+            &self.mapper,
+            self.theme,
+            self.tx.clone(),
+        ));
+    }
+
+    fn select_profile(&mut self, profile: String, region: Option<String>) {
+        self.loading.insert(Loading::Initialize);
+        self.view_stack = ViewStack::new(View::of_init(self.theme, self.tx.clone()));
+        let default_region = self.config.default_region.clone();
+        let compat_mode = self.config.compat_mode;
+        let operation_log = self.client.operation_log().clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            // the region is resolved from the profile unless specified
+            let client = Client::new(
+                region,
+                None,
+                Some(profile),
+                None,
+                default_region,
+                compat_mode,
+                operation_log,
+            )
+            .await;
+            tx.send(AppEvent::CompleteSwitchEnvironment(Box::new(client)));
+        });
+    }
+
+    fn complete_switch_environment(&mut self, client: Client) {
+        self.client = Arc::new(client);
+        self.tx.send(AppEvent::Initialize);
+    }
+
+    // Restart from the table list of the new region, with the table selected
+    fn complete_switch_region(&mut self, client: Client, table_name: String) {
+        self.client = Arc::new(client);
+        self.view_stack = ViewStack::new(View::of_init(self.theme, self.tx.clone()));
+        self.table_names.clear();
+        self.attribute_names.clear();
+        self.recent_values.clear();
+        self.table_permissions.clear();
+        // descriptions of the tables in the previous region are no longer relevant
+        self.describing_tables.clear();
+        self.pending_filter = None;
+        self.startup_target = Some(StartupTarget {
+            table_name,
+            key: None,
+        });
+        self.initialize();
+    }
+
+    // not modal, the table list shows the loading state in its detail pane instead
+    fn load_table_description(&mut self, name: String) {
+        if !self.describing_tables.insert(name.clone()) {
+            // the result of the request in flight will be applied
+            return;
+        }
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = client.describe_table_with_details(&name).await;
+            tx.send(AppEvent::CompleteLoadTableDescription(name, result));
+        });
+    }
+
+    fn complete_load_table_description(
+        &mut self,
+        table_name: String,
+        result: AppResult<TableDescription>,
+    ) {
+        if !self.describing_tables.remove(&table_name) {
+            // requested before switching the region
+            return;
+        }
+        match result {
+            Ok(desc) => {
+                if !desc.missing_fields.is_empty() {
+                    let msg = format!(
+                        "Table description is incomplete, default values are used for: {}",
+                        desc.missing_fields.join(", ")
+                    );
+                    self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                }
+                self.add_attribute_names(
+                    &desc.table_name,
+                    desc.attribute_definitions
+                        .iter()
+                        .map(|d| d.attribute_name.clone()),
+                );
+                self.record_size_snapshot(&desc);
+                let history = self.state.size_history(&desc.table_name).to_vec();
+                for view in self.view_stack.views_mut() {
+                    if let View::TableList(view) = view {
+                        view.set_size_history(desc.table_name.clone(), history.clone());
+                        view.set_table_description(desc.clone());
+                    }
+                }
+            }
+            Err(e) => {
+                for view in self.view_stack.views_mut() {
+                    if let View::TableList(view) = view {
+                        view.cancel_loading_table_description(&table_name);
+                    }
+                }
+                let retry = AppEvent::LoadTableDescription(table_name);
+                self.tx
+                    .send(AppEvent::NotifyErrorWithRetry(e, Box::new(retry)));
+            }
+        }
+    }
+
+    fn record_size_snapshot(&mut self, desc: &TableDescription) {
+        let snapshot = TableSizeSnapshot {
+            recorded_at: chrono::Utc::now(),
+            item_count: desc.item_count,
+            total_size_bytes: desc.total_size_bytes,
+        };
+        if self.state.record_size_snapshot(&desc.table_name, snapshot) {
+            if let Err(e) = self.state.save() {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+    }
+
+    fn load_table_items_preview(&mut self, desc: TableDescription, count: usize) {
+        let client = self.client.clone();
+        let page_size = self.config.scan.page_size;
+        let memory_limit_bytes = self.config.scan.memory_limit_bytes;
+        let tx = self.tx.clone();
+        spawn(async move {
+            let options = ScanOptions {
+                limit: Some(count),
+                ..Default::default()
+            };
+            let result = client
+                .scan_all_items(
+                    &desc.table_name,
+                    &desc.key_schema_type,
+                    &options,
+                    page_size,
+                    memory_limit_bytes,
+                    |_| {},
+                )
+                .await
+                .map(|loaded| loaded.items);
+            tx.send(AppEvent::CompleteLoadTableItemsPreview(
+                desc.table_name,
+                result,
+            ));
+        });
+    }
+
+    fn complete_load_table_items_preview(
+        &mut self,
+        table_name: String,
+        result: AppResult<Vec<Item>>,
+    ) {
+        match result {
+            Ok(items) => {
+                self.add_item_attribute_names(&table_name, &items);
+                for view in self.view_stack.views_mut() {
+                    if let View::TableList(view) = view {
+                        view.set_table_items_preview(table_name.clone(), items.clone());
+                    }
+                }
+            }
+            Err(e) => {
+                for view in self.view_stack.views_mut() {
+                    if let View::TableList(view) = view {
+                        view.cancel_loading_table_items_preview(&table_name);
+                    }
+                }
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+    }
+
+    fn load_table_items(
+        &mut self,
+        desc: TableDescription,
+        options: ScanOptions,
+        filter: Option<String>,
+    ) {
+        self.pending_filter = filter;
+
+        let estimate = ScanEstimate::new(desc.total_size_bytes);
+        if estimate.total_size_bytes <= self.config.scan.confirm_threshold_bytes {
+            let memory_limit_bytes = self.config.scan.memory_limit_bytes;
+            self.scan_table_items(desc, options, memory_limit_bytes);
+            return;
+        }
+
+        let title = format!(
+            "~{}, ~{} pages, ~{} RCUs - continue?",
+            humansize::format_size(estimate.total_size_bytes, humansize::DECIMAL),
+            estimate.pages,
+            format_count(estimate.read_capacity_units),
+        );
+        let sample_item_count = self.config.scan.sample_item_count;
+        let sample_options = ScanOptions {
+            limit: Some(sample_item_count),
+            ..options.clone()
+        };
+        let items = vec![
+            (
+                format!("Sample (first {sample_item_count} items)"),
+                AppEvent::ScanTableItems(desc.clone(), sample_options),
+            ),
+            ("Full scan".into(), AppEvent::ScanTableItems(desc, options)),
+            ("Cancel".into(), AppEvent::ClearStatus),
+        ];
+        self.open_select_dialog(title, items);
+    }
+
+    fn scan_table_items(
+        &mut self,
+        desc: TableDescription,
+        options: ScanOptions,
+        memory_limit_bytes: u64,
+    ) {
+        self.loading.insert(Loading::ScanTableItems);
+        let total = options.progress_total(desc.item_count, desc.total_size_bytes);
+        self.progress = total.map(|total| (0, total.total()));
+        self.progress_percent_only = total.is_some_and(|total| !total.is_count());
+        let client = self.client.clone();
+        let page_size = self.config.scan.page_size;
+        let tx = self.tx.clone();
+        spawn(async move {
+            let on_progress = |progress| {
+                if let Some(total) = total {
+                    tx.send(AppEvent::Progress(total.current(&progress), total.total()));
+                }
+            };
+            let result = client
+                .scan_all_items(
+                    &desc.table_name,
+                    &desc.key_schema_type,
+                    &options,
+                    page_size,
+                    memory_limit_bytes,
+                    on_progress,
+                )
+                .await;
+            tx.send(AppEvent::CompleteLoadTableItems(desc, options, result));
+        });
+    }
+
+    fn complete_load_table_items(
+        &mut self,
+        desc: TableDescription,
+        options: ScanOptions,
+        result: AppResult<LoadedItems>,
+    ) {
+        self.progress = None;
+        self.progress_percent_only = false;
+        match result {
+            Ok(loaded) if loaded.stats.memory_limited => {
+                self.open_memory_limited_dialog(desc, options, loaded);
+            }
+            Ok(loaded) => {
+                self.show_loaded_table_items(desc, options, loaded);
+            }
+            Err(e) => {
+                let retry = AppEvent::ScanTableItems(desc, options);
+                self.tx
+                    .send(AppEvent::NotifyErrorWithRetry(e, Box::new(retry)));
+            }
+        }
+        self.loading.remove(&Loading::ScanTableItems);
+    }
+
+    fn open_memory_limited_dialog(
+        &mut self,
+        desc: TableDescription,
+        options: ScanOptions,
+        loaded: LoadedItems,
+    ) {
+        let title = format!(
+            "Stopped at the memory limit (~{}) after {} items - continue?",
+            humansize::format_size(self.config.scan.memory_limit_bytes, humansize::DECIMAL),
+            format_count(loaded.items.len() as u64),
+        );
+        let items = vec![
+            (
+                "Show loaded items".into(),
+                AppEvent::ShowLoadedTableItems(desc.clone(), options.clone(), loaded),
+            ),
+            (
+                "Full scan without the limit".into(),
+                AppEvent::ScanTableItemsWithoutMemoryLimit(desc, options),
+            ),
+            ("Cancel".into(), AppEvent::ClearStatus),
+        ];
+        self.open_select_dialog(title, items);
+    }
+
+    fn show_loaded_table_items(
+        &mut self,
+        desc: TableDescription,
+        options: ScanOptions,
+        loaded: LoadedItems,
+    ) {
+        let pending_filter = self.pending_filter.take();
+        self.last_load_stats = Some(loaded.stats);
+        if matches!(self.view_stack.current_view(), View::Table(_)) {
+            // when reloading in table view, pop current table view first
+            self.view_stack.pop();
+        }
+        self.add_item_attribute_names(&desc.table_name, &loaded.items);
+        if loaded.items.is_empty() {
+            let msg = if options.filter.is_some() {
+                "No items matched the filter expression".to_string()
+            } else {
+                format!("Table {} has no items", desc.table_name)
+            };
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+        } else {
+            self.notify_success(loaded.stats.summary());
+            self.push_table_view(desc, loaded, ItemsSource::Scan(options));
+            if let Some(query) = pending_filter {
+                self.apply_filter(query);
+            }
+        }
+    }
+
+    fn open_partiql(&mut self, desc: TableDescription) {
+        let attribute_names = self
+            .attribute_names
+            .get(&desc.table_name)
+            .map(|names| names.iter().cloned().collect())
+            .unwrap_or_default();
+        let view = View::of_partiql(
+            desc,
+            self.table_names.clone(),
+            attribute_names,
+            &self.mapper,
+            self.theme,
+            self.tx.clone(),
+        );
+        self.view_stack.push(view);
+    }
+
+    fn open_filter_builder(&mut self, desc: TableDescription) {
+        let mut attribute_names: BTreeSet<String> = self
+            .attribute_names
+            .get(&desc.table_name)
+            .cloned()
+            .unwrap_or_default();
+        // key attributes are always available even if no items have been loaded yet
+        for def in &desc.attribute_definitions {
+            attribute_names.insert(def.attribute_name.clone());
+        }
+        let view = View::of_filter_builder(
+            desc,
+            attribute_names.into_iter().collect(),
+            &self.mapper,
+            self.theme,
+            self.tx.clone(),
+        );
+        self.view_stack.push(view);
+    }
+
+    fn open_query_builder(&mut self, desc: TableDescription) {
+        let view = View::of_query_builder(desc, &self.mapper, self.theme, self.tx.clone());
+        self.view_stack.push(view);
+    }
+
+    fn query_table_items(&mut self, desc: TableDescription, options: QueryOptions) {
+        self.loading.insert(Loading::QueryTableItems);
+        let client = self.client.clone();
+        let page_size = self.config.query.page_size;
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = client
+                .query_items(&desc.table_name, &options, page_size)
+                .await;
+            tx.send(AppEvent::CompleteQueryTableItems(desc, options, result));
+        });
+    }
+
+    fn complete_query_table_items(
+        &mut self,
+        desc: TableDescription,
+        options: QueryOptions,
+        result: AppResult<LoadedItems>,
+    ) {
+        match result {
+            Ok(loaded) => {
+                self.last_load_stats = Some(loaded.stats);
+                if matches!(self.view_stack.current_view(), View::Table(_)) {
+                    // when reloading in table view, pop current table view first
+                    self.view_stack.pop();
+                }
+                self.add_item_attribute_names(&desc.table_name, &loaded.items);
+                if loaded.items.is_empty() {
+                    let msg = "No items matched the query";
+                    self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                } else {
+                    self.notify_success(loaded.stats.summary());
+                    self.push_table_view(desc, loaded, ItemsSource::Query(options));
+                }
+            }
+            Err(e) => {
+                let retry = AppEvent::QueryTableItems(desc, options);
+                self.tx
+                    .send(AppEvent::NotifyErrorWithRetry(e, Box::new(retry)));
+            }
+        }
+        self.loading.remove(&Loading::QueryTableItems);
+    }
+
+    fn write_items(&mut self, desc: TableDescription, requests: Vec<WriteRequest>) {
+        if requests.is_empty() {
+            return;
+        }
+
+        if self.dry_run {
+            self.dry_run_write_items(desc, requests);
+            return;
+        }
+
+        let write_mode = self.config.write.table_write_mode(&desc.table_name);
+        if write_mode == Some(TableWriteMode::ReadOnly) {
+            let msg = format!(
+                "Table {} is read-only by the config, write operations are not allowed",
+                desc.table_name
+            );
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+
+        let threshold = self.config.write.low_capacity_threshold_units;
+        if let Some(wcu) = desc.low_write_capacity_units(threshold) {
+            let options = self.write_options(&desc);
+            let msg = format!(
+                "Table {} has only {wcu} WCUs provisioned, items will be written in chunks of {}",
+                desc.table_name, options.chunk_size
+            );
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+        }
+
+        let title = format!(
+            "{} in {} - continue?",
+            write_requests_summary(&requests),
+            desc.table_name
+        );
+        let execute = if write_mode == Some(TableWriteMode::Confirm) {
+            let table_name = desc.table_name.clone();
+            confirm_table_name_prompt(table_name, AppEvent::ExecuteWriteItems(desc, requests))
+        } else {
+            AppEvent::ExecuteWriteItems(desc, requests)
+        };
+        let items = vec![
+            ("Execute".into(), execute),
+            ("Cancel".into(), AppEvent::ClearStatus),
+        ];
+        self.open_select_dialog(title, items);
+    }
+
+    fn create_item(&mut self, desc: TableDescription, template: String, content: String) {
+        if content.trim() == template.trim() {
+            let msg = "Item was not edited, creation cancelled";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        let attributes = match parse_raw_json_item(&content) {
+            Ok(attributes) => attributes,
+            Err(e) => {
+                let message = e.describe(&content);
+                self.open_invalid_item_dialog(desc, content, message);
+                return;
+            }
+        };
+        let item = Item {
+            attributes: attributes.into_iter().collect(),
+        };
+        if to_key_attributes(&item, &desc.key_schema_type).is_none() {
+            let message = vec!["Item does not contain all key attributes".into()];
+            self.open_invalid_item_dialog(desc, content, message);
+            return;
+        }
+        self.write_items(desc, vec![WriteRequest::Put { item }]);
+    }
+
+    // Keep the edited content so that the user can fix it and retry
+    fn open_invalid_item_dialog(
+        &mut self,
+        desc: TableDescription,
+        content: String,
+        message: Vec<String>,
+    ) {
+        let items = vec![
+            ("Edit again".into(), AppEvent::EditNewItem(desc, content)),
+            ("Discard".into(), AppEvent::ClearStatus),
+        ];
+        let (labels, events): (Vec<String>, Vec<AppEvent>) = items.into_iter().unzip();
+        let state = SelectDialogState::new("Invalid item", labels).with_message(message);
+        self.dialog = Some((state, events));
+    }
+
+    fn dry_run_write_items(&mut self, desc: TableDescription, requests: Vec<WriteRequest>) {
+        let options = self.write_options(&desc);
+        let now = Local::now();
+        let entries: Vec<_> = requests
+            .iter()
+            .map(|request| {
+                AuditEntry::new(now, &desc.table_name, &desc.key_schema_type, request, None)
+            })
+            .collect();
+        let batches = requests.len().div_ceil(options.chunk_size);
+        let msg = format!(
+            "Dry run: {} would be sent to {} in {batches} batches of up to {} requests",
+            write_requests_summary(&requests),
+            desc.table_name,
+            options.chunk_size
+        );
+        let view = View::of_dry_run(
+            entries,
+            &self.mapper,
+            self.timezone,
+            self.theme,
+            self.tx.clone(),
+        );
+        self.view_stack.push(view);
+        self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+    }
+
+    fn toggle_dry_run(&mut self) {
+        self.dry_run = !self.dry_run;
+        let msg = if self.dry_run {
+            "Dry-run mode enabled, write operations will not be executed"
+        } else {
+            "Dry-run mode disabled"
+        };
+        self.tx.send(AppEvent::NotifySuccess(msg.into()));
+    }
+
+    fn write_options(&self, desc: &TableDescription) -> WriteOptions {
+        let threshold = self.config.write.low_capacity_threshold_units;
+        let interval = Duration::from_millis(self.config.write.low_capacity_interval_millis);
+        WriteOptions::new(desc, threshold, interval)
+    }
+
+    fn execute_write_items(&mut self, desc: TableDescription, requests: Vec<WriteRequest>) {
+        self.loading.insert(Loading::WriteItems);
+        self.progress = Some((0, requests.len()));
+        let abort = Arc::new(AtomicBool::new(false));
+        self.write_abort = Some(abort.clone());
+        let options = self.write_options(&desc);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let total = requests.len();
+            let on_progress = |written| tx.send(AppEvent::Progress(written, total));
+            let result = client
+                .write_items(&desc.table_name, &requests, &options, &abort, on_progress)
+                .await;
+            tx.send(AppEvent::CompleteWriteItems(desc, requests, result));
+        });
+    }
+
+    fn abort_write_items(&self) {
+        if let Some(abort) = &self.write_abort {
+            abort.store(true, Ordering::Relaxed);
+            let msg = "Aborting after the current chunk...";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+        }
+    }
+
+    fn complete_write_items(
+        &mut self,
+        desc: TableDescription,
+        mut requests: Vec<WriteRequest>,
+        result: AppResult<usize>,
+    ) {
+        self.progress = None;
+        self.write_abort = None;
+
+        // requests after the aborted chunk were never sent
+        let total = requests.len();
+        if let Ok(count) = result {
+            requests.truncate(count);
+        }
+
+        // record the requests even if they failed, since some of them may have been written
+        let error = result.as_ref().err().map(|e| e.msg.clone());
+        self.write_audit_log(&desc, &requests, error);
+
+        match result {
+            Ok(count) => {
+                let schema = &desc.key_schema_type;
+                let updates: Vec<_> = requests
+                    .into_iter()
+                    .filter_map(|request| request.into_result(schema))
+                    .collect();
+                for view in self.view_stack.views_mut() {
+                    if let View::Table(view) = view {
+                        if view.table_name() == desc.table_name {
+                            view.update_items(updates.clone());
+                        }
+                    }
+                }
+                // the item view of a deleted item is no longer meaningful
+                if let View::Item(view) = self.view_stack.current_view_mut() {
+                    let key = view.key_attributes();
+                    match updates.iter().find(|(k, _)| Some(k) == key.as_ref()) {
+                        Some((_, Some(item))) => view.set_item(item.clone()),
+                        Some((_, None)) => {
+                            self.view_stack.pop();
+                        }
+                        None => {}
+                    }
+                }
+                if count < total {
+                    let msg = format!(
+                        "Aborted: {} of {} items written to {}",
+                        format_count(count as u64),
+                        format_count(total as u64),
+                        desc.table_name
+                    );
+                    self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                } else {
+                    let msg = format!(
+                        "{} items written to {}",
+                        format_count(count as u64),
+                        desc.table_name
+                    );
+                    self.tx.send(AppEvent::NotifySuccess(msg));
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+        self.loading.remove(&Loading::WriteItems);
+    }
+
+    fn write_audit_log(
+        &self,
+        desc: &TableDescription,
+        requests: &[WriteRequest],
+        error: Option<String>,
+    ) {
+        let now = Local::now();
+        let entries: Vec<_> = requests
+            .iter()
+            .map(|request| {
+                AuditEntry::new(
+                    now,
+                    &desc.table_name,
+                    &desc.key_schema_type,
+                    request,
+                    error.clone(),
+                )
+            })
+            .collect();
+        if let Err(e) = self.audit_log.append(&entries) {
+            self.tx.send(AppEvent::NotifyError(e));
+        }
+    }
+
+    fn open_audit_log(&mut self) {
+        match self.audit_log.load() {
+            Ok(entries) if entries.is_empty() => {
+                let msg = "No operations have been recorded in the audit log";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+            Ok(mut entries) => {
+                // show the latest operation first
+                entries.reverse();
+                let view = View::of_audit_log(
+                    entries,
+                    &self.mapper,
+                    self.timezone,
+                    self.theme,
+                    self.tx.clone(),
+                );
+                self.view_stack.push(view);
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+    }
+
+    fn execute_statement(&mut self, desc: TableDescription, statement: String) {
+        self.loading.insert(Loading::ExecuteStatement);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = client.execute_statement(&statement).await;
+            tx.send(AppEvent::CompleteExecuteStatement(desc, statement, result));
+        });
+    }
+
+    fn complete_execute_statement(
+        &mut self,
+        desc: TableDescription,
+        statement: String,
+        result: AppResult<LoadedItems>,
+    ) {
+        match result {
+            Ok(loaded) => {
+                self.last_load_stats = Some(loaded.stats);
+                self.add_item_attribute_names(&desc.table_name, &loaded.items);
+                if matches!(self.view_stack.current_view(), View::Table(_)) {
+                    // when reloading in table view, pop current table view first
+                    self.view_stack.pop();
+                }
+                if loaded.items.is_empty() {
+                    let msg = "Statement returned no items";
+                    self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                } else {
+                    self.notify_success(loaded.stats.summary());
+                    self.push_table_view(desc, loaded, ItemsSource::Statement(statement));
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+        self.loading.remove(&Loading::ExecuteStatement);
+    }
+
+    fn push_table_view(
+        &mut self,
+        desc: TableDescription,
+        loaded: LoadedItems,
+        source: ItemsSource,
+    ) {
+        let permissions = self.table_permissions.get(&desc.table_name).copied();
+        if permissions.is_none() && self.should_probe_permissions(&desc.table_name) {
+            self.tx.send(AppEvent::ProbeTablePermissions(desc.clone()));
+        }
+        let preferences = self.state.view_preferences(&desc.table_name);
+        let mut view = View::of_table(
+            desc,
+            loaded,
+            source,
+            &self.mapper,
+            self.config.ui.table.clone(),
+            self.theme,
+            self.tx.clone(),
+        );
+        view.set_permissions(permissions.unwrap_or_default());
+        view.set_preferences(preferences);
+        self.view_stack.push(view);
+    }
+
+    fn open_item(&mut self, desc: TableDescription, item: Item) {
+        let permissions = self.table_permissions.get(&desc.table_name).copied();
+        let mut view = View::of_item(desc, item, &self.mapper, self.theme, self.tx.clone());
+        view.set_permissions(permissions.unwrap_or_default());
+        self.view_stack.push(view);
+    }
+
+    fn open_adjacent_item(&mut self, forward: bool) {
+        let item = match self.view_stack.parent_view_mut() {
+            Some(View::Table(view)) => view.select_adjacent_item(forward),
+            _ => {
+                let msg = "Items can only be moved through when opened from the table";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                return;
+            }
+        };
+        match item {
+            Some(item) => {
+                if let View::Item(view) = self.view_stack.current_view_mut() {
+                    view.set_item(item);
+                }
+            }
+            None => {
+                let msg = if forward {
+                    "No next item in the table"
+                } else {
+                    "No previous item in the table"
+                };
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+        }
+    }
+
+    // The probe is a real (conditional) DeleteItem, so it is not sent when no writes are expected
+    fn should_probe_permissions(&self, table_name: &str) -> bool {
+        self.config.write.conditional_delete_probe
+            && !self.dry_run
+            && self.config.write.table_write_mode(table_name) != Some(TableWriteMode::ReadOnly)
+    }
+
+    fn probe_table_permissions(&mut self, desc: TableDescription) {
+        // runs in the background without blocking key inputs
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let permissions = client.probe_table_permissions(&desc).await;
+            tx.send(AppEvent::CompleteProbeTablePermissions(
+                desc.table_name,
+                permissions,
+            ));
+        });
+    }
+
+    fn complete_probe_table_permissions(
+        &mut self,
+        table_name: String,
+        permissions: TablePermissions,
+    ) {
+        for view in self.view_stack.views_mut() {
+            if view.table_name() == Some(table_name.as_str()) {
+                view.set_permissions(permissions);
+            }
+        }
+        self.table_permissions.insert(table_name, permissions);
+    }
+
+    fn refresh_item(&mut self, desc: TableDescription, key: BTreeMap<String, Attribute>) {
+        self.loading.insert(Loading::RefreshItem);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = client.get_item(&desc.table_name, &key).await;
+            tx.send(AppEvent::CompleteRefreshItem(desc, key, result));
+        });
+    }
+
+    fn check_item_exists(&mut self, desc: TableDescription, key: BTreeMap<String, Attribute>) {
+        self.loading.insert(Loading::CheckItemExists);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = client.get_item(&desc.table_name, &key).await;
+            tx.send(AppEvent::CompleteCheckItemExists(desc, key, result));
+        });
+    }
+
+    fn complete_check_item_exists(
+        &mut self,
+        desc: TableDescription,
+        key: BTreeMap<String, Attribute>,
+        result: AppResult<Option<Item>>,
+    ) {
+        let key_string = key
+            .iter()
+            .map(|(name, value)| format!("{name}={}", value.to_simple_string()))
+            .collect::<Vec<_>>()
+            .join(",");
+        match result {
+            Ok(Some(item)) => {
+                self.add_item_attribute_names(&desc.table_name, std::slice::from_ref(&item));
+                self.open_item(desc, item);
+                let msg = format!("Item exists: {key_string}");
+                self.tx.send(AppEvent::NotifySuccess(msg));
+            }
+            Ok(None) => {
+                let msg = format!("Item does not exist: {key_string}");
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+            Err(e) => {
+                let retry = AppEvent::CheckItemExists(desc, key);
+                self.tx
+                    .send(AppEvent::NotifyErrorWithRetry(e, Box::new(retry)));
+            }
+        }
+        self.loading.remove(&Loading::CheckItemExists);
+    }
+
+    fn complete_refresh_item(
+        &mut self,
+        desc: TableDescription,
+        key: BTreeMap<String, Attribute>,
+        result: AppResult<Option<Item>>,
+    ) {
+        match result {
+            Ok(item) => {
+                for view in self.view_stack.views_mut() {
+                    match view {
+                        View::Table(view) if view.table_name() == desc.table_name => {
+                            view.update_items(vec![(key.clone(), item.clone())]);
+                        }
+                        View::Item(view) if view.table_name() == desc.table_name => {
+                            if let Some(item) = &item {
+                                if view.key_attributes().as_ref() == Some(&key) {
+                                    view.set_item(item.clone());
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                match item {
+                    Some(item) => {
+                        self.add_item_attribute_names(&desc.table_name, &[item]);
+                        self.tx
+                            .send(AppEvent::NotifySuccess("Item refreshed".into()));
+                    }
+                    None => {
+                        let msg = "Item no longer exists";
+                        self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                    }
+                }
+            }
+            Err(e) => {
+                let retry = AppEvent::RefreshItem(desc, key);
+                self.tx
+                    .send(AppEvent::NotifyErrorWithRetry(e, Box::new(retry)));
+            }
+        }
+        self.loading.remove(&Loading::RefreshItem);
     }
 
-    fn open_item(&mut self, desc: TableDescription, item: Item) {
-        let view = View::of_item(desc, item, &self.mapper, self.theme, self.tx.clone());
+    fn open_table_insight(&mut self, desc: TableDescription, items: Vec<Item>) {
+        let attribute_names = &self.config.ui.insight.near_duplicate_attributes;
+        let insight =
+            TableInsight::new(&desc, &items).with_near_duplicates(&desc, &items, attribute_names);
+        let view = View::of_table_insight(
+            insight,
+            &self.mapper,
+            self.config.ui.insight.clone(),
+            self.theme,
+            self.tx.clone(),
+        );
         self.view_stack.push(view);
     }
 
-    fn open_table_insight(&mut self, insight: TableInsight) {
-        let view = View::of_table_insight(insight, &self.mapper, self.theme, self.tx.clone());
+    fn update_contributor_insights(
+        &mut self,
+        table_name: String,
+        index_name: Option<String>,
+        enable: bool,
+    ) {
+        if self.dry_run {
+            let msg = "Contributor Insights cannot be updated in dry-run mode";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        match self.config.write.table_write_mode(&table_name) {
+            Some(TableWriteMode::ReadOnly) => {
+                let msg = format!("Table {table_name} is read-only by the config");
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+            Some(TableWriteMode::Confirm) => {
+                let execute = AppEvent::ExecuteUpdateContributorInsights(
+                    table_name.clone(),
+                    index_name,
+                    enable,
+                );
+                self.tx.send(confirm_table_name_prompt(table_name, execute));
+            }
+            None => {
+                self.execute_update_contributor_insights(table_name, index_name, enable);
+            }
+        }
+    }
+
+    fn execute_update_contributor_insights(
+        &mut self,
+        table_name: String,
+        index_name: Option<String>,
+        enable: bool,
+    ) {
+        self.loading.insert(Loading::UpdateContributorInsights);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = client
+                .update_contributor_insights(&table_name, index_name.as_deref(), enable)
+                .await;
+            tx.send(AppEvent::CompleteUpdateContributorInsights(
+                table_name, enable, result,
+            ));
+        });
+    }
+
+    fn complete_update_contributor_insights(
+        &mut self,
+        table_name: String,
+        enable: bool,
+        result: AppResult<()>,
+    ) {
+        self.loading.remove(&Loading::UpdateContributorInsights);
+        match result {
+            Ok(_) => {
+                let action = if enable { "enabling" } else { "disabling" };
+                let msg = format!("Started {action} Contributor Insights for {table_name}");
+                self.tx.send(AppEvent::NotifySuccess(msg));
+                if matches!(self.view_stack.current_view(), View::TableList(_)) {
+                    self.tx.send(AppEvent::LoadTableDescription(table_name));
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+    }
+
+    fn diff_tables(&mut self, left: String, right: String) {
+        self.loading.insert(Loading::DiffTables);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = async {
+                let (left, right) = tokio::join!(
+                    client.describe_table_with_ttl(&left),
+                    client.describe_table_with_ttl(&right),
+                );
+                Ok((left?, right?))
+            }
+            .await;
+            tx.send(AppEvent::CompleteDiffTables(result));
+        });
+    }
+
+    fn complete_diff_tables(&mut self, result: AppResult<(TableDescription, TableDescription)>) {
+        match result {
+            Ok((left, right)) => {
+                let view =
+                    View::of_table_diff(left, right, &self.mapper, self.theme, self.tx.clone());
+                self.view_stack.push(view);
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+        self.loading.remove(&Loading::DiffTables);
+    }
+
+    fn load_account_limits(&mut self) {
+        self.loading.insert(Loading::AccountLimits);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        let table_names = self.table_names.clone();
+        spawn(async move {
+            let result = client.describe_account_limits(&table_names).await;
+            tx.send(AppEvent::CompleteLoadAccountLimits(result));
+        });
+    }
+
+    fn complete_load_account_limits(&mut self, result: AppResult<AccountLimits>) {
+        match result {
+            Ok(limits) => {
+                let view =
+                    View::of_account_limits(limits, &self.mapper, self.theme, self.tx.clone());
+                self.view_stack.push(view);
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+        self.loading.remove(&Loading::AccountLimits);
+    }
+
+    fn compare_items(&mut self, desc: TableDescription, items: Vec<Item>, statement: String) {
+        self.loading.insert(Loading::CompareItems);
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = client.execute_statement(&statement).await;
+            tx.send(AppEvent::CompleteCompareItems(
+                desc, items, statement, result,
+            ));
+        });
+    }
+
+    fn complete_compare_items(
+        &mut self,
+        desc: TableDescription,
+        items: Vec<Item>,
+        statement: String,
+        result: AppResult<LoadedItems>,
+    ) {
+        match result {
+            Ok(loaded) => {
+                let comparison = compare_items(&items, &loaded.items, &desc.key_schema_type);
+                let view = View::of_items_comparison(
+                    desc.table_name,
+                    statement,
+                    comparison,
+                    &self.mapper,
+                    self.theme,
+                    self.tx.clone(),
+                );
+                self.view_stack.push(view);
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+        self.loading.remove(&Loading::CompareItems);
+    }
+
+    fn open_side_by_side(
+        &mut self,
+        desc: TableDescription,
+        loaded: LoadedItems,
+        source: ItemsSource,
+        environment: String,
+    ) {
+        let (profile, region) = parse_environment(&environment);
+        let left = SideBySidePane {
+            desc,
+            loaded,
+            source,
+            environment: environment_label(&self.client),
+        };
+
+        self.loading.insert(Loading::OpenSideBySide);
+        let default_region = self.config.default_region.clone();
+        let compat_mode = self.config.compat_mode;
+        let scan_page_size = self.config.scan.page_size;
+        let memory_limit_bytes = self.config.scan.memory_limit_bytes;
+        let query_page_size = self.config.query.page_size;
+        let operation_log = self.client.operation_log().clone();
+        let tx = self.tx.clone();
+        spawn(async move {
+            // the endpoint url is not inherited since another environment is usually a different account or region
+            let client = Client::new(
+                region,
+                None,
+                profile,
+                None,
+                default_region,
+                compat_mode,
+                operation_log,
+            )
+            .await;
+            let result = async {
+                let desc = client
+                    .describe_table_with_ttl(&left.desc.table_name)
+                    .await?;
+                let loaded = match &left.source {
+                    ItemsSource::Scan(options) => {
+                        client
+                            .scan_all_items(
+                                &desc.table_name,
+                                &desc.key_schema_type,
+                                options,
+                                scan_page_size,
+                                memory_limit_bytes,
+                                |_| {},
+                            )
+                            .await?
+                    }
+                    ItemsSource::Query(options) => {
+                        client
+                            .query_items(&desc.table_name, options, query_page_size)
+                            .await?
+                    }
+                    ItemsSource::Statement(statement) => {
+                        client.execute_statement(statement).await?
+                    }
+                };
+                Ok(Box::new(SideBySidePane {
+                    desc,
+                    loaded,
+                    source: left.source.clone(),
+                    environment: environment_label(&client),
+                }))
+            }
+            .await;
+            tx.send(AppEvent::CompleteOpenSideBySide(Box::new(left), result));
+        });
+    }
+
+    fn complete_open_side_by_side(
+        &mut self,
+        left: Box<SideBySidePane>,
+        result: AppResult<Box<SideBySidePane>>,
+    ) {
+        match result {
+            Ok(right) => {
+                let view = View::of_side_by_side(
+                    *left,
+                    *right,
+                    &self.mapper,
+                    self.config.ui.table.clone(),
+                    self.theme,
+                    self.tx.clone(),
+                );
+                self.view_stack.push(view);
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+        self.loading.remove(&Loading::OpenSideBySide);
+    }
+
+    fn jump_to_reference(&mut self, table_name: String, attribute_name: String, value: Attribute) {
+        let remembered = self.state.reference_table(&table_name, &attribute_name);
+        // the table chosen last time for the attribute comes first
+        let mut targets: Vec<&String> = self.table_names.iter().collect();
+        targets.sort_by_key(|name| Some(name.as_str()) != remembered);
+        let items = targets
+            .into_iter()
+            .map(|target| {
+                let event = AppEvent::LoadReferencedItems(
+                    table_name.clone(),
+                    attribute_name.clone(),
+                    target.clone(),
+                    value.clone(),
+                );
+                (target.clone(), event)
+            })
+            .collect();
+        let title = format!("Jump to {} in", value.to_simple_string());
+        self.open_select_dialog(title, items);
+    }
+
+    fn load_referenced_items(
+        &mut self,
+        table_name: String,
+        attribute_name: String,
+        target: String,
+        value: Attribute,
+    ) {
+        self.state
+            .set_reference_table(&table_name, &attribute_name, &target);
+        if let Err(e) = self.state.save() {
+            self.tx.send(AppEvent::NotifyError(e));
+        }
+
+        self.loading.insert(Loading::ReferencedItems);
+        let client = self.client.clone();
+        let page_size = self.config.query.page_size;
+        let tx = self.tx.clone();
+        spawn(async move {
+            let result = async {
+                let desc = client.describe_table_with_ttl(&target).await?;
+                let options = desc.reference_query_options(&value)?;
+                let loaded = client
+                    .query_items(&desc.table_name, &options, page_size)
+                    .await?;
+                Ok((desc, options, loaded))
+            }
+            .await;
+            tx.send(AppEvent::CompleteLoadReferencedItems(result));
+        });
+    }
+
+    fn complete_load_referenced_items(
+        &mut self,
+        result: AppResult<(TableDescription, QueryOptions, LoadedItems)>,
+    ) {
+        match result {
+            Ok((desc, _, loaded)) if loaded.items.is_empty() => {
+                let msg = format!("No items found in {}", desc.table_name);
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+            Ok((desc, options, loaded)) => {
+                self.last_load_stats = Some(loaded.stats);
+                self.add_item_attribute_names(&desc.table_name, &loaded.items);
+                self.notify_success(loaded.stats.summary());
+                // open the item directly if it is the only one, e.g. the table has no sort key
+                let item = match loaded.items.as_slice() {
+                    [item] => Some(item.clone()),
+                    _ => None,
+                };
+                self.push_table_view(desc.clone(), loaded, ItemsSource::Query(options));
+                if let Some(item) = item {
+                    self.open_item(desc, item);
+                }
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+        self.loading.remove(&Loading::ReferencedItems);
+    }
+
+    fn validate_items(&mut self, desc: TableDescription, items: Vec<Item>) {
+        let path = match self.config.validation.schemas.get(&desc.table_name) {
+            Some(path) => path,
+            None => {
+                let msg = format!("No JSON Schema is registered for {}", desc.table_name);
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                return;
+            }
+        };
+        let schema = match ItemSchema::load(path) {
+            Ok(schema) => schema,
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+                return;
+            }
+        };
+
+        let report = ValidationReport::new(&desc, &items, &schema);
+        if report.invalid_items.is_empty() {
+            let msg = format!(
+                "All {} items conform to the schema",
+                format_count(items.len() as u64)
+            );
+            self.tx.send(AppEvent::NotifySuccess(msg));
+            return;
+        }
+        let view = View::of_validation_report(report, &self.mapper, self.theme, self.tx.clone());
         self.view_stack.push(view);
     }
 
@@ -339,14 +2542,257 @@ impl App {
         self.view_stack.push(view);
     }
 
+    fn open_table_in_console(&self, table_name: String) {
+        let region = match self.client.region() {
+            Some(region) => region,
+            None => {
+                let e = AppError::msg("Region is not available");
+                self.tx.send(AppEvent::NotifyError(e));
+                return;
+            }
+        };
+        let url = crate::util::console_table_url(region, &table_name);
+        match crate::util::open_in_browser(&url) {
+            Ok(_) => {
+                let msg = format!("Opened table {table_name} in browser");
+                self.tx.send(AppEvent::NotifySuccess(msg));
+            }
+            Err(_) => {
+                // fallback to copying the url if the browser cannot be opened
+                self.copy_to_clipboard("console url".into(), url);
+            }
+        }
+    }
+
+    fn open_link(&self, link: String) {
+        let url = crate::util::link_url(&link);
+        match crate::util::open_in_browser(&url) {
+            Ok(_) => {
+                let msg = format!("Opened {link} in browser");
+                self.tx.send(AppEvent::NotifySuccess(msg));
+            }
+            Err(_) => {
+                // fallback to copying the url if the browser cannot be opened
+                self.copy_to_clipboard("link".into(), url);
+            }
+        }
+    }
+
+    fn add_attribute_names(&mut self, table_name: &str, names: impl IntoIterator<Item = String>) {
+        self.attribute_names
+            .entry(table_name.into())
+            .or_default()
+            .extend(names);
+    }
+
+    fn add_item_attribute_names(&mut self, table_name: &str, items: &[Item]) {
+        let names = items
+            .iter()
+            .flat_map(|item| item.attributes.keys().cloned());
+        self.add_attribute_names(table_name, names);
+
+        let recent_values = self.recent_values.entry(table_name.into()).or_default();
+        for attr in items.iter().flat_map(|item| item.attributes.values()) {
+            match attr {
+                Attribute::S(s) => recent_values.push(s.clone()),
+                Attribute::N(n) => recent_values.push(n.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    fn open_quick_open(&mut self) {
+        let (table_names, labels) = quick_open_candidates(
+            "",
+            &self.table_names,
+            &self.attribute_names,
+            &self.recent_values,
+        );
+        self.quick_open = Some(QuickOpen {
+            input: Input::default(),
+            table_names,
+            dialog: SelectDialogState::new("Tables", labels),
+        });
+    }
+
+    fn jump_to_table(&mut self, table_name: String) {
+        while !matches!(self.view_stack.current_view(), View::TableList(_)) {
+            if !self.view_stack.pop() {
+                return;
+            }
+        }
+        if let View::TableList(view) = self.view_stack.current_view_mut() {
+            view.select_table(&table_name);
+        }
+    }
+
+    fn open_prompt(&mut self, title: String, on_submit: PromptCallback) {
+        self.prompt = Some(Prompt {
+            title,
+            input: Input::default(),
+            on_submit,
+        });
+    }
+
+    fn open_select_dialog(&mut self, title: String, items: Vec<(String, AppEvent)>) {
+        if items.is_empty() {
+            return;
+        }
+        let (labels, events): (Vec<String>, Vec<AppEvent>) = items.into_iter().unzip();
+        let state = SelectDialogState::new(title, labels);
+        self.dialog = Some((state, events));
+    }
+
+    fn open_saved_filters(&mut self, desc: TableDescription) {
+        let filters = self.state.saved_filters(&desc.table_name);
+        let queries = self.state.saved_queries(&desc.table_name);
+        if filters.is_empty() && queries.is_empty() {
+            let msg = format!("Table {} has no saved filters or queries", desc.table_name);
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+
+        let in_table_view = matches!(self.view_stack.current_view(), View::Table(_));
+        let items = filters
+            .iter()
+            .map(|filter| {
+                let label = format!("{}: {}", filter.name, filter.query);
+                let event = if in_table_view {
+                    AppEvent::ApplyFilter(filter.query.clone())
+                } else {
+                    AppEvent::OpenTableWithFilter(desc.clone(), filter.query.clone())
+                };
+                (label, event)
+            })
+            .chain(queries.iter().map(|query| {
+                let label = format!("{}: {}", query.name, query.statement);
+                let event = AppEvent::ExecuteStatement(desc.clone(), query.statement.clone());
+                (label, event)
+            }))
+            .collect();
+        self.open_select_dialog("Saved filters".into(), items);
+    }
+
+    fn open_key_templates(&mut self, desc: TableDescription) {
+        let templates = match self.config.query.templates.get(&desc.table_name) {
+            Some(templates) if !templates.is_empty() => templates.clone(),
+            _ => {
+                let msg = format!("Table {} has no key templates", desc.table_name);
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                return;
+            }
+        };
+
+        let items = templates
+            .into_iter()
+            .map(|template| {
+                let mut label = format!("{}: {}", template.name, template.hash_key);
+                if let Some(sort_key) = &template.sort_key {
+                    label.push_str(&format!(" / {sort_key}"));
+                }
+                let event = fill_key_template_event(desc.clone(), template, Vec::new());
+                (label, event)
+            })
+            .collect();
+        self.open_select_dialog("Key templates".into(), items);
+    }
+
+    fn open_table_with_filter(&mut self, desc: TableDescription, query: String) {
+        self.load_table_items(desc, ScanOptions::default(), Some(query));
+    }
+
+    fn save_filter(&mut self, table_name: String, filter: SavedFilter) {
+        let name = filter.name.clone();
+        self.state.add_saved_filter(&table_name, filter);
+        match self.state.save() {
+            Ok(_) => {
+                let msg = format!("Saved filter {name} successfully");
+                self.tx.send(AppEvent::NotifySuccess(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+    }
+
+    fn save_query(&mut self, table_name: String, query: SavedQuery) {
+        let name = query.name.clone();
+        self.state.add_saved_query(&table_name, query);
+        match self.state.save() {
+            Ok(_) => {
+                let msg = format!("Saved query {name} successfully");
+                self.tx.send(AppEvent::NotifySuccess(msg));
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyError(e));
+            }
+        }
+    }
+
+    fn save_view_preferences(&mut self, table_name: String, preferences: TableViewPreferences) {
+        self.state.set_view_preferences(&table_name, preferences);
+        if let Err(e) = self.state.save() {
+            self.tx.send(AppEvent::NotifyError(e));
+        }
+    }
+
+    fn apply_filter(&mut self, query: String) {
+        if let View::Table(view) = self.view_stack.current_view_mut() {
+            view.apply_filter_query(query);
+        }
+    }
+
     fn back_to_before_view(&mut self) {
         self.view_stack.pop();
     }
 
     fn copy_to_clipboard(&self, name: String, content: String) {
-        match crate::util::copy_to_clipboard(&content) {
+        let tx = self.tx.clone();
+        // some clipboard managers take a while to respond, so do not block the UI thread
+        spawn_blocking(move || match crate::util::copy_to_clipboard(&content) {
             Ok(_) => {
                 let msg = format!("Copied {name} to clipboard successfully");
+                tx.send(AppEvent::NotifySuccess(msg));
+            }
+            Err(e) => {
+                tx.send(AppEvent::NotifyError(e));
+            }
+        });
+    }
+
+    fn pipe_to_command(&mut self, name: String, content: String) {
+        let title = format!("Pipe {name} to command");
+        let on_submit = Box::new(move |command| AppEvent::ExecuteCommand(command, content));
+        self.open_prompt(title, on_submit);
+    }
+
+    // Run an external program which uses the terminal while the TUI is suspended
+    fn run_external<B: Backend + Write>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        rx: &Receiver,
+        f: impl FnOnce() -> AppResult<()>,
+    ) -> Result<(), B::Error> {
+        rx.pause();
+        let result = with_suspended_terminal(terminal.backend_mut(), f);
+        rx.resume();
+
+        if let Err(e) = result {
+            self.tx.send(AppEvent::NotifyError(e));
+        }
+        terminal.clear()
+    }
+
+    fn save_to_file(&mut self, name: String, content: String) {
+        let title = format!("Save {name} to file");
+        let on_submit = Box::new(move |path| AppEvent::WriteFile(name, path, content));
+        self.open_prompt(title, on_submit);
+    }
+
+    fn write_file(&self, name: String, path: String, content: String) {
+        match crate::util::write_to_file(&path, &content) {
+            Ok(path) => {
+                let msg = format!("Saved {name} to {}", path.display());
                 self.tx.send(AppEvent::NotifySuccess(msg));
             }
             Err(e) => {
@@ -372,6 +2818,136 @@ impl App {
     }
 
     fn notify_error(&mut self, e: AppError) {
-        self.status = Status::NotificationError(e.msg);
+        self.status = Status::NotificationError(e.msg, None);
+    }
+
+    fn notify_error_with_retry(&mut self, e: AppError, retry: Box<AppEvent>) {
+        self.status = Status::NotificationError(e.msg, Some(retry));
+    }
+}
+
+// Prompt for the placeholders of the template one by one, then query with the filled keys
+fn fill_key_template_event(
+    desc: TableDescription,
+    template: KeyTemplate,
+    mut values: Vec<(String, String)>,
+) -> AppEvent {
+    if let Some(name) = template.placeholders().get(values.len()).cloned() {
+        let title = format!("{}: {name}", template.name);
+        let on_submit = Box::new(move |value: String| {
+            values.push((name, value));
+            fill_key_template_event(desc, template, values)
+        });
+        return AppEvent::OpenPrompt(title, on_submit);
+    }
+
+    let hash_key = fill_template(&template.hash_key, &values);
+    let sort_key = template
+        .sort_key
+        .as_ref()
+        .map(|sort_key| fill_template(sort_key, &values));
+    let sort_key_value = sort_key
+        .as_deref()
+        .map(|value| (value, template.sort_key_prefix));
+    match desc.key_query_options(template.index_name.as_deref(), &hash_key, sort_key_value) {
+        Ok(options) => AppEvent::QueryTableItems(desc, options),
+        Err(e) => AppEvent::NotifyWarning(e),
+    }
+}
+
+// Typing the table name guards against writing to a table that was opened by mistake
+fn confirm_table_name_prompt(table_name: String, execute: AppEvent) -> AppEvent {
+    let title = format!("Type the table name ({table_name}) to confirm");
+    let on_submit = Box::new(move |input: String| {
+        if input.trim() == table_name {
+            execute
+        } else {
+            let msg = "Table name did not match, write cancelled";
+            AppEvent::NotifyWarning(AppError::msg(msg))
+        }
+    });
+    AppEvent::OpenPrompt(title, on_submit)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("{profile}@{region}", "dev@ap-northeast-1")]
+    #[case("{table} [{position}] {elapsed}", "users [3/120] 3.4s")]
+    #[case("{unknown}", "{unknown}")]
+    fn test_format_status_line(#[case] format: &str, #[case] expected: &str) {
+        let values = StatusLineValues {
+            profile: "dev",
+            region: Some("ap-northeast-1"),
+            table: Some("users"),
+            position: Some((3, 120)),
+            elapsed: Some(Duration::from_millis(3400)),
+        };
+        assert_eq!(format_status_line(format, &values), expected);
+    }
+
+    #[test]
+    fn test_format_status_line_missing_values() {
+        let values = StatusLineValues {
+            profile: "default",
+            region: None,
+            table: None,
+            position: None,
+            elapsed: None,
+        };
+        let actual = format_status_line("{region} {table} {position} {elapsed}", &values);
+        assert_eq!(actual, "- - - -");
+    }
+
+    #[rstest]
+    #[case("", vec!["orders", "users"])]
+    #[case("user", vec!["users"])]
+    #[case("mail", vec!["users (attribute: email)"])]
+    #[case("EXAMPLE.COM", vec!["users (value: bob@example.com)"])]
+    #[case("42", vec!["orders (value: 42)"])]
+    #[case("unknown", vec![])]
+    fn test_quick_open_candidates(#[case] query: &str, #[case] expected: Vec<&str>) {
+        let table_names = vec!["orders".to_string(), "users".to_string()];
+        let attribute_names = BTreeMap::from([(
+            "users".to_string(),
+            BTreeSet::from(["id".to_string(), "email".to_string()]),
+        )]);
+        let mut recent_values = BTreeMap::new();
+        let users: &mut RecentValues = recent_values.entry("users".to_string()).or_default();
+        users.push("alice@example.com".into());
+        users.push("bob@example.com".into());
+        let orders: &mut RecentValues = recent_values.entry("orders".to_string()).or_default();
+        orders.push("42".into());
+
+        let (_, labels) =
+            quick_open_candidates(query, &table_names, &attribute_names, &recent_values);
+        assert_eq!(labels, expected);
+    }
+
+    #[test]
+    fn test_recent_values_drop_oldest() {
+        let mut values = RecentValues::default();
+        for i in 0..MAX_RECENT_VALUES_PER_TABLE + 2 {
+            values.push(i.to_string());
+        }
+        values.push("5".into());
+
+        assert_eq!(values.values.len(), MAX_RECENT_VALUES_PER_TABLE);
+        assert_eq!(values.values.front(), Some(&"2".to_string()));
+        assert!(!values.seen.contains("0"));
+    }
+
+    #[rstest]
+    #[case(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE), true)]
+    #[case(KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT), true)]
+    #[case(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL), false)]
+    #[case(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE), false)]
+    #[case(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), false)]
+    fn test_is_text_input_key(#[case] key_event: KeyEvent, #[case] expected: bool) {
+        assert_eq!(is_text_input_key(key_event), expected);
     }
 }
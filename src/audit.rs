@@ -0,0 +1,246 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::{
+        to_key_attributes, Item, KeySchemaType, RawAttributeJsonWrapper, RawJsonItem, WriteRequest,
+        SET_ATTRIBUTE_CONDITION_EXPRESSION, SET_ATTRIBUTE_UPDATE_EXPRESSION,
+    },
+    error::{AppError, AppResult},
+};
+
+const AUDIT_LOG_PATH_ENV_VAR: &str = "DDV_AUDIT_LOG";
+const DEFAULT_AUDIT_LOG_DIR_NAME: &str = ".ddv";
+const DEFAULT_AUDIT_LOG_FILE_NAME: &str = "audit.jsonl";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Local>,
+    pub operation: String,
+    pub table_name: String,
+    pub key: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(
+        timestamp: DateTime<Local>,
+        table_name: &str,
+        schema: &KeySchemaType,
+        request: &WriteRequest,
+        error: Option<String>,
+    ) -> AuditEntry {
+        let (operation, key, request, before, after) = match request {
+            WriteRequest::Put { item } => {
+                let key = Item {
+                    attributes: to_key_attributes(item, schema)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect(),
+                };
+                let item = to_json(item, schema);
+                let request = serde_json::json!({ "PutRequest": { "Item": item } });
+                // the previous state of an overwritten item is unknown
+                ("PUT", to_json(&key, schema), request, None, Some(item))
+            }
+            WriteRequest::SetAttribute {
+                key,
+                item,
+                name,
+                value,
+            } => {
+                let key_json = to_json(
+                    &Item {
+                        attributes: key.clone().into_iter().collect(),
+                    },
+                    schema,
+                );
+                let request = serde_json::json!({
+                    "Key": key_json,
+                    "UpdateExpression": SET_ATTRIBUTE_UPDATE_EXPRESSION,
+                    "ConditionExpression": SET_ATTRIBUTE_CONDITION_EXPRESSION,
+                    "ExpressionAttributeNames": {
+                        "#name": name,
+                        "#key": key.keys().next(),
+                    },
+                    "ExpressionAttributeValues": {
+                        ":value": RawAttributeJsonWrapper::new(value),
+                    },
+                });
+                let mut after = item.clone();
+                after.attributes.insert(name.clone(), value.clone());
+                (
+                    "UPDATE",
+                    key_json,
+                    request,
+                    Some(to_json(item, schema)),
+                    Some(to_json(&after, schema)),
+                )
+            }
+            WriteRequest::Delete { key, item } => {
+                let key = Item {
+                    attributes: key.clone().into_iter().collect(),
+                };
+                let key = to_json(&key, schema);
+                // the element of the BatchWriteItem request that is sent for this operation
+                let request = serde_json::json!({ "DeleteRequest": { "Key": key } });
+                ("DELETE", key, request, Some(to_json(item, schema)), None)
+            }
+        };
+        AuditEntry {
+            timestamp,
+            operation: operation.into(),
+            table_name: table_name.into(),
+            key,
+            request: Some(request),
+            before,
+            after,
+            error,
+        }
+    }
+}
+
+fn to_json(item: &Item, schema: &KeySchemaType) -> serde_json::Value {
+    serde_json::to_value(RawJsonItem::new(item, schema)).unwrap()
+}
+
+// Mutating operations are appended to a local file as JSON Lines
+pub struct AuditLog {
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    pub fn new() -> AuditLog {
+        AuditLog {
+            path: audit_log_path(),
+        }
+    }
+
+    pub fn append(&self, entries: &[AuditEntry]) -> AppResult<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Err(AppError::msg("audit log file path is not available")),
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| AppError::new("failed to create audit log directory", e))?;
+        }
+        let mut content = String::new();
+        for entry in entries {
+            let line = serde_json::to_string(entry)
+                .map_err(|e| AppError::new("failed to serialize audit log entry", e))?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| AppError::new("failed to open audit log file", e))?;
+        file.write_all(content.as_bytes())
+            .map_err(|e| AppError::new("failed to write audit log file", e))
+    }
+
+    pub fn load(&self) -> AppResult<Vec<AuditEntry>> {
+        let path = match &self.path {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Vec::new()),
+        };
+        let content = fs::read_to_string(path)
+            .map_err(|e| AppError::new("failed to read audit log file", e))?;
+        parse_audit_entries(&content)
+    }
+}
+
+fn parse_audit_entries(content: &str) -> AppResult<Vec<AuditEntry>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                AppError::new(format!("failed to parse audit log at line {}", i + 1), e)
+            })
+        })
+        .collect()
+}
+
+fn audit_log_path() -> Option<PathBuf> {
+    match env::var(AUDIT_LOG_PATH_ENV_VAR) {
+        Ok(path) => Some(PathBuf::from(path)),
+        Err(_) => env::home_dir().map(|home| {
+            home.join(DEFAULT_AUDIT_LOG_DIR_NAME)
+                .join(DEFAULT_AUDIT_LOG_FILE_NAME)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use crate::data::Attribute;
+
+    use super::*;
+
+    #[test]
+    fn test_audit_entry_roundtrip() {
+        let schema = KeySchemaType::Hash("id".into());
+        let item = Item {
+            attributes: vec![
+                ("id".to_string(), Attribute::S("a".into())),
+                ("v".to_string(), Attribute::BOOL(true)),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let request = WriteRequest::Delete {
+            key: vec![("id".to_string(), Attribute::S("a".into()))]
+                .into_iter()
+                .collect(),
+            item,
+        };
+        let timestamp = Local.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+        let entry = AuditEntry::new(timestamp, "test", &schema, &request, None);
+        let line = serde_json::to_string(&entry).unwrap();
+        let actual = parse_audit_entries(&format!("{line}\n\n{line}\n"))
+            .ok()
+            .unwrap();
+
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].operation, "DELETE");
+        assert_eq!(actual[0].table_name, "test");
+        assert_eq!(actual[0].key, serde_json::json!({"id": {"S": "a"}}));
+        assert_eq!(
+            actual[0].request,
+            Some(serde_json::json!({"DeleteRequest": {"Key": {"id": {"S": "a"}}}}))
+        );
+        assert_eq!(
+            actual[0].before,
+            Some(serde_json::json!({"id": {"S": "a"}, "v": {"BOOL": true}}))
+        );
+        assert_eq!(actual[0].after, None);
+        assert_eq!(actual[0].timestamp, timestamp);
+    }
+
+    #[test]
+    fn test_parse_audit_entries_invalid() {
+        let actual = parse_audit_entries("{}\n");
+        assert!(actual.is_err());
+    }
+}
@@ -1,18 +1,26 @@
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
 };
 
 use aws_config::{default_provider, meta::region::RegionProviderChain, BehaviorVersion, Region};
+use aws_sdk_applicationautoscaling::types::ServiceNamespace as AwsServiceNamespace;
+use aws_sdk_dynamodb::config::Credentials;
+use aws_sdk_dynamodb::error::ProvideErrorMetadata;
 use aws_sdk_dynamodb::types::{
     AttributeDefinition as AwsAttributeDefinition, AttributeValue as AwsAttributeValue,
+    ContributorInsightsAction as AwsContributorInsightsAction, DeleteRequest as AwsDeleteRequest,
     GlobalSecondaryIndexDescription as AwsGlobalSecondaryIndexDescription,
-    KeySchemaElement as AwsKeySchemaElement, KeyType as AwsKeyType,
+    IndexStatus as AwsIndexStatus, KeySchemaElement as AwsKeySchemaElement, KeyType as AwsKeyType,
     LocalSecondaryIndexDescription as AwsLocalSecondaryIndexDescription,
     Projection as AwsProjection, ProjectionType as AwsProjectionType,
     ProvisionedThroughputDescription as AwsProvisionedThroughputDescription,
+    PutRequest as AwsPutRequest, ReturnConsumedCapacity as AwsReturnConsumedCapacity,
     ScalarAttributeType as AwsScalarAttributeType, TableDescription as AwsTableDescription,
-    TableStatus as AwsTableStatus,
+    TableStatus as AwsTableStatus, TimeToLiveStatus as AwsTimeToLiveStatus,
+    WriteRequest as AwsWriteRequest,
 };
 use aws_smithy_types::DateTime as AwsDateTime;
 use chrono::{DateTime, Local, TimeZone as _};
@@ -20,15 +28,31 @@ use rust_decimal::Decimal;
 
 use crate::{
     data::{
-        Attribute, AttributeDefinition, GlobalSecondaryIndexDescription, Item, KeySchemaElement,
-        KeySchemaType, KeyType, LocalSecondaryIndexDescription, Projection, ProjectionType,
-        ProvisionedThroughput, ScalarAttributeType, Table, TableDescription, TableStatus,
+        scanned_bytes_of_capacity, AccountLimits, Attribute, AttributeDefinition,
+        AutoScalingTarget, ConnectionDiagnostics, ContributorInsights,
+        GlobalSecondaryIndexDescription, IndexStatus, Item, KeySchemaElement, KeySchemaType,
+        KeyType, KinesisStreamingDestination, LoadStats, LoadedItems,
+        LocalSecondaryIndexDescription, Permission, Projection, ProjectionType,
+        ProvisionedThroughput, QueryOptions, ScalarAttributeType, ScanOptions, ScanProgress, Table,
+        TableCapacity, TableDescription, TablePermissions, TableStatus, WriteOptions, WriteRequest,
+        SET_ATTRIBUTE_CONDITION_EXPRESSION, SET_ATTRIBUTE_UPDATE_EXPRESSION,
     },
     error::{AppError, AppResult},
+    inspector::OperationLog,
 };
 
+const WRITE_MAX_ATTEMPTS: usize = 10;
+const WRITE_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct Client {
     client: aws_sdk_dynamodb::Client,
+    autoscaling: aws_sdk_applicationautoscaling::Client,
+    region: Option<String>,
+    profile: Option<String>,
+    endpoint_url: Option<String>,
+    credentials_source: String,
+    compat_mode: bool,
+    operation_log: OperationLog,
 }
 
 impl Client {
@@ -36,7 +60,10 @@ impl Client {
         region: Option<String>,
         endpoint_url: Option<String>,
         profile: Option<String>,
+        credentials: Option<(String, String)>,
         default_region_fallback: String,
+        compat_mode: bool,
+        operation_log: OperationLog,
     ) -> Client {
         let mut region_builder = default_provider::region::Builder::default();
         if let Some(profile) = &profile {
@@ -54,13 +81,62 @@ impl Client {
         if let Some(profile) = &profile {
             config_loader = config_loader.profile_name(profile);
         }
+        let credentials_source = credentials_source(credentials.is_some(), profile.as_deref());
+        if let Some((access_key_id, secret_access_key)) = credentials {
+            let credentials = Credentials::new(
+                access_key_id,
+                secret_access_key,
+                None,
+                None,
+                "ddv-environment",
+            );
+            config_loader = config_loader.credentials_provider(credentials);
+        }
         let sdk_config = config_loader.load().await;
+        let profile = profile.or_else(|| std::env::var("AWS_PROFILE").ok());
+        let region = sdk_config.region().map(|r| r.to_string());
 
-        let config_builder = aws_sdk_dynamodb::config::Builder::from(&sdk_config);
+        let config_builder = aws_sdk_dynamodb::config::Builder::from(&sdk_config)
+            .interceptor(operation_log.interceptor());
         let config = config_builder.build();
+        let autoscaling_config = aws_sdk_applicationautoscaling::config::Builder::from(&sdk_config)
+            .interceptor(operation_log.interceptor())
+            .build();
 
         let client = aws_sdk_dynamodb::Client::from_conf(config);
-        Client { client }
+        let autoscaling = aws_sdk_applicationautoscaling::Client::from_conf(autoscaling_config);
+        Client {
+            client,
+            autoscaling,
+            region,
+            profile,
+            endpoint_url,
+            credentials_source,
+            compat_mode,
+            operation_log,
+        }
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    pub fn operation_log(&self) -> &OperationLog {
+        &self.operation_log
+    }
+
+    pub fn diagnostics(&self, error: &AppError) -> ConnectionDiagnostics {
+        ConnectionDiagnostics {
+            error_messages: error.messages(),
+            credentials_source: self.credentials_source.clone(),
+            profile: self.profile.clone(),
+            region: self.region.clone(),
+            endpoint_url: self.endpoint_url.clone(),
+        }
     }
 
     pub async fn list_all_tables(&self) -> AppResult<Vec<Table>> {
@@ -97,35 +173,608 @@ impl Client {
         let result = req.send().await;
         let output = result.map_err(|e| AppError::new("failed to load table description", e))?;
 
-        let desc = to_table_description(output.table.unwrap());
+        match output.table {
+            Some(desc) => to_table_description(table_name, desc, self.compat_mode),
+            None => Err(AppError::msg("table description is empty")),
+        }
+    }
+
+    // The TTL attribute is needed wherever the items are shown, to highlight it and to set the expiration
+    pub async fn describe_table_with_ttl(&self, table_name: &str) -> AppResult<TableDescription> {
+        let (desc, ttl) = tokio::join!(
+            self.describe_table(table_name),
+            self.describe_ttl_attribute_name(table_name),
+        );
+        let mut desc = desc?;
+        match ttl {
+            Ok(name) => desc.ttl_attribute_name = name,
+            Err(_) => desc.unavailable_details.push("TTL Attribute"),
+        }
         Ok(desc)
     }
 
+    // The other details are only shown in the detail pane of the table list.
+    // They may not be permitted or supported by the backend, so a failure only marks the detail as unavailable
+    pub async fn describe_table_with_details(
+        &self,
+        table_name: &str,
+    ) -> AppResult<TableDescription> {
+        let mut desc = self.describe_table(table_name).await?;
+        let (ttl, kinesis, insights, targets) = tokio::join!(
+            self.describe_ttl_attribute_name(table_name),
+            self.describe_kinesis_streaming_destinations(table_name),
+            self.describe_contributor_insights(table_name),
+            self.describe_auto_scaling_targets(&desc),
+        );
+        match ttl {
+            Ok(name) => desc.ttl_attribute_name = name,
+            Err(_) => desc.unavailable_details.push("TTL Attribute"),
+        }
+        match kinesis {
+            Ok(destinations) => desc.kinesis_streaming_destinations = destinations,
+            Err(_) => desc.unavailable_details.push("Kinesis Destinations"),
+        }
+        match insights {
+            Ok(insights) => desc.contributor_insights = insights,
+            Err(_) => desc.unavailable_details.push("Contributor Insights"),
+        }
+        match targets {
+            Ok(targets) => desc.auto_scaling_targets = targets,
+            Err(_) => desc.unavailable_details.push("Auto Scaling"),
+        }
+        Ok(desc)
+    }
+
+    async fn describe_ttl_attribute_name(&self, table_name: &str) -> AppResult<Option<String>> {
+        let req = self.client.describe_time_to_live().table_name(table_name);
+        let output = req
+            .send()
+            .await
+            .map_err(|e| AppError::new("failed to load time to live", e))?;
+        let name =
+            output
+                .time_to_live_description
+                .and_then(|desc| match desc.time_to_live_status {
+                    Some(AwsTimeToLiveStatus::Enabled) => desc.attribute_name,
+                    _ => None,
+                });
+        Ok(name)
+    }
+
+    async fn describe_kinesis_streaming_destinations(
+        &self,
+        table_name: &str,
+    ) -> AppResult<Vec<KinesisStreamingDestination>> {
+        let req = self
+            .client
+            .describe_kinesis_streaming_destination()
+            .table_name(table_name);
+        let output = req
+            .send()
+            .await
+            .map_err(|e| AppError::new("failed to load kinesis streaming destinations", e))?;
+        let destinations = output
+            .kinesis_data_stream_destinations
+            .unwrap_or_default()
+            .into_iter()
+            .map(|d| KinesisStreamingDestination {
+                stream_arn: d.stream_arn.unwrap_or_default(),
+                destination_status: d
+                    .destination_status
+                    .map(|s| s.as_str().to_string())
+                    .unwrap_or_default(),
+                destination_status_description: d.destination_status_description,
+            })
+            .collect();
+        Ok(destinations)
+    }
+
+    async fn describe_contributor_insights(
+        &self,
+        table_name: &str,
+    ) -> AppResult<Vec<ContributorInsights>> {
+        let req = self
+            .client
+            .list_contributor_insights()
+            .table_name(table_name);
+        let output = req
+            .send()
+            .await
+            .map_err(|e| AppError::new("failed to list contributor insights", e))?;
+        let mut insights = Vec::new();
+        for summary in output.contributor_insights_summaries.unwrap_or_default() {
+            let status = summary
+                .contributor_insights_status
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_default();
+            let mut insight = ContributorInsights {
+                index_name: summary.index_name,
+                status,
+                rules: Vec::new(),
+            };
+            // rules are only returned by DescribeContributorInsights
+            if insight.is_enabled() {
+                let req = self
+                    .client
+                    .describe_contributor_insights()
+                    .table_name(table_name)
+                    .set_index_name(insight.index_name.clone());
+                let output = req
+                    .send()
+                    .await
+                    .map_err(|e| AppError::new("failed to load contributor insights", e))?;
+                insight.rules = output.contributor_insights_rule_list.unwrap_or_default();
+            }
+            insights.push(insight);
+        }
+        Ok(insights)
+    }
+
+    // Scalable targets can be registered only for provisioned tables
+    async fn describe_auto_scaling_targets(
+        &self,
+        desc: &TableDescription,
+    ) -> AppResult<Vec<AutoScalingTarget>> {
+        if desc
+            .provisioned_throughput
+            .as_ref()
+            .is_none_or(|t| t.read_capacity_units == 0 && t.write_capacity_units == 0)
+        {
+            return Ok(Vec::new());
+        }
+
+        let table_resource_id = format!("table/{}", desc.table_name);
+        let index_resource_ids = desc
+            .global_secondary_indexes
+            .iter()
+            .flatten()
+            .map(|gsi| format!("{table_resource_id}/index/{}", gsi.index_name));
+        let resource_ids = std::iter::once(table_resource_id.clone())
+            .chain(index_resource_ids)
+            .collect();
+        let req = self
+            .autoscaling
+            .describe_scalable_targets()
+            .service_namespace(AwsServiceNamespace::Dynamodb)
+            .set_resource_ids(Some(resource_ids));
+        let output = req
+            .send()
+            .await
+            .map_err(|e| AppError::new("failed to load scalable targets", e))?;
+
+        let mut targets = Vec::new();
+        for target in output.scalable_targets.unwrap_or_default() {
+            let req = self
+                .autoscaling
+                .describe_scaling_policies()
+                .service_namespace(AwsServiceNamespace::Dynamodb)
+                .resource_id(&target.resource_id)
+                .scalable_dimension(target.scalable_dimension.clone());
+            let output = req
+                .send()
+                .await
+                .map_err(|e| AppError::new("failed to load scaling policies", e))?;
+            let target_utilization = output
+                .scaling_policies
+                .unwrap_or_default()
+                .into_iter()
+                .find_map(|p| p.target_tracking_scaling_policy_configuration)
+                .map(|c| c.target_value);
+            targets.push(AutoScalingTarget {
+                index_name: index_name_of_resource_id(&target.resource_id),
+                dimension: capacity_dimension(target.scalable_dimension.as_str()).into(),
+                min_capacity: target.min_capacity,
+                max_capacity: target.max_capacity,
+                target_utilization,
+            });
+        }
+        Ok(targets)
+    }
+
+    pub async fn update_contributor_insights(
+        &self,
+        table_name: &str,
+        index_name: Option<&str>,
+        enable: bool,
+    ) -> AppResult<()> {
+        let action = if enable {
+            AwsContributorInsightsAction::Enable
+        } else {
+            AwsContributorInsightsAction::Disable
+        };
+        let req = self
+            .client
+            .update_contributor_insights()
+            .table_name(table_name)
+            .set_index_name(index_name.map(Into::into))
+            .contributor_insights_action(action);
+        req.send()
+            .await
+            .map_err(|e| AppError::new("failed to update contributor insights", e))?;
+        Ok(())
+    }
+
+    pub async fn describe_account_limits(
+        &self,
+        table_names: &[String],
+    ) -> AppResult<AccountLimits> {
+        let req = self.client.describe_limits();
+        let output = req
+            .send()
+            .await
+            .map_err(|e| AppError::new("failed to load account limits", e))?;
+
+        let mut table_capacities = Vec::new();
+        for table_name in table_names {
+            let req = self.client.describe_table().table_name(table_name);
+            let result = req.send().await;
+            let desc = result.map_err(|e| AppError::new("failed to load table description", e))?;
+            if let Some(desc) = desc.table {
+                table_capacities.push(to_table_capacity(table_name, desc));
+            }
+        }
+
+        Ok(AccountLimits {
+            account_max_read_capacity_units: output.account_max_read_capacity_units.unwrap_or(0)
+                as u64,
+            account_max_write_capacity_units: output.account_max_write_capacity_units.unwrap_or(0)
+                as u64,
+            table_max_read_capacity_units: output.table_max_read_capacity_units.unwrap_or(0) as u64,
+            table_max_write_capacity_units: output.table_max_write_capacity_units.unwrap_or(0)
+                as u64,
+            table_capacities,
+        })
+    }
+
     pub async fn scan_all_items(
         &self,
         table_name: &str,
         schema: &KeySchemaType,
-    ) -> AppResult<Vec<Item>> {
-        let mut last_evaluated_key = None;
+        options: &ScanOptions,
+        page_size: usize,
+        memory_limit_bytes: u64,
+        on_progress: impl Fn(ScanProgress),
+    ) -> AppResult<LoadedItems> {
+        let limit = options.limit;
+        let filter = options.filter.as_ref().map(|f| f.build());
+        let mut last_evaluated_key = options
+            .exclusive_start_key
+            .as_ref()
+            .and_then(to_aws_attribute_values);
+        let started_at = Instant::now();
         let mut items = Vec::new();
+        let mut pages = 0;
+        let mut progress = ScanProgress::default();
+        let mut size_bytes = 0;
+        let mut memory_limited = false;
         loop {
-            let mut req = self.client.scan().table_name(table_name);
+            let mut req = self
+                .client
+                .scan()
+                .table_name(table_name)
+                .return_consumed_capacity(AwsReturnConsumedCapacity::Total);
             if last_evaluated_key.is_some() {
                 req = req.set_exclusive_start_key(last_evaluated_key);
             }
+            if let Some(filter) = &filter {
+                req = req
+                    .filter_expression(&filter.expression)
+                    .set_expression_attribute_names(Some(
+                        filter.names.clone().into_iter().collect(),
+                    ))
+                    .set_expression_attribute_values(to_aws_attribute_values(&filter.values));
+            }
+            req = req.set_limit(page_limit(limit, items.len(), page_size));
 
             let result = req.send().await;
             let output = result.map_err(|e| AppError::new("failed to scan items", e))?;
 
-            items.extend(output.items.unwrap_or_default().into_iter().map(to_item));
+            let page_items: Vec<Item> = output
+                .items
+                .unwrap_or_default()
+                .into_iter()
+                .map(to_item)
+                .collect();
+            size_bytes += page_items.iter().map(Item::approximate_size).sum::<usize>() as u64;
+            items.extend(page_items);
+            pages += 1;
+            progress.items = items.len();
+            progress.scanned_items += output.scanned_count as usize;
+            if let Some(units) = output.consumed_capacity.and_then(|c| c.capacity_units) {
+                progress.scanned_bytes += scanned_bytes_of_capacity(units);
+            }
+            on_progress(progress);
 
             if output.last_evaluated_key.is_none() {
                 break;
             }
+            if limit.is_some_and(|limit| items.len() >= limit) {
+                break;
+            }
+            if memory_limit_bytes > 0 && size_bytes >= memory_limit_bytes {
+                memory_limited = true;
+                break;
+            }
             last_evaluated_key = output.last_evaluated_key;
         }
         sort_items(&mut items, schema);
-        Ok(items)
+        let mut loaded = to_loaded_items(items, pages, started_at);
+        loaded.stats.memory_limited = memory_limited;
+        Ok(loaded)
+    }
+
+    // Scan the whole table page by page and pass each page to on_page without keeping the items,
+    // so that tables larger than the memory can be processed.
+    // on_page also receives the key to continue from, which is None for the last page.
+    pub async fn scan_pages(
+        &self,
+        table_name: &str,
+        exclusive_start_key: Option<BTreeMap<String, Attribute>>,
+        page_size: usize,
+        mut on_page: impl FnMut(Vec<Item>, Option<BTreeMap<String, Attribute>>) -> AppResult<()>,
+    ) -> AppResult<usize> {
+        let mut last_evaluated_key = exclusive_start_key
+            .as_ref()
+            .and_then(to_aws_attribute_values);
+        let mut count = 0;
+        loop {
+            let result = self
+                .client
+                .scan()
+                .table_name(table_name)
+                .set_exclusive_start_key(last_evaluated_key)
+                .set_limit(page_limit(None, count, page_size))
+                .send()
+                .await;
+            let output = result.map_err(|e| AppError::new("failed to scan items", e))?;
+
+            let items: Vec<Item> = output
+                .items
+                .unwrap_or_default()
+                .into_iter()
+                .map(to_item)
+                .collect();
+            count += items.len();
+            let next_key = output
+                .last_evaluated_key
+                .clone()
+                .map(|key| key.into_iter().map(|(k, v)| (k, v.into())).collect());
+            on_page(items, next_key)?;
+
+            if output.last_evaluated_key.is_none() {
+                break;
+            }
+            last_evaluated_key = output.last_evaluated_key;
+        }
+        Ok(count)
+    }
+
+    pub async fn query_items(
+        &self,
+        table_name: &str,
+        options: &QueryOptions,
+        page_size: usize,
+    ) -> AppResult<LoadedItems> {
+        let limit = options.limit;
+        let key_condition = options.build_key_condition();
+        let mut last_evaluated_key = options
+            .exclusive_start_key
+            .as_ref()
+            .and_then(to_aws_attribute_values);
+        let started_at = Instant::now();
+        let mut items = Vec::new();
+        let mut pages = 0;
+        loop {
+            let mut req = self
+                .client
+                .query()
+                .table_name(table_name)
+                .set_index_name(options.index_name.clone())
+                .key_condition_expression(&key_condition.expression)
+                .set_expression_attribute_names(Some(
+                    key_condition.names.clone().into_iter().collect(),
+                ))
+                .set_expression_attribute_values(to_aws_attribute_values(&key_condition.values))
+                .scan_index_forward(options.scan_index_forward);
+            if last_evaluated_key.is_some() {
+                req = req.set_exclusive_start_key(last_evaluated_key);
+            }
+            req = req.set_limit(page_limit(limit, items.len(), page_size));
+
+            let result = req.send().await;
+            let output = result.map_err(|e| AppError::new("failed to query items", e))?;
+
+            items.extend(output.items.unwrap_or_default().into_iter().map(to_item));
+            pages += 1;
+
+            if output.last_evaluated_key.is_none() {
+                break;
+            }
+            if limit.is_some_and(|limit| items.len() >= limit) {
+                break;
+            }
+            last_evaluated_key = output.last_evaluated_key;
+        }
+        // keep the order returned by DynamoDB because it depends on the query direction
+        Ok(to_loaded_items(items, pages, started_at))
+    }
+
+    pub async fn get_item(
+        &self,
+        table_name: &str,
+        key: &BTreeMap<String, Attribute>,
+    ) -> AppResult<Option<Item>> {
+        let req = self
+            .client
+            .get_item()
+            .table_name(table_name)
+            .set_key(to_aws_attribute_values(key))
+            .consistent_read(true);
+
+        let result = req.send().await;
+        let output = result.map_err(|e| AppError::new("failed to get item", e))?;
+
+        Ok(output.item.map(to_item))
+    }
+
+    // Check permissions with a conditional delete whose condition never holds,
+    // so that nothing is deleted even if the request is authorized
+    pub async fn probe_table_permissions(&self, desc: &TableDescription) -> TablePermissions {
+        let key = match desc.probe_key() {
+            Some(key) => key,
+            None => return TablePermissions::default(),
+        };
+        let req = self
+            .client
+            .delete_item()
+            .table_name(&desc.table_name)
+            .set_key(to_aws_attribute_values(&key))
+            .condition_expression("attribute_exists(#k) AND attribute_not_exists(#k)")
+            .expression_attribute_names("#k", desc.key_schema_type.hash_key_name());
+
+        let delete_item = match req.send().await {
+            Ok(_) => Permission::Allowed,
+            Err(e) => match e.as_service_error() {
+                Some(e) if e.is_conditional_check_failed_exception() => Permission::Allowed,
+                Some(e) if e.code() == Some("AccessDeniedException") => Permission::Denied,
+                _ => Permission::Unknown,
+            },
+        };
+        TablePermissions { delete_item }
+    }
+
+    pub async fn write_items(
+        &self,
+        table_name: &str,
+        requests: &[WriteRequest],
+        options: &WriteOptions,
+        abort: &AtomicBool,
+        on_progress: impl Fn(usize),
+    ) -> AppResult<usize> {
+        let mut written = 0;
+        for (i, chunk) in requests.chunks(options.chunk_size).enumerate() {
+            // aborting only between chunks keeps the written requests a prefix of the given ones
+            if abort.load(Ordering::Relaxed) {
+                break;
+            }
+            if let Some(interval) = options.interval.filter(|_| i > 0) {
+                tokio::time::sleep(interval).await;
+            }
+
+            let (updates, batch): (Vec<_>, Vec<_>) = chunk
+                .iter()
+                .partition(|r| matches!(r, WriteRequest::SetAttribute { .. }));
+            for request in updates {
+                self.update_item(table_name, request).await?;
+                written += 1;
+                on_progress(written);
+            }
+            if batch.is_empty() {
+                continue;
+            }
+
+            let mut write_requests = batch
+                .iter()
+                .map(|r| to_aws_write_request(r))
+                .collect::<AppResult<Vec<_>>>()?;
+            for attempt in 1.. {
+                let req = self
+                    .client
+                    .batch_write_item()
+                    .request_items(table_name, write_requests);
+
+                let result = req.send().await;
+                let output = result.map_err(|e| AppError::new("failed to write items", e))?;
+
+                // throttled requests are returned as unprocessed items and must be retried
+                let unprocessed = output
+                    .unprocessed_items
+                    .and_then(|mut items| items.remove(table_name))
+                    .unwrap_or_default();
+                if unprocessed.is_empty() {
+                    break;
+                }
+                if attempt >= WRITE_MAX_ATTEMPTS {
+                    let msg = format!(
+                        "{} items were not processed after {attempt} attempts ({written} items already written)",
+                        unprocessed.len()
+                    );
+                    return Err(AppError::msg(msg));
+                }
+                write_requests = unprocessed;
+                tokio::time::sleep(options.interval.unwrap_or(WRITE_RETRY_INTERVAL)).await;
+            }
+            written += batch.len();
+            on_progress(written);
+        }
+        Ok(written)
+    }
+
+    async fn update_item(&self, table_name: &str, request: &WriteRequest) -> AppResult<()> {
+        let WriteRequest::SetAttribute {
+            key, name, value, ..
+        } = request
+        else {
+            return Err(AppError::msg("not an update request"));
+        };
+        // the condition on a key attribute prevents creating a new item if it has been deleted in the meantime
+        let key_name = key.keys().next().cloned().unwrap_or_default();
+        let req = self
+            .client
+            .update_item()
+            .table_name(table_name)
+            .set_key(to_aws_attribute_values(key))
+            .update_expression(SET_ATTRIBUTE_UPDATE_EXPRESSION)
+            .condition_expression(SET_ATTRIBUTE_CONDITION_EXPRESSION)
+            .expression_attribute_names("#name", name)
+            .expression_attribute_names("#key", key_name)
+            .expression_attribute_values(":value", value.clone().into());
+
+        let result = req.send().await;
+        result.map_err(|e| AppError::new("failed to update item", e))?;
+        Ok(())
+    }
+
+    pub async fn execute_statement(&self, statement: &str) -> AppResult<LoadedItems> {
+        let mut next_token = None;
+        let started_at = Instant::now();
+        let mut items = Vec::new();
+        let mut pages = 0;
+        loop {
+            let mut req = self.client.execute_statement().statement(statement);
+            if next_token.is_some() {
+                req = req.set_next_token(next_token);
+            }
+
+            let result = req.send().await;
+            let output = result.map_err(|e| AppError::new("failed to execute statement", e))?;
+
+            items.extend(output.items.unwrap_or_default().into_iter().map(to_item));
+            pages += 1;
+
+            if output.next_token.is_none() {
+                break;
+            }
+            next_token = output.next_token;
+        }
+        Ok(to_loaded_items(items, pages, started_at))
+    }
+}
+
+// Describe where the credentials come from, in the order of the default provider chain
+fn credentials_source(static_credentials: bool, profile: Option<&str>) -> String {
+    if static_credentials {
+        return "static credentials of the environment preset".into();
+    }
+    if std::env::var("AWS_ACCESS_KEY_ID").is_ok() {
+        return "environment variables (AWS_ACCESS_KEY_ID)".into();
+    }
+    match profile
+        .map(Into::into)
+        .or_else(|| std::env::var("AWS_PROFILE").ok())
+    {
+        Some(profile) => format!("profile '{profile}' of the shared config and credentials files"),
+        None => "default credential provider chain".into(),
     }
 }
 
@@ -135,22 +784,72 @@ impl From<String> for Table {
     }
 }
 
-fn to_table_description(desc: AwsTableDescription) -> TableDescription {
-    let attribute_definitions = vec_into(desc.attribute_definitions.unwrap());
-    let table_name = desc.table_name.unwrap();
-    let key_schema = vec_into(desc.key_schema.unwrap());
-    let table_status = desc.table_status.unwrap().into();
-    let creation_date_time = convert_datetime(desc.creation_date_time.unwrap());
+fn to_loaded_items(items: Vec<Item>, pages: usize, started_at: Instant) -> LoadedItems {
+    let stats = LoadStats {
+        count: items.len(),
+        pages,
+        elapsed: started_at.elapsed(),
+        memory_limited: false,
+    };
+    LoadedItems { items, stats }
+}
+
+// Some DynamoDB-compatible backends return partially populated descriptions,
+// so only the key schema is required and missing fields are filled with default values
+fn to_table_description(
+    table_name: &str,
+    desc: AwsTableDescription,
+    compat_mode: bool,
+) -> AppResult<TableDescription> {
+    let key_schema: Vec<KeySchemaElement> = match desc.key_schema {
+        Some(key_schema) if !key_schema.is_empty() => vec_into(key_schema),
+        _ => {
+            return Err(AppError::msg(
+                "table description does not contain key schema",
+            ))
+        }
+    };
+    let key_schema_type = to_key_schema_type(key_schema.clone())?;
+
+    let mut missing_fields = Vec::new();
+
+    let attribute_definitions = value_or(
+        desc.attribute_definitions.map(vec_into),
+        vec![],
+        "AttributeDefinitions",
+        &mut missing_fields,
+    );
+    let table_name = value_or(
+        desc.table_name,
+        table_name.into(),
+        "TableName",
+        &mut missing_fields,
+    );
+    let table_status = value_or(
+        desc.table_status.map(Into::into),
+        TableStatus::Active,
+        "TableStatus",
+        &mut missing_fields,
+    );
+    let creation_date_time = value_or(
+        desc.creation_date_time.map(convert_datetime),
+        DateTime::default(),
+        "CreationDateTime",
+        &mut missing_fields,
+    );
+    let total_size_bytes = value_or(
+        desc.table_size_bytes,
+        0,
+        "TableSizeBytes",
+        &mut missing_fields,
+    ) as u64;
+    let item_count = value_or(desc.item_count, 0, "ItemCount", &mut missing_fields) as u64;
+    let table_arn = value_or(desc.table_arn, "".into(), "TableArn", &mut missing_fields);
     let provisioned_throughput = desc.provisioned_throughput.map(Into::into);
-    let total_size_bytes = desc.table_size_bytes.unwrap() as u64;
-    let item_count = desc.item_count.unwrap() as u64;
-    let table_arn = desc.table_arn.unwrap();
     let local_secondary_indexes = desc.local_secondary_indexes.map(vec_into);
     let global_secondary_indexes = desc.global_secondary_indexes.map(vec_into);
 
-    let key_schema_type = to_key_schema_type(key_schema.clone());
-
-    TableDescription {
+    let desc = TableDescription {
         attribute_definitions,
         table_name,
         key_schema,
@@ -164,6 +863,82 @@ fn to_table_description(desc: AwsTableDescription) -> TableDescription {
         global_secondary_indexes,
 
         key_schema_type,
+        ttl_attribute_name: None,
+        kinesis_streaming_destinations: vec![],
+        contributor_insights: vec![],
+        auto_scaling_targets: vec![],
+        unavailable_details: vec![],
+        missing_fields,
+    };
+
+    // DynamoDB-compatible backends may return values unknown to AWS, which are accepted only in compat mode
+    if !compat_mode {
+        if let Some(value) = unknown_values(&desc).first() {
+            return Err(AppError::msg(format!(
+                "table description contains an unknown value: {value} (set `compat_mode = true` to accept it)"
+            )));
+        }
+    }
+
+    Ok(desc)
+}
+
+fn unknown_values(desc: &TableDescription) -> Vec<String> {
+    let mut values = Vec::new();
+    for def in &desc.attribute_definitions {
+        if let ScalarAttributeType::Other(s) = &def.attribute_type {
+            values.push(format!("AttributeType {s}"));
+        }
+    }
+    if let TableStatus::Other(s) = &desc.table_status {
+        values.push(format!("TableStatus {s}"));
+    }
+    for gsi in desc.global_secondary_indexes.iter().flatten() {
+        if let Some(IndexStatus::Other(s)) = &gsi.index_status {
+            values.push(format!("IndexStatus {s}"));
+        }
+    }
+    let indexes = desc
+        .local_secondary_indexes
+        .iter()
+        .flatten()
+        .map(|i| (&i.key_schema, &i.projection))
+        .chain(
+            desc.global_secondary_indexes
+                .iter()
+                .flatten()
+                .map(|i| (&i.key_schema, &i.projection)),
+        )
+        .collect::<Vec<_>>();
+    let key_schemas = indexes.iter().map(|(key_schema, _)| *key_schema);
+    for elem in std::iter::once(&desc.key_schema)
+        .chain(key_schemas)
+        .flatten()
+    {
+        if let KeyType::Other(s) = &elem.key_type {
+            values.push(format!("KeyType {s}"));
+        }
+    }
+    for (_, projection) in &indexes {
+        if let ProjectionType::Other(s) = &projection.projection_type {
+            values.push(format!("ProjectionType {s}"));
+        }
+    }
+    values
+}
+
+fn value_or<T>(
+    value: Option<T>,
+    default: T,
+    name: &'static str,
+    missing_fields: &mut Vec<&'static str>,
+) -> T {
+    match value {
+        Some(value) => value,
+        None => {
+            missing_fields.push(name);
+            default
+        }
     }
 }
 
@@ -179,7 +954,7 @@ impl From<AwsScalarAttributeType> for ScalarAttributeType {
             AwsScalarAttributeType::B => ScalarAttributeType::B,
             AwsScalarAttributeType::N => ScalarAttributeType::N,
             AwsScalarAttributeType::S => ScalarAttributeType::S,
-            _ => unreachable!("unexpected scalar attribute type: {:?}", s),
+            other => ScalarAttributeType::Other(other.as_str().into()),
         }
     }
 }
@@ -196,7 +971,19 @@ impl From<AwsTableStatus> for TableStatus {
                 TableStatus::InaccessibleEncryptionCredentials
             }
             AwsTableStatus::Updating => TableStatus::Updating,
-            _ => unreachable!("unexpected table status: {:?}", s),
+            other => TableStatus::Other(other.as_str().into()),
+        }
+    }
+}
+
+impl From<AwsIndexStatus> for IndexStatus {
+    fn from(s: AwsIndexStatus) -> Self {
+        match s {
+            AwsIndexStatus::Active => IndexStatus::Active,
+            AwsIndexStatus::Creating => IndexStatus::Creating,
+            AwsIndexStatus::Deleting => IndexStatus::Deleting,
+            AwsIndexStatus::Updating => IndexStatus::Updating,
+            other => IndexStatus::Other(other.as_str().into()),
         }
     }
 }
@@ -219,16 +1006,19 @@ impl From<AwsKeyType> for KeyType {
         match t {
             AwsKeyType::Hash => KeyType::Hash,
             AwsKeyType::Range => KeyType::Range,
-            _ => unreachable!("unexpected key type: {:?}", t),
+            other => KeyType::Other(other.as_str().into()),
         }
     }
 }
 
 impl From<AwsLocalSecondaryIndexDescription> for LocalSecondaryIndexDescription {
     fn from(value: AwsLocalSecondaryIndexDescription) -> Self {
-        let index_name = value.index_name.unwrap();
-        let key_schema = to_key_schema(value.key_schema.unwrap());
-        let projection = value.projection.unwrap().into();
+        let index_name = value.index_name.unwrap_or_default();
+        let key_schema = to_key_schema(value.key_schema.unwrap_or_default());
+        let projection = value
+            .projection
+            .map(Into::into)
+            .unwrap_or_else(default_projection);
         let index_size_bytes = value.index_size_bytes.unwrap_or(0) as u64;
         let item_count = value.item_count.unwrap_or(0) as u64;
         let index_arn = value.index_arn.unwrap_or("".to_string());
@@ -245,16 +1035,23 @@ impl From<AwsLocalSecondaryIndexDescription> for LocalSecondaryIndexDescription
 
 impl From<AwsGlobalSecondaryIndexDescription> for GlobalSecondaryIndexDescription {
     fn from(value: AwsGlobalSecondaryIndexDescription) -> Self {
-        let index_name = value.index_name.unwrap();
-        let key_schema = to_key_schema(value.key_schema.unwrap());
-        let projection = value.projection.unwrap().into();
-        let index_size_bytes = value.index_size_bytes.unwrap() as u64;
-        let item_count = value.item_count.unwrap() as u64;
-        let index_arn = value.index_arn.unwrap();
+        let index_name = value.index_name.unwrap_or_default();
+        let key_schema = to_key_schema(value.key_schema.unwrap_or_default());
+        let projection = value
+            .projection
+            .map(Into::into)
+            .unwrap_or_else(default_projection);
+        let index_size_bytes = value.index_size_bytes.unwrap_or(0) as u64;
+        let item_count = value.item_count.unwrap_or(0) as u64;
+        let index_status = value.index_status.map(Into::into);
+        let provisioned_throughput = value.provisioned_throughput.map(Into::into);
+        let index_arn = value.index_arn.unwrap_or("".to_string());
         GlobalSecondaryIndexDescription {
             index_name,
             key_schema,
             projection,
+            index_status,
+            provisioned_throughput,
             index_size_bytes,
             item_count,
             index_arn,
@@ -264,7 +1061,10 @@ impl From<AwsGlobalSecondaryIndexDescription> for GlobalSecondaryIndexDescriptio
 
 impl From<AwsProjection> for Projection {
     fn from(p: AwsProjection) -> Self {
-        let projection_type = p.projection_type.unwrap().into();
+        let projection_type = p
+            .projection_type
+            .map(Into::into)
+            .unwrap_or(ProjectionType::All);
         let non_key_attributes = p.non_key_attributes;
         Projection {
             projection_type,
@@ -273,43 +1073,92 @@ impl From<AwsProjection> for Projection {
     }
 }
 
+fn default_projection() -> Projection {
+    Projection {
+        projection_type: ProjectionType::All,
+        non_key_attributes: None,
+    }
+}
+
 impl From<AwsProjectionType> for ProjectionType {
     fn from(t: AwsProjectionType) -> Self {
         match t {
             AwsProjectionType::All => ProjectionType::All,
             AwsProjectionType::KeysOnly => ProjectionType::KeysOnly,
             AwsProjectionType::Include => ProjectionType::Include,
-            _ => unreachable!("unexpected projection type: {:?}", t),
+            other => ProjectionType::Other(other.as_str().into()),
         }
     }
 }
 
-fn to_key_schema_type(elements: Vec<KeySchemaElement>) -> KeySchemaType {
+fn to_key_schema_type(elements: Vec<KeySchemaElement>) -> AppResult<KeySchemaType> {
     let mut hash_key = None;
     let mut range_key = None;
     for elem in elements {
         match elem.key_type {
             KeyType::Hash => {
                 if hash_key.is_some() {
-                    panic!("multiple hash keys");
+                    return Err(AppError::msg("key schema contains multiple hash keys"));
                 }
                 hash_key = Some(elem.attribute_name);
             }
             KeyType::Range => {
                 if range_key.is_some() {
-                    panic!("multiple range keys");
+                    return Err(AppError::msg("key schema contains multiple range keys"));
                 }
                 range_key = Some(elem.attribute_name);
             }
+            KeyType::Other(_) => {}
         }
     }
     match (hash_key, range_key) {
-        (Some(hash_key), Some(range_key)) => KeySchemaType::HashRange(hash_key, range_key),
-        (Some(hash_key), None) => KeySchemaType::Hash(hash_key),
-        (hash_key, range_key) => {
-            panic!("unexpected key schema: ({hash_key:?}, {range_key:?})")
-        }
+        (Some(hash_key), Some(range_key)) => Ok(KeySchemaType::HashRange(hash_key, range_key)),
+        (Some(hash_key), None) => Ok(KeySchemaType::Hash(hash_key)),
+        (None, _) => Err(AppError::msg("key schema does not contain a hash key")),
+    }
+}
+
+fn to_aws_attribute_values(
+    values: &BTreeMap<String, Attribute>,
+) -> Option<HashMap<String, AwsAttributeValue>> {
+    if values.is_empty() {
+        return None;
     }
+    let values = values
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone().into()))
+        .collect();
+    Some(values)
+}
+
+fn to_aws_write_request(request: &WriteRequest) -> AppResult<AwsWriteRequest> {
+    let write_request = match request {
+        WriteRequest::Put { item } => {
+            let attributes = item
+                .attributes
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone().into()))
+                .collect();
+            let put_request = AwsPutRequest::builder()
+                .set_item(Some(attributes))
+                .build()
+                .map_err(|e| AppError::new("failed to build put request", e))?;
+            AwsWriteRequest::builder().put_request(put_request).build()
+        }
+        WriteRequest::SetAttribute { .. } => {
+            return Err(AppError::msg("update requests cannot be sent in a batch"));
+        }
+        WriteRequest::Delete { key, .. } => {
+            let delete_request = AwsDeleteRequest::builder()
+                .set_key(to_aws_attribute_values(key))
+                .build()
+                .map_err(|e| AppError::new("failed to build delete request", e))?;
+            AwsWriteRequest::builder()
+                .delete_request(delete_request)
+                .build()
+        }
+    };
+    Ok(write_request)
 }
 
 fn to_item(attributes: HashMap<String, AwsAttributeValue>) -> Item {
@@ -351,14 +1200,37 @@ impl From<AwsAttributeValue> for Attribute {
     }
 }
 
+impl From<Attribute> for AwsAttributeValue {
+    fn from(value: Attribute) -> Self {
+        match value {
+            Attribute::S(s) => AwsAttributeValue::S(s),
+            Attribute::N(n) => AwsAttributeValue::N(n.to_string()),
+            Attribute::B(b) => AwsAttributeValue::B(b.into()),
+            Attribute::BOOL(b) => AwsAttributeValue::Bool(b),
+            Attribute::NULL => AwsAttributeValue::Null(true),
+            Attribute::M(m) => {
+                let m = m.into_iter().map(|(k, v)| (k, v.into())).collect();
+                AwsAttributeValue::M(m)
+            }
+            Attribute::L(vs) => {
+                let vs = vs.into_iter().map(Into::into).collect();
+                AwsAttributeValue::L(vs)
+            }
+            Attribute::SS(ss) => AwsAttributeValue::Ss(ss.into_iter().collect()),
+            Attribute::NS(ns) => AwsAttributeValue::Ns(ns.iter().map(|n| n.to_string()).collect()),
+            Attribute::BS(bs) => AwsAttributeValue::Bs(bs.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
 impl From<AwsProvisionedThroughputDescription> for ProvisionedThroughput {
     fn from(t: AwsProvisionedThroughputDescription) -> Self {
         ProvisionedThroughput {
             last_increase_date_time: t.last_increase_date_time.map(convert_datetime),
             last_decrease_date_time: t.last_decrease_date_time.map(convert_datetime),
-            number_of_decreases_today: t.number_of_decreases_today.unwrap() as u64,
-            read_capacity_units: t.read_capacity_units.unwrap() as u64,
-            write_capacity_units: t.write_capacity_units.unwrap() as u64,
+            number_of_decreases_today: t.number_of_decreases_today.unwrap_or(0) as u64,
+            read_capacity_units: t.read_capacity_units.unwrap_or(0) as u64,
+            write_capacity_units: t.write_capacity_units.unwrap_or(0) as u64,
         }
     }
 }
@@ -400,3 +1272,166 @@ where
 {
     ts.into_iter().map(Into::into).collect()
 }
+
+// On-demand tables are returned with zero provisioned capacity
+fn to_table_capacity(table_name: &str, desc: AwsTableDescription) -> TableCapacity {
+    let capacities = std::iter::once(desc.provisioned_throughput)
+        .chain(
+            desc.global_secondary_indexes
+                .unwrap_or_default()
+                .into_iter()
+                .map(|gsi| gsi.provisioned_throughput),
+        )
+        .flatten()
+        .map(ProvisionedThroughput::from);
+    let (read_capacity_units, write_capacity_units) = capacities.fold((0, 0), |(r, w), t| {
+        (r + t.read_capacity_units, w + t.write_capacity_units)
+    });
+    TableCapacity {
+        table_name: table_name.into(),
+        read_capacity_units,
+        write_capacity_units,
+    }
+}
+
+// e.g. table/foo/index/bar -> bar
+fn index_name_of_resource_id(resource_id: &str) -> Option<String> {
+    resource_id
+        .split_once("/index/")
+        .map(|(_, index_name)| index_name.to_string())
+}
+
+// e.g. dynamodb:table:ReadCapacityUnits -> Read
+fn capacity_dimension(scalable_dimension: &str) -> &str {
+    let units = scalable_dimension.rsplit(':').next().unwrap_or_default();
+    units.strip_suffix("CapacityUnits").unwrap_or(units)
+}
+
+// The Limit of the next page, which is the smaller of the remaining items and the page size.
+// A page size of 0 leaves it to DynamoDB, which returns up to 1 MB per page.
+fn page_limit(limit: Option<usize>, loaded: usize, page_size: usize) -> Option<i32> {
+    let remaining = limit.map(|limit| limit.saturating_sub(loaded));
+    let page_size = (page_size > 0).then_some(page_size);
+    let n = match (remaining, page_size) {
+        (Some(remaining), Some(page_size)) => Some(remaining.min(page_size)),
+        (remaining, page_size) => remaining.or(page_size),
+    };
+    n.map(|n| n.min(i32::MAX as usize) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aws_key_schema_element(name: &str, key_type: AwsKeyType) -> AwsKeySchemaElement {
+        AwsKeySchemaElement::builder()
+            .attribute_name(name)
+            .key_type(key_type)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_to_table_description_partial() {
+        let desc = AwsTableDescription::builder()
+            .key_schema(aws_key_schema_element("id", AwsKeyType::Hash))
+            .key_schema(aws_key_schema_element("ts", AwsKeyType::Range))
+            .item_count(10)
+            .build();
+
+        let actual = to_table_description("test", desc, false).ok().unwrap();
+
+        assert_eq!(actual.table_name, "test");
+        assert_eq!(actual.item_count, 10);
+        assert!(matches!(
+            actual.key_schema_type,
+            KeySchemaType::HashRange(ref h, ref r) if h == "id" && r == "ts"
+        ));
+        assert_eq!(
+            actual.missing_fields,
+            vec![
+                "AttributeDefinitions",
+                "TableName",
+                "TableStatus",
+                "CreationDateTime",
+                "TableSizeBytes",
+                "TableArn",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_table_description_unknown_value() {
+        let desc = || {
+            AwsTableDescription::builder()
+                .key_schema(aws_key_schema_element("id", AwsKeyType::Hash))
+                .table_status(AwsTableStatus::from("MIGRATING"))
+                .build()
+        };
+
+        assert!(to_table_description("test", desc(), false).is_err());
+
+        let actual = to_table_description("test", desc(), true).ok().unwrap();
+        assert!(matches!(actual.table_status, TableStatus::Other(ref s) if s == "MIGRATING"));
+    }
+
+    #[test]
+    fn test_to_table_capacity() {
+        let throughput = |r, w| {
+            AwsProvisionedThroughputDescription::builder()
+                .read_capacity_units(r)
+                .write_capacity_units(w)
+                .build()
+        };
+        let desc = AwsTableDescription::builder()
+            .table_name("test")
+            .provisioned_throughput(throughput(10, 5))
+            .global_secondary_indexes(
+                AwsGlobalSecondaryIndexDescription::builder()
+                    .index_name("gsi")
+                    .provisioned_throughput(throughput(3, 2))
+                    .build(),
+            )
+            .build();
+
+        let actual = to_table_capacity("test", desc);
+        assert_eq!(actual.table_name, "test");
+        assert_eq!(actual.read_capacity_units, 13);
+        assert_eq!(actual.write_capacity_units, 7);
+    }
+
+    #[test]
+    fn test_auto_scaling_resource() {
+        assert_eq!(index_name_of_resource_id("table/foo"), None);
+        assert_eq!(
+            index_name_of_resource_id("table/foo/index/bar"),
+            Some("bar".into())
+        );
+        assert_eq!(
+            capacity_dimension("dynamodb:table:ReadCapacityUnits"),
+            "Read"
+        );
+        assert_eq!(
+            capacity_dimension("dynamodb:index:WriteCapacityUnits"),
+            "Write"
+        );
+    }
+
+    #[test]
+    fn test_to_table_description_without_key_schema() {
+        let desc = AwsTableDescription::builder().table_name("test").build();
+
+        let actual = to_table_description("test", desc, false);
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_page_limit() {
+        assert_eq!(page_limit(None, 0, 0), None);
+        assert_eq!(page_limit(None, 300, 100), Some(100));
+        assert_eq!(page_limit(Some(250), 200, 0), Some(50));
+        assert_eq!(page_limit(Some(250), 200, 100), Some(50));
+        assert_eq!(page_limit(Some(250), 0, 100), Some(100));
+    }
+}
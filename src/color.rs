@@ -23,12 +23,26 @@ pub struct ColorTheme {
     pub cell_null_fg: Color,
     pub cell_undefined_fg: Color,
     pub cell_ellipsis_fg: Color,
+    pub cell_ttl_fg: Color,
+    pub cell_ttl_expired_fg: Color,
+    pub cell_type_mismatch_fg: Color,
 
     pub item_attribute_type_fg: Color,
 
+    pub table_status_creating_fg: Color,
+    pub table_status_updating_fg: Color,
+    pub table_status_deleting_fg: Color,
+    pub table_status_inactive_fg: Color,
+
+    pub query_keyword_fg: Color,
+
     pub insight_attribute_name_fg: Color,
     pub insight_attribute_value_fg: Color,
 
+    pub diff_added_fg: Color,
+    pub diff_removed_fg: Color,
+    pub diff_changed_fg: Color,
+
     pub help_key_fg: Color,
     pub help_link_fg: Color,
 
@@ -60,12 +74,26 @@ impl Default for ColorTheme {
             cell_null_fg: Color::Magenta,
             cell_undefined_fg: Color::DarkGray,
             cell_ellipsis_fg: Color::Reset,
+            cell_ttl_fg: Color::DarkGray,
+            cell_ttl_expired_fg: Color::Red,
+            cell_type_mismatch_fg: Color::LightRed,
 
             item_attribute_type_fg: Color::DarkGray,
 
+            table_status_creating_fg: Color::Cyan,
+            table_status_updating_fg: Color::Yellow,
+            table_status_deleting_fg: Color::Red,
+            table_status_inactive_fg: Color::DarkGray,
+
+            query_keyword_fg: Color::Magenta,
+
             insight_attribute_name_fg: Color::Green,
             insight_attribute_value_fg: Color::DarkGray,
 
+            diff_added_fg: Color::Green,
+            diff_removed_fg: Color::Red,
+            diff_changed_fg: Color::Yellow,
+
             help_key_fg: Color::Yellow,
             help_link_fg: Color::Blue,
 
@@ -1,6 +1,7 @@
-use std::env;
+use std::{collections::HashMap, env};
 
-use serde::Deserialize;
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
 use smart_default::SmartDefault;
 use umbra::optional;
 
@@ -24,17 +25,61 @@ impl Config {
 pub struct Config {
     #[default = "us-east-1"]
     pub default_region: String,
+    #[default = false]
+    pub compat_mode: bool,
+    #[default = "local"]
+    pub timezone: String,
+    // preset name -> connection settings, selected with `--env`
+    pub environments: HashMap<String, EnvironmentPreset>,
     #[nested]
     pub ui: UiConfig,
+    #[nested]
+    pub scan: ScanConfig,
+    #[nested]
+    pub write: WriteConfig,
+    #[nested]
+    pub validation: ValidationConfig,
+    #[nested]
+    pub query: QueryConfig,
+}
+
+// Connection settings for local development such as LocalStack or DynamoDB Local,
+// the credentials are used instead of the AWS profile if both are specified
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnvironmentPreset {
+    pub endpoint_url: Option<String>,
+    pub region: Option<String>,
+    pub profile: Option<String>,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+}
+
+impl EnvironmentPreset {
+    pub fn credentials(&self) -> Option<(String, String)> {
+        match (&self.access_key_id, &self.secret_access_key) {
+            (Some(id), Some(secret)) => Some((id.clone(), secret.clone())),
+            _ => None,
+        }
+    }
 }
 
 #[optional(derives = [Deserialize])]
 #[derive(Debug, Clone, SmartDefault)]
 pub struct UiConfig {
+    #[default = true]
+    pub enhanced_keyboard: bool,
+    #[default = false]
+    pub confirm_quit: bool,
     #[nested]
     pub table_list: UiTableListConfig,
     #[nested]
     pub table: UiTableConfig,
+    #[nested]
+    pub status_line: UiStatusLineConfig,
+    #[nested]
+    pub insight: UiInsightConfig,
+    #[nested]
+    pub inspector: UiInspectorConfig,
 }
 
 #[optional(derives = [Deserialize])]
@@ -42,6 +87,13 @@ pub struct UiConfig {
 pub struct UiTableListConfig {
     #[default = 30]
     pub list_width: u16,
+    // if non-zero, takes precedence over list_width
+    #[default = 0]
+    pub list_width_percent: u16,
+    #[default = 0]
+    pub min_detail_width: u16,
+    #[default = 10]
+    pub preview_item_count: usize,
 }
 
 #[optional(derives = [Deserialize])]
@@ -53,4 +105,234 @@ pub struct UiTableConfig {
     pub max_expand_width: u16,
     #[default = 6]
     pub max_expand_height: u16,
+    // place items without the sorted attribute first instead of last
+    #[default = false]
+    pub sort_undefined_first: bool,
+    #[default = true]
+    pub hide_unused_columns_when_filtered: bool,
+    // render BOOL and NULL cells with the glyphs instead of the text, only in the table cells
+    #[default = false]
+    pub use_glyphs: bool,
+    #[default = "✓"]
+    pub true_glyph: String,
+    #[default = "✗"]
+    pub false_glyph: String,
+    #[default = "∅"]
+    pub null_glyph: String,
+    // copying more visible items than this as TSV asks for confirmation
+    #[default = 1000]
+    pub copy_confirm_rows: usize,
+}
+
+#[optional(derives = [Deserialize])]
+#[derive(Debug, Clone, SmartDefault)]
+pub struct UiStatusLineConfig {
+    #[default = ""]
+    pub format: String,
+}
+
+#[optional(derives = [Deserialize])]
+#[derive(Debug, Clone, SmartDefault)]
+pub struct UiInsightConfig {
+    // attributes missing or NULL in at least this percentage of items are flagged, 0 to disable
+    #[default = 50]
+    pub missing_warning_percent: u16,
+    // sort key values are split at the first delimiter to count their prefixes, empty to disable
+    #[default = "#"]
+    pub sort_key_delimiter: String,
+    #[default = 10]
+    pub sort_key_prefix_count: usize,
+    // items with the same values of these attributes are listed as near duplicates, empty to disable
+    pub near_duplicate_attributes: Vec<String>,
+}
+
+#[optional(derives = [Deserialize])]
+#[derive(Debug, Clone, SmartDefault)]
+pub struct UiInspectorConfig {
+    // the number of the last operations to keep, 0 to disable recording
+    #[default = 50]
+    pub max_records: usize,
+    #[default = 12]
+    pub height: u16,
+}
+
+#[optional(derives = [Deserialize])]
+#[derive(Debug, Clone, SmartDefault)]
+pub struct ScanConfig {
+    #[default = 1_000_000_000]
+    pub confirm_threshold_bytes: u64,
+    #[default = 100]
+    pub sample_item_count: usize,
+    // the Limit of each scan request, 0 to leave it to DynamoDB (up to 1 MB per page)
+    #[default = 0]
+    pub page_size: usize,
+    // scanning stops when the loaded items exceed this size, 0 to disable
+    #[default = 500_000_000]
+    pub memory_limit_bytes: u64,
+}
+
+#[optional(derives = [Deserialize])]
+#[derive(Debug, Clone, SmartDefault)]
+pub struct WriteConfig {
+    #[default = 5]
+    pub low_capacity_threshold_units: u64,
+    #[default = 1000]
+    pub low_capacity_interval_millis: u64,
+    #[default = false]
+    pub dry_run: bool,
+    // the permission check is a conditional DeleteItem write request,
+    // which is recorded in CloudTrail and consumes capacity even though nothing is deleted
+    #[default = false]
+    pub conditional_delete_probe: bool,
+    // rules for the tables whose names match the pattern, the first matching rule is applied
+    pub table_rules: Vec<TableWriteRule>,
+}
+
+impl WriteConfig {
+    pub fn table_write_mode(&self, table_name: &str) -> Option<TableWriteMode> {
+        self.table_rules
+            .iter()
+            .find(|rule| rule.pattern.is_match(table_name))
+            .map(|rule| rule.mode)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TableWriteRule {
+    // matched against the whole table name
+    #[serde(deserialize_with = "deserialize_table_name_pattern")]
+    pub pattern: Regex,
+    pub mode: TableWriteMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TableWriteMode {
+    // write operations are rejected
+    ReadOnly,
+    // the table name must be typed to execute write operations
+    Confirm,
+}
+
+fn deserialize_table_name_pattern<'de, D>(deserializer: D) -> Result<Regex, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let pattern = String::deserialize(deserializer)?;
+    Regex::new(&format!("^(?:{pattern})$")).map_err(serde::de::Error::custom)
+}
+
+#[optional(derives = [Deserialize])]
+#[derive(Debug, Clone, SmartDefault)]
+pub struct ValidationConfig {
+    // table name -> path of the JSON Schema file
+    pub schemas: HashMap<String, String>,
+}
+
+#[optional(derives = [Deserialize])]
+#[derive(Debug, Clone, SmartDefault)]
+pub struct QueryConfig {
+    // table name -> key templates
+    pub templates: HashMap<String, Vec<KeyTemplate>>,
+    // the Limit of each query request, 0 to leave it to DynamoDB (up to 1 MB per page)
+    #[default = 0]
+    pub page_size: usize,
+}
+
+// Key values such as `USER#{user_id}`, the placeholders are filled in when the template is used
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeyTemplate {
+    pub name: String,
+    pub index_name: Option<String>,
+    pub hash_key: String,
+    pub sort_key: Option<String>,
+    // query with begins_with instead of = for the sort key
+    #[serde(default)]
+    pub sort_key_prefix: bool,
+}
+
+impl KeyTemplate {
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut names = template_placeholders(&self.hash_key);
+        for name in self.sort_key.iter().flat_map(|s| template_placeholders(s)) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+}
+
+// Names of the placeholders like {name} in the order of appearance, without duplicates
+fn template_placeholders(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + end];
+        if !name.is_empty() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &rest[start + end + 1..];
+    }
+    names
+}
+
+pub fn fill_template(template: &str, values: &[(String, String)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |s, (name, value)| {
+            s.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("USER#{user_id}", vec!["user_id"])]
+    #[case("{tenant}#ORDER#{order_id}#{tenant}", vec!["tenant", "order_id"])]
+    #[case("STATIC", vec![])]
+    #[case("{}#{unclosed", vec![])]
+    fn test_template_placeholders(#[case] template: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(template_placeholders(template), expected);
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let values = vec![
+            ("tenant".to_string(), "acme".to_string()),
+            ("order_id".to_string(), "42".to_string()),
+        ];
+        let actual = fill_template("{tenant}#ORDER#{order_id}#{tenant}", &values);
+        assert_eq!(actual, "acme#ORDER#42#acme");
+    }
+
+    #[test]
+    fn test_table_write_mode() {
+        let config: OptionalWriteConfig = toml::from_str(
+            r#"
+            [[table_rules]]
+            pattern = ".*-prod-.*"
+            mode = "read-only"
+
+            [[table_rules]]
+            pattern = "orders|users"
+            mode = "confirm"
+            "#,
+        )
+        .unwrap();
+        let config: WriteConfig = config.into();
+
+        let mode = |name| config.table_write_mode(name);
+        assert_eq!(mode("app-prod-orders"), Some(TableWriteMode::ReadOnly));
+        assert_eq!(mode("orders"), Some(TableWriteMode::Confirm));
+        assert_eq!(mode("orders-dev"), None);
+        assert_eq!(mode("app-dev-orders"), None);
+    }
 }
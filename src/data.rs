@@ -1,9 +1,11 @@
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     slice,
+    time::Duration,
 };
 
 use chrono::{DateTime, Local};
+use itsuki::zero_indexed_enum;
 use rust_decimal::{prelude::ToPrimitive, Decimal};
 use serde::{
     ser::{SerializeMap, SerializeSeq},
@@ -11,13 +13,22 @@ use serde::{
 };
 use serde_with::skip_serializing_none;
 
-use crate::util::to_base64_str;
+use crate::{
+    error::{AppError, AppResult},
+    util::{format_count, format_duration, from_base64_str, to_base64_str},
+};
 
 #[derive(Debug)]
 pub struct Table {
     pub name: String,
 }
 
+#[derive(Debug)]
+pub struct RegionTable {
+    pub region: String,
+    pub name: String,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -36,6 +47,56 @@ pub struct TableDescription {
 
     #[serde(skip)]
     pub key_schema_type: KeySchemaType,
+    // loaded separately by DescribeTimeToLive, only if TTL is enabled
+    #[serde(skip)]
+    pub ttl_attribute_name: Option<String>,
+    // loaded separately by DescribeKinesisStreamingDestination
+    #[serde(skip)]
+    pub kinesis_streaming_destinations: Vec<KinesisStreamingDestination>,
+    // loaded separately by ListContributorInsights and DescribeContributorInsights
+    #[serde(skip)]
+    pub contributor_insights: Vec<ContributorInsights>,
+    // loaded separately by Application Auto Scaling
+    #[serde(skip)]
+    pub auto_scaling_targets: Vec<AutoScalingTarget>,
+    // details above that failed to load (e.g. not permitted or not supported by the backend)
+    #[serde(skip)]
+    pub unavailable_details: Vec<&'static str>,
+    // fields that were not returned by DescribeTable and filled with default values
+    #[serde(skip)]
+    pub missing_fields: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct KinesisStreamingDestination {
+    pub stream_arn: String,
+    pub destination_status: String,
+    pub destination_status_description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContributorInsights {
+    // None for the table itself
+    pub index_name: Option<String>,
+    pub status: String,
+    pub rules: Vec<String>,
+}
+
+impl ContributorInsights {
+    pub fn is_enabled(&self) -> bool {
+        matches!(self.status.as_str(), "ENABLED" | "ENABLING")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AutoScalingTarget {
+    // None for the table itself
+    pub index_name: Option<String>,
+    // Read or Write
+    pub dimension: String,
+    pub min_capacity: i32,
+    pub max_capacity: i32,
+    pub target_utilization: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -54,11 +115,12 @@ impl AttributeDefinition {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum ScalarAttributeType {
     B,
     N,
     S,
+    Other(String),
 }
 
 impl ScalarAttributeType {
@@ -67,6 +129,7 @@ impl ScalarAttributeType {
             ScalarAttributeType::B => "B",
             ScalarAttributeType::N => "N",
             ScalarAttributeType::S => "S",
+            ScalarAttributeType::Other(s) => s,
         }
     }
 }
@@ -87,10 +150,11 @@ pub struct KeySchemaElement {
     pub key_type: KeyType,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum KeyType {
     Hash,
     Range,
+    Other(String),
 }
 
 impl KeyType {
@@ -98,6 +162,7 @@ impl KeyType {
         match self {
             KeyType::Hash => "HASH",
             KeyType::Range => "RANGE",
+            KeyType::Other(s) => s,
         }
     }
 }
@@ -117,6 +182,15 @@ pub enum KeySchemaType {
     HashRange(String, String),
 }
 
+impl KeySchemaType {
+    pub fn hash_key_name(&self) -> &str {
+        match self {
+            KeySchemaType::Hash(hash_key) => hash_key,
+            KeySchemaType::HashRange(hash_key, _) => hash_key,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum TableStatus {
     Active,
@@ -126,6 +200,7 @@ pub enum TableStatus {
     Deleting,
     InaccessibleEncryptionCredentials,
     Updating,
+    Other(String),
 }
 
 impl TableStatus {
@@ -138,6 +213,7 @@ impl TableStatus {
             TableStatus::Deleting => "DELETING",
             TableStatus::InaccessibleEncryptionCredentials => "INACCESSIBLE_ENCRYPTION_CREDENTIALS",
             TableStatus::Updating => "UPDATING",
+            TableStatus::Other(s) => s,
         }
     }
 }
@@ -173,28 +249,62 @@ pub struct LocalSecondaryIndexDescription {
     pub index_arn: String,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct GlobalSecondaryIndexDescription {
     pub index_name: String,
     pub key_schema: Vec<KeySchemaElement>,
     pub projection: Projection,
+    pub index_status: Option<IndexStatus>,
+    pub provisioned_throughput: Option<ProvisionedThroughput>,
     pub index_size_bytes: u64,
     pub item_count: u64,
     pub index_arn: String,
 }
 
+#[derive(Debug, Clone)]
+pub enum IndexStatus {
+    Active,
+    Creating,
+    Deleting,
+    Updating,
+    Other(String),
+}
+
+impl IndexStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            IndexStatus::Active => "ACTIVE",
+            IndexStatus::Creating => "CREATING",
+            IndexStatus::Deleting => "DELETING",
+            IndexStatus::Updating => "UPDATING",
+            IndexStatus::Other(s) => s,
+        }
+    }
+}
+
+impl Serialize for IndexStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Projection {
     pub projection_type: ProjectionType,
     pub non_key_attributes: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum ProjectionType {
     All,
     Include,
     KeysOnly,
+    Other(String),
 }
 
 impl ProjectionType {
@@ -203,6 +313,7 @@ impl ProjectionType {
             ProjectionType::All => "ALL",
             ProjectionType::Include => "INCLUDE",
             ProjectionType::KeysOnly => "KEYS_ONLY",
+            ProjectionType::Other(s) => s,
         }
     }
 }
@@ -221,6 +332,16 @@ pub struct Item {
     pub attributes: HashMap<String, Attribute>,
 }
 
+impl Item {
+    // The approximate number of bytes held by the item, used to guard the memory usage
+    pub fn approximate_size(&self) -> usize {
+        self.attributes
+            .iter()
+            .map(|(name, attr)| name.len() + attr.approximate_size())
+            .sum()
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Attribute {
@@ -230,6 +351,8 @@ pub enum Attribute {
     BOOL(bool),
     NULL,
     L(Vec<Attribute>),
+    // The SDK returns maps as HashMap, so the order in which the keys were written is not available
+    // and sorting them by name is the only stable order for previews and exports
     M(BTreeMap<String, Attribute>),
     SS(BTreeSet<String>),
     NS(BTreeSet<Decimal>),
@@ -275,6 +398,21 @@ impl Attribute {
             Attribute::BS(b) => set(b, |b| format!("Blob ({})", b.len())),
         }
     }
+
+    // The size of the values, without the overhead of the containers
+    pub fn approximate_size(&self) -> usize {
+        match self {
+            Attribute::S(s) => s.len(),
+            Attribute::N(_) => size_of::<Decimal>(),
+            Attribute::B(b) => b.len(),
+            Attribute::BOOL(_) | Attribute::NULL => 1,
+            Attribute::L(l) => l.iter().map(Attribute::approximate_size).sum(),
+            Attribute::M(m) => m.iter().map(|(k, v)| k.len() + v.approximate_size()).sum(),
+            Attribute::SS(s) => s.iter().map(String::len).sum(),
+            Attribute::NS(n) => n.len() * size_of::<Decimal>(),
+            Attribute::BS(b) => b.iter().map(Vec::len).sum(),
+        }
+    }
 }
 
 impl PartialOrd for Attribute {
@@ -337,6 +475,23 @@ impl AttributeType {
             AttributeType::None => "undefined",
         }
     }
+
+    // an empty value of the type, used as a placeholder to be filled in by the user
+    pub fn placeholder(&self) -> Option<Attribute> {
+        match self {
+            AttributeType::String => Some(Attribute::S("".into())),
+            AttributeType::Number => Some(Attribute::N(Decimal::ZERO)),
+            AttributeType::Blob => Some(Attribute::B(vec![])),
+            AttributeType::Bool => Some(Attribute::BOOL(false)),
+            AttributeType::Null => Some(Attribute::NULL),
+            AttributeType::List => Some(Attribute::L(vec![])),
+            AttributeType::Map => Some(Attribute::M(BTreeMap::new())),
+            AttributeType::StringSet => Some(Attribute::SS(BTreeSet::new())),
+            AttributeType::NumberSet => Some(Attribute::NS(BTreeSet::new())),
+            AttributeType::BlobSet => Some(Attribute::BS(BTreeSet::new())),
+            AttributeType::None => None,
+        }
+    }
 }
 
 pub struct RawJsonItem<'a> {
@@ -474,17 +629,109 @@ impl Serialize for RawAttributeJsonWrapper<'_> {
     }
 }
 
+// Returns jq-style paths corresponding to each line of the pretty-printed PlainJsonItem
+pub fn to_plain_json_string(item: &Item, schema: &KeySchemaType) -> String {
+    let json_item = PlainJsonItem::new(item, schema);
+    serde_json::to_string(&json_item).unwrap()
+}
+
+pub fn plain_json_line_paths(item: &Item, schema: &KeySchemaType) -> Vec<String> {
+    let mut paths = vec![".".to_string()];
+    for key in list_attribute_keys(slice::from_ref(item), schema) {
+        let attr = item.attributes.get(&key).unwrap();
+        push_attribute_line_paths(attr, join_path_key("", &key), &mut paths);
+    }
+    paths.push(".".to_string());
+    paths
+}
+
+fn push_attribute_line_paths(attr: &Attribute, path: String, paths: &mut Vec<String>) {
+    let len = match attr {
+        Attribute::L(v) => v.len(),
+        Attribute::M(m) => m.len(),
+        Attribute::SS(s) => s.len(),
+        Attribute::NS(s) => s.len(),
+        Attribute::BS(s) => s.len(),
+        _ => 0,
+    };
+    if len == 0 {
+        // scalar values and empty collections are printed in a single line
+        paths.push(path);
+        return;
+    }
+
+    paths.push(path.clone());
+    match attr {
+        Attribute::L(v) => {
+            for (i, a) in v.iter().enumerate() {
+                push_attribute_line_paths(a, format!("{path}[{i}]"), paths);
+            }
+        }
+        Attribute::M(m) => {
+            for (k, v) in m {
+                push_attribute_line_paths(v, join_path_key(&path, k), paths);
+            }
+        }
+        _ => {
+            paths.extend((0..len).map(|i| format!("{path}[{i}]")));
+        }
+    }
+    paths.push(path);
+}
+
+pub fn join_path_key(path: &str, key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_identifier {
+        format!("{path}.{key}")
+    } else {
+        let quoted = serde_json::to_string(key).unwrap();
+        if path.is_empty() {
+            format!(".[{quoted}]")
+        } else {
+            format!("{path}[{quoted}]")
+        }
+    }
+}
+
 pub fn to_key_string(item: &Item, schema: &KeySchemaType) -> String {
+    // key attributes may be missing if the items are loaded with a projection
+    let key_str = |key: &String| {
+        item.attributes
+            .get(key)
+            .map(|attr| attr.to_simple_string())
+            .unwrap_or_else(|| "-".into())
+    };
     match schema {
-        KeySchemaType::Hash(key) => item.attributes.get(key).unwrap().to_simple_string(),
+        KeySchemaType::Hash(key) => key_str(key),
         KeySchemaType::HashRange(key1, key2) => {
-            let key1_str = item.attributes.get(key1).unwrap().to_simple_string();
-            let key2_str = item.attributes.get(key2).unwrap().to_simple_string();
-            format!("{key1_str} / {key2_str}")
+            format!("{} / {}", key_str(key1), key_str(key2))
         }
     }
 }
 
+// key attributes may be missing if the items are loaded with a projection
+pub fn to_key_attributes(
+    item: &Item,
+    schema: &KeySchemaType,
+) -> Option<BTreeMap<String, Attribute>> {
+    let key_names = match schema {
+        KeySchemaType::Hash(key) => vec![key],
+        KeySchemaType::HashRange(key1, key2) => vec![key1, key2],
+    };
+    key_names
+        .into_iter()
+        .map(|name| {
+            item.attributes
+                .get(name)
+                .map(|attr| (name.clone(), attr.clone()))
+        })
+        .collect()
+}
+
 pub fn list_attribute_keys(items: &[Item], schema: &KeySchemaType) -> Vec<String> {
     let keys_set: HashSet<_> = get_all_keys(items);
     let mut keys: Vec<_> = keys_set.into_iter().cloned().collect();
@@ -499,7 +746,7 @@ fn get_all_keys(items: &[Item]) -> HashSet<&String> {
         .collect()
 }
 
-fn sort_keys(keys: &mut [String], schema: &KeySchemaType) {
+pub fn sort_keys(keys: &mut [String], schema: &KeySchemaType) {
     match schema {
         KeySchemaType::Hash(k) => {
             keys.sort_by(|a, b| {
@@ -530,6 +777,63 @@ fn sort_keys(keys: &mut [String], schema: &KeySchemaType) {
     }
 }
 
+// Sort the items by the attribute and return the new order as indices into the given items.
+// Values of different types are ordered by type (S, N, B, BOOL, NULL, L, M, SS, NS, BS),
+// items without the attribute are placed at the end (or the beginning if undefined_first)
+// regardless of the direction, and the sort is stable so that values which cannot be compared
+// (such as lists and maps) keep their loaded order.
+pub fn sort_items_by_attribute(
+    items: &[Item],
+    name: &str,
+    descending: bool,
+    undefined_first: bool,
+) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..items.len()).collect();
+    indices.sort_by(|&a, &b| {
+        match (items[a].attributes.get(name), items[b].attributes.get(name)) {
+            (Some(a), Some(b)) => {
+                let ord = compare_attribute_values(a, b);
+                if descending {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            }
+            (Some(_), None) if undefined_first => std::cmp::Ordering::Greater,
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) if undefined_first => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+    });
+    indices
+}
+
+fn compare_attribute_values(a: &Attribute, b: &Attribute) -> std::cmp::Ordering {
+    match (a, b) {
+        (Attribute::S(a), Attribute::S(b)) => a.cmp(b),
+        (Attribute::N(a), Attribute::N(b)) => a.cmp(b),
+        (Attribute::B(a), Attribute::B(b)) => a.cmp(b),
+        (Attribute::BOOL(a), Attribute::BOOL(b)) => a.cmp(b),
+        _ => attribute_type_order(a).cmp(&attribute_type_order(b)),
+    }
+}
+
+fn attribute_type_order(attr: &Attribute) -> usize {
+    match attr {
+        Attribute::S(_) => 0,
+        Attribute::N(_) => 1,
+        Attribute::B(_) => 2,
+        Attribute::BOOL(_) => 3,
+        Attribute::NULL => 4,
+        Attribute::L(_) => 5,
+        Attribute::M(_) => 6,
+        Attribute::SS(_) => 7,
+        Attribute::NS(_) => 8,
+        Attribute::BS(_) => 9,
+    }
+}
+
 enum DecimalType {
     Integer(i64),
     Float(f64),
@@ -548,134 +852,1868 @@ fn decimal_type(value: &Decimal) -> DecimalType {
     DecimalType::String(value.to_string())
 }
 
-pub struct TableInsight {
-    pub table_name: String,
-    pub total_items: usize,
-    pub attribute_distributions: Vec<AttributeDistribution>,
+// Scan reads at most 1 MB of data per request
+const SCAN_PAGE_SIZE_BYTES: u64 = 1024 * 1024;
+// An eventually consistent read consumes 0.5 RCU per 4 KB
+const RCU_UNIT_SIZE_BYTES: u64 = 4 * 1024;
+
+pub struct ScanEstimate {
+    pub total_size_bytes: u64,
+    pub pages: u64,
+    pub read_capacity_units: u64,
 }
 
-impl TableInsight {
-    pub fn new(table_description: &TableDescription, items: &[Item]) -> TableInsight {
-        let table_name = table_description.table_name.clone();
-        let total_items = items.len();
+impl ScanEstimate {
+    pub fn new(total_size_bytes: u64) -> ScanEstimate {
+        let pages = total_size_bytes.div_ceil(SCAN_PAGE_SIZE_BYTES).max(1);
+        let read_capacity_units = total_size_bytes.div_ceil(RCU_UNIT_SIZE_BYTES).div_ceil(2);
+        ScanEstimate {
+            total_size_bytes,
+            pages,
+            read_capacity_units,
+        }
+    }
+}
 
-        let attribute_keys = list_attribute_keys(items, &table_description.key_schema_type);
-        let attribute_distributions = build_attribute_distributions(items, &attribute_keys);
+// The size of the items read by a scan, from the capacity it consumed
+pub fn scanned_bytes_of_capacity(read_capacity_units: f64) -> u64 {
+    (read_capacity_units * 2.0 * RCU_UNIT_SIZE_BYTES as f64) as u64
+}
 
-        TableInsight {
-            table_name,
-            total_items,
-            attribute_distributions,
+// Approximate prices of the Standard table class in us-east-1 (USD)
+const STORAGE_PRICE_PER_GB_MONTH: f64 = 0.25;
+const RCU_PRICE_PER_HOUR: f64 = 0.00013;
+const WCU_PRICE_PER_HOUR: f64 = 0.00065;
+const HOURS_PER_MONTH: f64 = 730.0;
+const GB_BYTES: f64 = 1024.0 * 1024.0 * 1024.0;
+
+pub struct CostEstimate {
+    pub monthly_storage_cost: f64,
+    // None if the table is on-demand, because the cost depends on the requests
+    pub monthly_capacity_cost: Option<f64>,
+}
+
+impl CostEstimate {
+    pub fn new(desc: &TableDescription) -> CostEstimate {
+        let index_size_bytes: u64 = desc
+            .global_secondary_indexes
+            .iter()
+            .flatten()
+            .map(|gsi| gsi.index_size_bytes)
+            .sum();
+        let size_gb = (desc.total_size_bytes + index_size_bytes) as f64 / GB_BYTES;
+        let monthly_storage_cost = size_gb * STORAGE_PRICE_PER_GB_MONTH;
+
+        // on-demand tables report zero provisioned throughput
+        let monthly_capacity_cost = desc
+            .provisioned_throughput
+            .as_ref()
+            .filter(|pt| pt.read_capacity_units > 0 || pt.write_capacity_units > 0)
+            .map(|pt| {
+                let hourly = pt.read_capacity_units as f64 * RCU_PRICE_PER_HOUR
+                    + pt.write_capacity_units as f64 * WCU_PRICE_PER_HOUR;
+                hourly * HOURS_PER_MONTH
+            });
+
+        CostEstimate {
+            monthly_storage_cost,
+            monthly_capacity_cost,
         }
     }
 }
 
-fn build_attribute_distributions(
-    items: &[Item],
-    attribute_keys: &[String],
-) -> Vec<AttributeDistribution> {
-    let mut key_counter = Vec::new();
-    for key in attribute_keys {
-        key_counter.push((key, HashMap::new()));
-    }
-    for item in items {
-        for (i, key) in attribute_keys.iter().enumerate() {
-            let attr = item.attributes.get(key);
-            let attr_type = AttributeType::from(attr);
-            let (_, counter) = key_counter.get_mut(i).unwrap();
-            *counter.entry(attr_type).or_insert(0) += 1;
+pub struct LoadedItems {
+    pub items: Vec<Item>,
+    pub stats: LoadStats,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LoadStats {
+    pub count: usize,
+    pub pages: usize,
+    pub elapsed: Duration,
+    // loading was stopped before the last page because the items exceeded the memory limit
+    pub memory_limited: bool,
+}
+
+impl LoadStats {
+    pub fn summary(&self) -> String {
+        let pages = if self.pages == 1 { "page" } else { "pages" };
+        let mut summary = format!(
+            "{} items in {} ({} {pages})",
+            format_count(self.count as u64),
+            format_duration(self.elapsed),
+            self.pages,
+        );
+        if self.memory_limited {
+            summary.push_str(", stopped at the memory limit");
         }
+        summary
     }
-    key_counter
-        .into_iter()
-        .map(|(key, counter)| {
-            let mut distributions: Vec<(AttributeType, usize)> = counter.into_iter().collect();
-            distributions.sort_by_key(|d| std::cmp::Reverse(d.1)); // sort by count desc
-            AttributeDistribution {
-                attribute_name: key.clone(),
-                distributions,
-            }
-        })
-        .collect()
 }
 
-pub struct AttributeDistribution {
-    pub attribute_name: String,
-    pub distributions: Vec<(AttributeType, usize)>,
+// The settings used to connect, shown when the connection fails
+pub struct ConnectionDiagnostics {
+    pub error_messages: Vec<String>,
+    pub credentials_source: String,
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub endpoint_url: Option<String>,
 }
 
-#[cfg(test)]
-mod tests {
-    use rust_decimal::prelude::FromPrimitive;
+pub struct AccountLimits {
+    pub account_max_read_capacity_units: u64,
+    pub account_max_write_capacity_units: u64,
+    pub table_max_read_capacity_units: u64,
+    pub table_max_write_capacity_units: u64,
+    pub table_capacities: Vec<TableCapacity>,
+}
 
-    use super::*;
+// Provisioned capacity of the table including its global secondary indexes
+pub struct TableCapacity {
+    pub table_name: String,
+    pub read_capacity_units: u64,
+    pub write_capacity_units: u64,
+}
 
-    #[test]
-    fn test_raw_json_item_serialize() {
-        let item = fixture_item();
-        let schema = KeySchemaType::Hash("b".into());
+impl AccountLimits {
+    pub fn total_read_capacity_units(&self) -> u64 {
+        self.table_capacities
+            .iter()
+            .map(|c| c.read_capacity_units)
+            .sum()
+    }
 
-        let json_item = RawJsonItem::new(&item, &schema);
-        let json = serde_json::to_string_pretty(&json_item).unwrap();
+    pub fn total_write_capacity_units(&self) -> u64 {
+        self.table_capacities
+            .iter()
+            .map(|c| c.write_capacity_units)
+            .sum()
+    }
+}
 
-        assert_eq!(
-            json,
-            r#"{
-  "b": {
-    "N": 123
-  },
-  "a": {
-    "S": "aaa"
-  },
-  "c": {
-    "SS": [
-      "c1",
-      "c2"
-    ]
-  },
-  "d": {
-    "L": [
-      {
-        "NULL": true
-      },
-      {
-        "B": "YWJj"
-      },
-      {
-        "BS": [
-          "bG1u",
-          "eHl6"
-        ]
-      }
-    ]
-  },
-  "e": {
-    "M": {
-      "e1": {
-        "BOOL": true
-      },
-      "e2": {
-        "NS": [
-          -2.34,
-          0.2,
-          3
-        ]
-      }
+// BatchWriteItem accepts at most 25 requests at once
+const BATCH_WRITE_MAX_ITEMS: usize = 25;
+
+// UpdateItem expressions to set a single attribute of an existing item
+pub const SET_ATTRIBUTE_UPDATE_EXPRESSION: &str = "SET #name = :value";
+pub const SET_ATTRIBUTE_CONDITION_EXPRESSION: &str = "attribute_exists(#key)";
+
+#[derive(Debug, Clone)]
+pub enum WriteRequest {
+    Put {
+        item: Item,
+    },
+    // sent with UpdateItem, since BatchWriteItem does not support updates
+    SetAttribute {
+        key: BTreeMap<String, Attribute>,
+        item: Item,
+        name: String,
+        value: Attribute,
+    },
+    // the item is kept to record its state before deletion
+    Delete {
+        key: BTreeMap<String, Attribute>,
+        item: Item,
+    },
+}
+
+impl WriteRequest {
+    // The key of the written item and its state after the request succeeds (None if deleted)
+    pub fn into_result(
+        self,
+        schema: &KeySchemaType,
+    ) -> Option<(BTreeMap<String, Attribute>, Option<Item>)> {
+        match self {
+            WriteRequest::Put { item } => {
+                to_key_attributes(&item, schema).map(|key| (key, Some(item)))
+            }
+            WriteRequest::SetAttribute {
+                key,
+                mut item,
+                name,
+                value,
+            } => {
+                item.attributes.insert(name, value);
+                Some((key, Some(item)))
+            }
+            WriteRequest::Delete { key, .. } => Some((key, None)),
+        }
     }
-  }
-}"#
-        );
+}
+
+pub fn write_requests_summary(requests: &[WriteRequest]) -> String {
+    let count = |f: fn(&WriteRequest) -> bool| requests.iter().filter(|r| f(r)).count();
+    let counts = [
+        ("put", count(|r| matches!(r, WriteRequest::Put { .. }))),
+        (
+            "update",
+            count(|r| matches!(r, WriteRequest::SetAttribute { .. })),
+        ),
+        (
+            "delete",
+            count(|r| matches!(r, WriteRequest::Delete { .. })),
+        ),
+    ];
+    let summary = counts
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(operation, count)| {
+            if count == 1 {
+                format!("{operation} 1 item")
+            } else {
+                format!("{operation} {} items", format_count(count as u64))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut chars = summary.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => summary,
     }
+}
 
-    #[test]
-    fn test_plain_json_item_serialize() {
-        let item = fixture_item();
-        let schema = KeySchemaType::Hash("b".into());
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteOptions {
+    pub chunk_size: usize,
+    pub interval: Option<Duration>,
+}
 
-        let json_item = PlainJsonItem::new(&item, &schema);
-        let json = serde_json::to_string_pretty(&json_item).unwrap();
+impl WriteOptions {
+    // For provisioned tables with low write capacity, batches are split into smaller chunks
+    // that consume about half of the provisioned WCUs per interval,
+    // so that the remaining capacity is left for other traffic
+    pub fn new(desc: &TableDescription, low_capacity_threshold: u64, interval: Duration) -> Self {
+        match desc.low_write_capacity_units(low_capacity_threshold) {
+            Some(wcu) => WriteOptions {
+                chunk_size: ((wcu / 2) as usize).clamp(1, BATCH_WRITE_MAX_ITEMS),
+                interval: Some(interval),
+            },
+            None => WriteOptions {
+                chunk_size: BATCH_WRITE_MAX_ITEMS,
+                interval: None,
+            },
+        }
+    }
+}
 
-        assert_eq!(
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Permission {
+    #[default]
+    Unknown,
+    Allowed,
+    Denied,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TablePermissions {
+    pub delete_item: Permission,
+}
+
+#[derive(Debug, Clone)]
+pub enum ItemsSource {
+    Scan(ScanOptions),
+    Query(QueryOptions),
+    Statement(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    pub limit: Option<usize>,
+    pub filter: Option<FilterExpression>,
+    pub exclusive_start_key: Option<BTreeMap<String, Attribute>>,
+}
+
+impl ScanOptions {
+    // The amount of the scan expected in advance, if it is known.
+    // The item count and size of the table are only estimates, since DynamoDB updates them about every six hours.
+    pub fn progress_total(
+        &self,
+        table_item_count: u64,
+        table_size_bytes: u64,
+    ) -> Option<ScanProgressTotal> {
+        let from_start = self.exclusive_start_key.is_none();
+        let item_count = (from_start && table_item_count > 0).then_some(table_item_count as usize);
+        let size_bytes = (from_start && table_size_bytes > 0).then_some(table_size_bytes);
+        if self.filter.is_some() {
+            // the number of matching items is unknown, so follow the items evaluated by the scan
+            return match (item_count, size_bytes) {
+                (Some(count), _) => Some(ScanProgressTotal::ScannedItems(count)),
+                (None, Some(bytes)) => Some(ScanProgressTotal::Bytes(bytes)),
+                (None, None) => None,
+            };
+        }
+        match (self.limit, item_count, size_bytes) {
+            (Some(limit), Some(count), _) => Some(ScanProgressTotal::Items(limit.min(count))),
+            (Some(limit), None, _) => Some(ScanProgressTotal::Items(limit)),
+            (None, Some(count), _) => Some(ScanProgressTotal::Items(count)),
+            (None, None, Some(bytes)) => Some(ScanProgressTotal::Bytes(bytes)),
+            (None, None, None) => None,
+        }
+    }
+}
+
+// The amount of a scan done so far, reported after each page
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanProgress {
+    pub items: usize,
+    // the items evaluated before the filter is applied
+    pub scanned_items: usize,
+    // estimated from the consumed capacity
+    pub scanned_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanProgressTotal {
+    Items(usize),
+    ScannedItems(usize),
+    Bytes(u64),
+}
+
+impl ScanProgressTotal {
+    pub fn total(&self) -> usize {
+        match self {
+            ScanProgressTotal::Items(n) | ScanProgressTotal::ScannedItems(n) => *n,
+            ScanProgressTotal::Bytes(n) => *n as usize,
+        }
+    }
+
+    pub fn current(&self, progress: &ScanProgress) -> usize {
+        match self {
+            ScanProgressTotal::Items(_) => progress.items,
+            ScanProgressTotal::ScannedItems(_) => progress.scanned_items,
+            ScanProgressTotal::Bytes(_) => progress.scanned_bytes as usize,
+        }
+    }
+
+    // the number of bytes is not meaningful to show as is
+    pub fn is_count(&self) -> bool {
+        !matches!(self, ScanProgressTotal::Bytes(_))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterExpression {
+    pub conditions: Vec<FilterCondition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterCondition {
+    pub conjunction: Conjunction,
+    pub attribute_name: String,
+    pub operator: FilterOperator,
+    pub value: Option<Attribute>,
+}
+
+#[zero_indexed_enum]
+pub enum Conjunction {
+    And,
+    Or,
+}
+
+impl Conjunction {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Conjunction::And => "AND",
+            Conjunction::Or => "OR",
+        }
+    }
+}
+
+#[zero_indexed_enum]
+pub enum FilterOperator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    BeginsWith,
+    Contains,
+    Exists,
+    NotExists,
+}
+
+impl FilterOperator {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FilterOperator::Eq => "=",
+            FilterOperator::Ne => "<>",
+            FilterOperator::Lt => "<",
+            FilterOperator::Le => "<=",
+            FilterOperator::Gt => ">",
+            FilterOperator::Ge => ">=",
+            FilterOperator::BeginsWith => "begins_with",
+            FilterOperator::Contains => "contains",
+            FilterOperator::Exists => "exists",
+            FilterOperator::NotExists => "not exists",
+        }
+    }
+
+    pub fn needs_value(&self) -> bool {
+        !matches!(self, FilterOperator::Exists | FilterOperator::NotExists)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct BuiltExpression {
+    pub expression: String,
+    pub names: BTreeMap<String, String>,
+    pub values: BTreeMap<String, Attribute>,
+}
+
+impl FilterExpression {
+    pub fn build(&self) -> BuiltExpression {
+        let mut built = BuiltExpression::default();
+        let mut name_placeholders: HashMap<&str, String> = HashMap::new();
+        for (i, cond) in self.conditions.iter().enumerate() {
+            let n = name_placeholders.len();
+            let name = name_placeholders
+                .entry(&cond.attribute_name)
+                .or_insert_with(|| format!("#n{n}"))
+                .clone();
+            built
+                .names
+                .insert(name.clone(), cond.attribute_name.clone());
+
+            let value = format!(":v{}", built.values.len());
+            if let (true, Some(v)) = (cond.operator.needs_value(), &cond.value) {
+                built.values.insert(value.clone(), v.clone());
+            }
+
+            let expr = match cond.operator {
+                FilterOperator::BeginsWith => format!("begins_with({name}, {value})"),
+                FilterOperator::Contains => format!("contains({name}, {value})"),
+                FilterOperator::Exists => format!("attribute_exists({name})"),
+                FilterOperator::NotExists => format!("attribute_not_exists({name})"),
+                op => format!("{name} {} {value}", op.as_str()),
+            };
+            if i > 0 {
+                built
+                    .expression
+                    .push_str(&format!(" {} ", cond.conjunction.as_str()));
+            }
+            built.expression.push_str(&expr);
+        }
+        built
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryTarget {
+    pub index_name: Option<String>,
+    pub hash_key: String,
+    pub range_key: Option<String>,
+}
+
+impl TableDescription {
+    pub fn query_targets(&self) -> Vec<QueryTarget> {
+        let mut targets = vec![to_query_target(None, &self.key_schema)];
+        for index in self.global_secondary_indexes.iter().flatten() {
+            let name = Some(index.index_name.clone());
+            targets.push(to_query_target(name, &index.key_schema));
+        }
+        for index in self.local_secondary_indexes.iter().flatten() {
+            let name = Some(index.index_name.clone());
+            targets.push(to_query_target(name, &index.key_schema));
+        }
+        targets
+    }
+
+    // The table keys are always included in LastEvaluatedKey, and the index keys as well when querying an index
+    pub fn key_attribute_names(&self, index_name: Option<&str>) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .key_schema
+            .iter()
+            .map(|e| e.attribute_name.clone())
+            .collect();
+        if let Some(target) = self
+            .query_targets()
+            .into_iter()
+            .find(|t| index_name.is_some() && t.index_name.as_deref() == index_name)
+        {
+            for name in std::iter::once(target.hash_key).chain(target.range_key) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    pub fn contributor_insights_enabled(&self, index_name: Option<&str>) -> bool {
+        self.contributor_insights
+            .iter()
+            .any(|c| c.index_name.as_deref() == index_name && c.is_enabled())
+    }
+
+    // On-demand tables report zero capacity units, so they are never considered low capacity
+    pub fn low_write_capacity_units(&self, threshold: u64) -> Option<u64> {
+        self.provisioned_throughput
+            .as_ref()
+            .map(|t| t.write_capacity_units)
+            .filter(|&wcu| wcu > 0 && wcu <= threshold)
+    }
+
+    pub fn attribute_type(&self, attribute_name: &str) -> Option<ScalarAttributeType> {
+        self.attribute_definitions
+            .iter()
+            .find(|def| def.attribute_name == attribute_name)
+            .map(|def| def.attribute_type.clone())
+    }
+
+    // A request to set the TTL attribute of the item to the given epoch seconds
+    pub fn set_ttl_request(&self, item: &Item, expires_at: i64) -> AppResult<WriteRequest> {
+        let name = match &self.ttl_attribute_name {
+            Some(name) => name.clone(),
+            None => {
+                let msg = format!("TTL is not enabled on table {}", self.table_name);
+                return Err(AppError::msg(msg));
+            }
+        };
+        self.set_attribute_request(item, name, Attribute::N(Decimal::from(expires_at)))
+    }
+
+    // A request to set the (non-key) attribute of the item to the given value
+    pub fn set_attribute_request(
+        &self,
+        item: &Item,
+        name: String,
+        value: Attribute,
+    ) -> AppResult<WriteRequest> {
+        if self.key_attribute_names(None).contains(&name) {
+            let msg = format!("Key attribute {name} cannot be updated");
+            return Err(AppError::msg(msg));
+        }
+        let key = match to_key_attributes(item, &self.key_schema_type) {
+            Some(key) => key,
+            None => return Err(AppError::msg("Item does not contain all key attributes")),
+        };
+        Ok(WriteRequest::SetAttribute {
+            key,
+            item: item.clone(),
+            name,
+            value,
+        })
+    }
+
+    // A key that is only used to check permissions, requests with it are never expected to succeed
+    pub fn probe_key(&self) -> Option<BTreeMap<String, Attribute>> {
+        let key_names = match &self.key_schema_type {
+            KeySchemaType::Hash(key) => vec![key],
+            KeySchemaType::HashRange(key1, key2) => vec![key1, key2],
+        };
+        key_names
+            .into_iter()
+            .map(|name| {
+                let attr = match self.attribute_type(name)? {
+                    ScalarAttributeType::S => Attribute::S(PROBE_KEY_VALUE.into()),
+                    ScalarAttributeType::N => Attribute::N(Decimal::ZERO),
+                    ScalarAttributeType::B => Attribute::B(PROBE_KEY_VALUE.into()),
+                    ScalarAttributeType::Other(_) => return None,
+                };
+                Some((name.clone(), attr))
+            })
+            .collect()
+    }
+
+    // A query by the partition key for an attribute value that refers to an item of this table
+    pub fn reference_query_options(&self, value: &Attribute) -> AppResult<QueryOptions> {
+        let hash_key_name = self.key_schema_type.hash_key_name();
+        let hash_key_value = match (value, self.attribute_type(hash_key_name)) {
+            (Attribute::S(s), Some(ScalarAttributeType::N)) => match s.trim().parse() {
+                Ok(n) => Attribute::N(n),
+                Err(_) => {
+                    let msg = format!("{s} cannot be used as the number key {hash_key_name}");
+                    return Err(AppError::msg(msg));
+                }
+            },
+            (Attribute::N(n), Some(ScalarAttributeType::S)) => Attribute::S(n.to_string()),
+            (Attribute::S(_), Some(ScalarAttributeType::S))
+            | (Attribute::N(_), Some(ScalarAttributeType::N))
+            | (Attribute::B(_), Some(ScalarAttributeType::B)) => value.clone(),
+            (value, key_type) => {
+                let msg = format!(
+                    "{} attribute cannot be used as the {} key {hash_key_name}",
+                    value.as_type_str(),
+                    key_type.as_ref().map(|t| t.as_str()).unwrap_or("unknown"),
+                );
+                return Err(AppError::msg(msg));
+            }
+        };
+        Ok(QueryOptions {
+            index_name: None,
+            hash_key_name: hash_key_name.into(),
+            hash_key_value,
+            sort_key_condition: None,
+            limit: None,
+            scan_index_forward: true,
+            exclusive_start_key: None,
+        })
+    }
+
+    // A query of the table or the index by the key values given as strings,
+    // the sort key is matched exactly or as a prefix
+    pub fn key_query_options(
+        &self,
+        index_name: Option<&str>,
+        hash_key_value: &str,
+        sort_key_value: Option<(&str, bool)>,
+    ) -> AppResult<QueryOptions> {
+        let Some(target) = self
+            .query_targets()
+            .into_iter()
+            .find(|t| t.index_name.as_deref() == index_name)
+        else {
+            let msg = format!(
+                "Index {} not found in table {}",
+                index_name.unwrap_or_default(),
+                self.table_name
+            );
+            return Err(AppError::msg(msg));
+        };
+        let hash_key_value =
+            parse_key_value(hash_key_value, self.attribute_type(&target.hash_key))?;
+        let sort_key_condition = match (sort_key_value, target.range_key) {
+            (Some((value, prefix)), Some(name)) => {
+                let value = parse_key_value(value, self.attribute_type(&name))?;
+                let operator = if prefix {
+                    SortKeyOperator::BeginsWith
+                } else {
+                    SortKeyOperator::Eq
+                };
+                Some(SortKeyCondition {
+                    attribute_name: name,
+                    operator,
+                    values: vec![value],
+                })
+            }
+            (Some(_), None) => {
+                let msg = format!("{} has no sort key", self.table_name);
+                return Err(AppError::msg(msg));
+            }
+            (None, _) => None,
+        };
+        Ok(QueryOptions {
+            index_name: target.index_name,
+            hash_key_name: target.hash_key,
+            hash_key_value,
+            sort_key_condition,
+            limit: None,
+            scan_index_forward: true,
+            exclusive_start_key: None,
+        })
+    }
+}
+
+const PROBE_KEY_VALUE: &str = "ddv-permission-probe";
+
+fn to_query_target(index_name: Option<String>, key_schema: &[KeySchemaElement]) -> QueryTarget {
+    let hash_key = key_schema
+        .iter()
+        .find(|e| matches!(e.key_type, KeyType::Hash))
+        .map(|e| e.attribute_name.clone())
+        .unwrap_or_default();
+    let range_key = key_schema
+        .iter()
+        .find(|e| matches!(e.key_type, KeyType::Range))
+        .map(|e| e.attribute_name.clone());
+    QueryTarget {
+        index_name,
+        hash_key,
+        range_key,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    pub index_name: Option<String>,
+    pub hash_key_name: String,
+    pub hash_key_value: Attribute,
+    pub sort_key_condition: Option<SortKeyCondition>,
+    pub limit: Option<usize>,
+    pub scan_index_forward: bool,
+    pub exclusive_start_key: Option<BTreeMap<String, Attribute>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SortKeyCondition {
+    pub attribute_name: String,
+    pub operator: SortKeyOperator,
+    pub values: Vec<Attribute>,
+}
+
+#[zero_indexed_enum]
+pub enum SortKeyOperator {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    BeginsWith,
+    Between,
+}
+
+impl SortKeyOperator {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SortKeyOperator::Eq => "=",
+            SortKeyOperator::Lt => "<",
+            SortKeyOperator::Le => "<=",
+            SortKeyOperator::Gt => ">",
+            SortKeyOperator::Ge => ">=",
+            SortKeyOperator::BeginsWith => "begins_with",
+            SortKeyOperator::Between => "between",
+        }
+    }
+}
+
+impl QueryOptions {
+    pub fn build_key_condition(&self) -> BuiltExpression {
+        let mut built = BuiltExpression::default();
+        built.names.insert("#k0".into(), self.hash_key_name.clone());
+        built
+            .values
+            .insert(":k0".into(), self.hash_key_value.clone());
+        built.expression.push_str("#k0 = :k0");
+
+        if let Some(cond) = &self.sort_key_condition {
+            built
+                .names
+                .insert("#k1".into(), cond.attribute_name.clone());
+            for (i, v) in cond.values.iter().enumerate() {
+                built.values.insert(format!(":k{}", i + 1), v.clone());
+            }
+            let expr = match cond.operator {
+                SortKeyOperator::BeginsWith => "begins_with(#k1, :k1)".into(),
+                SortKeyOperator::Between => "#k1 BETWEEN :k1 AND :k2".into(),
+                op => format!("#k1 {} :k1", op.as_str()),
+            };
+            built.expression.push_str(" AND ");
+            built.expression.push_str(&expr);
+        }
+        built
+    }
+}
+
+pub fn parse_key_value(
+    value: &str,
+    attribute_type: Option<ScalarAttributeType>,
+) -> AppResult<Attribute> {
+    match attribute_type {
+        Some(ScalarAttributeType::S) | Some(ScalarAttributeType::Other(_)) | None => {
+            Ok(Attribute::S(value.into()))
+        }
+        Some(ScalarAttributeType::N) => value
+            .trim()
+            .parse()
+            .map(Attribute::N)
+            .map_err(|_| AppError::msg(format!("Invalid number: {value}"))),
+        Some(ScalarAttributeType::B) => from_base64_str(value.trim()).map(Attribute::B),
+    }
+}
+
+// Parse a key like `pk=USER#42,sk=ORDER#7` according to the key schema of the table
+pub fn parse_key(desc: &TableDescription, s: &str) -> AppResult<BTreeMap<String, Attribute>> {
+    let mut key = BTreeMap::new();
+    for pair in s.split(',').filter(|pair| !pair.trim().is_empty()) {
+        let (name, value) = match pair.split_once('=') {
+            Some((name, value)) => (name.trim(), value),
+            None => return Err(AppError::msg(format!("Invalid key: {pair}"))),
+        };
+        let value = parse_key_value(value, desc.attribute_type(name))?;
+        key.insert(name.to_string(), value);
+    }
+    let key_names = match &desc.key_schema_type {
+        KeySchemaType::Hash(hash) => vec![hash],
+        KeySchemaType::HashRange(hash, range) => vec![hash, range],
+    };
+    if key.len() != key_names.len() || key_names.iter().any(|name| !key.contains_key(*name)) {
+        let names = key_names
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        let msg = format!("Key must contain exactly the key attributes: {names}");
+        return Err(AppError::msg(msg));
+    }
+    Ok(key)
+}
+
+pub fn parse_raw_json_attributes(s: &str) -> AppResult<BTreeMap<String, Attribute>> {
+    parse_raw_json_item(s).map_err(|e| AppError::msg(e.message))
+}
+
+// Parse a single attribute value in DynamoDB JSON like `{"S": "active"}`
+pub fn parse_raw_json_attribute_value(s: &str) -> AppResult<Attribute> {
+    let value: serde_json::Value =
+        serde_json::from_str(s).map_err(|e| AppError::msg(format!("failed to parse JSON: {e}")))?;
+    parse_raw_json_attribute(&value)
+}
+
+pub struct JsonParseError {
+    pub message: String,
+    // 1-based line and column in the input, if the error can be located
+    pub position: Option<(usize, usize)>,
+}
+
+impl JsonParseError {
+    // The message followed by the line of the input where the error occurred and a caret under the column
+    pub fn describe(&self, s: &str) -> Vec<String> {
+        let mut lines = vec![self.message.clone()];
+        if let Some((line, column)) = self.position {
+            if let Some(text) = s.lines().nth(line.saturating_sub(1)) {
+                let text = text.replace('\t', " ");
+                let line_number = line.to_string();
+                let before_column = text
+                    .char_indices()
+                    .nth(column.saturating_sub(1))
+                    .map_or(text.as_str(), |(i, _)| &text[..i]);
+                let offset = console::measure_text_width(before_column);
+                lines.push(format!("{line_number} | {text}"));
+                lines.push(format!(
+                    "{} | {}^",
+                    " ".repeat(line_number.len()),
+                    " ".repeat(offset)
+                ));
+            }
+        }
+        lines
+    }
+}
+
+pub fn parse_raw_json_item(s: &str) -> Result<BTreeMap<String, Attribute>, JsonParseError> {
+    let value: serde_json::Value = serde_json::from_str(s).map_err(|e| JsonParseError {
+        message: format!("failed to parse JSON: {e}"),
+        position: (e.line() > 0).then(|| (e.line(), e.column())),
+    })?;
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .map(|(k, v)| match parse_raw_json_attribute(&v) {
+                Ok(attr) => Ok((k, attr)),
+                Err(e) => Err(JsonParseError {
+                    message: format!("attribute \"{k}\": {}", e.msg),
+                    position: find_json_key_position(s, &k),
+                }),
+            })
+            .collect(),
+        _ => Err(JsonParseError {
+            message: "JSON must be an object".into(),
+            position: None,
+        }),
+    }
+}
+
+// Locate the first occurrence of the key (a quoted string followed by a colon) in the JSON text
+fn find_json_key_position(s: &str, key: &str) -> Option<(usize, usize)> {
+    let quoted = serde_json::to_string(key).ok()?;
+    for (i, line) in s.lines().enumerate() {
+        for (j, _) in line.match_indices(&quoted) {
+            let rest = line[j + quoted.len()..].trim_start();
+            if rest.starts_with(':') {
+                return Some((i + 1, line[..j].chars().count() + 1));
+            }
+        }
+    }
+    None
+}
+
+fn parse_raw_json_attribute(value: &serde_json::Value) -> AppResult<Attribute> {
+    let invalid = || AppError::msg(format!("invalid DynamoDB JSON attribute: {value}"));
+    let (type_str, v) = match value.as_object() {
+        Some(obj) if obj.len() == 1 => obj.iter().next().unwrap(),
+        _ => return Err(invalid()),
+    };
+    let as_str = |v: &serde_json::Value| v.as_str().map(String::from).ok_or_else(invalid);
+    let as_decimal = |v: &serde_json::Value| {
+        v.as_str()
+            .and_then(|s| s.parse::<Decimal>().ok())
+            .ok_or_else(invalid)
+    };
+    let as_bytes = |v: &serde_json::Value| v.as_str().ok_or_else(invalid).and_then(from_base64_str);
+    let as_array = |v: &serde_json::Value| v.as_array().cloned().ok_or_else(invalid);
+    let attr = match type_str.as_str() {
+        "S" => Attribute::S(as_str(v)?),
+        "N" => Attribute::N(as_decimal(v)?),
+        "B" => Attribute::B(as_bytes(v)?),
+        "BOOL" => Attribute::BOOL(v.as_bool().ok_or_else(invalid)?),
+        "NULL" => Attribute::NULL,
+        "L" => Attribute::L(
+            as_array(v)?
+                .iter()
+                .map(parse_raw_json_attribute)
+                .collect::<AppResult<_>>()?,
+        ),
+        "M" => Attribute::M(
+            v.as_object()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(|(k, v)| parse_raw_json_attribute(v).map(|attr| (k.clone(), attr)))
+                .collect::<AppResult<_>>()?,
+        ),
+        "SS" => Attribute::SS(as_array(v)?.iter().map(as_str).collect::<AppResult<_>>()?),
+        "NS" => Attribute::NS(
+            as_array(v)?
+                .iter()
+                .map(as_decimal)
+                .collect::<AppResult<_>>()?,
+        ),
+        "BS" => Attribute::BS(
+            as_array(v)?
+                .iter()
+                .map(as_bytes)
+                .collect::<AppResult<_>>()?,
+        ),
+        _ => return Err(invalid()),
+    };
+    Ok(attr)
+}
+
+pub struct TableInsight {
+    pub table_name: String,
+    pub total_items: usize,
+    pub attribute_distributions: Vec<AttributeDistribution>,
+    // the number of items in which both attributes are defined,
+    // indexed in the same order as attribute_distributions
+    pub co_occurrences: Vec<Vec<usize>>,
+    // the name and the string values of the sort key, only for hash+range tables
+    pub sort_key: Option<(String, Vec<String>)>,
+    // the items whose primary keys collide after normalization
+    pub duplicate_keys: Vec<DuplicateGroup>,
+    // the configured attribute names and the items with the same normalized values of them
+    pub near_duplicates: Option<(Vec<String>, Vec<DuplicateGroup>)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    // the normalized values joined with ` / `
+    pub value: String,
+    // the primary keys of the items in the group
+    pub keys: Vec<String>,
+}
+
+impl TableInsight {
+    pub fn new(table_description: &TableDescription, items: &[Item]) -> TableInsight {
+        let table_name = table_description.table_name.clone();
+        let total_items = items.len();
+
+        let attribute_keys = list_attribute_keys(items, &table_description.key_schema_type);
+        let attribute_distributions = build_attribute_distributions(items, &attribute_keys);
+        let co_occurrences = build_co_occurrences(items, &attribute_keys);
+        let sort_key = match &table_description.key_schema_type {
+            KeySchemaType::Hash(_) => None,
+            KeySchemaType::HashRange(_, range) => {
+                let values = items
+                    .iter()
+                    .filter_map(|item| match item.attributes.get(range) {
+                        Some(Attribute::S(s)) => Some(s.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                Some((range.clone(), values))
+            }
+        };
+        let key_names = match &table_description.key_schema_type {
+            KeySchemaType::Hash(key) => vec![key.clone()],
+            KeySchemaType::HashRange(key1, key2) => vec![key1.clone(), key2.clone()],
+        };
+        let duplicate_keys = find_duplicates(items, &key_names, &table_description.key_schema_type);
+
+        TableInsight {
+            table_name,
+            total_items,
+            attribute_distributions,
+            co_occurrences,
+            sort_key,
+            duplicate_keys,
+            near_duplicates: None,
+        }
+    }
+
+    // Look for the items with the same values of the attributes, e.g. the email address of users,
+    // which are likely to be the same entity registered twice
+    pub fn with_near_duplicates(
+        mut self,
+        table_description: &TableDescription,
+        items: &[Item],
+        attribute_names: &[String],
+    ) -> TableInsight {
+        if !attribute_names.is_empty() {
+            let schema = &table_description.key_schema_type;
+            let groups = find_duplicates(items, attribute_names, schema);
+            self.near_duplicates = Some((attribute_names.to_vec(), groups));
+        }
+        self
+    }
+
+    // The most common prefixes of the sort key values, split at the first delimiter,
+    // values without the delimiter are counted as they are
+    pub fn sort_key_prefixes(&self, delimiter: &str, limit: usize) -> Vec<(String, usize)> {
+        let Some((_, values)) = &self.sort_key else {
+            return Vec::new();
+        };
+        if delimiter.is_empty() {
+            return Vec::new();
+        }
+        let mut counter: HashMap<&str, usize> = HashMap::new();
+        for value in values {
+            let prefix = match value.find(delimiter) {
+                Some(i) => &value[..i + delimiter.len()],
+                None => value.as_str(),
+            };
+            *counter.entry(prefix).or_insert(0) += 1;
+        }
+        let mut prefixes: Vec<(String, usize)> = counter
+            .into_iter()
+            .map(|(prefix, count)| (prefix.to_string(), count))
+            .collect();
+        prefixes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        prefixes.truncate(limit);
+        prefixes
+    }
+}
+
+fn build_attribute_distributions(
+    items: &[Item],
+    attribute_keys: &[String],
+) -> Vec<AttributeDistribution> {
+    let mut key_counter = Vec::new();
+    for key in attribute_keys {
+        key_counter.push((key, HashMap::new()));
+    }
+    for item in items {
+        for (i, key) in attribute_keys.iter().enumerate() {
+            let attr = item.attributes.get(key);
+            let attr_type = AttributeType::from(attr);
+            let (_, counter) = key_counter.get_mut(i).unwrap();
+            *counter.entry(attr_type).or_insert(0) += 1;
+        }
+    }
+    key_counter
+        .into_iter()
+        .map(|(key, counter)| {
+            let mut distributions: Vec<(AttributeType, usize)> = counter.into_iter().collect();
+            distributions.sort_by_key(|d| std::cmp::Reverse(d.1)); // sort by count desc
+            AttributeDistribution {
+                attribute_name: key.clone(),
+                distributions,
+            }
+        })
+        .collect()
+}
+
+fn build_co_occurrences(items: &[Item], attribute_keys: &[String]) -> Vec<Vec<usize>> {
+    let mut co_occurrences = vec![vec![0; attribute_keys.len()]; attribute_keys.len()];
+    for item in items {
+        let defined: Vec<usize> = attribute_keys
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| item.attributes.contains_key(*key))
+            .map(|(i, _)| i)
+            .collect();
+        for &i in &defined {
+            for &j in &defined {
+                co_occurrences[i][j] += 1;
+            }
+        }
+    }
+    co_occurrences
+}
+
+// Group the items by the normalized values of the attributes and return the groups with multiple items,
+// the items without any of the attributes are not grouped
+fn find_duplicates(
+    items: &[Item],
+    attribute_names: &[String],
+    schema: &KeySchemaType,
+) -> Vec<DuplicateGroup> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for item in items {
+        let values: Option<Vec<String>> = attribute_names
+            .iter()
+            .map(|name| item.attributes.get(name).map(normalize_attribute))
+            .collect();
+        if let Some(values) = values {
+            groups
+                .entry(values.join(" / "))
+                .or_default()
+                .push(to_key_string(item, schema));
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, keys)| keys.len() > 1)
+        .map(|(value, keys)| DuplicateGroup { value, keys })
+        .collect()
+}
+
+// Strings that differ only in case or surrounding whitespace are treated as the same value
+fn normalize_attribute(attr: &Attribute) -> String {
+    match attr {
+        Attribute::S(s) => s.trim().to_lowercase(),
+        _ => attr.to_simple_string(),
+    }
+}
+
+pub struct AttributeDistribution {
+    pub attribute_name: String,
+    pub distributions: Vec<(AttributeType, usize)>,
+}
+
+impl AttributeDistribution {
+    pub fn missing_or_null_count(&self) -> usize {
+        self.distributions
+            .iter()
+            .filter(|(t, _)| matches!(t, AttributeType::None | AttributeType::Null))
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ColumnStats {
+    pub attribute_name: String,
+    pub total_items: usize,
+    pub missing_count: usize,
+    pub null_count: usize,
+    pub distinct_count: usize,
+    // only for the number values, other types are ignored
+    pub number_range: Option<(Decimal, Decimal)>,
+    // the first distinct values in the order of the items
+    pub samples: Vec<String>,
+}
+
+impl ColumnStats {
+    pub fn new(items: &[&Item], attribute_name: &str, sample_count: usize) -> ColumnStats {
+        let mut missing_count = 0;
+        let mut null_count = 0;
+        let mut distinct: HashSet<String> = HashSet::new();
+        let mut number_range: Option<(Decimal, Decimal)> = None;
+        let mut samples = Vec::new();
+        for item in items {
+            let attr = match item.attributes.get(attribute_name) {
+                None => {
+                    missing_count += 1;
+                    continue;
+                }
+                Some(Attribute::NULL) => {
+                    null_count += 1;
+                    continue;
+                }
+                Some(attr) => attr,
+            };
+            if let Attribute::N(n) = attr {
+                number_range = match number_range {
+                    Some((min, max)) => Some((min.min(*n), max.max(*n))),
+                    None => Some((*n, *n)),
+                };
+            }
+            let value = attr.to_simple_string();
+            if distinct.insert(value.clone()) && samples.len() < sample_count {
+                samples.push(value);
+            }
+        }
+        ColumnStats {
+            attribute_name: attribute_name.into(),
+            total_items: items.len(),
+            missing_count,
+            null_count,
+            distinct_count: distinct.len(),
+            number_range,
+            samples,
+        }
+    }
+}
+
+// The expected type of each attribute, used to find values that deviate from it:
+// the type in the attribute definitions for key attributes, otherwise the type of
+// the majority of the defined values (NULL is not counted as it is used for optional values)
+pub fn expected_attribute_types(
+    desc: &TableDescription,
+    items: &[Item],
+) -> HashMap<String, AttributeType> {
+    let insight = TableInsight::new(desc, items);
+    insight
+        .attribute_distributions
+        .into_iter()
+        .filter_map(|dist| {
+            let name = dist.attribute_name;
+            if let Some(attr_type) = desc.attribute_type(&name) {
+                let expected = match attr_type {
+                    ScalarAttributeType::S => AttributeType::String,
+                    ScalarAttributeType::N => AttributeType::Number,
+                    ScalarAttributeType::B => AttributeType::Blob,
+                    ScalarAttributeType::Other(_) => return None,
+                };
+                return Some((name, expected));
+            }
+
+            let counts: Vec<_> = dist
+                .distributions
+                .into_iter()
+                .filter(|(t, _)| *t != AttributeType::None && *t != AttributeType::Null)
+                .collect();
+            let total: usize = counts.iter().map(|(_, count)| count).sum();
+            // distributions are sorted by count in descending order
+            counts
+                .first()
+                .filter(|(_, count)| count * 2 > total)
+                .map(|(t, _)| (name, *t))
+        })
+        .collect()
+}
+
+// Non-key attributes that appear in at least half of the sampled items are included in the template
+const NEW_ITEM_TEMPLATE_MIN_RATIO: f64 = 0.5;
+
+// Build a skeleton of a new item with the key attributes and the common attributes of the sampled items,
+// each attribute has an empty value of its (most common) type
+pub fn new_item_template(desc: &TableDescription, insight: &TableInsight) -> Item {
+    let mut attributes = HashMap::new();
+
+    let key_names = match &desc.key_schema_type {
+        KeySchemaType::Hash(key) => vec![key],
+        KeySchemaType::HashRange(key1, key2) => vec![key1, key2],
+    };
+    for name in key_names {
+        let attr = match desc.attribute_type(name) {
+            Some(ScalarAttributeType::N) => Attribute::N(Decimal::ZERO),
+            Some(ScalarAttributeType::B) => Attribute::B(vec![]),
+            _ => Attribute::S("".into()),
+        };
+        attributes.insert(name.clone(), attr);
+    }
+
+    let min_count = (insight.total_items as f64 * NEW_ITEM_TEMPLATE_MIN_RATIO).ceil() as usize;
+    for dist in &insight.attribute_distributions {
+        if attributes.contains_key(&dist.attribute_name) {
+            continue;
+        }
+        // distributions are sorted by count in descending order
+        let most_common = dist
+            .distributions
+            .iter()
+            .find(|(attr_type, _)| *attr_type != AttributeType::None);
+        let defined_count: usize = dist
+            .distributions
+            .iter()
+            .filter(|(attr_type, _)| *attr_type != AttributeType::None)
+            .map(|(_, count)| count)
+            .sum();
+        if let Some((attr_type, _)) = most_common {
+            if defined_count >= min_count.max(1) {
+                if let Some(attr) = attr_type.placeholder() {
+                    attributes.insert(dist.attribute_name.clone(), attr);
+                }
+            }
+        }
+    }
+
+    Item { attributes }
+}
+
+pub struct ItemDifference {
+    pub key: String,
+    pub attribute_names: Vec<String>,
+}
+
+// Items compared by primary key, each key is shown as a plain JSON string
+pub struct ItemsComparison {
+    pub only_left: Vec<String>,
+    pub only_right: Vec<String>,
+    pub different: Vec<ItemDifference>,
+    pub same_count: usize,
+}
+
+pub fn compare_items(left: &[Item], right: &[Item], schema: &KeySchemaType) -> ItemsComparison {
+    let key_string = |item: &Item| {
+        let key = Item {
+            attributes: to_key_attributes(item, schema)
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+        };
+        to_plain_json_string(&key, schema)
+    };
+
+    let mut right_items: HashMap<String, &Item> =
+        right.iter().map(|item| (key_string(item), item)).collect();
+
+    let mut only_left = Vec::new();
+    let mut different = Vec::new();
+    let mut same_count = 0;
+    for item in left {
+        let key = key_string(item);
+        match right_items.remove(&key) {
+            Some(right_item) => {
+                let attribute_names = differing_attribute_names(item, right_item, schema);
+                if attribute_names.is_empty() {
+                    same_count += 1;
+                } else {
+                    different.push(ItemDifference {
+                        key,
+                        attribute_names,
+                    });
+                }
+            }
+            None => only_left.push(key),
+        }
+    }
+    // keep the order of the right items
+    let only_right = right
+        .iter()
+        .map(key_string)
+        .filter(|key| right_items.contains_key(key))
+        .collect();
+
+    ItemsComparison {
+        only_left,
+        only_right,
+        different,
+        same_count,
+    }
+}
+
+fn differing_attribute_names(left: &Item, right: &Item, schema: &KeySchemaType) -> Vec<String> {
+    let names: HashSet<&String> = left
+        .attributes
+        .keys()
+        .chain(right.attributes.keys())
+        .collect();
+    let mut names: Vec<String> = names
+        .into_iter()
+        .filter(|name| left.attributes.get(*name) != right.attributes.get(*name))
+        .cloned()
+        .collect();
+    sort_keys(&mut names, schema);
+    names
+}
+
+pub struct TableDescriptionDiff {
+    pub property: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl TableDescriptionDiff {
+    pub fn is_same(&self) -> bool {
+        self.left == self.right
+    }
+}
+
+// Compare the schema related properties of two tables,
+// properties that exist only in the right table are listed after the left ones
+pub fn diff_table_descriptions(
+    left: &TableDescription,
+    right: &TableDescription,
+) -> Vec<TableDescriptionDiff> {
+    let left_properties = schema_properties(left);
+    let right_properties = schema_properties(right);
+    let right_names: Vec<String> = right_properties.iter().map(|(n, _)| n.clone()).collect();
+    let mut right_properties: HashMap<String, String> = right_properties.into_iter().collect();
+
+    let mut diffs = Vec::new();
+    for (name, value) in left_properties {
+        let right_value = right_properties.remove(&name);
+        diffs.push(TableDescriptionDiff {
+            property: name,
+            left: Some(value),
+            right: right_value,
+        });
+    }
+    for name in right_names {
+        if let Some(value) = right_properties.remove(&name) {
+            diffs.push(TableDescriptionDiff {
+                property: name,
+                left: None,
+                right: Some(value),
+            });
+        }
+    }
+    diffs
+}
+
+fn schema_properties(desc: &TableDescription) -> Vec<(String, String)> {
+    let key_schema = |keys: &[KeySchemaElement]| {
+        keys.iter()
+            .map(|key| format!("{} ({})", key.attribute_name, key.key_type.as_str()))
+            .collect::<Vec<String>>()
+            .join(" / ")
+    };
+    let projection = |projection: &Projection| match &projection.non_key_attributes {
+        Some(attrs) if !attrs.is_empty() => format!(
+            "{} ({})",
+            projection.projection_type.as_str(),
+            attrs.join(", ")
+        ),
+        _ => projection.projection_type.as_str().to_string(),
+    };
+
+    let mut properties = vec![("Key Schema".to_string(), key_schema(&desc.key_schema))];
+
+    let mut attribute_definitions: Vec<&AttributeDefinition> =
+        desc.attribute_definitions.iter().collect();
+    attribute_definitions.sort_by(|a, b| a.attribute_name.cmp(&b.attribute_name));
+    for attr in attribute_definitions {
+        properties.push((
+            format!("Attribute {}", attr.attribute_name),
+            attr.attribute_type.as_str().to_string(),
+        ));
+    }
+
+    let throughput = match &desc.provisioned_throughput {
+        Some(pt) => format!(
+            "Read: {} / Write: {}",
+            pt.read_capacity_units, pt.write_capacity_units
+        ),
+        None => "-".into(),
+    };
+    properties.push(("Provisioned Throughput".into(), throughput));
+    let ttl = match &desc.ttl_attribute_name {
+        Some(name) => name.clone(),
+        None if desc.unavailable_details.contains(&"TTL Attribute") => "unavailable".into(),
+        None => "-".into(),
+    };
+    properties.push(("TTL Attribute".into(), ttl));
+
+    let mut lsis: Vec<&LocalSecondaryIndexDescription> =
+        desc.local_secondary_indexes.iter().flatten().collect();
+    lsis.sort_by(|a, b| a.index_name.cmp(&b.index_name));
+    let lsis = lsis
+        .into_iter()
+        .map(|i| ("LSI", &i.index_name, &i.key_schema, &i.projection));
+    let mut gsis: Vec<&GlobalSecondaryIndexDescription> =
+        desc.global_secondary_indexes.iter().flatten().collect();
+    gsis.sort_by(|a, b| a.index_name.cmp(&b.index_name));
+    let gsis = gsis
+        .into_iter()
+        .map(|i| ("GSI", &i.index_name, &i.key_schema, &i.projection));
+    for (index_type, name, keys, proj) in lsis.chain(gsis) {
+        properties.push((format!("{index_type} {name} Key Schema"), key_schema(keys)));
+        properties.push((format!("{index_type} {name} Projection"), projection(proj)));
+    }
+
+    properties
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use rust_decimal::prelude::FromPrimitive;
+
+    use super::*;
+    use crate::view::snapshot::table_description;
+
+    #[rstest]
+    #[case(0, 1, 0)]
+    #[case(4096, 1, 1)]
+    #[case(8192, 1, 1)]
+    #[case(8193, 1, 2)]
+    #[case(1024 * 1024 + 1, 2, 129)]
+    #[case(2_100_000_000, 2003, 256348)]
+    fn test_scan_estimate(
+        #[case] total_size_bytes: u64,
+        #[case] pages: u64,
+        #[case] read_capacity_units: u64,
+    ) {
+        let estimate = ScanEstimate::new(total_size_bytes);
+        assert_eq!(estimate.pages, pages);
+        assert_eq!(estimate.read_capacity_units, read_capacity_units);
+    }
+
+    #[rstest]
+    #[case(None, false, false, 120, 0, Some(ScanProgressTotal::Items(120)))]
+    #[case(Some(50), false, false, 120, 0, Some(ScanProgressTotal::Items(50)))]
+    #[case(Some(500), false, false, 120, 0, Some(ScanProgressTotal::Items(120)))]
+    #[case(None, false, false, 0, 0, None)]
+    #[case(None, false, false, 0, 4096, Some(ScanProgressTotal::Bytes(4096)))]
+    #[case(Some(50), false, false, 0, 0, Some(ScanProgressTotal::Items(50)))]
+    #[case(None, false, true, 120, 4096, None)]
+    #[case(Some(50), false, true, 120, 4096, Some(ScanProgressTotal::Items(50)))]
+    #[case(
+        Some(50),
+        true,
+        false,
+        120,
+        4096,
+        Some(ScanProgressTotal::ScannedItems(120))
+    )]
+    #[case(None, true, false, 0, 4096, Some(ScanProgressTotal::Bytes(4096)))]
+    #[case(None, true, true, 120, 4096, None)]
+    fn test_scan_options_progress_total(
+        #[case] limit: Option<usize>,
+        #[case] filtered: bool,
+        #[case] resumed: bool,
+        #[case] table_item_count: u64,
+        #[case] table_size_bytes: u64,
+        #[case] expected: Option<ScanProgressTotal>,
+    ) {
+        let options = ScanOptions {
+            limit,
+            filter: filtered.then(|| FilterExpression { conditions: vec![] }),
+            exclusive_start_key: resumed.then(BTreeMap::new),
+        };
+        assert_eq!(
+            options.progress_total(table_item_count, table_size_bytes),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_scan_progress_total_current() {
+        let progress = ScanProgress {
+            items: 10,
+            scanned_items: 40,
+            scanned_bytes: scanned_bytes_of_capacity(1.5),
+        };
+        assert_eq!(ScanProgressTotal::Items(100).current(&progress), 10);
+        assert_eq!(ScanProgressTotal::ScannedItems(100).current(&progress), 40);
+        assert_eq!(ScanProgressTotal::Bytes(100).current(&progress), 12288);
+    }
+
+    #[rstest]
+    #[case(None, 0, 0.0, None)]
+    #[case(Some((0, 0)), 10 * 1024 * 1024 * 1024, 2.5, None)]
+    #[case(Some((10, 5)), 4 * 1024 * 1024 * 1024, 1.0, Some(3.3215))]
+    fn test_cost_estimate(
+        #[case] capacity_units: Option<(u64, u64)>,
+        #[case] total_size_bytes: u64,
+        #[case] storage_cost: f64,
+        #[case] capacity_cost: Option<f64>,
+    ) {
+        let desc = TableDescription {
+            provisioned_throughput: capacity_units.map(|(rcu, wcu)| ProvisionedThroughput {
+                last_increase_date_time: None,
+                last_decrease_date_time: None,
+                number_of_decreases_today: 0,
+                read_capacity_units: rcu,
+                write_capacity_units: wcu,
+            }),
+            total_size_bytes,
+            ..table_description("test", "id")
+        };
+
+        let actual = CostEstimate::new(&desc);
+
+        assert!((actual.monthly_storage_cost - storage_cost).abs() < 1e-9);
+        match (actual.monthly_capacity_cost, capacity_cost) {
+            (Some(a), Some(e)) => assert!((a - e).abs() < 1e-9),
+            (a, e) => assert_eq!(a, e),
+        }
+    }
+
+    #[rstest]
+    #[case(1_234, 12, 3_400, false, "1.2k items in 3.4s (12 pages)")]
+    #[case(5, 1, 80, false, "5 items in 80ms (1 page)")]
+    #[case(
+        90_000,
+        50,
+        4_000,
+        true,
+        "90.0k items in 4.0s (50 pages), stopped at the memory limit"
+    )]
+    fn test_load_stats_summary(
+        #[case] count: usize,
+        #[case] pages: usize,
+        #[case] elapsed_millis: u64,
+        #[case] memory_limited: bool,
+        #[case] expected: &str,
+    ) {
+        let stats = LoadStats {
+            count,
+            pages,
+            elapsed: Duration::from_millis(elapsed_millis),
+            memory_limited,
+        };
+        assert_eq!(stats.summary(), expected);
+    }
+
+    #[test]
+    fn test_item_approximate_size() {
+        let item = item(vec![
+            ("id", Attribute::S("user1".into())),
+            ("active", Attribute::BOOL(true)),
+            (
+                "tags",
+                Attribute::M(BTreeMap::from([
+                    ("a".into(), Attribute::B(vec![1, 2, 3])),
+                    ("bc".into(), Attribute::L(vec![Attribute::NULL])),
+                ])),
+            ),
+        ]);
+        // (2 + 5) + (6 + 1) + (4 + (1 + 3) + (2 + 1))
+        assert_eq!(item.approximate_size(), 25);
+    }
+
+    #[test]
+    fn test_filter_expression_build() {
+        let filter = FilterExpression {
+            conditions: vec![
+                FilterCondition {
+                    conjunction: Conjunction::And,
+                    attribute_name: "status".into(),
+                    operator: FilterOperator::Eq,
+                    value: Some(Attribute::S("active".into())),
+                },
+                FilterCondition {
+                    conjunction: Conjunction::And,
+                    attribute_name: "age".into(),
+                    operator: FilterOperator::Ge,
+                    value: Some(Attribute::N(Decimal::from(20))),
+                },
+                FilterCondition {
+                    conjunction: Conjunction::Or,
+                    attribute_name: "status".into(),
+                    operator: FilterOperator::BeginsWith,
+                    value: Some(Attribute::S("pend".into())),
+                },
+                FilterCondition {
+                    conjunction: Conjunction::And,
+                    attribute_name: "deleted".into(),
+                    operator: FilterOperator::NotExists,
+                    value: None,
+                },
+            ],
+        };
+        let built = filter.build();
+
+        assert_eq!(
+            built.expression,
+            "#n0 = :v0 AND #n1 >= :v1 OR begins_with(#n0, :v2) AND attribute_not_exists(#n2)"
+        );
+        assert_eq!(
+            built.names,
+            BTreeMap::from([
+                ("#n0".into(), "status".into()),
+                ("#n1".into(), "age".into()),
+                ("#n2".into(), "deleted".into()),
+            ])
+        );
+        assert_eq!(
+            built.values,
+            BTreeMap::from([
+                (":v0".into(), Attribute::S("active".into())),
+                (":v1".into(), Attribute::N(Decimal::from(20))),
+                (":v2".into(), Attribute::S("pend".into())),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_raw_json_attributes() {
+        let json = r#"{
+            "pk": {"S": "user#1"},
+            "sk": {"N": "10.5"},
+            "bin": {"B": "AQID"},
+            "flag": {"BOOL": true},
+            "nil": {"NULL": true},
+            "list": {"L": [{"S": "a"}, {"N": "1"}]},
+            "map": {"M": {"k": {"S": "v"}}},
+            "ss": {"SS": ["x", "y"]},
+            "ns": {"NS": ["1", "2"]},
+            "bs": {"BS": ["AQ=="]}
+        }"#;
+        let actual = parse_raw_json_attributes(json).ok().unwrap();
+        let expected = BTreeMap::from([
+            ("pk".into(), Attribute::S("user#1".into())),
+            ("sk".into(), Attribute::N(Decimal::new(105, 1))),
+            ("bin".into(), Attribute::B(vec![1, 2, 3])),
+            ("flag".into(), Attribute::BOOL(true)),
+            ("nil".into(), Attribute::NULL),
+            (
+                "list".into(),
+                Attribute::L(vec![
+                    Attribute::S("a".into()),
+                    Attribute::N(Decimal::from(1)),
+                ]),
+            ),
+            (
+                "map".into(),
+                Attribute::M(BTreeMap::from([("k".into(), Attribute::S("v".into()))])),
+            ),
+            (
+                "ss".into(),
+                Attribute::SS(BTreeSet::from(["x".into(), "y".into()])),
+            ),
+            (
+                "ns".into(),
+                Attribute::NS(BTreeSet::from([Decimal::from(1), Decimal::from(2)])),
+            ),
+            ("bs".into(), Attribute::BS(BTreeSet::from([vec![1]]))),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(r#"[]"#)]
+    #[case(r#"{"pk": "a"}"#)]
+    #[case(r#"{"pk": {"N": "abc"}}"#)]
+    #[case(r#"{"pk": {"X": "a"}}"#)]
+    #[case(r#"{"pk": {"S": "a", "N": "1"}}"#)]
+    fn test_parse_raw_json_attributes_error(#[case] json: &str) {
+        assert!(parse_raw_json_attributes(json).is_err());
+    }
+
+    #[rstest]
+    #[case(r#"{"S": "active"}"#, Some(Attribute::S("active".into())))]
+    #[case(r#" {"N": "42"} "#, Some(Attribute::N(Decimal::from(42))))]
+    #[case(r#"{"BOOL": false}"#, Some(Attribute::BOOL(false)))]
+    #[case(r#"active"#, None)]
+    #[case(r#"{"S": 1}"#, None)]
+    fn test_parse_raw_json_attribute_value(#[case] s: &str, #[case] expected: Option<Attribute>) {
+        assert_eq!(parse_raw_json_attribute_value(s).ok(), expected);
+    }
+
+    #[rstest]
+    #[case("{\n  \"pk\": {\"S\": \"a\"},\n  \"n\": ,\n}", Some((3, 8)), vec!["3 |   \"n\": ,", "  |        ^"])]
+    #[case("{\n  \"pk\": {\"S\": \"a\"},\n  \"n\": {\"N\": \"x\"}\n}", Some((3, 3)), vec!["3 |   \"n\": {\"N\": \"x\"}", "  |   ^"])]
+    #[case("[]", None, vec![])]
+    fn test_parse_raw_json_item_error_position(
+        #[case] json: &str,
+        #[case] position: Option<(usize, usize)>,
+        #[case] excerpt: Vec<&str>,
+    ) {
+        let actual = parse_raw_json_item(json).err().unwrap();
+        assert_eq!(actual.position, position);
+        assert_eq!(actual.describe(json)[1..], excerpt);
+    }
+
+    #[test]
+    fn test_query_options_build_key_condition() {
+        let options = QueryOptions {
+            index_name: None,
+            hash_key_name: "user_id".into(),
+            hash_key_value: Attribute::S("u1".into()),
+            sort_key_condition: Some(SortKeyCondition {
+                attribute_name: "created_at".into(),
+                operator: SortKeyOperator::Between,
+                values: vec![
+                    Attribute::N(Decimal::from(100)),
+                    Attribute::N(Decimal::from(200)),
+                ],
+            }),
+            limit: None,
+            scan_index_forward: true,
+            exclusive_start_key: None,
+        };
+        let built = options.build_key_condition();
+
+        assert_eq!(built.expression, "#k0 = :k0 AND #k1 BETWEEN :k1 AND :k2");
+        assert_eq!(
+            built.names,
+            BTreeMap::from([
+                ("#k0".into(), "user_id".into()),
+                ("#k1".into(), "created_at".into()),
+            ])
+        );
+        assert_eq!(
+            built.values,
+            BTreeMap::from([
+                (":k0".into(), Attribute::S("u1".into())),
+                (":k1".into(), Attribute::N(Decimal::from(100))),
+                (":k2".into(), Attribute::N(Decimal::from(200))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_plain_json_line_paths() {
+        let item = fixture_item();
+        let schema = KeySchemaType::Hash("b".into());
+
+        let json_item = PlainJsonItem::new(&item, &schema);
+        let json = serde_json::to_string_pretty(&json_item).unwrap();
+        let paths = plain_json_line_paths(&item, &schema);
+
+        assert_eq!(paths.len(), json.lines().count());
+        assert_eq!(
+            paths,
+            vec![
+                ".", ".b", ".a", ".c", ".c[0]", ".c[1]", ".c", ".d", ".d[0]", ".d[1]", ".d[2]",
+                ".d[2][0]", ".d[2][1]", ".d[2]", ".d", ".e", ".e.e1", ".e.e2", ".e.e2[0]",
+                ".e.e2[1]", ".e.e2[2]", ".e.e2", ".e", ".",
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("", "foo", ".foo")]
+    #[case("", "foo-bar", ".[\"foo-bar\"]")]
+    #[case(".a", "_b1", ".a._b1")]
+    #[case(".a", "1b", ".a[\"1b\"]")]
+    #[case(".a[2]", "c", ".a[2].c")]
+    fn test_join_path_key(#[case] path: &str, #[case] key: &str, #[case] expected: &str) {
+        assert_eq!(join_path_key(path, key), expected);
+    }
+
+    #[test]
+    fn test_raw_json_item_serialize() {
+        let item = fixture_item();
+        let schema = KeySchemaType::Hash("b".into());
+
+        let json_item = RawJsonItem::new(&item, &schema);
+        let json = serde_json::to_string_pretty(&json_item).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{
+  "b": {
+    "N": 123
+  },
+  "a": {
+    "S": "aaa"
+  },
+  "c": {
+    "SS": [
+      "c1",
+      "c2"
+    ]
+  },
+  "d": {
+    "L": [
+      {
+        "NULL": true
+      },
+      {
+        "B": "YWJj"
+      },
+      {
+        "BS": [
+          "bG1u",
+          "eHl6"
+        ]
+      }
+    ]
+  },
+  "e": {
+    "M": {
+      "e1": {
+        "BOOL": true
+      },
+      "e2": {
+        "NS": [
+          -2.34,
+          0.2,
+          3
+        ]
+      }
+    }
+  }
+}"#
+        );
+    }
+
+    #[test]
+    fn test_plain_json_item_serialize() {
+        let item = fixture_item();
+        let schema = KeySchemaType::Hash("b".into());
+
+        let json_item = PlainJsonItem::new(&item, &schema);
+        let json = serde_json::to_string_pretty(&json_item).unwrap();
+
+        assert_eq!(
             json,
             r#"{
   "b": 123,
@@ -766,6 +2804,537 @@ mod tests {
         assert_eq!(keys, vec!["b", "c", "a", "d", "e"]);
     }
 
+    #[test]
+    fn test_to_key_attributes() {
+        let item = Item {
+            attributes: vec![
+                ("pk".to_string(), Attribute::S("a".into())),
+                ("sk".to_string(), Attribute::N(Decimal::from(1))),
+                ("x".to_string(), Attribute::NULL),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let schema = KeySchemaType::HashRange("pk".into(), "sk".into());
+        let expected = vec![
+            ("pk".to_string(), Attribute::S("a".into())),
+            ("sk".to_string(), Attribute::N(Decimal::from(1))),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(to_key_attributes(&item, &schema), Some(expected));
+
+        let schema = KeySchemaType::HashRange("pk".into(), "ts".into());
+        assert_eq!(to_key_attributes(&item, &schema), None);
+    }
+
+    #[rstest]
+    #[case(None, 25, None)]
+    #[case(Some(0), 25, None)]
+    #[case(Some(100), 25, None)]
+    #[case(Some(5), 2, Some(1000))]
+    #[case(Some(1), 1, Some(1000))]
+    fn test_write_options(
+        #[case] write_capacity_units: Option<u64>,
+        #[case] chunk_size: usize,
+        #[case] interval_millis: Option<u64>,
+    ) {
+        let desc = TableDescription {
+            provisioned_throughput: write_capacity_units.map(|wcu| ProvisionedThroughput {
+                last_increase_date_time: None,
+                last_decrease_date_time: None,
+                number_of_decreases_today: 0,
+                read_capacity_units: 0,
+                write_capacity_units: wcu,
+            }),
+            ..table_description("test", "id")
+        };
+
+        let actual = WriteOptions::new(&desc, 5, Duration::from_millis(1000));
+
+        assert_eq!(
+            actual,
+            WriteOptions {
+                chunk_size,
+                interval: interval_millis.map(Duration::from_millis),
+            }
+        );
+    }
+
+    #[test]
+    fn test_new_item_template() {
+        let desc = TableDescription {
+            attribute_definitions: vec![AttributeDefinition::new(
+                "id".into(),
+                ScalarAttributeType::N,
+            )],
+            ..table_description("test", "id")
+        };
+        let items = vec![
+            item(vec![
+                ("id", Attribute::N(1.into())),
+                ("name", Attribute::S("a".into())),
+                ("tags", Attribute::SS(set(vec!["x"]))),
+                ("rare", Attribute::BOOL(true)),
+            ]),
+            item(vec![
+                ("id", Attribute::N(2.into())),
+                ("name", Attribute::S("b".into())),
+                ("tags", Attribute::L(vec![])),
+            ]),
+            item(vec![
+                ("id", Attribute::N(3.into())),
+                ("name", Attribute::N(3.into())),
+                ("tags", Attribute::SS(set(vec!["y"]))),
+            ]),
+        ];
+        let insight = TableInsight::new(&desc, &items);
+
+        let actual = new_item_template(&desc, &insight);
+
+        let expected = item(vec![
+            ("id", Attribute::N(Decimal::ZERO)),
+            ("name", Attribute::S("".into())),
+            ("tags", Attribute::SS(BTreeSet::new())),
+        ]);
+        assert_eq!(actual.attributes, expected.attributes);
+    }
+
+    #[test]
+    fn test_table_insight_co_occurrences() {
+        let desc = table_description("foo", "id");
+        let s = |s: &str| Attribute::S(s.into());
+        let items = vec![
+            item(vec![("id", s("1")), ("a", s("x"))]),
+            item(vec![("id", s("2")), ("b", s("y"))]),
+            item(vec![("id", s("3")), ("a", s("x")), ("b", s("y"))]),
+        ];
+
+        let insight = TableInsight::new(&desc, &items);
+
+        // attributes are ordered as id, a, b
+        let expected = vec![vec![3, 2, 2], vec![2, 2, 1], vec![2, 1, 2]];
+        assert_eq!(insight.co_occurrences, expected);
+    }
+
+    #[test]
+    fn test_table_insight_sort_key_prefixes() {
+        let desc = TableDescription {
+            key_schema_type: KeySchemaType::HashRange("pk".into(), "sk".into()),
+            ..table_description("foo", "pk")
+        };
+        let s = |s: &str| Attribute::S(s.into());
+        let items = vec![
+            item(vec![("pk", s("u1")), ("sk", s("ORDER#2024#1"))]),
+            item(vec![("pk", s("u1")), ("sk", s("ORDER#2024#2"))]),
+            item(vec![("pk", s("u1")), ("sk", s("PROFILE"))]),
+            item(vec![("pk", s("u2")), ("sk", s("ADDRESS#home"))]),
+            item(vec![("pk", s("u2")), ("sk", s("ORDER#2025#1"))]),
+            item(vec![("pk", s("u3")), ("sk", Attribute::N(1.into()))]),
+        ];
+
+        let insight = TableInsight::new(&desc, &items);
+
+        let actual = insight.sort_key_prefixes("#", 10);
+        let expected = vec![
+            ("ORDER#".to_string(), 3),
+            ("ADDRESS#".to_string(), 1),
+            ("PROFILE".to_string(), 1),
+        ];
+        assert_eq!(actual, expected);
+
+        let actual = insight.sort_key_prefixes("#", 1);
+        assert_eq!(actual, vec![("ORDER#".to_string(), 3)]);
+
+        assert!(insight.sort_key_prefixes("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_compare_items() {
+        let schema = KeySchemaType::Hash("id".into());
+        let left = vec![
+            item(vec![
+                ("id", Attribute::N(1.into())),
+                ("v", Attribute::S("a".into())),
+            ]),
+            item(vec![
+                ("id", Attribute::N(2.into())),
+                ("v", Attribute::S("b".into())),
+            ]),
+            item(vec![
+                ("id", Attribute::N(3.into())),
+                ("v", Attribute::S("c".into())),
+            ]),
+        ];
+        let right = vec![
+            item(vec![("id", Attribute::N(4.into()))]),
+            item(vec![
+                ("id", Attribute::N(2.into())),
+                ("w", Attribute::BOOL(true)),
+            ]),
+            item(vec![
+                ("id", Attribute::N(1.into())),
+                ("v", Attribute::S("a".into())),
+            ]),
+        ];
+
+        let actual = compare_items(&left, &right, &schema);
+
+        assert_eq!(actual.only_left, vec![r#"{"id":3}"#]);
+        assert_eq!(actual.only_right, vec![r#"{"id":4}"#]);
+        assert_eq!(actual.different.len(), 1);
+        assert_eq!(actual.different[0].key, r#"{"id":2}"#);
+        assert_eq!(actual.different[0].attribute_names, vec!["v", "w"]);
+        assert_eq!(actual.same_count, 1);
+    }
+
+    #[test]
+    fn test_diff_table_descriptions() {
+        let desc = |read: u64, gsi_name: &str, sk_type| {
+            let mut desc = TableDescription {
+                attribute_definitions: vec![
+                    AttributeDefinition::new("pk".into(), ScalarAttributeType::S),
+                    AttributeDefinition::new("sk".into(), sk_type),
+                ],
+                provisioned_throughput: Some(ProvisionedThroughput {
+                    last_increase_date_time: None,
+                    last_decrease_date_time: None,
+                    number_of_decreases_today: 0,
+                    read_capacity_units: read,
+                    write_capacity_units: 1,
+                }),
+                global_secondary_indexes: Some(vec![GlobalSecondaryIndexDescription {
+                    index_name: gsi_name.into(),
+                    key_schema: vec![KeySchemaElement {
+                        attribute_name: "sk".into(),
+                        key_type: KeyType::Hash,
+                    }],
+                    projection: Projection {
+                        projection_type: ProjectionType::All,
+                        non_key_attributes: None,
+                    },
+                    index_status: None,
+                    provisioned_throughput: None,
+                    index_size_bytes: 0,
+                    item_count: 0,
+                    index_arn: "".into(),
+                }]),
+                ..table_description("test", "pk")
+            };
+            desc.table_name = format!("test-{gsi_name}");
+            desc
+        };
+        let left = desc(1, "a", ScalarAttributeType::S);
+        let right = desc(2, "b", ScalarAttributeType::N);
+
+        let actual: Vec<(String, Option<String>, Option<String>)> =
+            diff_table_descriptions(&left, &right)
+                .into_iter()
+                .filter(|d| !d.is_same())
+                .map(|d| (d.property, d.left, d.right))
+                .collect();
+
+        let s = |s: &str| Some(s.to_string());
+        let expected = vec![
+            ("Attribute sk".into(), s("S"), s("N")),
+            (
+                "Provisioned Throughput".into(),
+                s("Read: 1 / Write: 1"),
+                s("Read: 2 / Write: 1"),
+            ),
+            ("GSI a Key Schema".into(), s("sk (HASH)"), None),
+            ("GSI a Projection".into(), s("ALL"), None),
+            ("GSI b Key Schema".into(), None, s("sk (HASH)")),
+            ("GSI b Projection".into(), None, s("ALL")),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_write_requests_summary() {
+        let put = WriteRequest::Put {
+            item: item(vec![("id", Attribute::N(1.into()))]),
+        };
+        let delete = WriteRequest::Delete {
+            key: BTreeMap::new(),
+            item: item(vec![("id", Attribute::N(1.into()))]),
+        };
+
+        assert_eq!(write_requests_summary(slice::from_ref(&put)), "Put 1 item");
+        assert_eq!(
+            write_requests_summary(&[delete.clone(), delete.clone()]),
+            "Delete 2 items"
+        );
+        assert_eq!(
+            write_requests_summary(&[put, delete.clone(), delete.clone()]),
+            "Put 1 item, delete 2 items"
+        );
+
+        let update = WriteRequest::SetAttribute {
+            key: BTreeMap::new(),
+            item: item(vec![("id", Attribute::N(1.into()))]),
+            name: "ttl".into(),
+            value: Attribute::N(1.into()),
+        };
+        assert_eq!(
+            write_requests_summary(&[delete, update]),
+            "Update 1 item, delete 1 item"
+        );
+    }
+
+    #[test]
+    fn test_probe_key() {
+        let desc = TableDescription {
+            attribute_definitions: vec![
+                AttributeDefinition::new("id".into(), ScalarAttributeType::S),
+                AttributeDefinition::new("ts".into(), ScalarAttributeType::N),
+            ],
+            key_schema_type: KeySchemaType::HashRange("id".into(), "ts".into()),
+            ..table_description("test", "id")
+        };
+
+        let actual = desc.probe_key();
+        let expected = vec![
+            (
+                "id".to_string(),
+                Attribute::S("ddv-permission-probe".into()),
+            ),
+            ("ts".to_string(), Attribute::N(Decimal::ZERO)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(actual, Some(expected));
+
+        let desc = TableDescription {
+            key_schema_type: KeySchemaType::Hash("unknown".into()),
+            ..desc
+        };
+        assert_eq!(desc.probe_key(), None);
+    }
+
+    #[rstest]
+    #[case("foo", Some(ScalarAttributeType::S), Some(Attribute::S("foo".into())))]
+    #[case("foo", None, Some(Attribute::S("foo".into())))]
+    #[case(
+        "100",
+        Some(ScalarAttributeType::N),
+        Some(Attribute::N(Decimal::from(100)))
+    )]
+    #[case("abc", Some(ScalarAttributeType::N), None)]
+    #[case("AQID", Some(ScalarAttributeType::B), Some(Attribute::B(vec![1, 2, 3])))]
+    #[case("!!", Some(ScalarAttributeType::B), None)]
+    fn test_parse_key_value(
+        #[case] value: &str,
+        #[case] attribute_type: Option<ScalarAttributeType>,
+        #[case] expected: Option<Attribute>,
+    ) {
+        let actual = parse_key_value(value, attribute_type).ok();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("id=a,ts=10", Some(vec![("id", Attribute::S("a".into())), ("ts", Attribute::N(10.into()))]))]
+    #[case(" ts = 10 , id=a=b", Some(vec![("id", Attribute::S("a=b".into())), ("ts", Attribute::N(10.into()))]))]
+    #[case("id=a", None)]
+    #[case("id=a,ts=x", None)]
+    #[case("id=a,ts=10,foo=b", None)]
+    #[case("id", None)]
+    fn test_parse_key(#[case] s: &str, #[case] expected: Option<Vec<(&str, Attribute)>>) {
+        let desc = TableDescription {
+            attribute_definitions: vec![
+                AttributeDefinition::new("id".into(), ScalarAttributeType::S),
+                AttributeDefinition::new("ts".into(), ScalarAttributeType::N),
+            ],
+            key_schema_type: KeySchemaType::HashRange("id".into(), "ts".into()),
+            ..table_description("test", "id")
+        };
+
+        let actual = parse_key(&desc, s).ok();
+        let expected = expected.map(|key| item(key).attributes.into_iter().collect());
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(ScalarAttributeType::S, Attribute::S("a".into()), Some(Attribute::S("a".into())))]
+    #[case(ScalarAttributeType::S, Attribute::N(10.into()), Some(Attribute::S("10".into())))]
+    #[case(ScalarAttributeType::N, Attribute::S(" 10 ".into()), Some(Attribute::N(10.into())))]
+    #[case(ScalarAttributeType::N, Attribute::S("a".into()), None)]
+    #[case(ScalarAttributeType::B, Attribute::B(vec![1]), Some(Attribute::B(vec![1])))]
+    #[case(ScalarAttributeType::S, Attribute::BOOL(true), None)]
+    #[case(ScalarAttributeType::S, Attribute::L(vec![]), None)]
+    fn test_reference_query_options(
+        #[case] key_type: ScalarAttributeType,
+        #[case] value: Attribute,
+        #[case] expected: Option<Attribute>,
+    ) {
+        let desc = TableDescription {
+            attribute_definitions: vec![AttributeDefinition::new("id".into(), key_type)],
+            key_schema_type: KeySchemaType::HashRange("id".into(), "ts".into()),
+            ..table_description("test", "id")
+        };
+
+        let actual = desc.reference_query_options(&value).ok();
+        if let Some(options) = &actual {
+            assert_eq!(options.hash_key_name, "id");
+            assert!(options.sort_key_condition.is_none());
+        }
+        assert_eq!(actual.map(|o| o.hash_key_value), expected);
+    }
+
+    #[test]
+    fn test_key_query_options() {
+        let key = |name: &str, key_type| KeySchemaElement {
+            attribute_name: name.into(),
+            key_type,
+        };
+        let desc = TableDescription {
+            attribute_definitions: vec![
+                AttributeDefinition::new("pk".into(), ScalarAttributeType::S),
+                AttributeDefinition::new("sk".into(), ScalarAttributeType::N),
+            ],
+            key_schema: vec![key("pk", KeyType::Hash), key("sk", KeyType::Range)],
+            key_schema_type: KeySchemaType::HashRange("pk".into(), "sk".into()),
+            ..table_description("test", "pk")
+        };
+
+        let options = desc
+            .key_query_options(None, "USER#42", Some(("7", false)))
+            .ok()
+            .unwrap();
+        assert_eq!(options.hash_key_name, "pk");
+        assert_eq!(options.hash_key_value, Attribute::S("USER#42".into()));
+        let cond = options.sort_key_condition.unwrap();
+        assert_eq!(cond.attribute_name, "sk");
+        assert!(matches!(cond.operator, SortKeyOperator::Eq));
+        assert_eq!(cond.values, vec![Attribute::N(7.into())]);
+
+        let options = desc.key_query_options(None, "USER#42", None).ok().unwrap();
+        assert!(options.sort_key_condition.is_none());
+
+        assert!(desc
+            .key_query_options(None, "USER#42", Some(("x", true)))
+            .is_err());
+        assert!(desc
+            .key_query_options(Some("gsi1"), "USER#42", None)
+            .is_err());
+    }
+
+    #[rstest]
+    #[case(false, false, vec![4, 1, 0, 2, 3, 5])]
+    #[case(true, false, vec![2, 0, 1, 4, 3, 5])]
+    #[case(false, true, vec![3, 5, 4, 1, 0, 2])]
+    #[case(true, true, vec![3, 5, 2, 0, 1, 4])]
+    fn test_sort_items_by_attribute(
+        #[case] descending: bool,
+        #[case] undefined_first: bool,
+        #[case] expected: Vec<usize>,
+    ) {
+        let items = vec![
+            item(vec![("v", Attribute::N(Decimal::from(20)))]),
+            item(vec![("v", Attribute::N(Decimal::from(3)))]),
+            item(vec![("v", Attribute::BOOL(true))]),
+            item(vec![("other", Attribute::S("x".into()))]),
+            item(vec![("v", Attribute::S("b".into()))]),
+            item(vec![]),
+        ];
+        let actual = sort_items_by_attribute(&items, "v", descending, undefined_first);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_expected_attribute_types() {
+        let desc = table_description("foo", "id");
+        let n = |n: i64| Attribute::N(Decimal::from(n));
+        let s = |s: &str| Attribute::S(s.into());
+        let items = vec![
+            item(vec![("id", n(1)), ("age", n(20)), ("tag", s("a"))]),
+            item(vec![
+                ("id", s("2")),
+                ("age", Attribute::NULL),
+                ("tag", n(1)),
+            ]),
+            item(vec![("id", s("3")), ("age", s("30"))]),
+            item(vec![("id", s("4")), ("age", n(40))]),
+        ];
+
+        let actual = expected_attribute_types(&desc, &items);
+        assert_eq!(actual.get("id"), Some(&AttributeType::String));
+        assert_eq!(actual.get("age"), Some(&AttributeType::Number));
+        assert_eq!(actual.get("tag"), None); // no majority
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let s = |s: &str| Attribute::S(s.into());
+        let n = |n: i64| Attribute::N(Decimal::from(n));
+        let schema = KeySchemaType::Hash("id".into());
+        let items = vec![
+            item(vec![("id", s("User#1")), ("email", s("a@example.com"))]),
+            item(vec![("id", s("user#1 ")), ("email", s("b@example.com"))]),
+            item(vec![("id", s("user#2")), ("email", s(" A@example.com"))]),
+            item(vec![("id", n(3))]),
+            item(vec![("id", n(3))]),
+            item(vec![("id", s("user#4"))]),
+        ];
+
+        let actual = find_duplicates(&items, &["id".into()], &schema);
+        let expected = vec![
+            DuplicateGroup {
+                value: "3".into(),
+                keys: vec!["3".into(), "3".into()],
+            },
+            DuplicateGroup {
+                value: "user#1".into(),
+                keys: vec!["User#1".into(), "user#1 ".into()],
+            },
+        ];
+        assert_eq!(actual, expected);
+
+        let actual = find_duplicates(&items, &["email".into()], &schema);
+        let expected = vec![DuplicateGroup {
+            value: "a@example.com".into(),
+            keys: vec!["User#1".into(), "user#2".into()],
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_column_stats() {
+        let n = |n: i64| Attribute::N(Decimal::from(n));
+        let s = |s: &str| Attribute::S(s.into());
+        let items = [
+            item(vec![("v", n(3))]),
+            item(vec![("v", s("a"))]),
+            item(vec![("v", Attribute::NULL)]),
+            item(vec![]),
+            item(vec![("v", n(-1))]),
+            item(vec![("v", n(3))]),
+            item(vec![("v", n(10))]),
+        ];
+        let items: Vec<&Item> = items.iter().collect();
+
+        let actual = ColumnStats::new(&items, "v", 3);
+        let expected = ColumnStats {
+            attribute_name: "v".into(),
+            total_items: 7,
+            missing_count: 1,
+            null_count: 1,
+            distinct_count: 4,
+            number_range: Some((Decimal::from(-1), Decimal::from(10))),
+            samples: vec!["3".into(), "a".into(), "-1".into()],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    fn item(attributes: Vec<(&str, Attribute)>) -> Item {
+        let attributes = attributes
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        Item { attributes }
+    }
+
     fn set<T, U>(values: Vec<T>) -> BTreeSet<U>
     where
         U: From<T> + Ord,
@@ -28,4 +28,18 @@ impl AppError {
             cause: Some(Box::new(e)),
         }
     }
+
+    // The message followed by the messages of the cause and its sources
+    pub fn messages(&self) -> Vec<String> {
+        let mut messages = vec![self.msg.clone()];
+        let mut source = self
+            .cause
+            .as_deref()
+            .map(|e| e as &(dyn std::error::Error + 'static));
+        while let Some(e) = source {
+            messages.push(e.to_string());
+            source = e.source();
+        }
+        messages
+    }
 }
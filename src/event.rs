@@ -1,32 +1,123 @@
-use std::{sync::mpsc, thread};
+use std::{collections::BTreeMap, sync::mpsc, thread, time::Duration};
 
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::{
-    data::{Item, Table, TableDescription, TableInsight},
+    client::Client,
+    data::{
+        AccountLimits, Attribute, Item, ItemsSource, LoadedItems, QueryOptions, ScanOptions, Table,
+        TableDescription, TablePermissions, WriteRequest,
+    },
     error::{AppError, AppResult},
     help::Spans,
+    profile::Profile,
+    state::{SavedFilter, SavedQuery, TableViewPreferences},
+    view::SideBySidePane,
 };
 
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+pub type PromptCallback = Box<dyn FnOnce(String) -> AppEvent + Send>;
+
 pub enum AppEvent {
     Key(KeyEvent),
+    Paste(String),
+    Quit,
     Resize(usize, usize),
     Initialize,
     CompleteInitialize(AppResult<Vec<Table>>),
+    CompleteOpenItemByKey(AppResult<(TableDescription, Option<Item>)>),
+    ListMultiRegionTables(Vec<String>),
+    CompleteListMultiRegionTables(Vec<String>, Vec<(String, AppResult<Vec<Table>>)>),
+    SwitchRegion(String, String),
+    CompleteSwitchRegion(Box<Client>, String),
+    OpenEnvironmentPicker,
+    SwitchEnvironment(String),
+    CompleteSwitchEnvironment(Box<Client>),
+    OpenProfilePicker(Vec<Profile>, Option<String>),
+    SelectProfile(String, Option<String>),
     LoadTableDescription(String),
-    CompleteLoadTableDescription(AppResult<TableDescription>),
-    LoadTableItems(TableDescription),
-    CompleteLoadTableItems(TableDescription, AppResult<Vec<Item>>),
+    CompleteLoadTableDescription(String, AppResult<TableDescription>),
+    LoadTableItemsPreview(TableDescription, usize),
+    CompleteLoadTableItemsPreview(String, AppResult<Vec<Item>>),
+    LoadTableItems(TableDescription, ScanOptions),
+    ScanTableItems(TableDescription, ScanOptions),
+    ScanTableItemsWithoutMemoryLimit(TableDescription, ScanOptions),
+    CompleteLoadTableItems(TableDescription, ScanOptions, AppResult<LoadedItems>),
+    ShowLoadedTableItems(TableDescription, ScanOptions, LoadedItems),
     OpenItem(TableDescription, Item),
-    OpenTableInsight(TableInsight),
+    // move the item view to the next (true) or previous (false) item of the table view
+    OpenAdjacentItem(bool),
+    RefreshItem(TableDescription, BTreeMap<String, Attribute>),
+    CompleteRefreshItem(
+        TableDescription,
+        BTreeMap<String, Attribute>,
+        AppResult<Option<Item>>,
+    ),
+    CheckItemExists(TableDescription, BTreeMap<String, Attribute>),
+    CompleteCheckItemExists(
+        TableDescription,
+        BTreeMap<String, Attribute>,
+        AppResult<Option<Item>>,
+    ),
+    OpenTableInsight(TableDescription, Vec<Item>),
+    UpdateContributorInsights(String, Option<String>, bool),
+    ExecuteUpdateContributorInsights(String, Option<String>, bool),
+    CompleteUpdateContributorInsights(String, bool, AppResult<()>),
+    DiffTables(String, String),
+    CompleteDiffTables(AppResult<(TableDescription, TableDescription)>),
+    LoadAccountLimits,
+    CompleteLoadAccountLimits(AppResult<AccountLimits>),
+    CompareItems(TableDescription, Vec<Item>, String),
+    CompleteCompareItems(TableDescription, Vec<Item>, String, AppResult<LoadedItems>),
+    OpenSideBySide(TableDescription, LoadedItems, ItemsSource, String),
+    CompleteOpenSideBySide(Box<SideBySidePane>, AppResult<Box<SideBySidePane>>),
+    ValidateItems(TableDescription, Vec<Item>),
+    JumpToReference(String, String, Attribute),
+    LoadReferencedItems(String, String, String, Attribute),
+    CompleteLoadReferencedItems(AppResult<(TableDescription, QueryOptions, LoadedItems)>),
+    OpenPartiQL(TableDescription),
+    OpenFilterBuilder(TableDescription),
+    OpenQueryBuilder(TableDescription),
+    QueryTableItems(TableDescription, QueryOptions),
+    CompleteQueryTableItems(TableDescription, QueryOptions, AppResult<LoadedItems>),
+    WriteItems(TableDescription, Vec<WriteRequest>),
+    ExecuteWriteItems(TableDescription, Vec<WriteRequest>),
+    EditNewItem(TableDescription, String),
+    ProbeTablePermissions(TableDescription),
+    CompleteProbeTablePermissions(String, TablePermissions),
+    Progress(usize, usize),
+    CompleteWriteItems(TableDescription, Vec<WriteRequest>, AppResult<usize>),
+    OpenAuditLog,
+    ExecuteStatement(TableDescription, String),
+    CompleteExecuteStatement(TableDescription, String, AppResult<LoadedItems>),
     OpenHelp(Vec<Spans>),
+    OpenTableInConsole(String),
+    OpenLink(String),
+    OpenPrompt(String, PromptCallback),
+    OpenSavedFilters(TableDescription),
+    OpenKeyTemplates(TableDescription),
+    OpenTableWithFilter(TableDescription, String),
+    OpenSelectDialog(String, Vec<(String, AppEvent)>),
+    JumpToTable(String),
+    SaveFilter(String, SavedFilter),
+    SaveQuery(String, SavedQuery),
+    SaveViewPreferences(String, TableViewPreferences),
+    ApplyFilter(String),
     BackToBeforeView,
     CopyToClipboard(String, String),
+    SaveToFile(String, String),
+    QuitWithOutput(String),
+    PipeToCommand(String, String),
+    ExecuteCommand(String, String),
+    OpenInPager(String),
+    WriteFile(String, String, String),
     ClearStatus,
     UpdateStatusInput(String, Option<u16>),
     NotifySuccess(String),
     NotifyWarning(AppError),
     NotifyError(AppError),
+    NotifyErrorWithRetry(AppError, Box<AppEvent>),
 }
 
 #[derive(Clone)]
@@ -42,26 +133,79 @@ impl Sender {
 
 pub struct Receiver {
     rx: mpsc::Receiver<AppEvent>,
+    control: mpsc::Sender<ReaderControl>,
+}
+
+enum ReaderControl {
+    // with the sender to acknowledge that the reader has stopped
+    Pause(mpsc::Sender<()>),
+    Resume,
 }
 
 impl Receiver {
     pub fn recv(&self) -> AppEvent {
         self.rx.recv().unwrap()
     }
+
+    // Stop reading terminal events so that external commands can use the terminal.
+    // Returns after the reader has stopped, so that no input of the command is taken by the reader
+    pub fn pause(&self) {
+        let (ack_tx, ack_rx) = mpsc::channel();
+        self.control.send(ReaderControl::Pause(ack_tx)).unwrap();
+        ack_rx.recv().unwrap();
+    }
+
+    pub fn resume(&self) {
+        self.control.send(ReaderControl::Resume).unwrap();
+    }
+}
+
+// A sender that does not read terminal events, the receiver must be kept alive while sending
+#[cfg(test)]
+pub fn test_channel() -> (Sender, mpsc::Receiver<AppEvent>) {
+    let (tx, rx) = mpsc::channel();
+    (Sender { tx }, rx)
 }
 
 pub fn init() -> (Sender, Receiver) {
     let (tx, rx) = mpsc::channel();
+    let (control_tx, control_rx) = mpsc::channel();
     let tx = Sender { tx };
-    let rx = Receiver { rx };
+    let rx = Receiver {
+        rx,
+        control: control_tx,
+    };
 
     let event_tx = tx.clone();
     thread::spawn(move || loop {
+        // checked only between polls, so the terminal is not touched once the pause is acknowledged
+        if let Ok(ReaderControl::Pause(ack)) = control_rx.try_recv() {
+            let _ = ack.send(());
+            loop {
+                match control_rx.recv() {
+                    Ok(ReaderControl::Resume) => break,
+                    Ok(ReaderControl::Pause(ack)) => {
+                        let _ = ack.send(());
+                    }
+                    Err(_) => return,
+                }
+            }
+        }
+        match ratatui::crossterm::event::poll(EVENT_POLL_INTERVAL) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                panic!("Failed to poll event: {e}");
+            }
+        }
         match ratatui::crossterm::event::read() {
             Ok(e) => match e {
                 ratatui::crossterm::event::Event::Key(key) => {
                     event_tx.send(AppEvent::Key(key));
                 }
+                ratatui::crossterm::event::Event::Paste(text) => {
+                    event_tx.send(AppEvent::Paste(text));
+                }
                 ratatui::crossterm::event::Event::Resize(w, h) => {
                     event_tx.send(AppEvent::Resize(w as usize, h as usize));
                 }
@@ -99,13 +243,68 @@ pub enum UserEvent {
     NextPreview,
     PrevPreview,
     Insight,
+    ColumnStats,
+    FilterByValue,
+    CountByValue,
+    Validate,
+    SideBySide,
+    JumpToReference,
+    ContributorInsights,
     Expand,
     ToggleWrap,
     ToggleNumber,
+    ToggleMaximize,
+    ToggleFullScreen,
     Widen,
     Narrow,
+    HideColumn,
+    ResetColumns,
     Reload,
+    Retry,
+    RefreshItem,
+    CheckKey,
+    CreateItem,
+    SetTtl,
+    BulkUpdate,
+    DeleteItem,
+    DeleteAllItems,
+    ReverseOrder,
+    SortByColumn,
+    GroupByHashKey,
+    NextSamePartition,
+    PrevSamePartition,
+    NextItem,
+    PrevItem,
+    ExclusiveStartKey,
     CopyToClipboard,
+    CopyTable,
+    CopyColumn,
+    CopyPath,
+    SaveToFile,
+    PrintAndQuit,
+    PipeToCommand,
+    OpenInPager,
+    ToggleCursor,
+    ToggleSelection,
+    Yank,
+    SaveFilter,
+    OpenSavedFilters,
+    KeyTemplates,
+    OpenInConsole,
+    OpenLink,
+    AuditLog,
+    Compare,
+    AccountLimits,
+    MultiRegion,
+    QuickOpen,
+    ToggleDryRun,
+    ToggleInspector,
+    PartiQL,
+    SaveQuery,
+    FilterBuilder,
+    QueryBuilder,
+    AddCondition,
+    DeleteCondition,
     Help,
 }
 
@@ -143,13 +342,68 @@ impl UserEventMapper {
             (KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE), UserEvent::NextPreview),
             (KeyEvent::new(KeyCode::Char('V'), KeyModifiers::SHIFT), UserEvent::PrevPreview),
             (KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE), UserEvent::Insight),
+            (KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE), UserEvent::ColumnStats),
+            (KeyEvent::new(KeyCode::Char('*'), KeyModifiers::NONE), UserEvent::FilterByValue),
+            (KeyEvent::new(KeyCode::Char('#'), KeyModifiers::NONE), UserEvent::CountByValue),
+            (KeyEvent::new(KeyCode::Char('I'), KeyModifiers::SHIFT), UserEvent::Validate),
+            (KeyEvent::new(KeyCode::Char('E'), KeyModifiers::SHIFT), UserEvent::SideBySide),
+            (KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT), UserEvent::JumpToReference),
+            (KeyEvent::new(KeyCode::Char('U'), KeyModifiers::SHIFT), UserEvent::ContributorInsights),
             (KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE), UserEvent::Expand),
             (KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE), UserEvent::ToggleWrap),
             (KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE), UserEvent::ToggleNumber),
+            (KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE), UserEvent::ToggleMaximize),
+            (KeyEvent::new(KeyCode::Char('Z'), KeyModifiers::SHIFT), UserEvent::ToggleFullScreen),
             (KeyEvent::new(KeyCode::Char('+'), KeyModifiers::NONE), UserEvent::Widen),
             (KeyEvent::new(KeyCode::Char('-'), KeyModifiers::NONE), UserEvent::Narrow),
+            (KeyEvent::new(KeyCode::Char('H'), KeyModifiers::SHIFT), UserEvent::HideColumn),
+            (KeyEvent::new(KeyCode::Char('='), KeyModifiers::NONE), UserEvent::ResetColumns),
             (KeyEvent::new(KeyCode::Char('R'), KeyModifiers::NONE), UserEvent::Reload),
+            (KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE), UserEvent::RefreshItem),
+            (KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL), UserEvent::CheckKey),
+            (KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT), UserEvent::CreateItem),
+            (KeyEvent::new(KeyCode::Char('T'), KeyModifiers::SHIFT), UserEvent::SetTtl),
+            (KeyEvent::new(KeyCode::Char('B'), KeyModifiers::SHIFT), UserEvent::BulkUpdate),
+            (KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT), UserEvent::DeleteItem),
+            (KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT), UserEvent::DeleteAllItems),
+            (KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE), UserEvent::ReverseOrder),
+            (KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL), UserEvent::Retry),
+            (KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE), UserEvent::SortByColumn),
+            (KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE), UserEvent::GroupByHashKey),
+            (KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE), UserEvent::NextSamePartition),
+            (KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE), UserEvent::PrevSamePartition),
+            (KeyEvent::new(KeyCode::Char('>'), KeyModifiers::NONE), UserEvent::NextItem),
+            (KeyEvent::new(KeyCode::Char('<'), KeyModifiers::NONE), UserEvent::PrevItem),
+            (KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT), UserEvent::ExclusiveStartKey),
             (KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE), UserEvent::CopyToClipboard),
+            (KeyEvent::new(KeyCode::Char('Y'), KeyModifiers::SHIFT), UserEvent::CopyTable),
+            (KeyEvent::new(KeyCode::Char('W'), KeyModifiers::SHIFT), UserEvent::CopyColumn),
+            (KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE), UserEvent::CopyPath),
+            (KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL), UserEvent::SaveToFile),
+            (KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL), UserEvent::PrintAndQuit),
+            (KeyEvent::new(KeyCode::Char('|'), KeyModifiers::NONE), UserEvent::PipeToCommand),
+            (KeyEvent::new(KeyCode::Char('O'), KeyModifiers::SHIFT), UserEvent::OpenInPager),
+            (KeyEvent::new(KeyCode::Char('C'), KeyModifiers::SHIFT), UserEvent::ToggleCursor),
+            (KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL), UserEvent::ToggleSelection),
+            (KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE), UserEvent::Yank),
+            (KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE), UserEvent::SaveFilter),
+            (KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT), UserEvent::OpenSavedFilters),
+            (KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE), UserEvent::KeyTemplates),
+            (KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE), UserEvent::OpenInConsole),
+            (KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE), UserEvent::OpenLink),
+            (KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT), UserEvent::AuditLog),
+            (KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE), UserEvent::Compare),
+            (KeyEvent::new(KeyCode::Char('L'), KeyModifiers::SHIFT), UserEvent::AccountLimits),
+            (KeyEvent::new(KeyCode::Char('M'), KeyModifiers::SHIFT), UserEvent::MultiRegion),
+            (KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT), UserEvent::PartiQL),
+            (KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL), UserEvent::SaveQuery),
+            (KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT), UserEvent::FilterBuilder),
+            (KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT), UserEvent::QueryBuilder),
+            (KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL), UserEvent::AddCondition),
+            (KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL), UserEvent::DeleteCondition),
+            (KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL), UserEvent::QuickOpen),
+            (KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL), UserEvent::ToggleDryRun),
+            (KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE), UserEvent::ToggleInspector),
             (KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE), UserEvent::Help),
         ];
         UserEventMapper { map }
@@ -274,3 +528,19 @@ pub fn key_event_to_string(key: KeyEvent, short: bool) -> String {
 
     key
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_default_keys_are_unique() {
+        let mapper = UserEventMapper::new();
+        let mut keys = HashSet::new();
+        for (key, event) in &mapper.map {
+            assert!(keys.insert(*key), "{key:?} is bound again to {event:?}");
+        }
+    }
+}
@@ -0,0 +1,497 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::Client,
+    data::{sort_keys, Attribute, Item, KeySchemaType, PlainJsonItem, RawJsonItem},
+    error::{AppError, AppResult},
+    util::{from_base64_str, to_base64_str},
+};
+
+const CHECKPOINT_FILE_SUFFIX: &str = ".checkpoint";
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportFormat {
+    /// One plain JSON object per line
+    Jsonl,
+    /// One DynamoDB JSON object per line, which keeps the attribute types
+    RawJsonl,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+pub enum ExportProgress {
+    // the number of items scanned to collect the CSV columns
+    CollectingColumns(usize),
+    // the number of items written, including the ones exported before resuming
+    Exporting(usize),
+}
+
+pub struct ExportSummary {
+    // including the items exported before resuming
+    pub count: usize,
+    // attributes added to the table after the CSV columns were collected
+    pub skipped_attributes: Vec<String>,
+}
+
+// The progress of an export, saved next to the output file while exporting
+// so that an interrupted export can be resumed from where it left off
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Checkpoint {
+    table_name: String,
+    format: ExportFormat,
+    // key attributes are always strings, numbers or binaries
+    exclusive_start_key: BTreeMap<String, CheckpointKeyValue>,
+    count: usize,
+    // the output file is truncated to this size on resume, to drop the items written after the checkpoint
+    output_bytes: u64,
+    columns: Option<Vec<String>>,
+}
+
+// Serialized in the same way as DynamoDB JSON, like `{"N": "42"}`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum CheckpointKeyValue {
+    S(String),
+    N(String),
+    B(String),
+}
+
+impl CheckpointKeyValue {
+    fn new(attr: &Attribute) -> AppResult<CheckpointKeyValue> {
+        match attr {
+            Attribute::S(s) => Ok(CheckpointKeyValue::S(s.clone())),
+            Attribute::N(n) => Ok(CheckpointKeyValue::N(n.to_string())),
+            Attribute::B(b) => Ok(CheckpointKeyValue::B(to_base64_str(b))),
+            _ => Err(AppError::msg(
+                "key attributes must be strings, numbers or binaries",
+            )),
+        }
+    }
+
+    fn to_attribute(&self) -> AppResult<Attribute> {
+        match self {
+            CheckpointKeyValue::S(s) => Ok(Attribute::S(s.clone())),
+            CheckpointKeyValue::N(n) => n
+                .parse()
+                .map(Attribute::N)
+                .map_err(|e| AppError::new("invalid number in the checkpoint", e)),
+            CheckpointKeyValue::B(b) => from_base64_str(b).map(Attribute::B),
+        }
+    }
+}
+
+impl Checkpoint {
+    fn load(path: &Path) -> AppResult<Checkpoint> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            AppError::new(format!("failed to read checkpoint {}", path.display()), e)
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::new(format!("failed to parse checkpoint {}", path.display()), e))
+    }
+
+    // write to a temporary file and rename it, so that the checkpoint is never left half-written
+    fn save(&self, path: &Path) -> AppResult<()> {
+        let content = serde_json::to_string(self).unwrap();
+        let tmp_path = with_suffix(path, ".tmp");
+        fs::write(&tmp_path, content)
+            .and_then(|_| fs::rename(&tmp_path, path))
+            .map_err(|e| AppError::new(format!("failed to save checkpoint {}", path.display()), e))
+    }
+
+    fn start_key(&self) -> AppResult<BTreeMap<String, Attribute>> {
+        self.exclusive_start_key
+            .iter()
+            .map(|(k, v)| v.to_attribute().map(|attr| (k.clone(), attr)))
+            .collect()
+    }
+}
+
+fn remove_checkpoint(path: &Path) -> AppResult<()> {
+    fs::remove_file(path)
+        .map_err(|e| AppError::new(format!("failed to remove checkpoint {}", path.display()), e))
+}
+
+pub fn checkpoint_path(output: &Path) -> PathBuf {
+    with_suffix(output, CHECKPOINT_FILE_SUFFIX)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(suffix);
+    PathBuf::from(s)
+}
+
+// Export the items of the table to the file page by page, so that the whole table is never held in memory.
+// A checkpoint is saved periodically, and the export continues from it if resume is true.
+// For CSV, the table is scanned once more before the export to collect all attribute names for the header.
+pub async fn export_table(
+    client: &Client,
+    table_name: &str,
+    path: &Path,
+    format: ExportFormat,
+    page_size: usize,
+    resume: bool,
+    on_progress: impl Fn(ExportProgress),
+) -> AppResult<ExportSummary> {
+    let desc = client.describe_table(table_name).await?;
+    let checkpoint_path = checkpoint_path(path);
+
+    let (file, checkpoint) = if resume {
+        let checkpoint = Checkpoint::load(&checkpoint_path)?;
+        if checkpoint.table_name != table_name || checkpoint.format != format {
+            let msg = format!(
+                "the checkpoint is for the export of {} as {:?}",
+                checkpoint.table_name, checkpoint.format
+            );
+            return Err(AppError::msg(msg));
+        }
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .and_then(|file| file.set_len(checkpoint.output_bytes).map(|_| file))
+            .map_err(|e| AppError::new(format!("failed to open {}", path.display()), e))?;
+        (file, Some(checkpoint))
+    } else {
+        // a checkpoint left by a previous export no longer matches the new output
+        if checkpoint_path.exists() {
+            remove_checkpoint(&checkpoint_path)?;
+        }
+        let file = File::create(path)
+            .map_err(|e| AppError::new(format!("failed to create {}", path.display()), e))?;
+        (file, None)
+    };
+
+    let mut writer = ItemWriter::new(format, desc.key_schema_type.clone(), BufWriter::new(file));
+    let mut exported = 0;
+    let mut start_key = None;
+    if let Some(checkpoint) = &checkpoint {
+        writer.columns = checkpoint.columns.clone();
+        writer.written_bytes = checkpoint.output_bytes;
+        exported = checkpoint.count;
+        start_key = Some(checkpoint.start_key()?);
+    } else if format == ExportFormat::Csv {
+        let columns = collect_columns(client, table_name, &desc.key_schema_type, page_size, |n| {
+            on_progress(ExportProgress::CollectingColumns(n))
+        })
+        .await?;
+        writer.write_csv_header(columns)?;
+    }
+
+    let mut last_saved_at = Instant::now();
+    client
+        .scan_pages(table_name, start_key, page_size, |items, next_key| {
+            writer.write_items(&items)?;
+            exported += items.len();
+            on_progress(ExportProgress::Exporting(exported));
+
+            if let Some(next_key) = next_key {
+                if last_saved_at.elapsed() >= CHECKPOINT_INTERVAL {
+                    // the items must be in the file before the checkpoint refers to them
+                    writer.flush()?;
+                    let exclusive_start_key = next_key
+                        .iter()
+                        .map(|(k, v)| CheckpointKeyValue::new(v).map(|v| (k.clone(), v)))
+                        .collect::<AppResult<_>>()?;
+                    let checkpoint = Checkpoint {
+                        table_name: table_name.into(),
+                        format,
+                        exclusive_start_key,
+                        count: exported,
+                        output_bytes: writer.written_bytes,
+                        columns: writer.columns.clone(),
+                    };
+                    checkpoint.save(&checkpoint_path)?;
+                    last_saved_at = Instant::now();
+                }
+            }
+            Ok(())
+        })
+        .await?;
+
+    let skipped_attributes = writer.finish()?;
+    if checkpoint_path.exists() {
+        remove_checkpoint(&checkpoint_path)?;
+    }
+    Ok(ExportSummary {
+        count: exported,
+        skipped_attributes,
+    })
+}
+
+async fn collect_columns(
+    client: &Client,
+    table_name: &str,
+    schema: &KeySchemaType,
+    page_size: usize,
+    on_progress: impl Fn(usize),
+) -> AppResult<Vec<String>> {
+    let mut names = BTreeSet::new();
+    let mut scanned = 0;
+    client
+        .scan_pages(table_name, None, page_size, |items, _| {
+            for item in &items {
+                names.extend(item.attributes.keys().cloned());
+            }
+            scanned += items.len();
+            on_progress(scanned);
+            Ok(())
+        })
+        .await?;
+    let mut columns: Vec<String> = names.into_iter().collect();
+    sort_keys(&mut columns, schema);
+    Ok(columns)
+}
+
+struct ItemWriter<W: Write> {
+    format: ExportFormat,
+    schema: KeySchemaType,
+    out: W,
+    // the CSV columns, collected from the whole table before writing the items
+    columns: Option<Vec<String>>,
+    skipped_attributes: BTreeSet<String>,
+    written_bytes: u64,
+}
+
+impl<W: Write> ItemWriter<W> {
+    fn new(format: ExportFormat, schema: KeySchemaType, out: W) -> Self {
+        ItemWriter {
+            format,
+            schema,
+            out,
+            columns: None,
+            skipped_attributes: BTreeSet::new(),
+            written_bytes: 0,
+        }
+    }
+
+    fn write_line(&mut self, line: &str) -> AppResult<()> {
+        writeln!(self.out, "{line}").map_err(|e| AppError::new("failed to write items", e))?;
+        self.written_bytes += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> AppResult<()> {
+        self.out
+            .flush()
+            .map_err(|e| AppError::new("failed to write items", e))
+    }
+
+    fn write_items(&mut self, items: &[Item]) -> AppResult<()> {
+        for item in items {
+            let line = match self.format {
+                ExportFormat::Jsonl => {
+                    serde_json::to_string(&PlainJsonItem::new(item, &self.schema)).unwrap()
+                }
+                ExportFormat::RawJsonl => {
+                    serde_json::to_string(&RawJsonItem::new(item, &self.schema)).unwrap()
+                }
+                ExportFormat::Csv => self.csv_line(item),
+            };
+            self.write_line(&line)?;
+        }
+        Ok(())
+    }
+
+    fn write_csv_header(&mut self, columns: Vec<String>) -> AppResult<()> {
+        // an empty table is exported as an empty file
+        if !columns.is_empty() {
+            let header = columns.iter().map(|c| csv_escape(c)).collect::<Vec<_>>();
+            self.write_line(&header.join(","))?;
+        }
+        self.columns = Some(columns);
+        Ok(())
+    }
+
+    fn csv_line(&mut self, item: &Item) -> String {
+        // the columns are set by write_csv_header, or restored from the checkpoint
+        let columns = self.columns.as_ref().unwrap();
+        for name in item.attributes.keys() {
+            if !columns.contains(name) {
+                self.skipped_attributes.insert(name.clone());
+            }
+        }
+        let cells = columns
+            .iter()
+            .map(|c| item.attributes.get(c).map(csv_value).unwrap_or_default())
+            .map(|v| csv_escape(&v))
+            .collect::<Vec<_>>();
+        cells.join(",")
+    }
+
+    fn finish(mut self) -> AppResult<Vec<String>> {
+        self.flush()?;
+        Ok(self.skipped_attributes.into_iter().collect())
+    }
+}
+
+pub fn csv_value(attr: &Attribute) -> String {
+    match attr {
+        Attribute::S(s) => s.clone(),
+        Attribute::N(n) => n.to_string(),
+        Attribute::B(b) => to_base64_str(b),
+        Attribute::BOOL(b) => b.to_string(),
+        Attribute::NULL => String::new(),
+        _ => serde_json::to_string(attr).unwrap(),
+    }
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rust_decimal::Decimal;
+
+    use crate::data::list_attribute_keys;
+
+    use super::*;
+
+    fn item(attributes: Vec<(&str, Attribute)>) -> Item {
+        let attributes: HashMap<_, _> = attributes
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        Item { attributes }
+    }
+
+    fn write_pages(format: ExportFormat, pages: Vec<Vec<Item>>) -> (String, Vec<String>) {
+        let mut out = Vec::new();
+        let schema = KeySchemaType::Hash("id".into());
+        let mut writer = ItemWriter::new(format, schema.clone(), &mut out);
+        if format == ExportFormat::Csv {
+            let columns = list_attribute_keys(&pages.concat(), &schema);
+            writer.write_csv_header(columns).ok().unwrap();
+        }
+        for page in pages {
+            writer.write_items(&page).ok().unwrap();
+        }
+        let skipped = writer.finish().ok().unwrap();
+        (String::from_utf8(out).unwrap(), skipped)
+    }
+
+    #[test]
+    fn test_write_jsonl() {
+        let pages = vec![
+            vec![item(vec![
+                ("id", Attribute::S("a".into())),
+                ("n", Attribute::N(Decimal::from(1))),
+            ])],
+            vec![item(vec![("id", Attribute::S("b".into()))])],
+        ];
+        let (actual, _) = write_pages(ExportFormat::Jsonl, pages.clone());
+        assert_eq!(actual, "{\"id\":\"a\",\"n\":1}\n{\"id\":\"b\"}\n");
+
+        let (actual, _) = write_pages(ExportFormat::RawJsonl, pages);
+        assert_eq!(
+            actual,
+            "{\"id\":{\"S\":\"a\"},\"n\":{\"N\":1}}\n{\"id\":{\"S\":\"b\"}}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let pages = vec![
+            vec![
+                item(vec![
+                    ("id", Attribute::S("a".into())),
+                    ("name", Attribute::S("x, \"y\"".into())),
+                ]),
+                item(vec![
+                    ("id", Attribute::S("b".into())),
+                    ("tags", Attribute::L(vec![Attribute::BOOL(true)])),
+                ]),
+            ],
+            vec![item(vec![
+                ("id", Attribute::S("c".into())),
+                ("extra", Attribute::NULL),
+            ])],
+        ];
+        let (actual, skipped) = write_pages(ExportFormat::Csv, pages);
+        let expected = "id,extra,name,tags\na,,\"x, \"\"y\"\"\",\nb,,,[true]\nc,,,\n";
+        assert_eq!(actual, expected);
+        assert!(skipped.is_empty());
+    }
+
+    #[test]
+    fn test_write_csv_skipped_attributes() {
+        let mut out = Vec::new();
+        let mut writer = ItemWriter::new(
+            ExportFormat::Csv,
+            KeySchemaType::Hash("id".into()),
+            &mut out,
+        );
+        writer.write_csv_header(vec!["id".into()]).ok().unwrap();
+        let page = vec![item(vec![
+            ("id", Attribute::S("a".into())),
+            ("added", Attribute::S("x".into())),
+        ])];
+        writer.write_items(&page).ok().unwrap();
+        let skipped = writer.finish().ok().unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "id\na\n");
+        assert_eq!(skipped, vec!["added"]);
+    }
+
+    #[test]
+    fn test_written_bytes() {
+        let mut out = Vec::new();
+        let mut writer = ItemWriter::new(
+            ExportFormat::Csv,
+            KeySchemaType::Hash("id".into()),
+            &mut out,
+        );
+        let page = vec![item(vec![("id", Attribute::S("あ".into()))])];
+        writer.write_csv_header(vec!["id".into()]).ok().unwrap();
+        writer.write_items(&page).ok().unwrap();
+        let written_bytes = writer.written_bytes;
+        writer.finish().ok().unwrap();
+
+        assert_eq!(written_bytes, out.len() as u64);
+    }
+
+    #[test]
+    fn test_checkpoint_serialize() {
+        let key = BTreeMap::from([
+            ("pk".to_string(), Attribute::S("USER#1".into())),
+            ("sk".to_string(), Attribute::N("10.50".parse().unwrap())),
+        ]);
+        let checkpoint = Checkpoint {
+            table_name: "users".into(),
+            format: ExportFormat::RawJsonl,
+            exclusive_start_key: key
+                .iter()
+                .map(|(k, v)| (k.clone(), CheckpointKeyValue::new(v).ok().unwrap()))
+                .collect(),
+            count: 100,
+            output_bytes: 2048,
+            columns: None,
+        };
+
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        assert_eq!(
+            json,
+            r#"{"table_name":"users","format":"raw-jsonl","exclusive_start_key":{"pk":{"S":"USER#1"},"sk":{"N":"10.50"}},"count":100,"output_bytes":2048,"columns":null}"#
+        );
+
+        let actual: Checkpoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, checkpoint);
+        assert_eq!(actual.start_key().ok().unwrap(), key);
+    }
+}
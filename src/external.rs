@@ -0,0 +1,209 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process::{Command, Stdio},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use ratatui::crossterm::{
+    cursor::Show,
+    event::{
+        self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyEventKind,
+        KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use tempfile::{Builder, TempPath};
+
+use crate::error::{AppError, AppResult};
+
+const DEFAULT_PAGER: &str = "less";
+const DEFAULT_EDITOR: &str = "vi";
+
+static KEYBOARD_ENHANCED: AtomicBool = AtomicBool::new(false);
+
+// Must be called in raw mode because the terminal is queried for the support
+// Terminals that do not respond to the query are treated as unsupported
+pub fn enable_keyboard_enhancement<W: Write>(w: &mut W) -> io::Result<()> {
+    if terminal::supports_keyboard_enhancement().unwrap_or(false) {
+        push_keyboard_enhancement(w)?;
+        KEYBOARD_ENHANCED.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+pub fn disable_keyboard_enhancement<W: Write>(w: &mut W) -> io::Result<()> {
+    if KEYBOARD_ENHANCED.swap(false, Ordering::SeqCst) {
+        execute!(w, PopKeyboardEnhancementFlags)?;
+    }
+    Ok(())
+}
+
+fn push_keyboard_enhancement<W: Write>(w: &mut W) -> io::Result<()> {
+    let flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES;
+    execute!(w, PushKeyboardEnhancementFlags(flags))
+}
+
+pub fn with_suspended_terminal<W: Write, T>(
+    w: &mut W,
+    f: impl FnOnce() -> AppResult<T>,
+) -> AppResult<T> {
+    suspend_terminal(w).map_err(|e| AppError::new("failed to suspend terminal", e))?;
+    let result = f();
+    resume_terminal(w).map_err(|e| AppError::new("failed to resume terminal", e))?;
+    result
+}
+
+fn suspend_terminal<W: Write>(w: &mut W) -> io::Result<()> {
+    // external commands expect the legacy key encoding
+    if KEYBOARD_ENHANCED.load(Ordering::SeqCst) {
+        execute!(w, PopKeyboardEnhancementFlags)?;
+    }
+    terminal::disable_raw_mode()?;
+    execute!(w, DisableBracketedPaste, LeaveAlternateScreen, Show)
+}
+
+fn resume_terminal<W: Write>(w: &mut W) -> io::Result<()> {
+    terminal::enable_raw_mode()?;
+    execute!(w, EnterAlternateScreen, EnableBracketedPaste)?;
+    if KEYBOARD_ENHANCED.load(Ordering::SeqCst) {
+        push_keyboard_enhancement(w)?;
+    }
+    Ok(())
+}
+
+pub fn pipe_to_command(command: &str, content: &str) -> AppResult<()> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::new("failed to execute command", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the command may exit without reading all of the input (e.g. head)
+        let _ = stdin.write_all(content.as_bytes());
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| AppError::new("failed to wait for command", e))?;
+
+    eprintln!();
+    eprint!("[{status}] Press any key to return to ddv");
+    wait_any_key()
+}
+
+// Open the content with $PAGER (or $EDITOR) as a read-only temporary file
+pub fn open_in_pager(content: &str) -> AppResult<()> {
+    let program = env::var("PAGER")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| DEFAULT_PAGER.into());
+
+    let path = write_temp_file(content)?;
+    let mut permissions = fs::metadata(&path)
+        .map_err(|e| AppError::new("failed to read temporary file", e))?
+        .permissions();
+    permissions.set_readonly(true);
+    let _ = fs::set_permissions(&path, permissions);
+
+    let command = format!("{program} {}", quote_path(&path));
+    shell_command(&command)
+        .status()
+        .map_err(|e| AppError::new("failed to open pager", e))?;
+    Ok(())
+}
+
+// Open the content with $EDITOR as a temporary file and return the edited content
+pub fn edit_in_editor(content: &str) -> AppResult<String> {
+    let program = env::var("EDITOR").unwrap_or_else(|_| DEFAULT_EDITOR.into());
+
+    let path = write_temp_file(content)?;
+
+    let command = format!("{program} {}", quote_path(&path));
+    let status = shell_command(&command)
+        .status()
+        .map_err(|e| AppError::new("failed to open editor", e))?;
+    if !status.success() {
+        return Err(AppError::msg(format!("editor exited with {status}")));
+    }
+    fs::read_to_string(&path).map_err(|e| AppError::new("failed to read temporary file", e))
+}
+
+// The file is created exclusively with a random name, so an existing file (or symlink) is never written through.
+// It is removed when the returned path is dropped, on every exit path
+fn write_temp_file(content: &str) -> AppResult<TempPath> {
+    let mut file = Builder::new()
+        .prefix("ddv-")
+        .suffix(".json")
+        .tempfile()
+        .map_err(|e| AppError::new("failed to create temporary file", e))?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| AppError::new("failed to write temporary file", e))?;
+    // close the file so that the external command can open it on any platform
+    Ok(file.into_temp_path())
+}
+
+fn quote_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if cfg!(windows) {
+        format!("\"{path}\"")
+    } else {
+        format!("'{}'", path.replace('\'', "'\\''"))
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+fn wait_any_key() -> AppResult<()> {
+    let read_key = || -> io::Result<()> {
+        terminal::enable_raw_mode()?;
+        loop {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    break;
+                }
+            }
+        }
+        terminal::disable_raw_mode()
+    };
+    read_key().map_err(|e| AppError::new("failed to read key", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[cfg(not(windows))]
+    #[rstest]
+    #[case("/tmp/ddv.json", "'/tmp/ddv.json'")]
+    #[case("/tmp/it's.json", "'/tmp/it'\\''s.json'")]
+    fn test_quote_path(#[case] path: &str, #[case] expected: &str) {
+        assert_eq!(quote_path(Path::new(path)), expected);
+    }
+
+    #[test]
+    fn test_write_temp_file() {
+        let path = write_temp_file("{}").ok().unwrap();
+        let other = write_temp_file("{}").ok().unwrap();
+        assert_ne!(path.to_path_buf(), other.to_path_buf());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+
+        let path_buf = path.to_path_buf();
+        drop(path);
+        assert!(!path_buf.exists());
+    }
+}
@@ -185,7 +185,10 @@ pub fn build_help_spans(
                 }
                 spans.push(Span::raw(": ".to_string()));
                 spans.push(Span::raw(item.description));
-                Some(Spans { spans })
+                Some(Spans {
+                    spans,
+                    event: Some(item.event),
+                })
             }
         })
         .collect()
@@ -194,6 +197,7 @@ pub fn build_help_spans(
 #[derive(Clone)]
 pub struct Spans {
     spans: Vec<Span<'static>>,
+    event: Option<UserEvent>,
 }
 
 impl Spans {
@@ -202,6 +206,32 @@ impl Spans {
     }
 }
 
+// Grey out the helps of the actions that cannot be performed
+pub fn disable_help_spans(
+    helps: &[Spans],
+    disabled_events: &[UserEvent],
+    theme: ColorTheme,
+) -> Vec<Spans> {
+    helps
+        .iter()
+        .map(|help| match help.event {
+            Some(event) if disabled_events.contains(&event) => {
+                let mut spans: Vec<Span<'static>> = help
+                    .spans
+                    .iter()
+                    .map(|span| span.clone().fg(theme.disabled))
+                    .collect();
+                spans.push(" (not permitted)".fg(theme.disabled));
+                Spans {
+                    spans,
+                    event: help.event,
+                }
+            }
+            _ => help.clone(),
+        })
+        .collect()
+}
+
 pub fn group_spans_to_fit_width(
     spanss: &[Spans],
     max_width: usize,
@@ -0,0 +1,210 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use aws_sdk_dynamodb::config::{
+    interceptors::{
+        BeforeSerializationInterceptorContextRef, BeforeTransmitInterceptorContextRef,
+        FinalizerInterceptorContextRef,
+    },
+    ConfigBag, Intercept, RuntimeComponents,
+};
+use aws_sdk_dynamodb::error::BoxError;
+use aws_smithy_runtime_api::client::orchestrator::Metadata;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use chrono::{DateTime, Local};
+
+const REQUEST_ID_HEADER: &str = "x-amzn-RequestId";
+
+#[derive(Debug, Clone)]
+pub struct OperationRecord {
+    pub started_at: DateTime<Local>,
+    pub operation: String,
+    pub parameters: String,
+    pub duration: Duration,
+    pub status: Option<u16>,
+    pub request_id: Option<String>,
+    pub retries: usize,
+    pub error: Option<String>,
+}
+
+impl OperationRecord {
+    pub fn is_failed(&self) -> bool {
+        self.error.is_some() || self.status.is_some_and(|s| s >= 400)
+    }
+}
+
+// The last operations sent by the clients, shared with the interceptor
+#[derive(Debug, Clone)]
+pub struct OperationLog {
+    records: Arc<Mutex<VecDeque<OperationRecord>>>,
+    capacity: usize,
+}
+
+impl OperationLog {
+    pub fn new(capacity: usize) -> OperationLog {
+        OperationLog {
+            records: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, record: OperationRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut records = self.records.lock().unwrap();
+        while records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    // newest first
+    pub fn records(&self) -> Vec<OperationRecord> {
+        let records = self.records.lock().unwrap();
+        records.iter().rev().cloned().collect()
+    }
+
+    pub fn interceptor(&self) -> RecordOperationInterceptor {
+        RecordOperationInterceptor { log: self.clone() }
+    }
+}
+
+// The state of an operation in progress, kept in the config bag of the operation
+#[derive(Debug, Clone)]
+struct OperationState {
+    started_at: DateTime<Local>,
+    instant: Instant,
+    attempts: usize,
+    parameters: String,
+}
+
+impl Storable for OperationState {
+    type Storer = StoreReplace<Self>;
+}
+
+#[derive(Debug)]
+pub struct RecordOperationInterceptor {
+    log: OperationLog,
+}
+
+impl Intercept for RecordOperationInterceptor {
+    fn name(&self) -> &'static str {
+        "RecordOperationInterceptor"
+    }
+
+    fn read_before_execution(
+        &self,
+        _context: &BeforeSerializationInterceptorContextRef<'_>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        cfg.interceptor_state().store_put(OperationState {
+            started_at: Local::now(),
+            instant: Instant::now(),
+            attempts: 0,
+            parameters: String::new(),
+        });
+        Ok(())
+    }
+
+    fn read_before_attempt(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if let Some(state) = cfg.get_mut::<OperationState>() {
+            state.attempts += 1;
+        }
+        Ok(())
+    }
+
+    fn read_before_transmit(
+        &self,
+        context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        // the parameters are serialized as JSON in the request body
+        let body = context.request().body().bytes().unwrap_or_default();
+        if let Some(state) = cfg.get_mut::<OperationState>() {
+            state.parameters = String::from_utf8_lossy(body).into_owned();
+        }
+        Ok(())
+    }
+
+    fn read_after_execution(
+        &self,
+        context: &FinalizerInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(state) = cfg.load::<OperationState>() else {
+            return Ok(());
+        };
+        let operation = cfg
+            .load::<Metadata>()
+            .map(|m| m.name().to_string())
+            .unwrap_or_default();
+        let response = context.response();
+        let status = response.map(|r| r.status().as_u16());
+        let request_id = response
+            .and_then(|r| r.headers().get(REQUEST_ID_HEADER))
+            .map(Into::into);
+        let error = match context.output_or_error() {
+            Some(Err(e)) => Some(e.to_string()),
+            _ => None,
+        };
+        self.log.push(OperationRecord {
+            started_at: state.started_at,
+            operation,
+            parameters: state.parameters.clone(),
+            duration: state.instant.elapsed(),
+            status,
+            request_id,
+            retries: state.attempts.saturating_sub(1),
+            error,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(operation: &str) -> OperationRecord {
+        OperationRecord {
+            started_at: DateTime::default(),
+            operation: operation.into(),
+            parameters: "{}".into(),
+            duration: Duration::from_millis(10),
+            status: Some(200),
+            request_id: None,
+            retries: 0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_operation_log_keeps_last_records() {
+        let log = OperationLog::new(2);
+        log.push(record("ListTables"));
+        log.push(record("DescribeTable"));
+        log.push(record("Scan"));
+
+        let actual: Vec<_> = log.records().into_iter().map(|r| r.operation).collect();
+        assert_eq!(actual, vec!["Scan", "DescribeTable"]);
+    }
+
+    #[test]
+    fn test_operation_log_disabled() {
+        let log = OperationLog::new(0);
+        log.push(record("Scan"));
+
+        assert!(log.records().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 mod app;
+mod audit;
 mod client;
 mod color;
 mod config;
@@ -6,15 +7,46 @@ mod constant;
 mod data;
 mod error;
 mod event;
+mod export;
+mod external;
 mod help;
+mod inspector;
 mod macros;
+mod profile;
+mod state;
 mod util;
+mod validation;
 mod view;
 mod widget;
 
-use clap::Parser;
+use std::{
+    io::{self, IsTerminal, Stderr},
+    path::{Path, PathBuf},
+};
 
-use crate::{app::App, client::Client, color::ColorTheme, config::Config, event::UserEventMapper};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
+use ratatui::{
+    crossterm::{
+        cursor::Show,
+        event::{DisableBracketedPaste, EnableBracketedPaste},
+        execute,
+        terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    prelude::CrosstermBackend,
+    Terminal,
+};
+
+use crate::{
+    app::{App, StartupTarget},
+    client::Client,
+    color::ColorTheme,
+    config::Config,
+    event::UserEventMapper,
+    export::{checkpoint_path, export_table, ExportFormat, ExportProgress},
+    inspector::OperationLog,
+    profile::{credentials_specified_by_env, load_profiles},
+    state::State,
+};
 
 /// DDV - Terminal DynamoDB Viewer ⚡️
 #[derive(Parser)]
@@ -31,31 +63,283 @@ struct Args {
     /// AWS profile name
     #[arg(short, long, value_name = "NAME")]
     profile: Option<String>,
+
+    /// Print the selected item as JSON to stdout on exit
+    #[arg(long)]
+    print_on_exit: bool,
+
+    /// Start in dry-run mode, showing write requests without sending them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Table name to select on startup
+    #[arg(short, long, value_name = "NAME")]
+    table: Option<String>,
+
+    /// Key of the item to open on startup (e.g. 'pk=USER#42,sk=ORDER#7')
+    #[arg(short, long, requires = "table")]
+    key: Option<String>,
+
+    /// Environment preset defined in the config, choose on startup if the name is omitted
+    #[arg(long, value_name = "NAME", num_args = 0..=1, default_missing_value = "")]
+    env: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Export all items of a table to a file without loading them into memory
+    Export {
+        /// Table name to export
+        #[arg(short, long, value_name = "NAME")]
+        table: String,
+
+        /// Output file path
+        #[arg(short, long, value_name = "PATH")]
+        output: PathBuf,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Jsonl)]
+        format: ExportFormat,
+
+        /// Resume the interrupted export from its checkpoint
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Export the saved filters, references and view preferences to share them with others
+    ExportState {
+        /// Output file path
+        #[arg(short, long, value_name = "PATH")]
+        output: PathBuf,
+    },
+    /// Import the state exported by export-state, merging it into the local state
+    ImportState {
+        /// Input file path
+        #[arg(short, long, value_name = "PATH")]
+        input: PathBuf,
+    },
 }
 
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
     let args = Args::parse();
-    let config = Config::load();
+    let mut config = Config::load();
+    config.write.dry_run |= args.dry_run;
     let theme = ColorTheme::default();
     let mapper = UserEventMapper::new();
 
+    match &args.command {
+        Some(Command::ExportState { output }) => {
+            run_export_state(output);
+            return Ok(());
+        }
+        Some(Command::ImportState { input }) => {
+            run_import_state(input);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // the profile is chosen on startup if nothing specifies which credentials to use
+    let pick_profile = args.profile.is_none()
+        && args.endpoint_url.is_none()
+        && args.env.is_none()
+        && !credentials_specified_by_env();
+    let startup_region = args.region.clone();
+
+    // the command line options take precedence over the preset
+    let pick_environment = args.env.as_deref() == Some("");
+    let (region, endpoint_url, profile, credentials) = match args.env.as_deref() {
+        None | Some("") => (args.region, args.endpoint_url, args.profile, None),
+        Some(name) => match config.environments.get(name) {
+            Some(preset) => (
+                args.region.or(preset.region.clone()),
+                args.endpoint_url.or(preset.endpoint_url.clone()),
+                args.profile.or(preset.profile.clone()),
+                preset.credentials(),
+            ),
+            None => {
+                let msg = format!("environment '{name}' is not defined in the config");
+                Args::command().error(ErrorKind::InvalidValue, msg).exit()
+            }
+        },
+    };
+    if pick_environment && config.environments.is_empty() {
+        let msg = "no environments are defined in the config";
+        Args::command().error(ErrorKind::InvalidValue, msg).exit()
+    }
+
     let client = Client::new(
-        args.region,
-        args.endpoint_url,
-        args.profile,
+        region,
+        endpoint_url,
+        profile,
+        credentials,
         config.default_region.clone(),
+        config.compat_mode,
+        OperationLog::new(config.ui.inspector.max_records),
     )
     .await;
+
+    if let Some(Command::Export {
+        table,
+        output,
+        format,
+        resume,
+    }) = args.command
+    {
+        run_export(&client, &config, &table, &output, format, resume).await;
+        return Ok(());
+    }
     let (tx, rx) = event::init();
 
-    tx.send(event::AppEvent::Initialize);
+    let state = State::load().unwrap_or_else(|e| {
+        tx.send(event::AppEvent::NotifyError(e));
+        State::default()
+    });
+
+    let profiles = if pick_profile {
+        load_profiles()
+    } else {
+        Vec::new()
+    };
+    if pick_environment {
+        tx.send(event::AppEvent::OpenEnvironmentPicker);
+    } else if profiles.len() > 1 {
+        tx.send(event::AppEvent::OpenProfilePicker(profiles, startup_region));
+    } else {
+        tx.send(event::AppEvent::Initialize);
+    }
 
-    let mut terminal = ratatui::init();
+    let enhanced_keyboard = config.ui.enhanced_keyboard;
+    let mut app = App::new(config, theme, mapper, state, client, args.print_on_exit, tx);
+    if let Some(table_name) = args.table {
+        app.set_startup_target(StartupTarget {
+            table_name,
+            key: args.key,
+        });
+    }
 
-    let mut app = App::new(config, theme, mapper, client, tx);
-    let ret = app.run(&mut terminal, rx);
+    let ret = if io::stdout().is_terminal() {
+        let mut terminal = ratatui::init();
+        execute!(io::stdout(), EnableBracketedPaste)?;
+        if enhanced_keyboard {
+            external::enable_keyboard_enhancement(&mut io::stdout())?;
+        }
+        let ret = app.run(&mut terminal, rx);
+        external::disable_keyboard_enhancement(&mut io::stdout())?;
+        execute!(io::stdout(), DisableBracketedPaste)?;
+        ratatui::restore();
+        ret
+    } else {
+        // render to stderr so that stdout can be piped to other commands
+        let mut terminal = init_stderr_terminal()?;
+        if enhanced_keyboard {
+            external::enable_keyboard_enhancement(&mut io::stderr())?;
+        }
+        let ret = app.run(&mut terminal, rx);
+        external::disable_keyboard_enhancement(&mut io::stderr())?;
+        restore_stderr_terminal()?;
+        ret
+    };
 
-    ratatui::restore();
+    if let Some(output) = app.output() {
+        println!("{output}");
+    }
     ret
 }
+
+async fn run_export(
+    client: &Client,
+    config: &Config,
+    table_name: &str,
+    output: &Path,
+    format: ExportFormat,
+    resume: bool,
+) {
+    let page_size = config.scan.page_size;
+    let on_progress = |progress| match progress {
+        ExportProgress::CollectingColumns(count) => {
+            eprint!("\rScanned {count} items for the CSV columns")
+        }
+        ExportProgress::Exporting(count) => eprint!("\rExported {count} items"),
+    };
+    let result = export_table(
+        client,
+        table_name,
+        output,
+        format,
+        page_size,
+        resume,
+        on_progress,
+    )
+    .await;
+    match result {
+        Ok(summary) => {
+            eprintln!("\rExported {} items to {}", summary.count, output.display());
+            if !summary.skipped_attributes.is_empty() {
+                eprintln!(
+                    "Attributes added during the export were not written to the CSV: {}",
+                    summary.skipped_attributes.join(", ")
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!();
+            eprintln!("error: {}", e.messages().join(": "));
+            if checkpoint_path(output).exists() {
+                eprintln!("The export can be continued by running the same command with --resume");
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_export_state(output: &Path) {
+    let result = State::load().and_then(|state| state.export_to(output));
+    match result {
+        Ok(count) => eprintln!(
+            "Exported the state of {count} tables to {}",
+            output.display()
+        ),
+        Err(e) => {
+            eprintln!("error: {}", e.messages().join(": "));
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_import_state(input: &Path) {
+    let result = State::load().and_then(|mut state| {
+        let count = state.import_from(input)?;
+        state.save()?;
+        Ok(count)
+    });
+    match result {
+        Ok(count) => eprintln!(
+            "Imported the state of {count} tables from {}",
+            input.display()
+        ),
+        Err(e) => {
+            eprintln!("error: {}", e.messages().join(": "));
+            std::process::exit(1);
+        }
+    }
+}
+
+fn init_stderr_terminal() -> io::Result<Terminal<CrosstermBackend<Stderr>>> {
+    terminal::enable_raw_mode()?;
+    execute!(io::stderr(), EnterAlternateScreen, EnableBracketedPaste)?;
+    Terminal::new(CrosstermBackend::new(io::stderr()))
+}
+
+fn restore_stderr_terminal() -> io::Result<()> {
+    terminal::disable_raw_mode()?;
+    execute!(
+        io::stderr(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen,
+        Show
+    )
+}
@@ -0,0 +1,123 @@
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+
+const PROFILE_ENV_VAR: &str = "AWS_PROFILE";
+const ACCESS_KEY_ID_ENV_VAR: &str = "AWS_ACCESS_KEY_ID";
+const CONFIG_FILE_ENV_VAR: &str = "AWS_CONFIG_FILE";
+const CREDENTIALS_FILE_ENV_VAR: &str = "AWS_SHARED_CREDENTIALS_FILE";
+const DEFAULT_AWS_DIR_NAME: &str = ".aws";
+const DEFAULT_CONFIG_FILE_NAME: &str = "config";
+const DEFAULT_CREDENTIALS_FILE_NAME: &str = "credentials";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub region: Option<String>,
+}
+
+pub fn credentials_specified_by_env() -> bool {
+    env::var(PROFILE_ENV_VAR).is_ok() || env::var(ACCESS_KEY_ID_ENV_VAR).is_ok()
+}
+
+// The profiles defined in the shared config and credentials files, sorted by name
+pub fn load_profiles() -> Vec<Profile> {
+    let read = |var: &str, file_name: &str| {
+        aws_file_path(var, file_name)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .unwrap_or_default()
+    };
+    let config = read(CONFIG_FILE_ENV_VAR, DEFAULT_CONFIG_FILE_NAME);
+    let credentials = read(CREDENTIALS_FILE_ENV_VAR, DEFAULT_CREDENTIALS_FILE_NAME);
+    parse_profiles(&config, &credentials)
+}
+
+fn aws_file_path(var: &str, file_name: &str) -> Option<PathBuf> {
+    match env::var(var) {
+        Ok(path) => Some(PathBuf::from(path)),
+        Err(_) => env::home_dir().map(|home| home.join(DEFAULT_AWS_DIR_NAME).join(file_name)),
+    }
+}
+
+// In the config file, the sections other than `default` are written as `[profile name]`,
+// while the credentials file uses `[name]` for all profiles
+fn parse_profiles(config: &str, credentials: &str) -> Vec<Profile> {
+    let mut profiles: BTreeMap<String, Option<String>> = BTreeMap::new();
+
+    let mut current = None;
+    for line in config.lines().map(str::trim) {
+        if let Some(section) = section_name(line) {
+            current = match section.strip_prefix("profile ") {
+                Some(name) => Some(name.trim().to_string()),
+                None if section == "default" => Some(section.to_string()),
+                None => None, // other sections such as `sso-session`
+            };
+            if let Some(name) = &current {
+                profiles.entry(name.clone()).or_default();
+            }
+        } else if let Some(name) = &current {
+            if let Some(("region", value)) = line.split_once('=').map(|(k, v)| (k.trim(), v)) {
+                profiles.insert(name.clone(), Some(value.trim().to_string()));
+            }
+        }
+    }
+
+    for line in credentials.lines().map(str::trim) {
+        if let Some(section) = section_name(line) {
+            profiles.entry(section.to_string()).or_default();
+        }
+    }
+
+    profiles
+        .into_iter()
+        .map(|(name, region)| Profile { name, region })
+        .collect()
+}
+
+fn section_name(line: &str) -> Option<&str> {
+    line.strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profiles() {
+        let config = "
+[default]
+region = us-east-1
+
+[profile dev]
+region=ap-northeast-1
+output = json
+
+[sso-session my-sso]
+sso_region = us-west-2
+
+# [profile commented]
+[profile prod]
+";
+        let credentials = "
+[default]
+aws_access_key_id = AKIA
+
+[local]
+aws_access_key_id = test
+";
+
+        let actual = parse_profiles(config, credentials);
+
+        let profile = |name: &str, region: Option<&str>| Profile {
+            name: name.into(),
+            region: region.map(Into::into),
+        };
+        let expected = vec![
+            profile("default", Some("us-east-1")),
+            profile("dev", Some("ap-northeast-1")),
+            profile("local", None),
+            profile("prod", None),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,371 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+const STATE_PATH_ENV_VAR: &str = "DDV_STATE";
+const DEFAULT_STATE_DIR_NAME: &str = ".ddv";
+const DEFAULT_STATE_FILE_NAME: &str = "state.json";
+const MAX_SIZE_HISTORY_LEN: usize = 30;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    #[serde(default)]
+    pub tables: BTreeMap<String, TableEntry>,
+
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TableEntry {
+    #[serde(default)]
+    pub saved_filters: Vec<SavedFilter>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub saved_queries: Vec<SavedQuery>,
+    // attribute name -> name of the table the attribute value refers to
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub references: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view_preferences: Option<TableViewPreferences>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub size_history: Vec<TableSizeSnapshot>,
+}
+
+// DynamoDB updates the item count and size only about every six hours,
+// so a snapshot is recorded only when the values change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSizeSnapshot {
+    pub recorded_at: DateTime<Utc>,
+    pub item_count: u64,
+    pub total_size_bytes: u64,
+}
+
+// Restored when the table is opened again
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableViewPreferences {
+    // attribute name -> column width
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_widths: BTreeMap<String, usize>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub hidden_columns: BTreeSet<String>,
+    #[serde(default)]
+    pub reversed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub name: String,
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQuery {
+    pub name: String,
+    pub statement: String,
+}
+
+impl State {
+    pub fn load() -> AppResult<State> {
+        let path = match state_path() {
+            Some(path) => path,
+            None => return Ok(State::default()),
+        };
+        if !path.exists() {
+            return Ok(State {
+                path: Some(path),
+                ..Default::default()
+            });
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| AppError::new("failed to read state file", e))?;
+        let mut state: State = serde_json::from_str(&content)
+            .map_err(|e| AppError::new("failed to parse state file", e))?;
+        state.path = Some(path);
+        Ok(state)
+    }
+
+    pub fn save(&self) -> AppResult<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Err(AppError::msg("state file path is not available")),
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| AppError::new("failed to create state directory", e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| AppError::new("failed to serialize state", e))?;
+        std::fs::write(path, content).map_err(|e| AppError::new("failed to write state file", e))
+    }
+
+    // Writes the saved filters, references and view preferences to share them with others.
+    // The size history is left out since it only records what this machine has observed.
+    // Returns the number of exported tables.
+    pub fn export_to(&self, path: &Path) -> AppResult<usize> {
+        let shared = self.shared();
+        let content = serde_json::to_string_pretty(&shared)
+            .map_err(|e| AppError::new("failed to serialize state", e))?;
+        std::fs::write(path, content)
+            .map_err(|e| AppError::new("failed to write exported state file", e))?;
+        Ok(shared.tables.len())
+    }
+
+    // Merges the exported state file into this state. Returns the number of imported tables.
+    pub fn import_from(&mut self, path: &Path) -> AppResult<usize> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AppError::new("failed to read exported state file", e))?;
+        let imported: State = serde_json::from_str(&content)
+            .map_err(|e| AppError::new("failed to parse exported state file", e))?;
+        let count = imported.tables.len();
+        self.merge(imported);
+        Ok(count)
+    }
+
+    fn shared(&self) -> State {
+        let tables = self
+            .tables
+            .iter()
+            .map(|(table_name, entry)| {
+                let entry = TableEntry {
+                    size_history: Vec::new(),
+                    ..entry.clone()
+                };
+                (table_name.clone(), entry)
+            })
+            .filter(|(_, entry)| {
+                !entry.saved_filters.is_empty()
+                    || !entry.saved_queries.is_empty()
+                    || !entry.references.is_empty()
+                    || entry.view_preferences.is_some()
+            })
+            .collect();
+        State { tables, path: None }
+    }
+
+    // The imported values take precedence, and the local ones missing from them are kept
+    fn merge(&mut self, other: State) {
+        for (table_name, entry) in other.tables {
+            for filter in entry.saved_filters {
+                self.add_saved_filter(&table_name, filter);
+            }
+            for query in entry.saved_queries {
+                self.add_saved_query(&table_name, query);
+            }
+            for (attribute_name, target_table_name) in entry.references {
+                self.set_reference_table(&table_name, &attribute_name, &target_table_name);
+            }
+            if let Some(preferences) = entry.view_preferences {
+                self.set_view_preferences(&table_name, preferences);
+            }
+        }
+    }
+
+    pub fn saved_filters(&self, table_name: &str) -> &[SavedFilter] {
+        self.tables
+            .get(table_name)
+            .map(|t| t.saved_filters.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn add_saved_filter(&mut self, table_name: &str, filter: SavedFilter) {
+        let entry = self.tables.entry(table_name.into()).or_default();
+        // overwrite filter with the same name
+        entry.saved_filters.retain(|f| f.name != filter.name);
+        entry.saved_filters.push(filter);
+    }
+
+    pub fn saved_queries(&self, table_name: &str) -> &[SavedQuery] {
+        self.tables
+            .get(table_name)
+            .map(|t| t.saved_queries.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn add_saved_query(&mut self, table_name: &str, query: SavedQuery) {
+        let entry = self.tables.entry(table_name.into()).or_default();
+        // overwrite query with the same name
+        entry.saved_queries.retain(|q| q.name != query.name);
+        entry.saved_queries.push(query);
+    }
+
+    pub fn view_preferences(&self, table_name: &str) -> TableViewPreferences {
+        self.tables
+            .get(table_name)
+            .and_then(|t| t.view_preferences.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_view_preferences(&mut self, table_name: &str, preferences: TableViewPreferences) {
+        let entry = self.tables.entry(table_name.into()).or_default();
+        entry.view_preferences = Some(preferences);
+    }
+
+    pub fn size_history(&self, table_name: &str) -> &[TableSizeSnapshot] {
+        self.tables
+            .get(table_name)
+            .map(|t| t.size_history.as_slice())
+            .unwrap_or_default()
+    }
+
+    // Returns true if the snapshot is recorded
+    pub fn record_size_snapshot(&mut self, table_name: &str, snapshot: TableSizeSnapshot) -> bool {
+        let entry = self.tables.entry(table_name.into()).or_default();
+        if let Some(last) = entry.size_history.last() {
+            if last.item_count == snapshot.item_count
+                && last.total_size_bytes == snapshot.total_size_bytes
+            {
+                return false;
+            }
+        }
+        entry.size_history.push(snapshot);
+        if entry.size_history.len() > MAX_SIZE_HISTORY_LEN {
+            let excess = entry.size_history.len() - MAX_SIZE_HISTORY_LEN;
+            entry.size_history.drain(..excess);
+        }
+        true
+    }
+
+    pub fn reference_table(&self, table_name: &str, attribute_name: &str) -> Option<&str> {
+        self.tables
+            .get(table_name)
+            .and_then(|t| t.references.get(attribute_name))
+            .map(|s| s.as_str())
+    }
+
+    pub fn set_reference_table(
+        &mut self,
+        table_name: &str,
+        attribute_name: &str,
+        target_table_name: &str,
+    ) {
+        let entry = self.tables.entry(table_name.into()).or_default();
+        entry
+            .references
+            .insert(attribute_name.into(), target_table_name.into());
+    }
+}
+
+fn state_path() -> Option<PathBuf> {
+    match env::var(STATE_PATH_ENV_VAR) {
+        Ok(path) => Some(PathBuf::from(path)),
+        Err(_) => env::home_dir().map(|home| {
+            home.join(DEFAULT_STATE_DIR_NAME)
+                .join(DEFAULT_STATE_FILE_NAME)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_preferences() {
+        let mut state = State::default();
+        assert_eq!(
+            state.view_preferences("foo"),
+            TableViewPreferences::default()
+        );
+
+        let preferences = TableViewPreferences {
+            column_widths: BTreeMap::from([("name".into(), 12)]),
+            hidden_columns: BTreeSet::from(["payload".into()]),
+            reversed: true,
+            filter: Some("bar".into()),
+        };
+        state.set_view_preferences("foo", preferences.clone());
+
+        let json = serde_json::to_string(&state).unwrap();
+        let state: State = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.view_preferences("foo"), preferences);
+    }
+
+    #[test]
+    fn test_record_size_snapshot() {
+        let snapshot = |item_count, total_size_bytes| TableSizeSnapshot {
+            recorded_at: DateTime::from_timestamp(item_count as i64, 0).unwrap(),
+            item_count,
+            total_size_bytes,
+        };
+        let mut state = State::default();
+        assert!(state.size_history("foo").is_empty());
+
+        assert!(state.record_size_snapshot("foo", snapshot(1, 100)));
+        assert!(!state.record_size_snapshot("foo", snapshot(1, 100)));
+        assert!(state.record_size_snapshot("foo", snapshot(2, 100)));
+        assert_eq!(state.size_history("foo").len(), 2);
+
+        for i in 3..=(MAX_SIZE_HISTORY_LEN as u64 + 5) {
+            state.record_size_snapshot("foo", snapshot(i, 100));
+        }
+        let history = state.size_history("foo");
+        assert_eq!(history.len(), MAX_SIZE_HISTORY_LEN);
+        assert_eq!(history[0].item_count, 6);
+    }
+
+    #[test]
+    fn test_export_and_merge() {
+        let filter = |name: &str, query: &str| SavedFilter {
+            name: name.into(),
+            query: query.into(),
+        };
+        let saved_query = |name: &str, statement: &str| SavedQuery {
+            name: name.into(),
+            statement: statement.into(),
+        };
+        let mut exporter = State::default();
+        exporter.add_saved_filter("foo", filter("active", "status = active"));
+        exporter.add_saved_filter("foo", filter("recent", "year = 2026"));
+        exporter.set_reference_table("foo", "user_id", "users");
+        exporter.add_saved_query("baz", saved_query("all", "SELECT * FROM baz"));
+        exporter.record_size_snapshot(
+            "bar",
+            TableSizeSnapshot {
+                recorded_at: DateTime::from_timestamp(0, 0).unwrap(),
+                item_count: 1,
+                total_size_bytes: 100,
+            },
+        );
+
+        let shared = exporter.shared();
+        assert_eq!(shared.tables.len(), 2);
+        assert!(shared.size_history("foo").is_empty());
+
+        let mut importer = State::default();
+        importer.add_saved_filter("foo", filter("active", "status = enabled"));
+        importer.add_saved_filter("foo", filter("mine", "owner = me"));
+        importer.set_reference_table("foo", "order_id", "orders");
+        importer.add_saved_query("baz", saved_query("all", "SELECT id FROM baz"));
+        importer.merge(shared);
+
+        let filters: Vec<(&str, &str)> = importer
+            .saved_filters("foo")
+            .iter()
+            .map(|f| (f.name.as_str(), f.query.as_str()))
+            .collect();
+        assert_eq!(
+            filters,
+            vec![
+                ("mine", "owner = me"),
+                ("active", "status = active"),
+                ("recent", "year = 2026"),
+            ]
+        );
+        assert_eq!(importer.reference_table("foo", "user_id"), Some("users"));
+        assert_eq!(importer.reference_table("foo", "order_id"), Some("orders"));
+        assert!(!importer.tables.contains_key("bar"));
+
+        let queries = importer.saved_queries("baz");
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].statement, "SELECT * FROM baz");
+    }
+}
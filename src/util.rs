@@ -1,5 +1,11 @@
+use std::{env, fs, io::Read, path::PathBuf, time::Duration};
+
 use arboard::Clipboard;
 use base64::Engine;
+use chrono::{DateTime, FixedOffset, TimeZone};
+use tui_input::{Input, InputRequest};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::error::{AppError, AppResult};
 
@@ -7,8 +13,531 @@ pub fn to_base64_str(bytes: &[u8]) -> String {
     base64::engine::general_purpose::STANDARD.encode(bytes)
 }
 
+pub fn from_base64_str(s: &str) -> AppResult<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(s)
+        .map_err(|e| AppError::new("failed to decode base64", e))
+}
+
+// Decompressed data larger than this is truncated, to avoid expanding a huge blob in memory
+const DECOMPRESS_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    // Detect the compression format from the magic number at the beginning of the data
+    pub fn detect(bytes: &[u8]) -> Option<Compression> {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Some(Compression::Gzip)
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+        }
+    }
+}
+
+// Returns the decompressed data and whether it was truncated
+pub fn decompress(bytes: &[u8], compression: Compression) -> AppResult<(Vec<u8>, bool)> {
+    let mut buf = Vec::new();
+    let limit = DECOMPRESS_MAX_BYTES + 1;
+    let result = match compression {
+        Compression::Gzip => flate2::read::MultiGzDecoder::new(bytes)
+            .take(limit)
+            .read_to_end(&mut buf),
+        Compression::Zstd => ruzstd::decoding::StreamingDecoder::new(bytes)
+            .map_err(|e| AppError::new("failed to decompress zstd data", e))?
+            .take(limit)
+            .read_to_end(&mut buf),
+    };
+    result.map_err(|e| {
+        AppError::new(
+            format!("failed to decompress {} data", compression.as_str()),
+            e,
+        )
+    })?;
+    let truncated = buf.len() as u64 > DECOMPRESS_MAX_BYTES;
+    buf.truncate(DECOMPRESS_MAX_BYTES as usize);
+    Ok((buf, truncated))
+}
+
+// The timezone in which datetimes are displayed
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DisplayTimeZone {
+    #[default]
+    Local,
+    Utc,
+    Named(chrono_tz::Tz),
+}
+
+impl DisplayTimeZone {
+    // Accepts "local", "UTC" or an IANA timezone name such as "Asia/Tokyo"
+    pub fn parse(s: &str) -> AppResult<DisplayTimeZone> {
+        if s.eq_ignore_ascii_case("local") {
+            return Ok(DisplayTimeZone::Local);
+        }
+        if s.eq_ignore_ascii_case("utc") {
+            return Ok(DisplayTimeZone::Utc);
+        }
+        s.parse()
+            .map(DisplayTimeZone::Named)
+            .map_err(|_| AppError::msg(format!("invalid timezone: {s}")))
+    }
+
+    pub fn convert<Tz: TimeZone>(&self, dt: &DateTime<Tz>) -> DateTime<FixedOffset> {
+        match self {
+            DisplayTimeZone::Local => dt.with_timezone(&chrono::Local).fixed_offset(),
+            DisplayTimeZone::Utc => dt.to_utc().fixed_offset(),
+            DisplayTimeZone::Named(tz) => dt.with_timezone(tz).fixed_offset(),
+        }
+    }
+}
+
+// Parse "profile@region" into the profile and the region, either of which can be omitted
+pub fn parse_environment(s: &str) -> (Option<String>, Option<String>) {
+    let non_empty = |s: &str| {
+        let s = s.trim();
+        (!s.is_empty()).then(|| s.to_string())
+    };
+    match s.split_once('@') {
+        Some((profile, region)) => (non_empty(profile), non_empty(region)),
+        None => (non_empty(s), None),
+    }
+}
+
+pub fn format_count(n: u64) -> String {
+    match n {
+        0..1_000 => n.to_string(),
+        // values that round up to 1000.0k are shown as 1.0M
+        1_000..999_950 => format!("{:.1}k", n as f64 / 1_000.0),
+        _ => format!("{:.1}M", n as f64 / 1_000_000.0),
+    }
+}
+
+// Format with thousands separators like "56,012"
+pub fn format_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut s = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            s.push(',');
+        }
+        s.push(c);
+    }
+    s
+}
+
+// Shown while a filter hides some of the rows, empty otherwise
+pub fn filtered_count_string(shown: usize, total: usize) -> String {
+    if shown == total {
+        return "".into();
+    }
+    format!(
+        " showing {} of {} (filter active) ",
+        format_thousands(shown),
+        format_thousands(total)
+    )
+}
+
+// Parse durations such as "30m", "12h", "7d" or "1d12h"
+pub fn parse_duration(s: &str) -> AppResult<Duration> {
+    let invalid = || AppError::msg(format!("invalid duration: {s} (e.g. 30m, 12h, 7d)"));
+    let mut secs: u64 = 0;
+    let mut num = String::new();
+    for c in s.trim().chars() {
+        if c.is_ascii_digit() {
+            num.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        let n: u64 = num.parse().map_err(|_| invalid())?;
+        secs = n
+            .checked_mul(unit)
+            .and_then(|n| secs.checked_add(n))
+            .ok_or_else(invalid)?;
+        num.clear();
+    }
+    if !num.is_empty() || secs == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+// e.g. "expires in 3d 4h", "expired 5m 6s ago"
+pub fn format_ttl(expires_at: i64, now: i64) -> String {
+    let remaining = expires_at - now;
+    let secs = remaining.unsigned_abs();
+    let units = [
+        (secs / 86400, "d"),
+        (secs / 3600 % 24, "h"),
+        (secs / 60 % 60, "m"),
+        (secs % 60, "s"),
+    ];
+    // show the two most significant units
+    let start = units
+        .iter()
+        .position(|(n, _)| *n > 0)
+        .unwrap_or(units.len() - 1);
+    let s = units[start..]
+        .iter()
+        .take(2)
+        .map(|(n, unit)| format!("{n}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if remaining > 0 {
+        format!("expires in {s}")
+    } else {
+        format!("expired {s} ago")
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    if d.as_secs() == 0 {
+        format!("{}ms", d.as_millis())
+    } else {
+        format!("{:.1}s", d.as_secs_f64())
+    }
+}
+
+// Measure the width in the same way as the terminal buffer renders it (per grapheme cluster)
+pub fn text_width(s: &str) -> usize {
+    s.graphemes(true).map(UnicodeWidthStr::width).sum()
+}
+
+// Truncate without splitting grapheme clusters so that the result never exceeds max_width
+pub fn truncate_to_width(s: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    for (i, g) in s.grapheme_indices(true) {
+        width += g.width();
+        if width > max_width {
+            return &s[..i];
+        }
+    }
+    s
+}
+
 pub fn copy_to_clipboard(text: &str) -> AppResult<()> {
     Clipboard::new()
         .and_then(|mut c| c.set_text(text))
         .map_err(|e| AppError::new("failed to copy to clipboard", e))
 }
+
+pub fn expand_home_path(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => match env::home_dir() {
+            Some(home) => home.join(rest),
+            None => PathBuf::from(path),
+        },
+        None => PathBuf::from(path),
+    }
+}
+
+pub fn write_to_file(path: &str, content: &str) -> AppResult<PathBuf> {
+    let path = expand_home_path(path);
+    fs::write(&path, content).map_err(|e| AppError::new("failed to write file", e))?;
+    Ok(path)
+}
+
+pub fn open_in_browser(url: &str) -> AppResult<()> {
+    open::that(url).map_err(|e| AppError::new("failed to open browser", e))
+}
+
+// Inputs are single line, so line breaks in the pasted text are replaced with spaces
+pub fn paste_to_input(input: &mut Input, text: &str) {
+    let text = text.trim_end_matches(['\r', '\n']);
+    let text = text.replace("\r\n", " ").replace(['\r', '\n'], " ");
+    for c in text.chars() {
+        input.handle(InputRequest::InsertChar(c));
+    }
+}
+
+const LINK_PREFIXES: [&str; 3] = ["arn:", "https://", "http://"];
+
+// Returns the byte ranges of ARNs and URLs in the string
+pub fn find_links(s: &str) -> Vec<(usize, usize)> {
+    let mut links = Vec::new();
+    let mut pos = 0;
+    while pos < s.len() {
+        let rest = &s[pos..];
+        let Some((start, prefix)) = LINK_PREFIXES
+            .iter()
+            .filter_map(|p| rest.find(p).map(|i| (pos + i, p)))
+            .min_by_key(|(i, _)| *i)
+        else {
+            break;
+        };
+        let end = s[start..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '<' | '>' | ','))
+            .map(|i| start + i)
+            .unwrap_or(s.len());
+        let end = start
+            + s[start..end]
+                .trim_end_matches(['.', ')', ']', '}', ';'])
+                .len();
+
+        let preceded_by_word = s[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric());
+        if !preceded_by_word && end > start + prefix.len() {
+            links.push((start, end));
+        }
+        pos = end.max(start + prefix.len());
+    }
+    links
+}
+
+// ARNs are opened with the console link that resolves the resource from the ARN
+pub fn link_url(link: &str) -> String {
+    if !link.starts_with("arn:") {
+        return link.into();
+    }
+    let domain = match link.split(':').nth(1) {
+        Some("aws-cn") => "console.amazonaws.cn",
+        Some("aws-us-gov") => "console.amazonaws-us-gov.com",
+        _ => "console.aws.amazon.com",
+    };
+    format!("https://{domain}/go/view?arn={link}")
+}
+
+pub fn console_table_url(region: &str, table_name: &str) -> String {
+    let domain = if region.starts_with("cn-") {
+        format!("{region}.console.amazonaws.cn")
+    } else if region.starts_with("us-gov-") {
+        format!("{region}.console.amazonaws-us-gov.com")
+    } else {
+        format!("{region}.console.aws.amazon.com")
+    };
+    format!("https://{domain}/dynamodbv2/home?region={region}#table?name={table_name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("prod", Some("prod"), None)]
+    #[case("prod@us-west-2", Some("prod"), Some("us-west-2"))]
+    #[case("@eu-west-1", None, Some("eu-west-1"))]
+    #[case(" ", None, None)]
+    fn test_parse_environment(
+        #[case] s: &str,
+        #[case] profile: Option<&str>,
+        #[case] region: Option<&str>,
+    ) {
+        let (actual_profile, actual_region) = parse_environment(s);
+        assert_eq!(actual_profile.as_deref(), profile);
+        assert_eq!(actual_region.as_deref(), region);
+    }
+
+    #[test]
+    fn test_display_time_zone() {
+        let dt = chrono::Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+        let tz = DisplayTimeZone::parse("UTC").ok().unwrap();
+        assert_eq!(tz.convert(&dt).to_string(), "2024-01-02 03:04:05 +00:00");
+
+        let tz = DisplayTimeZone::parse("Asia/Tokyo").ok().unwrap();
+        assert_eq!(tz.convert(&dt).to_string(), "2024-01-02 12:04:05 +09:00");
+
+        let tz = DisplayTimeZone::parse("local").ok().unwrap();
+        assert_eq!(tz, DisplayTimeZone::Local);
+
+        assert!(DisplayTimeZone::parse("Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn test_decompress() {
+        let data = br#"{"a":[1,2,3]}"#;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        let gzip = encoder.finish().unwrap();
+        assert_eq!(Compression::detect(&gzip), Some(Compression::Gzip));
+        let (actual, truncated) = decompress(&gzip, Compression::Gzip).ok().unwrap();
+        assert_eq!(actual, data);
+        assert!(!truncated);
+
+        let zstd = ruzstd::encoding::compress_to_vec(
+            &data[..],
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+        assert_eq!(Compression::detect(&zstd), Some(Compression::Zstd));
+        let (actual, truncated) = decompress(&zstd, Compression::Zstd).ok().unwrap();
+        assert_eq!(actual, data);
+        assert!(!truncated);
+
+        assert_eq!(Compression::detect(data), None);
+        assert!(decompress(&gzip[..4], Compression::Gzip).is_err());
+    }
+
+    #[rstest]
+    #[case(
+        "us-east-1",
+        "https://us-east-1.console.aws.amazon.com/dynamodbv2/home?region=us-east-1#table?name=foo"
+    )]
+    #[case(
+        "cn-north-1",
+        "https://cn-north-1.console.amazonaws.cn/dynamodbv2/home?region=cn-north-1#table?name=foo"
+    )]
+    #[case(
+        "us-gov-west-1",
+        "https://us-gov-west-1.console.amazonaws-us-gov.com/dynamodbv2/home?region=us-gov-west-1#table?name=foo"
+    )]
+    fn test_console_table_url(#[case] region: &str, #[case] expected: &str) {
+        assert_eq!(console_table_url(region, "foo"), expected);
+    }
+
+    #[rstest]
+    #[case("foo", "foo")]
+    #[case("foo\n", "foo")]
+    #[case("SELECT *\r\nFROM foo\nWHERE", "SELECT * FROM foo WHERE")]
+    fn test_paste_to_input(#[case] text: &str, #[case] expected: &str) {
+        let mut input = Input::default();
+        paste_to_input(&mut input, text);
+        assert_eq!(input.value(), expected);
+        assert_eq!(input.cursor(), expected.chars().count());
+    }
+
+    #[rstest]
+    #[case("no links here", vec![])]
+    #[case("arn:aws:dynamodb:us-east-1:123456789012:table/foo", vec!["arn:aws:dynamodb:us-east-1:123456789012:table/foo"])]
+    #[case(r#""TableArn": "arn:aws:dynamodb:us-east-1:123456789012:table/foo","#, vec!["arn:aws:dynamodb:us-east-1:123456789012:table/foo"])]
+    #[case("see https://example.com/a?b=c. and (http://example.com)", vec!["https://example.com/a?b=c", "http://example.com"])]
+    #[case("learn:more https://", vec![])]
+    fn test_find_links(#[case] s: &str, #[case] expected: Vec<&str>) {
+        let actual: Vec<&str> = find_links(s).into_iter().map(|(a, b)| &s[a..b]).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("https://example.com", "https://example.com")]
+    #[case(
+        "arn:aws:dynamodb:us-east-1:123456789012:table/foo",
+        "https://console.aws.amazon.com/go/view?arn=arn:aws:dynamodb:us-east-1:123456789012:table/foo"
+    )]
+    #[case(
+        "arn:aws-cn:dynamodb:cn-north-1:123456789012:table/foo",
+        "https://console.amazonaws.cn/go/view?arn=arn:aws-cn:dynamodb:cn-north-1:123456789012:table/foo"
+    )]
+    fn test_link_url(#[case] link: &str, #[case] expected: &str) {
+        assert_eq!(link_url(link), expected);
+    }
+
+    #[rstest]
+    #[case("30s", Some(30))]
+    #[case("30m", Some(30 * 60))]
+    #[case("12h", Some(12 * 3600))]
+    #[case("1d12h", Some(86400 + 12 * 3600))]
+    #[case("2w", Some(14 * 86400))]
+    #[case("", None)]
+    #[case("10", None)]
+    #[case("h", None)]
+    #[case("3x", None)]
+    #[case("0d", None)]
+    fn test_parse_duration(#[case] s: &str, #[case] expected: Option<u64>) {
+        let actual = parse_duration(s).ok().map(|d| d.as_secs());
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(1_000 + 3 * 86400 + 4 * 3600 + 5, "expires in 3d 4h")]
+    #[case(1_000 + 3600 + 60, "expires in 1h 1m")]
+    #[case(1_000 + 30, "expires in 30s")]
+    #[case(1_000, "expired 0s ago")]
+    #[case(1_000 - 5 * 60 - 6, "expired 5m 6s ago")]
+    fn test_format_ttl(#[case] expires_at: i64, #[case] expected: &str) {
+        assert_eq!(format_ttl(expires_at, 1_000), expected);
+    }
+
+    #[rstest]
+    #[case(999, "999")]
+    #[case(1_234, "1.2k")]
+    #[case(999_949, "999.9k")]
+    #[case(999_950, "1.0M")]
+    #[case(999_999, "1.0M")]
+    #[case(2_500_000, "2.5M")]
+    fn test_format_count(#[case] n: u64, #[case] expected: &str) {
+        assert_eq!(format_count(n), expected);
+    }
+
+    #[rstest]
+    #[case(0, "0")]
+    #[case(999, "999")]
+    #[case(1_000, "1,000")]
+    #[case(56_012, "56,012")]
+    #[case(1_234_567, "1,234,567")]
+    fn test_format_thousands(#[case] n: usize, #[case] expected: &str) {
+        assert_eq!(format_thousands(n), expected);
+    }
+
+    #[rstest]
+    #[case(10, 10, "")]
+    #[case(134, 56_012, " showing 134 of 56,012 (filter active) ")]
+    fn test_filtered_count_string(
+        #[case] shown: usize,
+        #[case] total: usize,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(filtered_count_string(shown, total), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::from_millis(120), "120ms")]
+    #[case(Duration::from_millis(3456), "3.5s")]
+    fn test_format_duration(#[case] d: Duration, #[case] expected: &str) {
+        assert_eq!(format_duration(d), expected);
+    }
+
+    #[rstest]
+    #[case("abc", 3)]
+    #[case("あいう", 6)]
+    #[case("👍", 2)]
+    #[case("👨‍👩‍👧", 2)]
+    #[case("e\u{301}", 1)]
+    fn test_text_width(#[case] s: &str, #[case] expected: usize) {
+        assert_eq!(text_width(s), expected);
+    }
+
+    #[rstest]
+    #[case("abcdef", 3, "abc")]
+    #[case("あいう", 3, "あ")]
+    #[case("あいう", 4, "あい")]
+    #[case("a👨‍👩‍👧b", 2, "a")]
+    #[case("a👨‍👩‍👧b", 3, "a👨‍👩‍👧")]
+    #[case("abc", 10, "abc")]
+    fn test_truncate_to_width(#[case] s: &str, #[case] max_width: usize, #[case] expected: &str) {
+        assert_eq!(truncate_to_width(s, max_width), expected);
+    }
+
+    #[test]
+    fn test_write_to_file() {
+        let path = env::temp_dir().join("ddv-test-write-to-file.json");
+        let actual = write_to_file(path.to_str().unwrap(), "{}").ok();
+        assert_eq!(actual, Some(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,193 @@
+use std::fs;
+
+use crate::{
+    data::{
+        join_path_key, to_key_attributes, Item, KeySchemaType, PlainJsonItem, TableDescription,
+    },
+    error::{AppError, AppResult},
+    util::expand_home_path,
+};
+
+// Validates items in their plain JSON representation (the same as the item view shows)
+pub struct ItemSchema {
+    path: String,
+    validator: jsonschema::Validator,
+}
+
+impl ItemSchema {
+    pub fn load(path: &str) -> AppResult<ItemSchema> {
+        let content = fs::read_to_string(expand_home_path(path))
+            .map_err(|e| AppError::new(format!("failed to read JSON Schema file {path}"), e))?;
+        let schema: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| AppError::new(format!("failed to parse JSON Schema file {path}"), e))?;
+        ItemSchema::new(path, &schema)
+    }
+
+    fn new(path: &str, schema: &serde_json::Value) -> AppResult<ItemSchema> {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| AppError::msg(format!("invalid JSON Schema {path}: {}", e.masked())))?;
+        Ok(ItemSchema {
+            path: path.into(),
+            validator,
+        })
+    }
+
+    pub fn validate(&self, item: &Item, schema: &KeySchemaType) -> Vec<SchemaViolation> {
+        let json = serde_json::to_value(PlainJsonItem::new(item, schema)).unwrap();
+        self.validator
+            .iter_errors(&json)
+            .map(|e| SchemaViolation {
+                path: pointer_to_path(&json, e.instance_path().as_str()),
+                message: e.masked().to_string(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    pub path: String,
+    pub message: String,
+}
+
+pub struct InvalidItem {
+    pub key: serde_json::Value,
+    pub violations: Vec<SchemaViolation>,
+}
+
+pub struct ValidationReport {
+    pub table_name: String,
+    pub schema_path: String,
+    pub total_items: usize,
+    pub invalid_items: Vec<InvalidItem>,
+}
+
+impl ValidationReport {
+    pub fn new(desc: &TableDescription, items: &[Item], schema: &ItemSchema) -> ValidationReport {
+        let key_schema = &desc.key_schema_type;
+        let invalid_items = items
+            .iter()
+            .filter_map(|item| {
+                let violations = schema.validate(item, key_schema);
+                if violations.is_empty() {
+                    return None;
+                }
+                let key = Item {
+                    attributes: to_key_attributes(item, key_schema)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect(),
+                };
+                let key = serde_json::to_value(PlainJsonItem::new(&key, key_schema)).unwrap();
+                Some(InvalidItem { key, violations })
+            })
+            .collect();
+        ValidationReport {
+            table_name: desc.table_name.clone(),
+            schema_path: schema.path.clone(),
+            total_items: items.len(),
+            invalid_items,
+        }
+    }
+}
+
+// Convert a JSON Pointer (e.g. /a/0) into a jq-style path (e.g. .a[0]) like `CopyPath` does
+fn pointer_to_path(json: &serde_json::Value, pointer: &str) -> String {
+    let mut path = String::new();
+    let mut current = Some(json);
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        match current {
+            Some(serde_json::Value::Array(values)) if token.parse::<usize>().is_ok() => {
+                let i: usize = token.parse().unwrap();
+                path = format!("{path}[{i}]");
+                current = values.get(i);
+            }
+            _ => {
+                path = join_path_key(&path, &token);
+                current = current.and_then(|v| v.get(&token));
+            }
+        }
+    }
+    if path.is_empty() {
+        path.push('.');
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::data::Attribute;
+
+    use super::*;
+
+    fn item(attributes: Vec<(&str, Attribute)>) -> Item {
+        Item {
+            attributes: attributes
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = ItemSchema::new(
+            "schema.json",
+            &serde_json::json!({
+                "type": "object",
+                "required": ["id", "name"],
+                "properties": {
+                    "id": { "type": "string" },
+                    "age": { "type": "integer", "minimum": 0 },
+                    "tags": { "type": "array", "items": { "type": "string" } }
+                }
+            }),
+        )
+        .ok()
+        .unwrap();
+        let key_schema = KeySchemaType::Hash("id".into());
+
+        let valid = item(vec![
+            ("id", Attribute::S("a".into())),
+            ("name", Attribute::S("x".into())),
+            ("age", Attribute::N(20.into())),
+        ]);
+        assert_eq!(schema.validate(&valid, &key_schema), vec![]);
+
+        let invalid = item(vec![
+            ("id", Attribute::S("b".into())),
+            ("age", Attribute::N((-1).into())),
+            (
+                "tags",
+                Attribute::L(vec![Attribute::S("t".into()), Attribute::BOOL(true)]),
+            ),
+        ]);
+        let mut actual: Vec<String> = schema
+            .validate(&invalid, &key_schema)
+            .into_iter()
+            .map(|v| v.path)
+            .collect();
+        actual.sort();
+        assert_eq!(actual, vec![".", ".age", ".tags[1]"]);
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        let actual = ItemSchema::new("schema.json", &serde_json::json!({ "type": "foo" }));
+        assert!(actual.is_err());
+    }
+
+    #[rstest]
+    #[case("", ".")]
+    #[case("/a", ".a")]
+    #[case("/a/0/b", ".a[0].b")]
+    #[case("/m/0", ".m[\"0\"]")]
+    #[case("/a~1b", ".[\"a/b\"]")]
+    fn test_pointer_to_path(#[case] pointer: &str, #[case] expected: &str) {
+        let json = serde_json::json!({ "a": [{ "b": 1 }], "m": { "0": 1 } });
+        assert_eq!(pointer_to_path(&json, pointer), expected);
+    }
+}
@@ -1,11 +1,26 @@
 mod views;
 
+mod account_limits;
+mod audit_log;
 mod common;
+mod connection_diagnostics;
+mod filter_builder;
 mod help;
 mod init;
 mod item;
+mod items_comparison;
+mod multi_region_table_list;
+mod partiql;
+mod query_builder;
+mod side_by_side;
 mod table;
+mod table_diff;
 mod table_insight;
 mod table_list;
+mod validation_report;
 
+#[cfg(test)]
+pub(crate) mod snapshot;
+
+pub use side_by_side::SideBySidePane;
 pub use views::*;
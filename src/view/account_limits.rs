@@ -0,0 +1,272 @@
+use ratatui::{
+    crossterm::event::KeyEvent, layout::Rect, style::Stylize, text::Line, widgets::Block, Frame,
+};
+
+use crate::{
+    color::ColorTheme,
+    data::AccountLimits,
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    view::common::raw_string_from_scroll_lines_state,
+    widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState},
+};
+
+pub struct AccountLimitsView {
+    helps: AccountLimitsViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    scroll_lines_state: ScrollLinesState,
+}
+
+struct AccountLimitsViewHelps {
+    limits: Vec<Spans>,
+    limits_short: Vec<SpansWithPriority>,
+}
+
+impl AccountLimitsView {
+    pub fn new(
+        account_limits: AccountLimits,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let lines = get_limits_lines(&account_limits);
+        let scroll_lines_state =
+            ScrollLinesState::new(lines, ScrollLinesOptions::new(false, false));
+        let helps = AccountLimitsViewHelps::new(mapper, theme);
+
+        AccountLimitsView {
+            helps,
+            theme,
+            tx,
+
+            scroll_lines_state,
+        }
+    }
+}
+
+impl AccountLimitsView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::Close => {
+                self.tx.send(AppEvent::BackToBeforeView);
+            }
+            UserEvent::Down => {
+                self.scroll_lines_state.scroll_forward();
+            }
+            UserEvent::Up => {
+                self.scroll_lines_state.scroll_backward();
+            }
+            UserEvent::PageDown => {
+                self.scroll_lines_state.scroll_page_forward();
+            }
+            UserEvent::PageUp => {
+                self.scroll_lines_state.scroll_page_backward();
+            }
+            UserEvent::GoToTop => {
+                self.scroll_lines_state.scroll_to_top();
+            }
+            UserEvent::GoToBottom => {
+                self.scroll_lines_state.scroll_to_end();
+            }
+            UserEvent::Right => {
+                self.scroll_lines_state.scroll_right();
+            }
+            UserEvent::Left => {
+                self.scroll_lines_state.scroll_left();
+            }
+            UserEvent::ToggleWrap => {
+                self.scroll_lines_state.toggle_wrap();
+            }
+            UserEvent::ToggleNumber => {
+                self.scroll_lines_state.toggle_number();
+            }
+            UserEvent::SaveToFile => {
+                self.save_to_file();
+            }
+            UserEvent::Help => {
+                self.open_help();
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let title = " Account Limits ";
+        let scroll = ScrollLines::default()
+            .block(
+                Block::bordered()
+                    .title_top(Line::from(title).left_aligned())
+                    .fg(self.theme.fg)
+                    .bg(self.theme.bg),
+            )
+            .theme(&self.theme);
+
+        f.render_stateful_widget(scroll, area, &mut self.scroll_lines_state);
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        &self.helps.limits_short
+    }
+}
+
+impl AccountLimitsViewHelps {
+    fn new(mapper: &UserEventMapper, theme: ColorTheme) -> Self {
+        let limits = build_helps(mapper, theme);
+        let limits_short = build_short_helps(mapper);
+        Self {
+            limits,
+            limits_short,
+        }
+    }
+}
+
+fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> Vec<Spans> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Close, "Back to table list"),
+        BuildHelpsItem::new(UserEvent::Down, "Scroll down"),
+        BuildHelpsItem::new(UserEvent::Up, "Scroll up"),
+        BuildHelpsItem::new(UserEvent::Right, "Scroll right"),
+        BuildHelpsItem::new(UserEvent::Left, "Scroll left"),
+        BuildHelpsItem::new(UserEvent::PageDown, "Scroll page down"),
+        BuildHelpsItem::new(UserEvent::PageUp, "Scroll page up"),
+        BuildHelpsItem::new(UserEvent::GoToTop, "Scroll to top"),
+        BuildHelpsItem::new(UserEvent::GoToBottom, "Scroll to bottom"),
+        BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
+        BuildHelpsItem::new(UserEvent::ToggleNumber, "Toggle number"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save limits report to file"),
+    ];
+    build_help_spans(helps, mapper, theme)
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Close, "Back", 1),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Scroll", 2),
+        BuildShortHelpsItem::group(vec![UserEvent::GoToTop, UserEvent::GoToBottom], "Top/Bottom", 3),
+        BuildShortHelpsItem::group(vec![UserEvent::ToggleWrap, UserEvent::ToggleNumber], "Toggle wrap/number", 4),
+        BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+impl AccountLimitsView {
+    fn save_to_file(&self) {
+        let content = raw_string_from_scroll_lines_state(&self.scroll_lines_state);
+        self.tx
+            .send(AppEvent::SaveToFile("limits report".into(), content));
+    }
+
+    fn open_help(&self) {
+        self.tx.send(AppEvent::OpenHelp(self.helps.limits.clone()))
+    }
+}
+
+fn get_limits_lines(limits: &AccountLimits) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from("Account (provisioned / limit):".bold()),
+        Line::raw(""),
+        Line::from(vec![
+            "   Read Capacity Units : ".bold(),
+            format_usage(
+                limits.total_read_capacity_units(),
+                limits.account_max_read_capacity_units,
+            )
+            .into(),
+        ]),
+        Line::from(vec![
+            "  Write Capacity Units : ".bold(),
+            format_usage(
+                limits.total_write_capacity_units(),
+                limits.account_max_write_capacity_units,
+            )
+            .into(),
+        ]),
+        Line::raw(""),
+        Line::from("Per table limit:".bold()),
+        Line::raw(""),
+        Line::from(vec![
+            "   Read Capacity Units : ".bold(),
+            limits.table_max_read_capacity_units.to_string().into(),
+        ]),
+        Line::from(vec![
+            "  Write Capacity Units : ".bold(),
+            limits.table_max_write_capacity_units.to_string().into(),
+        ]),
+        Line::raw(""),
+    ];
+
+    // on-demand tables do not count toward the provisioned totals
+    let mut capacities: Vec<_> = limits
+        .table_capacities
+        .iter()
+        .filter(|c| c.read_capacity_units > 0 || c.write_capacity_units > 0)
+        .collect();
+    capacities.sort_by(|a, b| {
+        let a_total = a.read_capacity_units + a.write_capacity_units;
+        let b_total = b.read_capacity_units + b.write_capacity_units;
+        b_total.cmp(&a_total).then(a.table_name.cmp(&b.table_name))
+    });
+
+    lines.push(Line::from(
+        format!(
+            "Provisioned tables ({} of {}):",
+            capacities.len(),
+            limits.table_capacities.len()
+        )
+        .bold(),
+    ));
+    lines.push(Line::raw(""));
+
+    if let Some(max_width) = capacities.iter().map(|c| c.table_name.len()).max() {
+        for capacity in capacities {
+            lines.push(Line::from(vec![
+                "  ".into(),
+                format!("{:>max_width$}", capacity.table_name).bold(),
+                " : ".bold(),
+                format!(
+                    "Read {} / Write {}",
+                    format_usage(
+                        capacity.read_capacity_units,
+                        limits.table_max_read_capacity_units
+                    ),
+                    format_usage(
+                        capacity.write_capacity_units,
+                        limits.table_max_write_capacity_units
+                    ),
+                )
+                .into(),
+            ]));
+        }
+    }
+
+    lines
+}
+
+fn format_usage(n: u64, limit: u64) -> String {
+    if limit == 0 {
+        return format!("{n} / -");
+    }
+    let ratio = (n as f64 / limit as f64) * 100.0;
+    format!("{n} / {limit} ({ratio:.1}%)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_usage() {
+        assert_eq!(format_usage(1200, 80000), "1200 / 80000 (1.5%)");
+        assert_eq!(format_usage(0, 40000), "0 / 40000 (0.0%)");
+        assert_eq!(format_usage(10, 0), "10 / -");
+    }
+}
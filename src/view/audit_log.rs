@@ -0,0 +1,360 @@
+use itsuki::zero_indexed_enum;
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, ListItem},
+    Frame,
+};
+
+use crate::{
+    audit::AuditEntry,
+    color::ColorTheme,
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    util::DisplayTimeZone,
+    view::common::to_highlighted_lines,
+    widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState, ScrollList, ScrollListState},
+};
+
+const LIST_WIDTH: u16 = 50;
+
+pub struct AuditLogView {
+    title: &'static str,
+    entries: Vec<AuditEntry>,
+    timezone: DisplayTimeZone,
+
+    helps: AuditLogViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    list_state: ScrollListState,
+    scroll_lines_state: ScrollLinesState,
+    focused: Focused,
+}
+
+struct AuditLogViewHelps {
+    list: Vec<Spans>,
+    detail: Vec<Spans>,
+    list_short: Vec<SpansWithPriority>,
+    detail_short: Vec<SpansWithPriority>,
+}
+
+#[zero_indexed_enum]
+enum Focused {
+    List,
+    Detail,
+}
+
+impl AuditLogView {
+    pub fn new(
+        title: &'static str,
+        entries: Vec<AuditEntry>,
+        mapper: &UserEventMapper,
+        timezone: DisplayTimeZone,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let list_state = ScrollListState::new(entries.len());
+        let scroll_lines_state =
+            ScrollLinesState::new(vec![], ScrollLinesOptions::new(false, false));
+        let helps = AuditLogViewHelps::new(mapper, theme);
+
+        let mut view = AuditLogView {
+            title,
+            entries,
+            timezone,
+
+            helps,
+            theme,
+            tx,
+
+            list_state,
+            scroll_lines_state,
+            focused: Focused::List,
+        };
+        view.update_detail();
+        view
+    }
+}
+
+impl AuditLogView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        match self.focused {
+            Focused::List => {
+                handle_user_events! { user_events =>
+                    UserEvent::Close => {
+                        self.tx.send(AppEvent::BackToBeforeView);
+                    }
+                    UserEvent::Down => {
+                        self.list_state.select_next();
+                        self.update_detail();
+                    }
+                    UserEvent::Up => {
+                        self.list_state.select_prev();
+                        self.update_detail();
+                    }
+                    UserEvent::PageDown => {
+                        self.list_state.select_next_page();
+                        self.update_detail();
+                    }
+                    UserEvent::PageUp => {
+                        self.list_state.select_prev_page();
+                        self.update_detail();
+                    }
+                    UserEvent::GoToTop => {
+                        self.list_state.select_first();
+                        self.update_detail();
+                    }
+                    UserEvent::GoToBottom => {
+                        self.list_state.select_last();
+                        self.update_detail();
+                    }
+                    UserEvent::NextPane => {
+                        self.next_pane();
+                    }
+                    UserEvent::CopyToClipboard => {
+                        self.copy_to_clipboard();
+                    }
+                    UserEvent::Help => {
+                        self.open_help();
+                    }
+                }
+            }
+            Focused::Detail => {
+                handle_user_events! { user_events =>
+                    UserEvent::Close => {
+                        self.tx.send(AppEvent::BackToBeforeView);
+                    }
+                    UserEvent::Down => {
+                        self.scroll_lines_state.scroll_forward();
+                    }
+                    UserEvent::Up => {
+                        self.scroll_lines_state.scroll_backward();
+                    }
+                    UserEvent::PageDown => {
+                        self.scroll_lines_state.scroll_page_forward();
+                    }
+                    UserEvent::PageUp => {
+                        self.scroll_lines_state.scroll_page_backward();
+                    }
+                    UserEvent::GoToTop => {
+                        self.scroll_lines_state.scroll_to_top();
+                    }
+                    UserEvent::GoToBottom => {
+                        self.scroll_lines_state.scroll_to_end();
+                    }
+                    UserEvent::Right => {
+                        self.scroll_lines_state.scroll_right();
+                    }
+                    UserEvent::Left => {
+                        self.scroll_lines_state.scroll_left();
+                    }
+                    UserEvent::NextPane => {
+                        self.next_pane();
+                    }
+                    UserEvent::ToggleWrap => {
+                        self.scroll_lines_state.toggle_wrap();
+                    }
+                    UserEvent::ToggleNumber => {
+                        self.scroll_lines_state.toggle_number();
+                    }
+                    UserEvent::CopyToClipboard => {
+                        self.copy_to_clipboard();
+                    }
+                    UserEvent::Help => {
+                        self.open_help();
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Length(LIST_WIDTH), Constraint::Min(0)]).areas(area);
+
+        self.render_list(f, list_area);
+        self.render_detail(f, detail_area);
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        match self.focused {
+            Focused::List => &self.helps.list_short,
+            Focused::Detail => &self.helps.detail_short,
+        }
+    }
+}
+
+impl AuditLogViewHelps {
+    fn new(mapper: &UserEventMapper, theme: ColorTheme) -> Self {
+        let (list, detail) = build_helps(mapper, theme);
+        let (list_short, detail_short) = build_short_helps(mapper);
+        Self {
+            list,
+            detail,
+            list_short,
+            detail_short,
+        }
+    }
+}
+
+fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> (Vec<Spans>, Vec<Spans>) {
+    #[rustfmt::skip]
+    let list_helps = vec![
+        BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Close, "Back to previous view"),
+        BuildHelpsItem::new(UserEvent::Down, "Select next operation"),
+        BuildHelpsItem::new(UserEvent::Up, "Select previous operation"),
+        BuildHelpsItem::new(UserEvent::PageDown, "Select next page"),
+        BuildHelpsItem::new(UserEvent::PageUp, "Select previous page"),
+        BuildHelpsItem::new(UserEvent::GoToTop, "Select first operation"),
+        BuildHelpsItem::new(UserEvent::GoToBottom, "Select last operation"),
+        BuildHelpsItem::new(UserEvent::NextPane, "Switch pane"),
+        BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy selected operation"),
+    ];
+    #[rustfmt::skip]
+    let detail_helps = vec![
+        BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Close, "Back to previous view"),
+        BuildHelpsItem::new(UserEvent::Down, "Scroll down"),
+        BuildHelpsItem::new(UserEvent::Up, "Scroll up"),
+        BuildHelpsItem::new(UserEvent::Right, "Scroll right"),
+        BuildHelpsItem::new(UserEvent::Left, "Scroll left"),
+        BuildHelpsItem::new(UserEvent::PageDown, "Scroll page down"),
+        BuildHelpsItem::new(UserEvent::PageUp, "Scroll page up"),
+        BuildHelpsItem::new(UserEvent::GoToTop, "Scroll to top"),
+        BuildHelpsItem::new(UserEvent::GoToBottom, "Scroll to bottom"),
+        BuildHelpsItem::new(UserEvent::NextPane, "Switch pane"),
+        BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
+        BuildHelpsItem::new(UserEvent::ToggleNumber, "Toggle number"),
+        BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy selected operation"),
+    ];
+    (
+        build_help_spans(list_helps, mapper, theme),
+        build_help_spans(detail_helps, mapper, theme),
+    )
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> (Vec<SpansWithPriority>, Vec<SpansWithPriority>) {
+    #[rustfmt::skip]
+    let list_helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Close, "Back", 1),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Select", 2),
+        BuildShortHelpsItem::group(vec![UserEvent::GoToTop, UserEvent::GoToBottom], "Top/Bottom", 5),
+        BuildShortHelpsItem::single(UserEvent::NextPane, "Switch pane", 3),
+        BuildShortHelpsItem::single(UserEvent::CopyToClipboard, "Copy", 4),
+        BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+    ];
+    #[rustfmt::skip]
+    let detail_helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Close, "Back", 1),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Scroll", 2),
+        BuildShortHelpsItem::group(vec![UserEvent::GoToTop, UserEvent::GoToBottom], "Top/Bottom", 5),
+        BuildShortHelpsItem::single(UserEvent::NextPane, "Switch pane", 3),
+        BuildShortHelpsItem::group(vec![UserEvent::ToggleWrap, UserEvent::ToggleNumber], "Toggle wrap/number", 6),
+        BuildShortHelpsItem::single(UserEvent::CopyToClipboard, "Copy", 4),
+        BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+    ];
+    (
+        build_short_help_spans(list_helps, mapper),
+        build_short_help_spans(detail_helps, mapper),
+    )
+}
+
+impl AuditLogView {
+    fn render_list(&mut self, f: &mut Frame, area: Rect) {
+        let show_items_count = (area.height as usize).saturating_sub(2 /* border */);
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(self.list_state.offset)
+            .take(show_items_count)
+            .map(|(i, entry)| {
+                let mut spans = vec![
+                    Span::raw(
+                        self.timezone
+                            .convert(&entry.timestamp)
+                            .format("%Y-%m-%d %H:%M:%S")
+                            .to_string(),
+                    ),
+                    Span::raw(" "),
+                    Span::raw(entry.operation.clone()).bold(),
+                    Span::raw(" "),
+                    Span::raw(entry.table_name.clone()),
+                ];
+                if entry.error.is_some() {
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::raw("FAILED").fg(self.theme.notification_error).bold());
+                }
+                let mut style = Style::default();
+                if i == self.list_state.selected {
+                    style = style.fg(self.theme.selected_fg);
+                    if self.focused == Focused::List {
+                        style = style.bg(self.theme.selected_bg);
+                    } else {
+                        style = style.bg(self.theme.disabled);
+                    }
+                }
+                ListItem::new(Line::from(spans)).style(style)
+            })
+            .collect();
+        let list = ScrollList::new(items)
+            .title(format!(" {} ", self.title))
+            .theme(&self.theme)
+            .focused(self.focused == Focused::List);
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_detail(&mut self, f: &mut Frame, area: Rect) {
+        let mut block = Block::bordered().fg(self.theme.fg).bg(self.theme.bg);
+        if self.focused != Focused::Detail {
+            block = block.border_style(Style::default().fg(self.theme.disabled));
+        }
+        let scroll = ScrollLines::default().block(block).theme(&self.theme);
+
+        f.render_stateful_widget(scroll, area, &mut self.scroll_lines_state);
+    }
+
+    fn update_detail(&mut self) {
+        let lines = self
+            .selected_entry_json()
+            .map(|json| to_highlighted_lines(&json, &self.theme))
+            .unwrap_or_default();
+        let options = self.scroll_lines_state.current_options();
+        self.scroll_lines_state = ScrollLinesState::new(lines, options);
+    }
+
+    fn selected_entry_json(&self) -> Option<String> {
+        self.entries
+            .get(self.list_state.selected)
+            .map(|entry| serde_json::to_string_pretty(entry).unwrap())
+    }
+
+    fn next_pane(&mut self) {
+        self.focused = self.focused.next();
+    }
+
+    fn copy_to_clipboard(&self) {
+        if let Some(content) = self.selected_entry_json() {
+            self.tx
+                .send(AppEvent::CopyToClipboard("operation".into(), content));
+        }
+    }
+
+    fn open_help(&self) {
+        let helps = match self.focused {
+            Focused::List => &self.helps.list,
+            Focused::Detail => &self.helps.detail,
+        };
+        self.tx.send(AppEvent::OpenHelp(helps.clone()))
+    }
+}
@@ -6,6 +6,7 @@ use ratatui::{
     style::{Color as RatatuiColor, Stylize},
     text::{Line, Span},
 };
+use rust_decimal::prelude::ToPrimitive;
 use syntect::{
     easy::HighlightLines,
     highlighting::{Color as SyntectColor, ScopeSelectors, StyleModifier, Theme, ThemeItem},
@@ -13,7 +14,35 @@ use syntect::{
     util::{as_24_bit_terminal_escaped, LinesWithEndings},
 };
 
-use crate::{color::ColorTheme, data::Attribute, widget::ScrollLinesState};
+use crate::{
+    color::ColorTheme,
+    data::Attribute,
+    error::AppError,
+    event::{AppEvent, Sender},
+    util::{find_links, format_ttl, text_width, truncate_to_width},
+    widget::ScrollLinesState,
+};
+
+// TTL attributes are rendered as the raw epoch seconds followed by the relative time to expiry
+pub fn ttl_attribute_to_spans(
+    attr: &Attribute,
+    now: i64,
+    theme: &ColorTheme,
+) -> Vec<Span<'static>> {
+    let mut spans = attribute_to_spans(attr, theme);
+    if let Attribute::N(n) = attr {
+        if let Some(expires_at) = n.to_i64().filter(|t| *t >= 0) {
+            let color = if expires_at <= now {
+                theme.cell_ttl_expired_fg
+            } else {
+                theme.cell_ttl_fg
+            };
+            let text = format!(" ({})", format_ttl(expires_at, now));
+            spans.push(Span::from(text).fg(color));
+        }
+    }
+    spans
+}
 
 pub fn attribute_to_spans(attr: &Attribute, theme: &ColorTheme) -> Vec<Span<'static>> {
     match attr {
@@ -118,8 +147,17 @@ pub fn attribute_to_spans(attr: &Attribute, theme: &ColorTheme) -> Vec<Span<'sta
 }
 
 pub fn raw_string_from_scroll_lines_state(state: &ScrollLinesState) -> String {
-    state
-        .lines()
+    raw_string_from_lines(state.lines())
+}
+
+pub fn selected_raw_string_from_scroll_lines_state(state: &ScrollLinesState) -> Option<String> {
+    let (start, end) = state.selected_range()?;
+    let lines = state.lines().get(start..=end)?;
+    Some(raw_string_from_lines(lines))
+}
+
+fn raw_string_from_lines(lines: &[Line]) -> String {
+    lines
         .iter()
         .map(|l| {
             l.iter()
@@ -131,6 +169,76 @@ pub fn raw_string_from_scroll_lines_state(state: &ScrollLinesState) -> String {
         .join("\n")
 }
 
+pub fn underline_links(lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
+    lines
+        .into_iter()
+        .map(|mut line| {
+            line.spans = line
+                .spans
+                .into_iter()
+                .flat_map(underline_span_links)
+                .collect();
+            line
+        })
+        .collect()
+}
+
+fn underline_span_links(span: Span<'static>) -> Vec<Span<'static>> {
+    let links = find_links(&span.content);
+    if links.is_empty() {
+        return vec![span];
+    }
+    let content = span.content.as_ref();
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for (start, end) in links {
+        if pos < start {
+            spans.push(Span::styled(content[pos..start].to_string(), span.style));
+        }
+        spans.push(Span::styled(content[start..end].to_string(), span.style).underlined());
+        pos = end;
+    }
+    if pos < content.len() {
+        spans.push(Span::styled(content[pos..].to_string(), span.style));
+    }
+    spans
+}
+
+pub fn links_in_lines(lines: &[Line]) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    for line in lines {
+        let s = raw_string_from_lines(std::slice::from_ref(line));
+        for (start, end) in find_links(&s) {
+            let link = &s[start..end];
+            if !links.iter().any(|l| l == link) {
+                links.push(link.into());
+            }
+        }
+    }
+    links
+}
+
+pub fn open_links_dialog(links: Vec<String>, tx: &Sender) {
+    if links.is_empty() {
+        let msg = "No links found";
+        tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+        return;
+    }
+    let items = links
+        .into_iter()
+        .flat_map(|link| {
+            [
+                (format!("Open {link}"), AppEvent::OpenLink(link.clone())),
+                (
+                    format!("Copy {link}"),
+                    AppEvent::CopyToClipboard("link".into(), link),
+                ),
+            ]
+        })
+        .collect();
+    tx.send(AppEvent::OpenSelectDialog("Links".into(), items));
+}
+
 pub fn cut_spans_by_width<'a>(
     spans: Vec<Span<'a>>,
     max_width: usize,
@@ -138,16 +246,13 @@ pub fn cut_spans_by_width<'a>(
     theme: &ColorTheme,
 ) -> Vec<Span<'a>> {
     let total_spans = spans.len();
-    let span_widths: Vec<usize> = spans
-        .iter()
-        .map(|s| console::measure_text_width(&s.content))
-        .collect();
+    let span_widths: Vec<usize> = spans.iter().map(|s| text_width(&s.content)).collect();
 
     if span_widths.iter().sum::<usize>() <= max_width {
         return spans;
     }
 
-    let ellipsis_width = console::measure_text_width(ellipsis);
+    let ellipsis_width = text_width(ellipsis);
     if ellipsis_width >= max_width {
         return vec![Span::from(ellipsis).fg(theme.cell_ellipsis_fg)];
     }
@@ -172,7 +277,7 @@ pub fn cut_spans_by_width<'a>(
     }
 
     let last_span = ret.pop().unwrap();
-    let truncated = console::truncate_str(&last_span.content, rest_w, "").to_string();
+    let truncated = truncate_to_width(&last_span.content, rest_w).to_string();
 
     ret.push(Span::from(truncated).style(last_span.style));
     ret.push(Span::from(ellipsis).fg(theme.cell_ellipsis_fg));
@@ -180,6 +285,14 @@ pub fn cut_spans_by_width<'a>(
     ret
 }
 
+// Statements are executed without the checks of the writes, so only reads are accepted
+pub fn is_select_statement(statement: &str) -> bool {
+    statement
+        .split_whitespace()
+        .next()
+        .is_some_and(|w| w.eq_ignore_ascii_case("SELECT"))
+}
+
 pub fn to_highlighted_lines(json_str: &str, theme: &ColorTheme) -> Vec<Line<'static>> {
     let mut h = HighlightLines::new(&JSON_SYNTAX, &THEME);
     let s = LinesWithEndings::from(json_str)
@@ -199,6 +312,103 @@ pub fn to_highlighted_lines(json_str: &str, theme: &ColorTheme) -> Vec<Line<'sta
     lines
 }
 
+pub fn to_yaml_lines(value: &serde_json::Value, theme: &ColorTheme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            push_yaml_object_lines(map, 0, None, &mut lines, theme);
+        }
+        serde_json::Value::Array(values) if !values.is_empty() => {
+            push_yaml_array_lines(values, 0, None, &mut lines, theme);
+        }
+        _ => lines.push(Line::from(yaml_scalar_span(value, theme))),
+    }
+    lines
+}
+
+fn push_yaml_object_lines(
+    map: &serde_json::Map<String, serde_json::Value>,
+    indent: usize,
+    first_prefix: Option<String>,
+    lines: &mut Vec<Line<'static>>,
+    theme: &ColorTheme,
+) {
+    let mut first_prefix = first_prefix;
+    for (k, v) in map {
+        let prefix = first_prefix.take().unwrap_or_else(|| " ".repeat(indent));
+        let key = Span::from(format!("{}:", yaml_string(k))).bold();
+        match v {
+            serde_json::Value::Object(m) if !m.is_empty() => {
+                lines.push(Line::from(vec![prefix.into(), key]));
+                push_yaml_object_lines(m, indent + 2, None, lines, theme);
+            }
+            serde_json::Value::Array(vs) if !vs.is_empty() => {
+                lines.push(Line::from(vec![prefix.into(), key]));
+                push_yaml_array_lines(vs, indent + 2, None, lines, theme);
+            }
+            _ => {
+                let value = yaml_scalar_span(v, theme);
+                lines.push(Line::from(vec![prefix.into(), key, " ".into(), value]));
+            }
+        }
+    }
+}
+
+fn push_yaml_array_lines(
+    values: &[serde_json::Value],
+    indent: usize,
+    first_prefix: Option<String>,
+    lines: &mut Vec<Line<'static>>,
+    theme: &ColorTheme,
+) {
+    let mut first_prefix = first_prefix;
+    for v in values {
+        let prefix = first_prefix.take().unwrap_or_else(|| " ".repeat(indent)) + "- ";
+        match v {
+            serde_json::Value::Object(m) if !m.is_empty() => {
+                push_yaml_object_lines(m, indent + 2, Some(prefix), lines, theme);
+            }
+            serde_json::Value::Array(vs) if !vs.is_empty() => {
+                push_yaml_array_lines(vs, indent + 2, Some(prefix), lines, theme);
+            }
+            _ => {
+                let value = yaml_scalar_span(v, theme);
+                lines.push(Line::from(vec![prefix.into(), value]));
+            }
+        }
+    }
+}
+
+fn yaml_scalar_span(value: &serde_json::Value, theme: &ColorTheme) -> Span<'static> {
+    match value {
+        serde_json::Value::Null => Span::from("null").fg(theme.cell_null_fg),
+        serde_json::Value::Bool(b) => Span::from(b.to_string()).fg(theme.cell_bool_fg),
+        serde_json::Value::Number(n) => Span::from(n.to_string()).fg(theme.cell_number_fg),
+        serde_json::Value::String(s) => Span::from(yaml_string(s)).fg(theme.cell_string_fg),
+        serde_json::Value::Array(_) => Span::from("[]"),
+        serde_json::Value::Object(_) => Span::from("{}"),
+    }
+}
+
+// Quote the string only if it would be parsed as something other than a plain string
+fn yaml_string(s: &str) -> String {
+    let reserved = ["true", "false", "null", "yes", "no", "on", "off", "~"];
+    let needs_quote = s.is_empty()
+        || s.trim() != s
+        || reserved.contains(&s.to_lowercase().as_str())
+        || s.parse::<f64>().is_ok()
+        || s.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        || s.contains(": ")
+        || s.contains(" #")
+        || s.ends_with(':')
+        || s.contains(|c: char| c.is_control());
+    if needs_quote {
+        serde_json::to_string(s).unwrap()
+    } else {
+        s.into()
+    }
+}
+
 fn replace_span_color(span: &mut Span<'_>, theme: &ColorTheme) {
     if let Some(fg) = span.style.fg {
         match fg {
@@ -325,3 +535,79 @@ contexts:
     - match: '\\.'
       scope: constant.character.escape.json
 "###;
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_underline_links() {
+        let lines = vec![Line::from(vec![
+            Span::raw("Table ARN : "),
+            Span::raw("arn:aws:dynamodb:us-east-1:123456789012:table/foo (active)"),
+        ])];
+        let actual = underline_links(lines);
+        let expected = vec![Line::from(vec![
+            Span::raw("Table ARN : "),
+            Span::raw("arn:aws:dynamodb:us-east-1:123456789012:table/foo").underlined(),
+            Span::raw(" (active)"),
+        ])];
+        assert_eq!(actual, expected);
+        assert_eq!(
+            links_in_lines(&actual),
+            vec!["arn:aws:dynamodb:us-east-1:123456789012:table/foo"]
+        );
+    }
+
+    #[rstest]
+    #[case("foo", "foo")]
+    #[case("foo bar", "foo bar")]
+    #[case("", "\"\"")]
+    #[case("true", "\"true\"")]
+    #[case("123", "\"123\"")]
+    #[case("a: b", "\"a: b\"")]
+    #[case("-foo", "\"-foo\"")]
+    fn test_yaml_string(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(yaml_string(s), expected);
+    }
+
+    #[test]
+    fn test_to_yaml_lines() {
+        let value = json!({
+            "table_name": "test",
+            "item_count": 10,
+            "deletion_protection": false,
+            "stream": null,
+            "key_schema": [
+                { "attribute_name": "id", "key_type": "HASH" },
+                { "attribute_name": "sk", "key_type": "RANGE" },
+            ],
+            "tags": [],
+            "nested": { "values": [1, 2] },
+        });
+        let lines = to_yaml_lines(&value, &ColorTheme::default());
+        let actual = lines
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>()
+            .join("\n");
+        let expected = r#"deletion_protection: false
+item_count: 10
+key_schema:
+  - attribute_name: id
+    key_type: HASH
+  - attribute_name: sk
+    key_type: RANGE
+nested:
+  values:
+    - 1
+    - 2
+stream: null
+table_name: test
+tags: []"#;
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,272 @@
+use ratatui::{
+    crossterm::event::KeyEvent, layout::Rect, style::Stylize, text::Line, widgets::Block, Frame,
+};
+
+use crate::{
+    color::ColorTheme,
+    data::ConnectionDiagnostics,
+    event::{key_event_to_string, AppEvent, Sender, UserEvent, UserEventMapper},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState},
+};
+
+const COMMON_FIXES: &[&str] = &[
+    "Check that the profile is defined in ~/.aws/config or ~/.aws/credentials, or specify it with --profile",
+    "If the profile uses SSO, run `aws sso login` to refresh the session",
+    "Check that the credentials have not expired and are allowed to call dynamodb:ListTables",
+    "Check that the region is correct, or specify it with --region",
+    "For a local endpoint such as LocalStack or DynamoDB Local, check that the server is running at the endpoint",
+];
+
+pub struct ConnectionDiagnosticsView {
+    helps: ConnectionDiagnosticsViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    scroll_lines_state: ScrollLinesState,
+}
+
+struct ConnectionDiagnosticsViewHelps {
+    diagnostics: Vec<Spans>,
+    diagnostics_short: Vec<SpansWithPriority>,
+}
+
+impl ConnectionDiagnosticsView {
+    pub fn new(
+        diagnostics: ConnectionDiagnostics,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let retry_key = mapper
+            .find_first_key(UserEvent::Reload)
+            .map(|key| key_event_to_string(key, false));
+        let lines = get_diagnostics_lines(&diagnostics, retry_key, &theme);
+        let scroll_lines_state = ScrollLinesState::new(lines, ScrollLinesOptions::new(false, true));
+        let helps = ConnectionDiagnosticsViewHelps::new(mapper, theme);
+
+        ConnectionDiagnosticsView {
+            helps,
+            theme,
+            tx,
+
+            scroll_lines_state,
+        }
+    }
+}
+
+impl ConnectionDiagnosticsView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::Close => {
+                // there is nothing to go back to
+                self.tx.send(AppEvent::Quit);
+            }
+            UserEvent::Reload => {
+                self.tx.send(AppEvent::Initialize);
+            }
+            UserEvent::Down => {
+                self.scroll_lines_state.scroll_forward();
+            }
+            UserEvent::Up => {
+                self.scroll_lines_state.scroll_backward();
+            }
+            UserEvent::PageDown => {
+                self.scroll_lines_state.scroll_page_forward();
+            }
+            UserEvent::PageUp => {
+                self.scroll_lines_state.scroll_page_backward();
+            }
+            UserEvent::GoToTop => {
+                self.scroll_lines_state.scroll_to_top();
+            }
+            UserEvent::GoToBottom => {
+                self.scroll_lines_state.scroll_to_end();
+            }
+            UserEvent::ToggleWrap => {
+                self.scroll_lines_state.toggle_wrap();
+            }
+            UserEvent::Help => {
+                self.open_help();
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let title = " Connection Failed ";
+        let scroll = ScrollLines::default()
+            .block(
+                Block::bordered()
+                    .title_top(Line::from(title).left_aligned())
+                    .fg(self.theme.fg)
+                    .bg(self.theme.bg),
+            )
+            .theme(&self.theme);
+
+        f.render_stateful_widget(scroll, area, &mut self.scroll_lines_state);
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        &self.helps.diagnostics_short
+    }
+}
+
+impl ConnectionDiagnosticsViewHelps {
+    fn new(mapper: &UserEventMapper, theme: ColorTheme) -> Self {
+        let diagnostics = build_helps(mapper, theme);
+        let diagnostics_short = build_short_helps(mapper);
+        Self {
+            diagnostics,
+            diagnostics_short,
+        }
+    }
+}
+
+fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> Vec<Spans> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Close, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Reload, "Retry to connect"),
+        BuildHelpsItem::new(UserEvent::Down, "Scroll down"),
+        BuildHelpsItem::new(UserEvent::Up, "Scroll up"),
+        BuildHelpsItem::new(UserEvent::PageDown, "Scroll page down"),
+        BuildHelpsItem::new(UserEvent::PageUp, "Scroll page up"),
+        BuildHelpsItem::new(UserEvent::GoToTop, "Scroll to top"),
+        BuildHelpsItem::new(UserEvent::GoToBottom, "Scroll to bottom"),
+        BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
+    ];
+    build_help_spans(helps, mapper, theme)
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Reload, "Retry", 1),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Scroll", 2),
+        BuildShortHelpsItem::single(UserEvent::ToggleWrap, "Toggle wrap", 3),
+        BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+impl ConnectionDiagnosticsView {
+    fn open_help(&self) {
+        self.tx
+            .send(AppEvent::OpenHelp(self.helps.diagnostics.clone()))
+    }
+}
+
+fn get_diagnostics_lines(
+    diagnostics: &ConnectionDiagnostics,
+    retry_key: Option<String>,
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+
+    lines.push(Line::from("Failed to connect to DynamoDB:".bold()));
+    lines.push(Line::raw(""));
+    for msg in &diagnostics.error_messages {
+        lines.push(Line::from(vec![
+            "  ".into(),
+            msg.clone().fg(theme.notification_error),
+        ]));
+    }
+    lines.push(Line::raw(""));
+
+    let or_default = |s: &Option<String>, default: &str| s.clone().unwrap_or(default.into());
+    lines.push(Line::from("Connection settings:".bold()));
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        "  Credentials : ".bold(),
+        diagnostics.credentials_source.clone().into(),
+    ]));
+    lines.push(Line::from(vec![
+        "      Profile : ".bold(),
+        or_default(&diagnostics.profile, "default").into(),
+    ]));
+    lines.push(Line::from(vec![
+        "       Region : ".bold(),
+        or_default(&diagnostics.region, "-").into(),
+    ]));
+    lines.push(Line::from(vec![
+        "     Endpoint : ".bold(),
+        or_default(&diagnostics.endpoint_url, "AWS default").into(),
+    ]));
+    lines.push(Line::raw(""));
+
+    lines.push(Line::from("Common fixes:".bold()));
+    lines.push(Line::raw(""));
+    for fix in COMMON_FIXES {
+        lines.push(Line::raw(format!("  - {fix}")));
+    }
+
+    if let Some(key) = retry_key {
+        lines.push(Line::raw(""));
+        lines.push(Line::from(
+            format!("Press {key} to retry after fixing the settings.").fg(theme.short_help),
+        ));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{event::test_channel, view::snapshot::render_lines};
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let (tx, _rx) = test_channel();
+        let mapper = UserEventMapper::new();
+        let diagnostics = ConnectionDiagnostics {
+            error_messages: vec!["failed to list tables".into(), "dispatch failure".into()],
+            credentials_source: "default credential provider chain".into(),
+            profile: None,
+            region: Some("us-east-1".into()),
+            endpoint_url: None,
+        };
+        let mut view =
+            ConnectionDiagnosticsView::new(diagnostics, &mapper, ColorTheme::default(), tx);
+
+        let actual = render_lines(60, 28, |f, area| view.render(f, area));
+        let expected = vec![
+            "┌ Connection Failed ───────────────────────────────────────┐",
+            "│ Failed to connect to DynamoDB:                           │",
+            "│                                                          │",
+            "│   failed to list tables                                  │",
+            "│   dispatch failure                                       │",
+            "│                                                          │",
+            "│ Connection settings:                                     │",
+            "│                                                          │",
+            "│   Credentials : default credential provider chain        │",
+            "│       Profile : default                                  │",
+            "│        Region : us-east-1                                │",
+            "│      Endpoint : AWS default                              │",
+            "│                                                          │",
+            "│ Common fixes:                                            │",
+            "│                                                          │",
+            "│   - Check that the profile is defined in ~/.aws/config   │",
+            "│ or ~/.aws/credentials, or specify it with --profile      │",
+            "│   - If the profile uses SSO, run `aws sso login` to      │",
+            "│ refresh the session                                      │",
+            "│   - Check that the credentials have not expired and are  │",
+            "│ allowed to call dynamodb:ListTables                      │",
+            "│   - Check that the region is correct, or specify it with │",
+            "│ --region                                                 │",
+            "│   - For a local endpoint such as LocalStack or DynamoDB  │",
+            "│ Local, check that the server is running at the endpoint  │",
+            "│                                                          │",
+            "│ Press R to retry after fixing the settings.              │",
+            "└──────────────────────────────────────────────────────────┘",
+        ];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,452 @@
+use std::str::FromStr;
+
+use itsuki::zero_indexed_enum;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Padding, Paragraph},
+    Frame,
+};
+use rust_decimal::Decimal;
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    color::ColorTheme,
+    data::{
+        Attribute, Conjunction, FilterCondition, FilterExpression, FilterOperator, ScanOptions,
+        TableDescription,
+    },
+    error::AppError,
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    help::{build_short_help_spans, BuildShortHelpsItem, SpansWithPriority},
+    util::paste_to_input,
+};
+
+const MIN_VALUE_WIDTH: usize = 20;
+
+pub struct FilterBuilderView {
+    table_description: TableDescription,
+    attribute_names: Vec<String>,
+
+    helps: FilterBuilderViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    rows: Vec<ConditionRow>,
+    selected_row: usize,
+    selected_field: Field,
+}
+
+struct FilterBuilderViewHelps {
+    short: Vec<SpansWithPriority>,
+}
+
+struct ConditionRow {
+    conjunction: Conjunction,
+    attribute_index: usize,
+    operator: FilterOperator,
+    value_type: ValueType,
+    value: Input,
+}
+
+impl Default for ConditionRow {
+    fn default() -> Self {
+        ConditionRow {
+            conjunction: Conjunction::And,
+            attribute_index: 0,
+            operator: FilterOperator::Eq,
+            value_type: ValueType::String,
+            value: Input::default(),
+        }
+    }
+}
+
+#[zero_indexed_enum]
+enum Field {
+    Conjunction,
+    Attribute,
+    Operator,
+    ValueType,
+    Value,
+}
+
+#[zero_indexed_enum]
+enum ValueType {
+    String,
+    Number,
+    Bool,
+}
+
+impl ValueType {
+    fn as_str(&self) -> &str {
+        match self {
+            ValueType::String => "S",
+            ValueType::Number => "N",
+            ValueType::Bool => "BOOL",
+        }
+    }
+}
+
+impl FilterBuilderView {
+    pub fn new(
+        table_description: TableDescription,
+        attribute_names: Vec<String>,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let helps = FilterBuilderViewHelps::new(mapper);
+
+        FilterBuilderView {
+            table_description,
+            attribute_names,
+            helps,
+            theme,
+            tx,
+            rows: vec![ConditionRow::default()],
+            selected_row: 0,
+            selected_field: Field::Attribute,
+        }
+    }
+}
+
+impl FilterBuilderView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        if user_events.contains(&UserEvent::AddCondition) {
+            self.add_row();
+            return;
+        }
+        if user_events.contains(&UserEvent::DeleteCondition) {
+            self.delete_row();
+            return;
+        }
+
+        // handle raw keys because most of the keys are used for editing the value
+        match key_event.code {
+            KeyCode::Enter => {
+                self.execute();
+            }
+            KeyCode::Esc => {
+                self.tx.send(AppEvent::BackToBeforeView);
+            }
+            KeyCode::Tab => {
+                self.select_next_field();
+            }
+            KeyCode::BackTab => {
+                self.select_prev_field();
+            }
+            KeyCode::Down => {
+                self.select_row(self.selected_row + 1);
+            }
+            KeyCode::Up => {
+                self.select_row(self.selected_row.saturating_sub(1));
+            }
+            KeyCode::Right if self.selected_field != Field::Value => {
+                self.change_selected_field_value(true);
+            }
+            KeyCode::Left if self.selected_field != Field::Value => {
+                self.change_selected_field_value(false);
+            }
+            _ => {
+                if self.selected_field == Field::Value {
+                    let row = &mut self.rows[self.selected_row];
+                    row.value.handle_event(&Event::Key(key_event));
+                }
+            }
+        }
+    }
+
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.selected_field == Field::Value {
+            let row = &mut self.rows[self.selected_row];
+            paste_to_input(&mut row.value, text);
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let title = format!(
+            " Filter expression builder ({}) ",
+            self.table_description.table_name
+        );
+        let block = Block::bordered()
+            .title_top(Line::from(title).left_aligned())
+            .padding(Padding::horizontal(1))
+            .fg(self.theme.fg)
+            .bg(self.theme.bg);
+        let inner = block.inner(area);
+
+        let attr_width = self
+            .attribute_names
+            .iter()
+            .map(|n| console::measure_text_width(n))
+            .max()
+            .unwrap_or_default();
+
+        let mut lines: Vec<Line> = Vec::new();
+        let mut cursor = None;
+        for (i, row) in self.rows.iter().enumerate() {
+            let mut spans = Vec::new();
+            let push_field = |field: Field, text: String, spans: &mut Vec<Span>| {
+                let style = if i == self.selected_row && field == self.selected_field {
+                    Style::default()
+                        .fg(self.theme.selected_fg)
+                        .bg(self.theme.selected_bg)
+                } else {
+                    Style::default().bg(self.theme.selected_axis_bg)
+                };
+                spans.push(Span::styled(text, style));
+                spans.push(Span::raw(" "));
+            };
+
+            if i == 0 {
+                spans.push(Span::raw("WHERE "));
+            } else {
+                let conj = format!(" {:<3} ", row.conjunction.as_str());
+                push_field(Field::Conjunction, conj, &mut spans);
+            }
+            let attr = self
+                .attribute_names
+                .get(row.attribute_index)
+                .map(String::as_str)
+                .unwrap_or_default();
+            push_field(
+                Field::Attribute,
+                format!(" {attr:attr_width$} "),
+                &mut spans,
+            );
+            let op = format!(" {:<11} ", row.operator.as_str());
+            push_field(Field::Operator, op, &mut spans);
+            if row.operator.needs_value() {
+                let value_type = format!(" {:<4} ", row.value_type.as_str());
+                push_field(Field::ValueType, value_type, &mut spans);
+                if i == self.selected_row && self.selected_field == Field::Value {
+                    let x: usize = spans.iter().map(Span::width).sum();
+                    cursor = Some((x + 1 + row.value.visual_cursor(), i));
+                }
+                let value = format!(" {:MIN_VALUE_WIDTH$} ", row.value.value());
+                push_field(Field::Value, value, &mut spans);
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::raw(""));
+        lines.extend(self.build_preview_lines());
+
+        let paragraph = Paragraph::new(lines).block(block);
+        f.render_widget(paragraph, area);
+
+        if let Some((x, y)) = cursor {
+            let x = (inner.x + x as u16).min(inner.right().saturating_sub(1));
+            f.set_cursor_position((x, inner.y + y as u16));
+        }
+    }
+
+    fn build_preview_lines(&self) -> Vec<Line<'static>> {
+        match self.build_filter() {
+            Ok(filter) => {
+                let built = filter.build();
+                let mut lines = vec![Line::from(vec![
+                    "FilterExpression: ".bold(),
+                    built.expression.into(),
+                ])];
+                lines.push(Line::from("ExpressionAttributeNames:".bold()));
+                for (k, v) in built.names {
+                    lines.push(Line::raw(format!("  {k} = {v}")));
+                }
+                if !built.values.is_empty() {
+                    lines.push(Line::from("ExpressionAttributeValues:".bold()));
+                    for (k, v) in built.values {
+                        let value =
+                            format!("  {k} = {} ({})", v.to_simple_string(), v.as_type_str());
+                        lines.push(Line::raw(value));
+                    }
+                }
+                lines
+            }
+            Err(e) => vec![Line::raw(e.msg).fg(self.theme.notification_error)],
+        }
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        &self.helps.short
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_description.table_name
+    }
+}
+
+impl FilterBuilderViewHelps {
+    fn new(mapper: &UserEventMapper) -> Self {
+        let short = build_short_helps(mapper);
+        Self { short }
+    }
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Reset, "Back", 1),
+        BuildShortHelpsItem::single(UserEvent::Confirm, "Scan", 2),
+        BuildShortHelpsItem::single(UserEvent::NextPane, "Next field", 3),
+        BuildShortHelpsItem::single(UserEvent::AddCondition, "Add condition", 4),
+        BuildShortHelpsItem::single(UserEvent::DeleteCondition, "Delete condition", 5),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+impl FilterBuilderView {
+    fn add_row(&mut self) {
+        self.rows
+            .insert(self.selected_row + 1, ConditionRow::default());
+        self.select_row(self.selected_row + 1);
+    }
+
+    fn delete_row(&mut self) {
+        if self.rows.len() > 1 {
+            self.rows.remove(self.selected_row);
+            self.select_row(self.selected_row.min(self.rows.len() - 1));
+        }
+    }
+
+    fn select_row(&mut self, row: usize) {
+        if row < self.rows.len() {
+            self.selected_row = row;
+            if !self.is_available_field(self.selected_field) {
+                self.selected_field = self.selected_field.prev_in(|f| self.is_available_field(f));
+            }
+        }
+    }
+
+    fn select_next_field(&mut self) {
+        self.selected_field = self.selected_field.next_in(|f| self.is_available_field(f));
+    }
+
+    fn select_prev_field(&mut self) {
+        self.selected_field = self.selected_field.prev_in(|f| self.is_available_field(f));
+    }
+
+    fn is_available_field(&self, field: Field) -> bool {
+        let row = &self.rows[self.selected_row];
+        match field {
+            Field::Conjunction => self.selected_row > 0,
+            Field::Attribute | Field::Operator => true,
+            Field::ValueType | Field::Value => row.operator.needs_value(),
+        }
+    }
+
+    fn change_selected_field_value(&mut self, forward: bool) {
+        let attribute_count = self.attribute_names.len();
+        let row = &mut self.rows[self.selected_row];
+        match self.selected_field {
+            Field::Conjunction => {
+                row.conjunction = if forward {
+                    row.conjunction.next()
+                } else {
+                    row.conjunction.prev()
+                };
+            }
+            Field::Attribute => {
+                if attribute_count > 0 {
+                    row.attribute_index = if forward {
+                        (row.attribute_index + 1) % attribute_count
+                    } else {
+                        (row.attribute_index + attribute_count - 1) % attribute_count
+                    };
+                }
+            }
+            Field::Operator => {
+                row.operator = if forward {
+                    row.operator.next()
+                } else {
+                    row.operator.prev()
+                };
+            }
+            Field::ValueType => {
+                row.value_type = if forward {
+                    row.value_type.next()
+                } else {
+                    row.value_type.prev()
+                };
+            }
+            Field::Value => {}
+        }
+    }
+
+    fn build_filter(&self) -> Result<FilterExpression, AppError> {
+        let mut conditions = Vec::new();
+        for row in &self.rows {
+            let attribute_name = match self.attribute_names.get(row.attribute_index) {
+                Some(name) => name.clone(),
+                None => return Err(AppError::msg("No attributes available")),
+            };
+            let value = if row.operator.needs_value() {
+                Some(parse_value(row.value.value(), row.value_type)?)
+            } else {
+                None
+            };
+            conditions.push(FilterCondition {
+                conjunction: row.conjunction,
+                attribute_name,
+                operator: row.operator,
+                value,
+            });
+        }
+        Ok(FilterExpression { conditions })
+    }
+
+    fn execute(&self) {
+        match self.build_filter() {
+            Ok(filter) => {
+                let desc = self.table_description.clone();
+                let options = ScanOptions {
+                    filter: Some(filter),
+                    ..Default::default()
+                };
+                self.tx.send(AppEvent::LoadTableItems(desc, options));
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyWarning(e));
+            }
+        }
+    }
+}
+
+fn parse_value(value: &str, value_type: ValueType) -> Result<Attribute, AppError> {
+    match value_type {
+        ValueType::String => Ok(Attribute::S(value.into())),
+        ValueType::Number => Decimal::from_str(value.trim())
+            .map(Attribute::N)
+            .map_err(|_| AppError::msg(format!("Invalid number: {value}"))),
+        ValueType::Bool => match value.trim() {
+            "true" => Ok(Attribute::BOOL(true)),
+            "false" => Ok(Attribute::BOOL(false)),
+            _ => Err(AppError::msg(format!("Invalid bool: {value}"))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("foo", ValueType::String, Some(Attribute::S("foo".into())))]
+    #[case("12.5", ValueType::Number, Some(Attribute::N(Decimal::new(125, 1))))]
+    #[case("abc", ValueType::Number, None)]
+    #[case("true", ValueType::Bool, Some(Attribute::BOOL(true)))]
+    #[case("yes", ValueType::Bool, None)]
+    fn test_parse_value(
+        #[case] value: &str,
+        #[case] value_type: ValueType,
+        #[case] expected: Option<Attribute>,
+    ) {
+        let actual = parse_value(value, value_type).ok();
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,31 +1,164 @@
-use ratatui::{crossterm::event::KeyEvent, layout::Rect, style::Stylize, widgets::Block, Frame};
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, ListItem},
+    Frame,
+};
 
 use crate::{
     color::ColorTheme,
-    event::{Sender, UserEvent},
-    help::SpansWithPriority,
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    handle_user_events,
+    help::{build_short_help_spans, BuildShortHelpsItem, SpansWithPriority},
+    profile::Profile,
+    widget::{ScrollList, ScrollListState},
 };
 
 pub struct InitView {
+    state: InitViewState,
+
     theme: ColorTheme,
-    _tx: Sender,
+    tx: Sender,
+}
+
+enum InitViewState {
+    Connecting,
+    PickingProfile {
+        profiles: Vec<Profile>,
+        // the region specified by the command line option, if any
+        region: Option<String>,
+        list_state: ScrollListState,
+        helps: Vec<SpansWithPriority>,
+    },
 }
 
 impl InitView {
     pub fn new(theme: ColorTheme, tx: Sender) -> Self {
-        InitView { theme, _tx: tx }
+        InitView {
+            state: InitViewState::Connecting,
+            theme,
+            tx,
+        }
+    }
+
+    pub fn with_profiles(
+        profiles: Vec<Profile>,
+        region: Option<String>,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let list_state = ScrollListState::new(profiles.len());
+        let helps = build_short_helps(mapper);
+        InitView {
+            state: InitViewState::PickingProfile {
+                profiles,
+                region,
+                list_state,
+                helps,
+            },
+            theme,
+            tx,
+        }
     }
 }
 
 impl InitView {
-    pub fn handle_user_key_event(&mut self, _user_events: Vec<UserEvent>, _key_event: KeyEvent) {}
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        let InitViewState::PickingProfile {
+            profiles,
+            region,
+            list_state,
+            ..
+        } = &mut self.state
+        else {
+            return;
+        };
+        handle_user_events! { user_events =>
+            UserEvent::Down => {
+                list_state.select_next();
+            }
+            UserEvent::Up => {
+                list_state.select_prev();
+            }
+            UserEvent::GoToTop => {
+                list_state.select_first();
+            }
+            UserEvent::GoToBottom => {
+                list_state.select_last();
+            }
+            UserEvent::Confirm => {
+                if let Some(profile) = profiles.get(list_state.selected) {
+                    let name = profile.name.clone();
+                    self.tx.send(AppEvent::SelectProfile(name, region.clone()));
+                }
+            }
+        }
+    }
 
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
-        let block = Block::bordered().fg(self.theme.fg).bg(self.theme.bg);
-        f.render_widget(block, area);
+        match &mut self.state {
+            InitViewState::Connecting => {
+                let block = Block::bordered().fg(self.theme.fg).bg(self.theme.bg);
+                f.render_widget(block, area);
+            }
+            InitViewState::PickingProfile {
+                profiles,
+                region,
+                list_state,
+                ..
+            } => {
+                let show_items_count = (area.height as usize).saturating_sub(2 /* border */);
+                let name_width = profiles
+                    .iter()
+                    .map(|p| p.name.len())
+                    .max()
+                    .unwrap_or_default();
+                let items: Vec<ListItem> = profiles
+                    .iter()
+                    .enumerate()
+                    .skip(list_state.offset)
+                    .take(show_items_count)
+                    .map(|(i, p)| {
+                        let spans = vec![
+                            Span::raw(format!(" {:name_width$}", p.name)),
+                            Span::raw("  "),
+                            Span::raw(region.clone().or(p.region.clone()).unwrap_or_default())
+                                .dim(),
+                        ];
+                        let mut style = Style::default();
+                        if i == list_state.selected {
+                            style = style.fg(self.theme.selected_fg).bg(self.theme.selected_bg);
+                        }
+                        ListItem::new(Line::from(spans)).style(style)
+                    })
+                    .collect();
+                let list = ScrollList::new(items)
+                    .title(" Profiles ")
+                    .theme(&self.theme)
+                    .focused(true);
+                f.render_stateful_widget(list, area, list_state);
+            }
+        }
     }
 
     pub fn short_helps(&self) -> &[SpansWithPriority] {
-        &[]
+        match &self.state {
+            InitViewState::Connecting => &[],
+            InitViewState::PickingProfile { helps, .. } => helps,
+        }
     }
 }
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Select", 1),
+        BuildShortHelpsItem::group(vec![UserEvent::GoToTop, UserEvent::GoToBottom], "Top/Bottom", 3),
+        BuildShortHelpsItem::single(UserEvent::Confirm, "Connect", 2),
+    ];
+    build_short_help_spans(helps, mapper)
+}
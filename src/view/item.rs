@@ -1,5 +1,6 @@
-use std::slice;
+use std::{collections::BTreeMap, slice};
 
+use chrono::Local;
 use itsuki::zero_indexed_enum;
 use ratatui::{
     crossterm::event::KeyEvent, layout::Rect, style::Stylize, text::Line, widgets::Block, Frame,
@@ -8,16 +9,23 @@ use ratatui::{
 use crate::{
     color::ColorTheme,
     data::{
-        list_attribute_keys, to_key_string, Item, KeySchemaType, PlainJsonItem, RawJsonItem,
-        TableDescription,
+        join_path_key, list_attribute_keys, plain_json_line_paths, to_key_attributes,
+        to_key_string, to_plain_json_string, Attribute, Item, KeySchemaType, Permission,
+        PlainJsonItem, RawJsonItem, TableDescription, TablePermissions, WriteRequest,
     },
+    error::AppError,
     event::{AppEvent, Sender, UserEvent, UserEventMapper},
     handle_user_events,
     help::{
-        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
-        SpansWithPriority,
+        build_help_spans, build_short_help_spans, disable_help_spans, BuildHelpsItem,
+        BuildShortHelpsItem, Spans, SpansWithPriority,
+    },
+    util::parse_duration,
+    view::common::{
+        attribute_to_spans, links_in_lines, open_links_dialog, raw_string_from_scroll_lines_state,
+        selected_raw_string_from_scroll_lines_state, to_highlighted_lines, ttl_attribute_to_spans,
+        underline_links,
     },
-    view::common::{attribute_to_spans, raw_string_from_scroll_lines_state, to_highlighted_lines},
     widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState},
 };
 
@@ -25,12 +33,14 @@ pub struct ItemView {
     table_description: TableDescription,
     item: Item,
     key_string: String,
+    permissions: TablePermissions,
 
     helps: ItemViewHelps,
     theme: ColorTheme,
     tx: Sender,
 
     scroll_lines_state: ScrollLinesState,
+    line_paths: Vec<String>,
 
     preview_type: PreviewType,
 }
@@ -65,12 +75,14 @@ impl ItemView {
             table_description,
             item,
             key_string,
+            permissions: TablePermissions::default(),
 
             helps,
             theme,
             tx,
 
             scroll_lines_state,
+            line_paths: Vec::new(),
             preview_type: PreviewType::KeyValue,
         };
         view.update_preview();
@@ -122,9 +134,57 @@ impl ItemView {
             UserEvent::ToggleNumber => {
                 self.scroll_lines_state.toggle_number();
             }
+            UserEvent::ToggleCursor => {
+                self.scroll_lines_state.toggle_cursor();
+            }
+            UserEvent::ToggleSelection => {
+                self.scroll_lines_state.toggle_selection();
+            }
+            UserEvent::Yank => {
+                self.yank_selected_lines();
+            }
+            UserEvent::Reset => {
+                self.scroll_lines_state.end_selection();
+            }
             UserEvent::CopyToClipboard => {
                 self.copy_to_clipboard();
             }
+            UserEvent::CopyPath => {
+                self.copy_path_to_clipboard();
+            }
+            UserEvent::OpenLink => {
+                self.open_links();
+            }
+            UserEvent::RefreshItem => {
+                self.refresh_item();
+            }
+            UserEvent::NextItem => {
+                self.tx.send(AppEvent::OpenAdjacentItem(true));
+            }
+            UserEvent::PrevItem => {
+                self.tx.send(AppEvent::OpenAdjacentItem(false));
+            }
+            UserEvent::SetTtl => {
+                self.set_ttl();
+            }
+            UserEvent::DeleteItem => {
+                self.delete_item();
+            }
+            UserEvent::SaveToFile => {
+                self.save_to_file();
+            }
+            UserEvent::OpenInPager => {
+                let content = raw_string_from_scroll_lines_state(&self.scroll_lines_state);
+                self.tx.send(AppEvent::OpenInPager(content));
+            }
+            UserEvent::PipeToCommand => {
+                let content = raw_string_from_scroll_lines_state(&self.scroll_lines_state);
+                self.tx
+                    .send(AppEvent::PipeToCommand("item".into(), content));
+            }
+            UserEvent::PrintAndQuit => {
+                self.tx.send(AppEvent::QuitWithOutput(self.item_json()));
+            }
             UserEvent::Help => {
                 self.open_help();
             }
@@ -136,7 +196,7 @@ impl ItemView {
             " {} ({}) ",
             self.table_description.table_name, self.key_string
         );
-        let scroll = ScrollLines::default()
+        let mut scroll = ScrollLines::default()
             .block(
                 Block::bordered()
                     .title_top(Line::from(title).left_aligned())
@@ -145,12 +205,39 @@ impl ItemView {
             )
             .theme(&self.theme);
 
+        // keep the key visible while scrolling long items
+        let show_lines_count = area.height.saturating_sub(2 /* border */) as usize;
+        if let PreviewType::KeyValue = self.preview_type {
+            if self.scroll_lines_state.lines().len() > show_lines_count {
+                let schema = &self.table_description.key_schema_type;
+                scroll = scroll.header(get_key_header_line(&self.item, schema));
+            }
+        }
+
         f.render_stateful_widget(scroll, area, &mut self.scroll_lines_state);
     }
 
     pub fn short_helps(&self) -> &[SpansWithPriority] {
         &self.helps.item_short
     }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_description.table_name
+    }
+
+    pub fn key_attributes(&self) -> Option<BTreeMap<String, Attribute>> {
+        to_key_attributes(&self.item, &self.table_description.key_schema_type)
+    }
+
+    pub fn set_item(&mut self, item: Item) {
+        self.key_string = to_key_string(&item, &self.table_description.key_schema_type);
+        self.item = item;
+        self.update_preview();
+    }
+
+    pub fn set_permissions(&mut self, permissions: TablePermissions) {
+        self.permissions = permissions;
+    }
 }
 
 impl ItemViewHelps {
@@ -178,7 +265,22 @@ fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> Vec<Spans> {
         BuildHelpsItem::new(UserEvent::PrevPreview, "Switch to previous preview"),
         BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
         BuildHelpsItem::new(UserEvent::ToggleNumber, "Toggle number"),
+        BuildHelpsItem::new(UserEvent::ToggleCursor, "Toggle cursor"),
+        BuildHelpsItem::new(UserEvent::ToggleSelection, "Start/end line selection"),
+        BuildHelpsItem::new(UserEvent::Yank, "Copy selected lines"),
+        BuildHelpsItem::new(UserEvent::Reset, "Cancel line selection"),
+        BuildHelpsItem::new(UserEvent::NextItem, "Open next item of table"),
+        BuildHelpsItem::new(UserEvent::PrevItem, "Open previous item of table"),
+        BuildHelpsItem::new(UserEvent::RefreshItem, "Refresh item"),
+        BuildHelpsItem::new(UserEvent::SetTtl, "Set TTL of item"),
+        BuildHelpsItem::new(UserEvent::DeleteItem, "Delete item"),
         BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy descriptions"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save descriptions to file"),
+        BuildHelpsItem::new(UserEvent::PipeToCommand, "Pipe item to command"),
+        BuildHelpsItem::new(UserEvent::OpenInPager, "Open item in pager"),
+        BuildHelpsItem::new(UserEvent::PrintAndQuit, "Quit and print item"),
+        BuildHelpsItem::new(UserEvent::CopyPath, "Copy attribute path at cursor"),
+        BuildHelpsItem::new(UserEvent::OpenLink, "Open/copy links (at cursor)"),
     ];
     build_help_spans(helps, mapper, theme)
 }
@@ -193,6 +295,7 @@ fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
         BuildShortHelpsItem::single(UserEvent::NextPreview, "Switch preview", 3),
         BuildShortHelpsItem::group(vec![UserEvent::ToggleWrap, UserEvent::ToggleNumber], "Toggle wrap/number", 6),
         BuildShortHelpsItem::single(UserEvent::CopyToClipboard, "Copy", 4),
+        BuildShortHelpsItem::group(vec![UserEvent::PrevItem, UserEvent::NextItem], "Prev/Next item", 7),
         BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
     ];
     build_short_help_spans(helps, mapper)
@@ -210,16 +313,86 @@ impl ItemView {
     fn update_preview(&mut self) {
         let item = &self.item;
         let schema = &self.table_description.key_schema_type;
+        let ttl_attribute_name = self.table_description.ttl_attribute_name.as_deref();
         let theme = &self.theme;
 
         let lines = match self.preview_type {
-            PreviewType::KeyValue => get_key_value_lines(item, schema, theme),
+            PreviewType::KeyValue => get_key_value_lines(item, schema, ttl_attribute_name, theme),
             PreviewType::PlainJson => get_plain_json_lines(item, schema, theme),
             PreviewType::RawJson => get_raw_json_lines(item, schema, theme),
         };
+        self.line_paths = match self.preview_type {
+            PreviewType::KeyValue => list_attribute_keys(slice::from_ref(item), schema)
+                .iter()
+                .map(|key| join_path_key("", key))
+                .collect(),
+            PreviewType::PlainJson => plain_json_line_paths(item, schema),
+            PreviewType::RawJson => Vec::new(),
+        };
         let options = self.scroll_lines_state.current_options();
 
-        self.scroll_lines_state = ScrollLinesState::new(lines, options);
+        self.scroll_lines_state = ScrollLinesState::new(underline_links(lines), options);
+    }
+
+    fn refresh_item(&self) {
+        match self.key_attributes() {
+            Some(key) => {
+                let desc = self.table_description.clone();
+                self.tx.send(AppEvent::RefreshItem(desc, key));
+            }
+            None => {
+                let msg = "Item does not contain all key attributes";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+        }
+    }
+
+    fn set_ttl(&self) {
+        let item = self.item.clone();
+        if self.table_description.ttl_attribute_name.is_none() {
+            let msg = format!(
+                "TTL is not enabled on table {}",
+                self.table_description.table_name
+            );
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        let desc = self.table_description.clone();
+        let on_submit = Box::new(move |s: String| {
+            let request = parse_duration(&s).and_then(|d| {
+                let expires_at = Local::now().timestamp() + d.as_secs() as i64;
+                desc.set_ttl_request(&item, expires_at)
+            });
+            match request {
+                Ok(request) => AppEvent::WriteItems(desc, vec![request]),
+                Err(e) => AppEvent::NotifyWarning(e),
+            }
+        });
+        let title = "Expire in (e.g. 30m, 12h, 7d)".into();
+        self.tx.send(AppEvent::OpenPrompt(title, on_submit));
+    }
+
+    fn delete_item(&self) {
+        if self.permissions.delete_item == Permission::Denied {
+            let msg = format!(
+                "You do not have permission to delete items from {}",
+                self.table_description.table_name
+            );
+            self.tx.send(AppEvent::NotifyError(AppError::msg(msg)));
+            return;
+        }
+        match self.key_attributes() {
+            Some(key) => {
+                let desc = self.table_description.clone();
+                let item = self.item.clone();
+                let requests = vec![WriteRequest::Delete { key, item }];
+                self.tx.send(AppEvent::WriteItems(desc, requests));
+            }
+            None => {
+                let msg = "Item does not contain all key attributes";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+        }
     }
 
     fn copy_to_clipboard(&self) {
@@ -228,16 +401,99 @@ impl ItemView {
             .send(AppEvent::CopyToClipboard("item".into(), content));
     }
 
+    pub fn item_json(&self) -> String {
+        let schema = &self.table_description.key_schema_type;
+        to_plain_json_string(&self.item, schema)
+    }
+
+    fn save_to_file(&self) {
+        let content = raw_string_from_scroll_lines_state(&self.scroll_lines_state);
+        self.tx.send(AppEvent::SaveToFile("item".into(), content));
+    }
+
+    fn yank_selected_lines(&mut self) {
+        match selected_raw_string_from_scroll_lines_state(&self.scroll_lines_state) {
+            Some(content) => {
+                self.tx
+                    .send(AppEvent::CopyToClipboard("selected lines".into(), content));
+                self.scroll_lines_state.end_selection();
+            }
+            None => {
+                let msg = "Start the line selection to select the lines to copy";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+        }
+    }
+
+    fn open_links(&self) {
+        let lines = self.scroll_lines_state.lines();
+        let links = match self.scroll_lines_state.cursor() {
+            Some(cursor) => links_in_lines(&lines[cursor..=cursor]),
+            None => links_in_lines(lines),
+        };
+        open_links_dialog(links, &self.tx);
+    }
+
+    fn copy_path_to_clipboard(&self) {
+        let cursor = match self.scroll_lines_state.cursor() {
+            Some(cursor) => cursor,
+            None => {
+                let msg = "Enable the cursor to select the attribute to copy the path";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                return;
+            }
+        };
+        match self.line_paths.get(cursor) {
+            Some(path) => {
+                self.tx
+                    .send(AppEvent::CopyToClipboard("path".into(), path.clone()));
+            }
+            None => {
+                let msg = "Copying path is not supported in this preview";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+        }
+    }
+
     fn open_help(&self) {
-        self.tx.send(AppEvent::OpenHelp(self.helps.item.clone()))
+        let mut disabled_events = Vec::new();
+        if self.permissions.delete_item == Permission::Denied {
+            disabled_events.push(UserEvent::DeleteItem);
+        }
+        let helps = disable_help_spans(&self.helps.item, &disabled_events, self.theme);
+        self.tx.send(AppEvent::OpenHelp(helps))
     }
 }
 
+fn get_key_header_line(item: &Item, schema: &KeySchemaType) -> Line<'static> {
+    let key_names = match schema {
+        KeySchemaType::Hash(key) => vec![key],
+        KeySchemaType::HashRange(key1, key2) => vec![key1, key2],
+    };
+    let mut spans = vec![];
+    for (i, name) in key_names.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(" / ".into());
+        }
+        // key attributes may be missing if the items are loaded with a projection
+        let value = item
+            .attributes
+            .get(name)
+            .map(|attr| attr.to_simple_string())
+            .unwrap_or_else(|| "-".into());
+        spans.push(format!("{name}: ").bold());
+        spans.push(value.into());
+    }
+    Line::from(spans).underlined()
+}
+
 fn get_key_value_lines(
     item: &Item,
     schema: &KeySchemaType,
+    ttl_attribute_name: Option<&str>,
     theme: &ColorTheme,
 ) -> Vec<Line<'static>> {
+    let now = Local::now().timestamp();
     let attribute_keys = list_attribute_keys(slice::from_ref(item), schema);
     let max_key_width = attribute_keys.iter().map(|k| k.len()).max().unwrap();
     let max_attr_width = attribute_keys
@@ -257,7 +513,11 @@ fn get_key_value_lines(
                     .bold(),
             );
             spans.push(": ".into());
-            spans.extend(attribute_to_spans(attr, theme));
+            if ttl_attribute_name == Some(key.as_str()) {
+                spans.extend(ttl_attribute_to_spans(attr, now, theme));
+            } else {
+                spans.extend(attribute_to_spans(attr, theme));
+            }
             lines.push(Line::from(spans));
         }
     }
@@ -283,3 +543,67 @@ fn get_raw_json_lines(
     let json_str = serde_json::to_string_pretty(&json_item).unwrap();
     to_highlighted_lines(&json_str, theme)
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+    use rust_decimal::Decimal;
+
+    use crate::{
+        event::test_channel,
+        view::snapshot::{item, render_lines, table_description},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let (tx, _rx) = test_channel();
+        let mapper = UserEventMapper::new();
+        let item = item(vec![
+            ("id", Attribute::S("u1".into())),
+            ("age", Attribute::N(Decimal::from(30))),
+            ("name", Attribute::S("Alice".into())),
+        ]);
+        let desc = table_description("users", "id");
+        let mut view = ItemView::new(desc, item, &mapper, ColorTheme::default(), tx);
+
+        let actual = render_lines(30, 6, |f, area| view.render(f, area));
+        let expected = vec![
+            "┌ users (u1) ────────────────┐",
+            "│   id S : \"u1\"              │",
+            "│  age N : 30                │",
+            "│ name S : \"Alice\"           │",
+            "│                            │",
+            "└────────────────────────────┘",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_long_item() {
+        let (tx, _rx) = test_channel();
+        let mapper = UserEventMapper::new();
+        let item = item(vec![
+            ("id", Attribute::S("u1".into())),
+            ("a", Attribute::N(Decimal::from(1))),
+            ("b", Attribute::N(Decimal::from(2))),
+            ("c", Attribute::N(Decimal::from(3))),
+            ("d", Attribute::N(Decimal::from(4))),
+        ]);
+        let desc = table_description("users", "id");
+        let mut view = ItemView::new(desc, item, &mapper, ColorTheme::default(), tx);
+        view.handle_user_key_event(vec![UserEvent::Down], KeyEvent::from(KeyCode::Char('j')));
+
+        let actual = render_lines(30, 6, |f, area| view.render(f, area));
+        let expected = vec![
+            "┌ users (u1) ────────────────┐",
+            "│ id: u1                     │",
+            "│  a N : 1                   │",
+            "│  b N : 2                  ││",
+            "│  c N : 3                   │",
+            "└───────────────── 2/5 · 50% ┘",
+        ];
+        assert_eq!(actual, expected);
+    }
+}
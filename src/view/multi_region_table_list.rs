@@ -0,0 +1,203 @@
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::ListItem,
+    Frame,
+};
+
+use crate::{
+    color::ColorTheme,
+    data::RegionTable,
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    widget::{ScrollList, ScrollListState},
+};
+
+pub struct MultiRegionTableListView {
+    tables: Vec<RegionTable>,
+    regions: Vec<String>,
+
+    helps: MultiRegionTableListViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    list_state: ScrollListState,
+}
+
+struct MultiRegionTableListViewHelps {
+    list: Vec<Spans>,
+    list_short: Vec<SpansWithPriority>,
+}
+
+impl MultiRegionTableListView {
+    pub fn new(
+        tables: Vec<RegionTable>,
+        regions: Vec<String>,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let list_state = ScrollListState::new(tables.len());
+        let helps = MultiRegionTableListViewHelps::new(mapper, theme);
+
+        MultiRegionTableListView {
+            tables,
+            regions,
+
+            helps,
+            theme,
+            tx,
+
+            list_state,
+        }
+    }
+}
+
+impl MultiRegionTableListView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::Close => {
+                self.tx.send(AppEvent::BackToBeforeView);
+            }
+            UserEvent::Down => {
+                self.list_state.select_next();
+            }
+            UserEvent::Up => {
+                self.list_state.select_prev();
+            }
+            UserEvent::PageDown => {
+                self.list_state.select_next_page();
+            }
+            UserEvent::PageUp => {
+                self.list_state.select_prev_page();
+            }
+            UserEvent::GoToTop => {
+                self.list_state.select_first();
+            }
+            UserEvent::GoToBottom => {
+                self.list_state.select_last();
+            }
+            UserEvent::Confirm => {
+                self.switch_region();
+            }
+            UserEvent::CopyToClipboard => {
+                self.copy_table_name_to_clipboard();
+            }
+            UserEvent::Help => {
+                self.open_help();
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let show_items_count = (area.height as usize).saturating_sub(2 /* border */);
+        let region_width = self
+            .tables
+            .iter()
+            .map(|t| t.region.len())
+            .max()
+            .unwrap_or_default();
+        let items: Vec<ListItem> = self
+            .tables
+            .iter()
+            .enumerate()
+            .skip(self.list_state.offset)
+            .take(show_items_count)
+            .map(|(i, t)| {
+                let spans = vec![
+                    Span::raw(format!(" {:region_width$}", t.region)).dim(),
+                    Span::raw("  "),
+                    Span::raw(t.name.clone()),
+                ];
+                let mut style = Style::default();
+                if i == self.list_state.selected {
+                    style = style.fg(self.theme.selected_fg).bg(self.theme.selected_bg);
+                }
+                ListItem::new(Line::from(spans)).style(style)
+            })
+            .collect();
+        let title = format!(" Tables ({}) ", self.regions.join(", "));
+        let list = ScrollList::new(items)
+            .title(title)
+            .theme(&self.theme)
+            .focused(true);
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        &self.helps.list_short
+    }
+
+    pub fn current_selected_table_name(&self) -> Option<&str> {
+        self.tables
+            .get(self.list_state.selected)
+            .map(|t| t.name.as_str())
+    }
+}
+
+impl MultiRegionTableListViewHelps {
+    fn new(mapper: &UserEventMapper, theme: ColorTheme) -> Self {
+        let list = build_helps(mapper, theme);
+        let list_short = build_short_helps(mapper);
+        Self { list, list_short }
+    }
+}
+
+fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> Vec<Spans> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Close, "Back to table list"),
+        BuildHelpsItem::new(UserEvent::Down, "Select next table"),
+        BuildHelpsItem::new(UserEvent::Up, "Select prev table"),
+        BuildHelpsItem::new(UserEvent::PageDown, "Select next page"),
+        BuildHelpsItem::new(UserEvent::PageUp, "Select prev page"),
+        BuildHelpsItem::new(UserEvent::GoToTop, "Select first table"),
+        BuildHelpsItem::new(UserEvent::GoToBottom, "Select last table"),
+        BuildHelpsItem::new(UserEvent::Confirm, "Switch to the region of the table"),
+        BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy table name"),
+    ];
+    build_help_spans(helps, mapper, theme)
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Close, "Back", 1),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Select", 2),
+        BuildShortHelpsItem::group(vec![UserEvent::GoToTop, UserEvent::GoToBottom], "Top/Bottom", 5),
+        BuildShortHelpsItem::single(UserEvent::Confirm, "Switch region", 3),
+        BuildShortHelpsItem::single(UserEvent::CopyToClipboard, "Copy", 4),
+        BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+impl MultiRegionTableListView {
+    fn switch_region(&self) {
+        if let Some(table) = self.tables.get(self.list_state.selected) {
+            self.tx.send(AppEvent::SwitchRegion(
+                table.region.clone(),
+                table.name.clone(),
+            ));
+        }
+    }
+
+    fn copy_table_name_to_clipboard(&self) {
+        if let Some(name) = self.current_selected_table_name() {
+            self.tx
+                .send(AppEvent::CopyToClipboard("table name".into(), name.into()));
+        }
+    }
+
+    fn open_help(&self) {
+        self.tx.send(AppEvent::OpenHelp(self.helps.list.clone()))
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::HashSet;
+
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, ListItem, Padding, Paragraph},
+    Frame,
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    color::ColorTheme,
+    data::TableDescription,
+    error::AppError,
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    help::{build_short_help_spans, BuildShortHelpsItem, SpansWithPriority},
+    state::SavedQuery,
+    util::paste_to_input,
+    view::common::is_select_statement,
+    widget::{ScrollList, ScrollListState},
+};
+
+const KEYWORDS: &[&str] = &[
+    "SELECT",
+    "FROM",
+    "WHERE",
+    "AND",
+    "OR",
+    "NOT",
+    "IN",
+    "IS",
+    "BETWEEN",
+    "MISSING",
+    "NULL",
+    "TRUE",
+    "FALSE",
+    "EXISTS",
+    "BEGINS_WITH",
+    "CONTAINS",
+    "SIZE",
+    "ATTRIBUTE_TYPE",
+    "ORDER",
+    "BY",
+    "ASC",
+    "DESC",
+];
+
+const MAX_COMPLETIONS: usize = 10;
+const COMPLETION_WIDTH: u16 = 40;
+
+pub struct PartiQLView {
+    table_description: TableDescription,
+    table_names: Vec<String>,
+    attribute_names: Vec<String>,
+
+    helps: PartiQLViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    input: Input,
+    completions: Vec<String>,
+    completion_state: ScrollListState,
+}
+
+struct PartiQLViewHelps {
+    short: Vec<SpansWithPriority>,
+}
+
+impl PartiQLView {
+    pub fn new(
+        table_description: TableDescription,
+        table_names: Vec<String>,
+        attribute_names: Vec<String>,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let statement = format!(
+            "SELECT * FROM {}",
+            quote_identifier(&table_description.table_name)
+        );
+        let input = Input::new(statement);
+        let helps = PartiQLViewHelps::new(mapper);
+
+        PartiQLView {
+            table_description,
+            table_names,
+            attribute_names,
+            helps,
+            theme,
+            tx,
+            input,
+            completions: Vec::new(),
+            completion_state: ScrollListState::new(0),
+        }
+    }
+}
+
+impl PartiQLView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        if user_events.contains(&UserEvent::SaveQuery) {
+            self.save_query();
+            return;
+        }
+        // handle raw keys because most of the keys are used for editing the statement
+        match key_event.code {
+            KeyCode::Enter => {
+                self.execute();
+            }
+            KeyCode::Tab => {
+                self.apply_completion();
+            }
+            KeyCode::Down => {
+                self.completion_state.select_next();
+            }
+            KeyCode::Up => {
+                self.completion_state.select_prev();
+            }
+            KeyCode::Esc => {
+                if self.completions.is_empty() {
+                    self.tx.send(AppEvent::BackToBeforeView);
+                } else {
+                    self.clear_completions();
+                }
+            }
+            _ => {
+                self.input.handle_event(&Event::Key(key_event));
+                self.update_completions();
+            }
+        }
+    }
+
+    pub fn handle_paste(&mut self, text: &str) {
+        paste_to_input(&mut self.input, text);
+        self.update_completions();
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let title = format!(" PartiQL ({}) ", self.table_description.table_name);
+        let block = Block::bordered()
+            .title_top(Line::from(title).left_aligned())
+            .padding(Padding::horizontal(1))
+            .fg(self.theme.fg)
+            .bg(self.theme.bg);
+        let inner = block.inner(area);
+
+        let mut spans = vec![Span::raw("> ")];
+        spans.extend(highlight_statement(self.input.value(), &self.theme));
+        let paragraph = Paragraph::new(Line::from(spans)).block(block);
+        f.render_widget(paragraph, area);
+
+        let cursor_x = inner.x + 2 + self.input.visual_cursor() as u16;
+        f.set_cursor_position((cursor_x.min(inner.right().saturating_sub(1)), inner.y));
+
+        self.render_completions(f, inner);
+    }
+
+    fn render_completions(&mut self, f: &mut Frame, area: Rect) {
+        if self.completions.is_empty() {
+            return;
+        }
+        let (start, _) = current_word(self.input.value(), self.input.cursor());
+        let x = (area.x + 2 + start as u16).min(area.right().saturating_sub(COMPLETION_WIDTH));
+        let h = (self.completions.len() as u16 + 2).min(area.height.saturating_sub(1));
+        let w = COMPLETION_WIDTH.min(area.width);
+        let completion_area = Rect::new(x, area.y + 1, w, h);
+
+        let item_width = (w as usize).saturating_sub(4); // border + padding
+        let items: Vec<ListItem> = self
+            .completions
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let c = console::truncate_str(c, item_width, "..");
+                let mut style = Style::default();
+                if i == self.completion_state.selected {
+                    style = style.fg(self.theme.selected_fg).bg(self.theme.selected_bg);
+                }
+                ListItem::new(Line::raw(format!(" {c:item_width$} "))).style(style)
+            })
+            .collect();
+        let list = ScrollList::new(items).theme(&self.theme).focused(true);
+
+        f.render_widget(Clear, completion_area);
+        f.render_stateful_widget(list, completion_area, &mut self.completion_state);
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        &self.helps.short
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_description.table_name
+    }
+}
+
+impl PartiQLViewHelps {
+    fn new(mapper: &UserEventMapper) -> Self {
+        let short = build_short_helps(mapper);
+        Self { short }
+    }
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Reset, "Back", 1),
+        BuildShortHelpsItem::single(UserEvent::Confirm, "Execute", 2),
+        BuildShortHelpsItem::single(UserEvent::NextPane, "Complete", 3),
+        BuildShortHelpsItem::single(UserEvent::SaveQuery, "Save", 4),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+impl PartiQLView {
+    fn execute(&mut self) {
+        let statement = self.input.value().trim().to_string();
+        if statement.is_empty() {
+            return;
+        }
+        if !is_select_statement(&statement) {
+            let msg = "Only SELECT statements are supported";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        self.clear_completions();
+        let desc = self.table_description.clone();
+        self.tx.send(AppEvent::ExecuteStatement(desc, statement));
+    }
+
+    fn save_query(&self) {
+        let statement = self.input.value().trim().to_string();
+        if !is_select_statement(&statement) {
+            let msg = "Only SELECT statements can be saved";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        let table_name = self.table_description.table_name.clone();
+        let on_submit =
+            Box::new(move |name| AppEvent::SaveQuery(table_name, SavedQuery { name, statement }));
+        self.tx
+            .send(AppEvent::OpenPrompt("Query name".into(), on_submit));
+    }
+
+    fn update_completions(&mut self) {
+        let (_, word) = current_word(self.input.value(), self.input.cursor());
+        self.completions = completion_candidates(&word, &self.table_names, &self.attribute_names);
+        self.completion_state = ScrollListState::new(self.completions.len());
+    }
+
+    fn clear_completions(&mut self) {
+        self.completions.clear();
+        self.completion_state = ScrollListState::new(0);
+    }
+
+    fn apply_completion(&mut self) {
+        if let Some(candidate) = self.completions.get(self.completion_state.selected) {
+            let (value, cursor) = complete(self.input.value(), self.input.cursor(), candidate);
+            self.input = Input::new(value).with_cursor(cursor);
+        }
+        self.clear_completions();
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    s.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn quote_identifier(s: &str) -> String {
+    if is_identifier(s) && !is_keyword(s) {
+        s.to_string()
+    } else {
+        format!("\"{s}\"")
+    }
+}
+
+fn is_keyword(s: &str) -> bool {
+    KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(s))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+// Returns the start position (in chars) and the content of the word before the cursor
+fn current_word(value: &str, cursor: usize) -> (usize, String) {
+    let chars: Vec<char> = value.chars().take(cursor).collect();
+    let mut start = chars.len();
+    while start > 0 && is_word_char(chars[start - 1]) {
+        start -= 1;
+    }
+    if start > 0 && chars[start - 1] == '"' {
+        start -= 1;
+    }
+    (start, chars[start..].iter().collect())
+}
+
+fn completion_candidates(
+    word: &str,
+    table_names: &[String],
+    attribute_names: &[String],
+) -> Vec<String> {
+    let quoted = word.starts_with('"');
+    let word = word.trim_start_matches('"').to_lowercase();
+    if word.is_empty() {
+        return Vec::new();
+    }
+
+    let keywords = KEYWORDS.iter().filter(|_| !quoted).map(|k| k.to_string());
+    // the same name can be both a table and an attribute
+    let mut seen = HashSet::new();
+    keywords
+        .chain(table_names.iter().cloned())
+        .chain(attribute_names.iter().cloned())
+        .filter(|c| {
+            let c = c.to_lowercase();
+            c.starts_with(&word) && c != word
+        })
+        .filter(|c| seen.insert(c.clone()))
+        .take(MAX_COMPLETIONS)
+        .collect()
+}
+
+// Returns the new value and cursor position after replacing the word before the cursor
+fn complete(value: &str, cursor: usize, candidate: &str) -> (String, usize) {
+    let (start, word) = current_word(value, cursor);
+    let replacement = if is_keyword(candidate) && !word.starts_with('"') {
+        candidate.to_string()
+    } else if word.starts_with('"') || !is_identifier(candidate) {
+        format!("\"{candidate}\"")
+    } else {
+        candidate.to_string()
+    };
+
+    let chars: Vec<char> = value.chars().collect();
+    let mut new_value: String = chars[..start].iter().collect();
+    new_value.push_str(&replacement);
+    let new_cursor = new_value.chars().count();
+    new_value.extend(&chars[cursor.min(chars.len())..]);
+    (new_value, new_cursor)
+}
+
+fn highlight_statement(statement: &str, theme: &ColorTheme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let chars: Vec<char> = statement.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\'' || c == '"' {
+            let mut j = i + 1;
+            while j < chars.len() && chars[j] != c {
+                j += 1;
+            }
+            let end = (j + 1).min(chars.len());
+            let s: String = chars[i..end].iter().collect();
+            if c == '\'' {
+                spans.push(Span::from(s).fg(theme.cell_string_fg));
+            } else {
+                spans.push(Span::from(s));
+            }
+            i = end;
+        } else if is_word_char(c) {
+            let mut j = i;
+            while j < chars.len() && is_word_char(chars[j]) {
+                j += 1;
+            }
+            let s: String = chars[i..j].iter().collect();
+            if is_keyword(&s) {
+                spans.push(Span::from(s).fg(theme.query_keyword_fg).bold());
+            } else if s.chars().all(|c| c.is_ascii_digit()) {
+                spans.push(Span::from(s).fg(theme.cell_number_fg));
+            } else {
+                spans.push(Span::from(s));
+            }
+            i = j;
+        } else {
+            spans.push(Span::from(c.to_string()));
+            i += 1;
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("SELECT * FR", 11, (9, "FR"))]
+    #[case("SELECT * FROM \"my-ta", 20, (14, "\"my-ta"))]
+    #[case("SELECT * FROM t WHERE a.b", 25, (24, "b"))]
+    #[case("SELECT ", 7, (7, ""))]
+    fn test_current_word(
+        #[case] value: &str,
+        #[case] cursor: usize,
+        #[case] expected: (usize, &str),
+    ) {
+        let (start, word) = current_word(value, cursor);
+        assert_eq!((start, word.as_str()), expected);
+    }
+
+    #[rstest]
+    #[case("sel", vec!["SELECT"])]
+    #[case("us", vec!["users", "user_id", "user-name"])]
+    #[case("\"us", vec!["users", "user_id", "user-name"])]
+    #[case("users", vec![])]
+    #[case("", vec![])]
+    fn test_completion_candidates(#[case] word: &str, #[case] expected: Vec<&str>) {
+        let table_names = vec!["users".to_string(), "orders".to_string()];
+        let attribute_names = vec!["user_id".to_string(), "user-name".to_string()];
+        let actual = completion_candidates(word, &table_names, &attribute_names);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_completion_candidates_duplicated() {
+        let table_names = vec!["users".to_string(), "user_logs".to_string()];
+        let attribute_names = vec!["users".to_string(), "user_id".to_string()];
+        let actual = completion_candidates("us", &table_names, &attribute_names);
+        assert_eq!(actual, vec!["users", "user_logs", "user_id"]);
+    }
+
+    #[rstest]
+    #[case("sel", 3, "SELECT", ("SELECT", 6))]
+    #[case("SELECT * FROM us", 16, "users", ("SELECT * FROM users", 19))]
+    #[case("SELECT * FROM us", 16, "user-table", ("SELECT * FROM \"user-table\"", 26))]
+    #[case("SELECT * FROM \"us", 17, "users", ("SELECT * FROM \"users\"", 21))]
+    #[case("WHERE us = 1", 8, "user_id", ("WHERE user_id = 1", 13))]
+    fn test_complete(
+        #[case] value: &str,
+        #[case] cursor: usize,
+        #[case] candidate: &str,
+        #[case] expected: (&str, usize),
+    ) {
+        let (new_value, new_cursor) = complete(value, cursor, candidate);
+        assert_eq!((new_value.as_str(), new_cursor), expected);
+    }
+}
@@ -0,0 +1,430 @@
+use itsuki::zero_indexed_enum;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Padding, Paragraph},
+    Frame,
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use crate::{
+    color::ColorTheme,
+    data::{
+        parse_key_value, QueryOptions, QueryTarget, SortKeyCondition, SortKeyOperator,
+        TableDescription,
+    },
+    error::AppError,
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    help::{build_short_help_spans, BuildShortHelpsItem, SpansWithPriority},
+    util::paste_to_input,
+};
+
+const MIN_VALUE_WIDTH: usize = 20;
+
+pub struct QueryBuilderView {
+    table_description: TableDescription,
+    targets: Vec<QueryTarget>,
+
+    helps: QueryBuilderViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    target_index: usize,
+    hash_key_input: Input,
+    sort_key_operator: Option<SortKeyOperator>,
+    sort_key_input: Input,
+    sort_key_to_input: Input,
+    limit_input: Input,
+    scan_index_forward: bool,
+    selected_field: Field,
+}
+
+struct QueryBuilderViewHelps {
+    short: Vec<SpansWithPriority>,
+}
+
+#[zero_indexed_enum]
+enum Field {
+    Index,
+    HashKey,
+    SortKeyOperator,
+    SortKey,
+    SortKeyTo,
+    Limit,
+    Direction,
+}
+
+impl QueryBuilderView {
+    pub fn new(
+        table_description: TableDescription,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let targets = table_description.query_targets();
+        let helps = QueryBuilderViewHelps::new(mapper);
+
+        QueryBuilderView {
+            table_description,
+            targets,
+            helps,
+            theme,
+            tx,
+            target_index: 0,
+            hash_key_input: Input::default(),
+            sort_key_operator: None,
+            sort_key_input: Input::default(),
+            sort_key_to_input: Input::default(),
+            limit_input: Input::default(),
+            scan_index_forward: true,
+            selected_field: Field::HashKey,
+        }
+    }
+}
+
+impl QueryBuilderView {
+    pub fn handle_user_key_event(&mut self, _user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        // handle raw keys because most of the keys are used for editing the values
+        match key_event.code {
+            KeyCode::Enter => {
+                self.execute();
+            }
+            KeyCode::Esc => {
+                self.tx.send(AppEvent::BackToBeforeView);
+            }
+            KeyCode::Tab | KeyCode::Down => {
+                self.select_next_field();
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                self.select_prev_field();
+            }
+            KeyCode::Right if self.is_selector_field() => {
+                self.change_selected_field_value(true);
+            }
+            KeyCode::Left if self.is_selector_field() => {
+                self.change_selected_field_value(false);
+            }
+            _ => {
+                if let Some(input) = self.selected_input_mut() {
+                    input.handle_event(&Event::Key(key_event));
+                }
+            }
+        }
+    }
+
+    pub fn handle_paste(&mut self, text: &str) {
+        if let Some(input) = self.selected_input_mut() {
+            paste_to_input(input, text);
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let title = format!(" Query builder ({}) ", self.table_description.table_name);
+        let block = Block::bordered()
+            .title_top(Line::from(title).left_aligned())
+            .padding(Padding::horizontal(1))
+            .fg(self.theme.fg)
+            .bg(self.theme.bg);
+        let inner = block.inner(area);
+
+        let target = self.current_target();
+        let hash_key_label = self.key_label(&target.hash_key);
+        let sort_key_label = target.range_key.as_ref().map(|key| self.key_label(key));
+        let label_width = ["Index", "Limit", "Direction", &hash_key_label]
+            .into_iter()
+            .chain(sort_key_label.as_deref())
+            .map(console::measure_text_width)
+            .max()
+            .unwrap_or_default();
+
+        let mut rows: Vec<(String, Vec<(Field, String)>)> = Vec::new();
+        let index_name = target.index_name.as_deref().unwrap_or("(base table)");
+        rows.push(("Index".into(), vec![(Field::Index, index_name.into())]));
+        rows.push((
+            hash_key_label,
+            vec![(Field::HashKey, self.hash_key_input.value().into())],
+        ));
+        if let Some(label) = sort_key_label {
+            let mut fields = vec![(
+                Field::SortKeyOperator,
+                match self.sort_key_operator {
+                    Some(op) => op.as_str().into(),
+                    None => "(none)".into(),
+                },
+            )];
+            if self.sort_key_operator.is_some() {
+                fields.push((Field::SortKey, self.sort_key_input.value().into()));
+            }
+            if self.sort_key_operator == Some(SortKeyOperator::Between) {
+                fields.push((Field::SortKeyTo, self.sort_key_to_input.value().into()));
+            }
+            rows.push((label, fields));
+        }
+        rows.push((
+            "Limit".into(),
+            vec![(Field::Limit, self.limit_input.value().into())],
+        ));
+        let direction = if self.scan_index_forward {
+            "Ascending"
+        } else {
+            "Descending"
+        };
+        rows.push((
+            "Direction".into(),
+            vec![(Field::Direction, direction.into())],
+        ));
+
+        let mut lines: Vec<Line> = Vec::new();
+        let mut cursor = None;
+        for (y, (label, fields)) in rows.into_iter().enumerate() {
+            let mut spans = vec![Span::raw(format!("{label:label_width$} : ")).bold()];
+            for (field, text) in fields {
+                let selected = field == self.selected_field;
+                if selected {
+                    if let Some(input) = self.selected_input() {
+                        let x: usize = spans.iter().map(Span::width).sum();
+                        cursor = Some((x + 1 + input.visual_cursor(), y));
+                    }
+                }
+                let text = if self.is_selector_field_of(field) {
+                    format!(" {text} ")
+                } else {
+                    format!(" {text:MIN_VALUE_WIDTH$} ")
+                };
+                let style = if selected {
+                    Style::default()
+                        .fg(self.theme.selected_fg)
+                        .bg(self.theme.selected_bg)
+                } else {
+                    Style::default().bg(self.theme.selected_axis_bg)
+                };
+                spans.push(Span::styled(text, style));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::raw(""));
+        lines.extend(self.build_preview_lines());
+
+        let paragraph = Paragraph::new(lines).block(block);
+        f.render_widget(paragraph, area);
+
+        if let Some((x, y)) = cursor {
+            let x = (inner.x + x as u16).min(inner.right().saturating_sub(1));
+            f.set_cursor_position((x, inner.y + y as u16));
+        }
+    }
+
+    fn build_preview_lines(&self) -> Vec<Line<'static>> {
+        match self.build_options() {
+            Ok(options) => {
+                let built = options.build_key_condition();
+                let mut lines = vec![Line::from(vec![
+                    "KeyConditionExpression: ".bold(),
+                    built.expression.into(),
+                ])];
+                lines.push(Line::from(vec![
+                    "ScanIndexForward: ".bold(),
+                    options.scan_index_forward.to_string().into(),
+                ]));
+                lines.push(Line::from("ExpressionAttributeNames:".bold()));
+                for (k, v) in built.names {
+                    lines.push(Line::raw(format!("  {k} = {v}")));
+                }
+                lines.push(Line::from("ExpressionAttributeValues:".bold()));
+                for (k, v) in built.values {
+                    let value = format!("  {k} = {} ({})", v.to_simple_string(), v.as_type_str());
+                    lines.push(Line::raw(value));
+                }
+                lines
+            }
+            Err(e) => vec![Line::raw(e.msg).fg(self.theme.notification_error)],
+        }
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        &self.helps.short
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_description.table_name
+    }
+}
+
+impl QueryBuilderViewHelps {
+    fn new(mapper: &UserEventMapper) -> Self {
+        let short = build_short_helps(mapper);
+        Self { short }
+    }
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Reset, "Back", 1),
+        BuildShortHelpsItem::single(UserEvent::Confirm, "Query", 2),
+        BuildShortHelpsItem::single(UserEvent::NextPane, "Next field", 3),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+impl QueryBuilderView {
+    fn current_target(&self) -> &QueryTarget {
+        &self.targets[self.target_index]
+    }
+
+    fn key_label(&self, key: &str) -> String {
+        match self.table_description.attribute_type(key) {
+            Some(t) => format!("{key} ({})", t.as_str()),
+            None => key.into(),
+        }
+    }
+
+    fn select_next_field(&mut self) {
+        self.selected_field = self.selected_field.next_in(|f| self.is_available_field(f));
+    }
+
+    fn select_prev_field(&mut self) {
+        self.selected_field = self.selected_field.prev_in(|f| self.is_available_field(f));
+    }
+
+    fn is_available_field(&self, field: Field) -> bool {
+        let has_range_key = self.current_target().range_key.is_some();
+        match field {
+            Field::Index | Field::HashKey | Field::Limit | Field::Direction => true,
+            Field::SortKeyOperator => has_range_key,
+            Field::SortKey => has_range_key && self.sort_key_operator.is_some(),
+            Field::SortKeyTo => {
+                has_range_key && self.sort_key_operator == Some(SortKeyOperator::Between)
+            }
+        }
+    }
+
+    fn is_selector_field(&self) -> bool {
+        self.is_selector_field_of(self.selected_field)
+    }
+
+    fn is_selector_field_of(&self, field: Field) -> bool {
+        matches!(
+            field,
+            Field::Index | Field::SortKeyOperator | Field::Direction
+        )
+    }
+
+    fn selected_input(&self) -> Option<&Input> {
+        match self.selected_field {
+            Field::HashKey => Some(&self.hash_key_input),
+            Field::SortKey => Some(&self.sort_key_input),
+            Field::SortKeyTo => Some(&self.sort_key_to_input),
+            Field::Limit => Some(&self.limit_input),
+            Field::Index | Field::SortKeyOperator | Field::Direction => None,
+        }
+    }
+
+    fn selected_input_mut(&mut self) -> Option<&mut Input> {
+        match self.selected_field {
+            Field::HashKey => Some(&mut self.hash_key_input),
+            Field::SortKey => Some(&mut self.sort_key_input),
+            Field::SortKeyTo => Some(&mut self.sort_key_to_input),
+            Field::Limit => Some(&mut self.limit_input),
+            Field::Index | Field::SortKeyOperator | Field::Direction => None,
+        }
+    }
+
+    fn change_selected_field_value(&mut self, forward: bool) {
+        match self.selected_field {
+            Field::Index => {
+                let n = self.targets.len();
+                self.target_index = if forward {
+                    (self.target_index + 1) % n
+                } else {
+                    (self.target_index + n - 1) % n
+                };
+                // the sort key may differ between indexes
+                self.sort_key_operator = None;
+            }
+            Field::SortKeyOperator => {
+                self.sort_key_operator = match (self.sort_key_operator, forward) {
+                    (None, true) => Some(SortKeyOperator::Eq),
+                    (None, false) => Some(SortKeyOperator::Between),
+                    (Some(SortKeyOperator::Between), true) => None,
+                    (Some(SortKeyOperator::Eq), false) => None,
+                    (Some(op), true) => Some(op.next()),
+                    (Some(op), false) => Some(op.prev()),
+                };
+            }
+            Field::Direction => {
+                self.scan_index_forward = !self.scan_index_forward;
+            }
+            Field::HashKey | Field::SortKey | Field::SortKeyTo | Field::Limit => {}
+        }
+    }
+
+    fn build_options(&self) -> Result<QueryOptions, AppError> {
+        let target = self.current_target();
+
+        let hash_key_value = self.hash_key_input.value();
+        if hash_key_value.is_empty() {
+            let msg = format!("Value of {} is required", target.hash_key);
+            return Err(AppError::msg(msg));
+        }
+        let hash_key_type = self.table_description.attribute_type(&target.hash_key);
+        let hash_key_value = parse_key_value(hash_key_value, hash_key_type)?;
+
+        let sort_key_condition = match (&target.range_key, self.sort_key_operator) {
+            (Some(range_key), Some(operator)) => {
+                let range_key_type = self.table_description.attribute_type(range_key);
+                let mut values = vec![parse_key_value(
+                    self.sort_key_input.value(),
+                    range_key_type.clone(),
+                )?];
+                if operator == SortKeyOperator::Between {
+                    values.push(parse_key_value(
+                        self.sort_key_to_input.value(),
+                        range_key_type,
+                    )?);
+                }
+                Some(SortKeyCondition {
+                    attribute_name: range_key.clone(),
+                    operator,
+                    values,
+                })
+            }
+            _ => None,
+        };
+
+        let limit = match self.limit_input.value().trim() {
+            "" => None,
+            s => match s.parse::<usize>() {
+                Ok(n) if n > 0 => Some(n),
+                _ => return Err(AppError::msg(format!("Invalid limit: {s}"))),
+            },
+        };
+
+        Ok(QueryOptions {
+            index_name: target.index_name.clone(),
+            hash_key_name: target.hash_key.clone(),
+            hash_key_value,
+            sort_key_condition,
+            limit,
+            scan_index_forward: self.scan_index_forward,
+            exclusive_start_key: None,
+        })
+    }
+
+    fn execute(&self) {
+        match self.build_options() {
+            Ok(options) => {
+                let desc = self.table_description.clone();
+                self.tx.send(AppEvent::QueryTableItems(desc, options));
+            }
+            Err(e) => {
+                self.tx.send(AppEvent::NotifyWarning(e));
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    Frame,
+};
+
+use crate::{
+    color::ColorTheme,
+    config::UiTableConfig,
+    data::{ItemsSource, LoadedItems, TableDescription},
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    view::table::TableView,
+};
+
+// The same table loaded from an environment (profile and region) to be shown in a pane
+pub struct SideBySidePane {
+    pub desc: TableDescription,
+    pub loaded: LoadedItems,
+    pub source: ItemsSource,
+    pub environment: String,
+}
+
+pub struct SideBySideView {
+    left: Box<TableView>,
+    right: Box<TableView>,
+    focused: Focused,
+
+    helps: SideBySideViewHelps,
+    tx: Sender,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Focused {
+    Left,
+    Right,
+}
+
+struct SideBySideViewHelps {
+    side_by_side: Vec<Spans>,
+    side_by_side_short: Vec<SpansWithPriority>,
+}
+
+impl SideBySideView {
+    pub fn new(
+        left: SideBySidePane,
+        right: SideBySidePane,
+        mapper: &UserEventMapper,
+        config: UiTableConfig,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let new_pane = |pane: SideBySidePane| {
+            let mut view = TableView::new(
+                pane.desc,
+                pane.loaded,
+                pane.source,
+                mapper,
+                config.clone(),
+                theme,
+                tx.clone(),
+            );
+            view.set_environment(pane.environment);
+            Box::new(view)
+        };
+        let left = new_pane(left);
+        let mut right = new_pane(right);
+        right.set_pane_focused(false);
+        let helps = SideBySideViewHelps::new(mapper, theme);
+
+        SideBySideView {
+            left,
+            right,
+            focused: Focused::Left,
+
+            helps,
+            tx,
+        }
+    }
+}
+
+impl SideBySideView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        if self.focused_view().is_capturing_input() {
+            self.focused_view_mut()
+                .handle_user_key_event(user_events, key_event);
+            return;
+        }
+
+        // only the operations that do not send requests are available,
+        // since the requests would be sent from the primary environment
+        handle_user_events! { user_events =>
+            UserEvent::Close => {
+                self.tx.send(AppEvent::BackToBeforeView);
+            }
+            UserEvent::NextPane => {
+                self.toggle_focus();
+            }
+            UserEvent::Help => {
+                self.open_help();
+            }
+            UserEvent::Down
+            | UserEvent::Up
+            | UserEvent::Left
+            | UserEvent::Right
+            | UserEvent::GoToTop
+            | UserEvent::GoToBottom
+            | UserEvent::GoToLeft
+            | UserEvent::GoToRight
+            | UserEvent::PageDown
+            | UserEvent::PageUp
+            | UserEvent::ScrollDown
+            | UserEvent::ScrollUp
+            | UserEvent::QuickFilter
+            | UserEvent::Reset
+            | UserEvent::Expand
+            | UserEvent::Widen
+            | UserEvent::Narrow
+            | UserEvent::HideColumn
+            | UserEvent::ResetColumns
+            | UserEvent::ReverseOrder
+            | UserEvent::GroupByHashKey
+            | UserEvent::NextSamePartition
+            | UserEvent::PrevSamePartition
+            | UserEvent::CopyToClipboard => {
+                let events = user_events.clone();
+                self.focused_view_mut().handle_user_key_event(events, key_event);
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let [left_area, right_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(area);
+        self.left.render(f, left_area);
+        self.right.render(f, right_area);
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        if self.focused_view().is_capturing_input() {
+            self.focused_view().short_helps()
+        } else {
+            &self.helps.side_by_side_short
+        }
+    }
+
+    pub fn table_name(&self) -> &str {
+        self.left.table_name()
+    }
+
+    fn focused_view(&self) -> &TableView {
+        match self.focused {
+            Focused::Left => &self.left,
+            Focused::Right => &self.right,
+        }
+    }
+
+    fn focused_view_mut(&mut self) -> &mut TableView {
+        match self.focused {
+            Focused::Left => &mut self.left,
+            Focused::Right => &mut self.right,
+        }
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focused = match self.focused {
+            Focused::Left => Focused::Right,
+            Focused::Right => Focused::Left,
+        };
+        let left_focused = self.focused == Focused::Left;
+        self.left.set_pane_focused(left_focused);
+        self.right.set_pane_focused(!left_focused);
+    }
+
+    fn open_help(&self) {
+        self.tx
+            .send(AppEvent::OpenHelp(self.helps.side_by_side.clone()))
+    }
+}
+
+impl SideBySideViewHelps {
+    fn new(mapper: &UserEventMapper, theme: ColorTheme) -> Self {
+        let side_by_side = build_helps(mapper, theme);
+        let side_by_side_short = build_short_helps(mapper);
+        Self {
+            side_by_side,
+            side_by_side_short,
+        }
+    }
+}
+
+fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> Vec<Spans> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Close, "Back to table"),
+        BuildHelpsItem::new(UserEvent::NextPane, "Switch to other environment"),
+        BuildHelpsItem::new(UserEvent::Down, "Select next row"),
+        BuildHelpsItem::new(UserEvent::Up, "Select previous row"),
+        BuildHelpsItem::new(UserEvent::Right, "Select next column"),
+        BuildHelpsItem::new(UserEvent::Left, "Select previous column"),
+        BuildHelpsItem::new(UserEvent::PageDown, "Select next page"),
+        BuildHelpsItem::new(UserEvent::PageUp, "Select previous page"),
+        BuildHelpsItem::new(UserEvent::GoToTop, "Select first row"),
+        BuildHelpsItem::new(UserEvent::GoToBottom, "Select last row"),
+        BuildHelpsItem::new(UserEvent::GoToLeft, "Select first column"),
+        BuildHelpsItem::new(UserEvent::GoToRight, "Select last column"),
+        BuildHelpsItem::new(UserEvent::QuickFilter, "Filter items"),
+        BuildHelpsItem::new(UserEvent::Reset, "Clear filter"),
+        BuildHelpsItem::new(UserEvent::Expand, "Expand selected attribute"),
+        BuildHelpsItem::new(UserEvent::Widen, "Widen selected column"),
+        BuildHelpsItem::new(UserEvent::Narrow, "Narrow selected column"),
+        BuildHelpsItem::new(UserEvent::HideColumn, "Hide selected column"),
+        BuildHelpsItem::new(UserEvent::ResetColumns, "Reset column widths and hidden columns"),
+        BuildHelpsItem::new(UserEvent::ReverseOrder, "Reverse item order"),
+        BuildHelpsItem::new(UserEvent::GroupByHashKey, "Toggle grouping by hash key"),
+        BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy selected item"),
+    ];
+    build_help_spans(helps, mapper, theme)
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Close, "Back", 1),
+        BuildShortHelpsItem::single(UserEvent::NextPane, "Switch pane", 2),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Select row", 3),
+        BuildShortHelpsItem::group(vec![UserEvent::Left, UserEvent::Right], "Select col", 4),
+        BuildShortHelpsItem::single(UserEvent::QuickFilter, "Filter", 5),
+        BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+    ];
+    build_short_help_spans(helps, mapper)
+}
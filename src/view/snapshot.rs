@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use ratatui::{backend::TestBackend, layout::Rect, Frame, Terminal};
+
+use crate::data::{
+    Attribute, AttributeDefinition, Item, KeySchemaElement, KeySchemaType, KeyType,
+    ScalarAttributeType, TableDescription, TableStatus,
+};
+
+// Render with the test backend and return the symbols of each row, so that the layout can be
+// compared as plain text regardless of the colors of the theme
+pub fn render_lines(width: u16, height: u16, render: impl FnOnce(&mut Frame, Rect)) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|f| {
+            let area = f.area();
+            render(f, area);
+        })
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+        .collect()
+}
+
+pub fn table_description(table_name: &str, hash_key: &str) -> TableDescription {
+    TableDescription {
+        attribute_definitions: vec![AttributeDefinition {
+            attribute_name: hash_key.into(),
+            attribute_type: ScalarAttributeType::S,
+        }],
+        table_name: table_name.into(),
+        key_schema: vec![KeySchemaElement {
+            attribute_name: hash_key.into(),
+            key_type: KeyType::Hash,
+        }],
+        table_status: TableStatus::Active,
+        creation_date_time: DateTime::default(),
+        provisioned_throughput: None,
+        total_size_bytes: 0,
+        item_count: 0,
+        table_arn: format!("arn:aws:dynamodb:us-east-1:123456789012:table/{table_name}"),
+        local_secondary_indexes: None,
+        global_secondary_indexes: None,
+        key_schema_type: KeySchemaType::Hash(hash_key.into()),
+        ttl_attribute_name: None,
+        kinesis_streaming_destinations: vec![],
+        contributor_insights: vec![],
+        auto_scaling_targets: vec![],
+        unavailable_details: vec![],
+        missing_fields: vec![],
+    }
+}
+
+pub fn item(attributes: Vec<(&str, Attribute)>) -> Item {
+    let attributes: HashMap<_, _> = attributes
+        .into_iter()
+        .map(|(name, attr)| (name.to_string(), attr))
+        .collect();
+    Item { attributes }
+}
@@ -1,3 +1,6 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use chrono::Local;
 use ratatui::{
     crossterm::event::KeyEvent,
     layout::{Margin, Rect},
@@ -13,24 +16,50 @@ use crate::{
     color::ColorTheme,
     config::UiTableConfig,
     data::{
-        list_attribute_keys, Attribute, Item, KeySchemaType, RawAttributeJsonWrapper, RawJsonItem,
-        TableDescription, TableInsight,
+        expected_attribute_types, list_attribute_keys, new_item_template, parse_key,
+        parse_raw_json_attribute_value, parse_raw_json_attributes, sort_items_by_attribute,
+        to_key_attributes, to_plain_json_string, Attribute, AttributeType, ColumnStats, Item,
+        ItemsSource, KeySchemaType, LoadStats, LoadedItems, Permission, QueryOptions,
+        RawAttributeJsonWrapper, RawJsonItem, ScanOptions, TableDescription, TableInsight,
+        TablePermissions, WriteRequest,
     },
+    error::{AppError, AppResult},
     event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    export::csv_value,
     handle_user_events, handle_user_events_with_default,
     help::{
-        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
-        SpansWithPriority,
+        build_help_spans, build_short_help_spans, disable_help_spans, BuildHelpsItem,
+        BuildShortHelpsItem, Spans, SpansWithPriority,
+    },
+    state::{SavedFilter, TableViewPreferences},
+    util::{
+        decompress, filtered_count_string, format_thousands, parse_duration, paste_to_input,
+        text_width, to_base64_str, Compression,
+    },
+    view::common::{
+        attribute_to_spans, cut_spans_by_width, is_select_statement, links_in_lines,
+        open_links_dialog, to_highlighted_lines, ttl_attribute_to_spans, underline_links,
+    },
+    widget::{
+        CellItem, ColumnStatsDialog, ScrollLines, ScrollLinesOptions, ScrollLinesState, Table,
+        TableState,
     },
-    view::common::{attribute_to_spans, cut_spans_by_width, to_highlighted_lines},
-    widget::{CellItem, ScrollLines, ScrollLinesOptions, ScrollLinesState, Table, TableState},
 };
 
 const ELLIPSIS: &str = "...";
+const COLUMN_STATS_SAMPLE_COUNT: usize = 5;
+const VALUE_CONDITION_MAX_WIDTH: usize = 30;
 
 pub struct TableView {
     table_description: TableDescription,
     items: Vec<Item>,
+    source: ItemsSource,
+    stats: LoadStats,
+    // attribute name -> type, cells of other types are highlighted as mismatches
+    expected_types: HashMap<String, AttributeType>,
+    permissions: TablePermissions,
+    environment: Option<String>,
+    pane_focused: bool,
 
     config: UiTableConfig,
     theme: ColorTheme,
@@ -41,11 +70,29 @@ pub struct TableView {
     header_row_cells: Vec<Cell<'static>>,
     table_state: TableState,
     attr_expanded: bool,
+    attr_full_screen: bool,
     attr_scroll_lines_state: ScrollLinesState,
+    // shown over the table until any key is pressed
+    column_stats: Option<ColumnStats>,
 
     filter_state: FilterState,
     filter_input: Input,
+    // attribute name and value (None for undefined) selected by filter by value,
+    // applied in addition to the filter input
+    value_filter: Option<(String, Option<Attribute>)>,
     view_indices: Vec<usize>,
+    reversed: bool,
+    // attribute name and whether the order is descending
+    sort: Option<(String, bool)>,
+    preferences: TableViewPreferences,
+    persist_preferences: bool,
+    // attributes not used by any of the filtered items, hidden while the filter is applied
+    unused_columns: BTreeSet<String>,
+
+    grouped: bool,
+    collapsed_groups: HashSet<String>,
+    groups: Vec<ItemGroup>,
+    rows: Vec<TableRow>,
 }
 
 enum FilterState {
@@ -54,6 +101,16 @@ enum FilterState {
     Filtered,
 }
 
+enum TableRow {
+    Item(usize),
+    Group(usize),
+}
+
+struct ItemGroup {
+    key: String,
+    cell_items: Vec<CellItem<'static>>,
+}
+
 struct TableViewHelps {
     table: Vec<Spans>,
     table_filtered: Vec<Spans>,
@@ -66,22 +123,40 @@ struct TableViewHelps {
 impl TableView {
     pub fn new(
         table_description: TableDescription,
-        items: Vec<Item>,
+        loaded: LoadedItems,
+        source: ItemsSource,
         mapper: &UserEventMapper,
         config: UiTableConfig,
         theme: ColorTheme,
         tx: Sender,
     ) -> Self {
-        let (table_state, row_cell_items, header_row_cells) =
-            new_table_state(&table_description, &items, &config, theme);
+        let LoadedItems { items, stats } = loaded;
+        let preferences = TableViewPreferences::default();
+        let expected_types = expected_attribute_types(&table_description, &items);
+        let (table_state, row_cell_items, header_row_cells) = new_table_state(
+            &table_description,
+            &items,
+            &expected_types,
+            &preferences,
+            &BTreeSet::new(),
+            &config,
+            theme,
+        );
         let helps = TableViewHelps::new(mapper, theme);
         let attr_scroll_lines_state =
             ScrollLinesState::new(vec![], ScrollLinesOptions::new(false, false));
-        let view_indices = (0..items.len()).collect();
+        let view_indices: Vec<usize> = (0..items.len()).collect();
+        let rows = view_indices.iter().map(|&i| TableRow::Item(i)).collect();
 
         TableView {
             table_description,
             items,
+            source,
+            stats,
+            expected_types,
+            permissions: TablePermissions::default(),
+            environment: None,
+            pane_focused: true,
 
             config,
             theme,
@@ -92,16 +167,33 @@ impl TableView {
             header_row_cells,
             table_state,
             attr_expanded: false,
+            attr_full_screen: false,
             attr_scroll_lines_state,
+            column_stats: None,
             filter_state: FilterState::None,
             filter_input: Input::default(),
+            value_filter: None,
             view_indices,
+            reversed: false,
+            sort: None,
+            preferences,
+            persist_preferences: false,
+            unused_columns: BTreeSet::new(),
+            grouped: false,
+            collapsed_groups: HashSet::new(),
+            groups: Vec::new(),
+            rows,
         }
     }
 }
 
 impl TableView {
     pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
+        if self.column_stats.is_some() {
+            self.column_stats = None;
+            return;
+        }
+
         if let FilterState::Filtering = self.filter_state {
             handle_user_events_with_default! { user_events =>
                 UserEvent::Confirm => {
@@ -152,12 +244,28 @@ impl TableView {
                     UserEvent::ToggleNumber => {
                         self.attr_scroll_lines_state.toggle_number();
                     }
+                    UserEvent::ToggleFullScreen => {
+                        self.attr_full_screen = !self.attr_full_screen;
+                    }
+                    UserEvent::OpenLink => {
+                        let links = links_in_lines(self.attr_scroll_lines_state.lines());
+                        open_links_dialog(links, &self.tx);
+                    }
                     UserEvent::Reload => {
                         self.reload_table();
                     }
                     UserEvent::CopyToClipboard => {
                         self.copy_to_clipboard();
                     }
+                    UserEvent::SaveToFile => {
+                        self.save_to_file();
+                    }
+                    UserEvent::PrintAndQuit => {
+                        self.print_and_quit();
+                    }
+                    UserEvent::PipeToCommand => {
+                        self.pipe_to_command();
+                    }
                     UserEvent::Help => {
                         self.open_help();
                     }
@@ -222,28 +330,141 @@ impl TableView {
                     self.reset_filter();
                 }
                 UserEvent::Confirm => {
-                    self.open_item();
+                    if self.selected_group().is_some() {
+                        self.toggle_selected_group();
+                    } else {
+                        self.open_item();
+                    }
                 }
                 UserEvent::Insight => {
                     self.open_table_insight();
                 }
+                UserEvent::ColumnStats => {
+                    self.open_column_stats();
+                }
+                UserEvent::FilterByValue => {
+                    self.filter_by_selected_value();
+                }
+                UserEvent::CountByValue => {
+                    self.count_selected_value();
+                }
+                UserEvent::Validate => {
+                    self.validate_items();
+                }
+                UserEvent::Compare => {
+                    self.compare_items();
+                }
+                UserEvent::SideBySide => {
+                    self.open_side_by_side();
+                }
+                UserEvent::JumpToReference => {
+                    self.jump_to_reference();
+                }
                 UserEvent::Expand => {
                     self.open_expand_selected_attr();
                 }
                 UserEvent::Widen => {
                     self.table_state.widen_col();
                     self.recalculate_cells();
+                    self.update_column_width_preference();
                 }
                 UserEvent::Narrow => {
                     self.table_state.narrow_col();
                     self.recalculate_cells();
+                    self.update_column_width_preference();
+                }
+                UserEvent::HideColumn => {
+                    self.hide_selected_column();
+                }
+                UserEvent::ResetColumns => {
+                    self.reset_columns();
                 }
                 UserEvent::Reload => {
                     self.reload_table();
                 }
+                UserEvent::RefreshItem => {
+                    self.refresh_item();
+                }
+                UserEvent::CheckKey => {
+                    self.check_key();
+                }
+                UserEvent::CreateItem => {
+                    self.create_item();
+                }
+                UserEvent::SetTtl => {
+                    self.set_ttl();
+                }
+                UserEvent::BulkUpdate => {
+                    self.set_attribute_of_visible_items();
+                }
+                UserEvent::DeleteItem => {
+                    self.delete_selected_item();
+                }
+                UserEvent::DeleteAllItems => {
+                    self.delete_visible_items();
+                }
+                UserEvent::ReverseOrder => {
+                    self.reverse_order();
+                }
+                UserEvent::SortByColumn => {
+                    self.sort_by_selected_column();
+                }
+                UserEvent::GroupByHashKey => {
+                    self.toggle_grouped();
+                }
+                UserEvent::NextSamePartition => {
+                    self.select_same_partition_item(true);
+                    self.table_state.update_table_state();
+                }
+                UserEvent::PrevSamePartition => {
+                    self.select_same_partition_item(false);
+                    self.table_state.update_table_state();
+                }
+                UserEvent::ExclusiveStartKey => {
+                    self.open_exclusive_start_key_dialog();
+                }
                 UserEvent::CopyToClipboard => {
                     self.copy_to_clipboard();
                 }
+                UserEvent::CopyTable => {
+                    self.copy_visible_items_as_tsv();
+                }
+                UserEvent::CopyColumn => {
+                    self.copy_selected_column();
+                }
+                UserEvent::SaveToFile => {
+                    self.save_to_file();
+                }
+                UserEvent::PrintAndQuit => {
+                    self.print_and_quit();
+                }
+                UserEvent::PipeToCommand => {
+                    self.pipe_to_command();
+                }
+                UserEvent::OpenInConsole => {
+                    self.open_table_in_console();
+                }
+                UserEvent::OpenLink => {
+                    self.open_selected_attr_links();
+                }
+                UserEvent::PartiQL => {
+                    self.open_partiql();
+                }
+                UserEvent::FilterBuilder => {
+                    self.open_filter_builder();
+                }
+                UserEvent::QueryBuilder => {
+                    self.open_query_builder();
+                }
+                UserEvent::SaveFilter => {
+                    self.save_filter();
+                }
+                UserEvent::OpenSavedFilters => {
+                    self.open_saved_filters();
+                }
+                UserEvent::KeyTemplates => {
+                    self.open_key_templates();
+                }
                 UserEvent::Help => {
                     self.open_help();
                 }
@@ -251,21 +472,86 @@ impl TableView {
         }
     }
 
+    pub fn handle_paste(&mut self, text: &str) {
+        if let FilterState::Filtering = self.filter_state {
+            paste_to_input(&mut self.filter_input, text);
+            self.filter_view_indices();
+            self.update_status_input();
+        }
+    }
+
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
-        let title = format!(" {} ", self.table_description.table_name);
+        if self.is_full_screen() {
+            self.render_full_screen_expanded_item(f, area);
+            return;
+        }
+
+        let mut title = match self.source {
+            ItemsSource::Scan(ScanOptions { filter: None, .. }) => {
+                format!(" {} ", self.table_description.table_name)
+            }
+            ItemsSource::Scan(ScanOptions {
+                filter: Some(_), ..
+            }) => {
+                format!(
+                    " {} (Filter expression) ",
+                    self.table_description.table_name
+                )
+            }
+            ItemsSource::Query(_) => {
+                format!(" {} (Query) ", self.table_description.table_name)
+            }
+            ItemsSource::Statement(_) => {
+                format!(" {} (PartiQL) ", self.table_description.table_name)
+            }
+        };
+        if self.reversed {
+            title.push_str("(Reversed) ");
+        }
+        if let Some((key, descending)) = &self.sort {
+            let direction = if *descending { "desc" } else { "asc" };
+            title.push_str(&format!("(Sorted by {key} {direction}) "));
+        }
+        if self.grouped {
+            title.push_str("(Grouped) ");
+        }
+        if let Some((key, value)) = &self.value_filter {
+            title.push_str(&format!(
+                "({}) ",
+                value_condition_string(key, value.as_ref())
+            ));
+        }
+        if !self.preferences.hidden_columns.is_empty() {
+            let hidden = self.preferences.hidden_columns.len();
+            title.push_str(&format!("({hidden} hidden) "));
+        }
+        if let Some(environment) = &self.environment {
+            title.push_str(&format!("[{environment}] "));
+        }
         let count = self.table_state.selected_count_string();
+        let filtered_count = filtered_count_string(self.view_indices.len(), self.items.len());
+        let fg = if self.pane_focused {
+            self.theme.fg
+        } else {
+            self.theme.disabled
+        };
         let block = Block::bordered()
             .title_top(Line::from(title).left_aligned())
             .title_top(Line::from(count).right_aligned())
-            .fg(self.theme.fg)
+            .title_bottom(Line::from(filtered_count).left_aligned())
+            .title_bottom(Line::from(format!(" {} ", self.stats.summary())).right_aligned())
+            .fg(fg)
             .bg(self.theme.bg);
         f.render_widget(block, area);
 
         let table_area = area.inner(Margin::new(2, 1));
         let filtered_row_cell_items: Vec<&Vec<CellItem<'static>>> = self
-            .view_indices
+            .rows
             .iter()
-            .map(|&i| &self.row_cell_items[i])
+            .map(|row| match row {
+                TableRow::Item(i) => &self.row_cell_items[*i],
+                TableRow::Group(g) => &self.groups[*g].cell_items,
+            })
             .collect();
         let query = self.filter_input.value();
         let table =
@@ -275,6 +561,11 @@ impl TableView {
         if self.attr_expanded {
             self.render_expanded_item(f, table_area);
         }
+
+        if let Some(stats) = &self.column_stats {
+            let dialog = ColumnStatsDialog::new(stats).theme(self.theme);
+            f.render_widget(dialog, area);
+        }
     }
 
     pub fn short_helps(&self) -> &[SpansWithPriority] {
@@ -287,6 +578,39 @@ impl TableView {
             }
         }
     }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_description.table_name
+    }
+
+    pub fn set_environment(&mut self, environment: String) {
+        self.environment = Some(environment);
+    }
+
+    pub fn set_pane_focused(&mut self, focused: bool) {
+        self.pane_focused = focused;
+    }
+
+    pub fn is_full_screen(&self) -> bool {
+        self.attr_expanded && self.attr_full_screen
+    }
+
+    // Whether key inputs are consumed by the filter input or the expanded attribute
+    pub fn is_capturing_input(&self) -> bool {
+        matches!(self.filter_state, FilterState::Filtering)
+            || self.attr_expanded
+            || self.column_stats.is_some()
+    }
+
+    pub fn position(&self) -> (usize, usize) {
+        let total = self.rows.len();
+        let current = if total == 0 {
+            0
+        } else {
+            self.table_state.selected_row + 1
+        };
+        (current, total)
+    }
 }
 
 impl TableViewHelps {
@@ -324,14 +648,47 @@ fn build_helps(
         BuildHelpsItem::new(UserEvent::GoToRight, "Select last column"),
         BuildHelpsItem::new(UserEvent::ScrollDown, "Scroll down"),
         BuildHelpsItem::new(UserEvent::ScrollUp, "Scroll up"),
-        BuildHelpsItem::new(UserEvent::Confirm, "Open selected item"),
+        BuildHelpsItem::new(UserEvent::Confirm, "Open selected item / Toggle group"),
         BuildHelpsItem::new(UserEvent::QuickFilter, "Filter items"),
+        BuildHelpsItem::new(UserEvent::OpenSavedFilters, "Open saved filters and queries"),
+        BuildHelpsItem::new(UserEvent::KeyTemplates, "Query with key template"),
         BuildHelpsItem::new(UserEvent::Expand, "Expand selected attribute"),
         BuildHelpsItem::new(UserEvent::Insight, "Open table insight"),
+        BuildHelpsItem::new(UserEvent::ColumnStats, "Show stats of selected column"),
+        BuildHelpsItem::new(UserEvent::FilterByValue, "Filter to items with selected value"),
+        BuildHelpsItem::new(UserEvent::CountByValue, "Count items with selected value"),
+        BuildHelpsItem::new(UserEvent::Validate, "Validate items against JSON Schema"),
+        BuildHelpsItem::new(UserEvent::Compare, "Compare items with PartiQL statement result"),
+        BuildHelpsItem::new(UserEvent::SideBySide, "Open side by side with another profile/region"),
+        BuildHelpsItem::new(UserEvent::JumpToReference, "Jump to item referred by selected attribute"),
         BuildHelpsItem::new(UserEvent::Widen, "Widen selected column"),
         BuildHelpsItem::new(UserEvent::Narrow, "Narrow selected column"),
+        BuildHelpsItem::new(UserEvent::HideColumn, "Hide selected column"),
+        BuildHelpsItem::new(UserEvent::ResetColumns, "Reset column widths and hidden columns"),
         BuildHelpsItem::new(UserEvent::Reload, "Reload table data"),
+        BuildHelpsItem::new(UserEvent::RefreshItem, "Refresh selected item"),
+        BuildHelpsItem::new(UserEvent::CheckKey, "Check if item with key exists"),
+        BuildHelpsItem::new(UserEvent::CreateItem, "Create new item"),
+        BuildHelpsItem::new(UserEvent::SetTtl, "Set TTL of selected item"),
+        BuildHelpsItem::new(UserEvent::BulkUpdate, "Set selected attribute of all visible items"),
+        BuildHelpsItem::new(UserEvent::DeleteItem, "Delete selected item"),
+        BuildHelpsItem::new(UserEvent::DeleteAllItems, "Delete all visible items"),
+        BuildHelpsItem::new(UserEvent::ReverseOrder, "Reverse item order"),
+        BuildHelpsItem::new(UserEvent::SortByColumn, "Sort by selected column (toggle asc/desc)"),
+        BuildHelpsItem::new(UserEvent::GroupByHashKey, "Toggle grouping by hash key"),
+        BuildHelpsItem::new(UserEvent::NextSamePartition, "Select next item with same hash key"),
+        BuildHelpsItem::new(UserEvent::PrevSamePartition, "Select previous item with same hash key"),
+        BuildHelpsItem::new(UserEvent::ExclusiveStartKey, "Resume from exclusive start key"),
         BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy selected item"),
+        BuildHelpsItem::new(UserEvent::CopyTable, "Copy visible items as TSV"),
+        BuildHelpsItem::new(UserEvent::CopyColumn, "Copy values of selected column"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save selected item to file"),
+        BuildHelpsItem::new(UserEvent::PrintAndQuit, "Quit and print selected item"),
+        BuildHelpsItem::new(UserEvent::PipeToCommand, "Pipe items to command as JSON Lines"),
+        BuildHelpsItem::new(UserEvent::OpenInConsole, "Open table in AWS console"),
+        BuildHelpsItem::new(UserEvent::OpenLink, "Open/copy links in selected attribute"),
+        BuildHelpsItem::new(UserEvent::PartiQL, "Open PartiQL editor"),
+        BuildHelpsItem::new(UserEvent::FilterBuilder, "Open filter expression builder"),
     ];
     #[rustfmt::skip]
     let table_filtered_helps = vec![
@@ -349,14 +706,48 @@ fn build_helps(
         BuildHelpsItem::new(UserEvent::GoToRight, "Select last column"),
         BuildHelpsItem::new(UserEvent::ScrollDown, "Scroll down"),
         BuildHelpsItem::new(UserEvent::ScrollUp, "Scroll up"),
-        BuildHelpsItem::new(UserEvent::Confirm, "Open selected item"),
+        BuildHelpsItem::new(UserEvent::Confirm, "Open selected item / Toggle group"),
         BuildHelpsItem::new(UserEvent::Reset, "Clear filter"),
+        BuildHelpsItem::new(UserEvent::SaveFilter, "Save current filter"),
+        BuildHelpsItem::new(UserEvent::OpenSavedFilters, "Open saved filters and queries"),
+        BuildHelpsItem::new(UserEvent::KeyTemplates, "Query with key template"),
         BuildHelpsItem::new(UserEvent::Expand, "Expand selected attribute"),
         BuildHelpsItem::new(UserEvent::Insight, "Open table insight"),
+        BuildHelpsItem::new(UserEvent::ColumnStats, "Show stats of selected column"),
+        BuildHelpsItem::new(UserEvent::FilterByValue, "Filter to items with selected value"),
+        BuildHelpsItem::new(UserEvent::CountByValue, "Count items with selected value"),
+        BuildHelpsItem::new(UserEvent::Validate, "Validate items against JSON Schema"),
+        BuildHelpsItem::new(UserEvent::Compare, "Compare items with PartiQL statement result"),
+        BuildHelpsItem::new(UserEvent::SideBySide, "Open side by side with another profile/region"),
+        BuildHelpsItem::new(UserEvent::JumpToReference, "Jump to item referred by selected attribute"),
         BuildHelpsItem::new(UserEvent::Widen, "Widen selected column"),
         BuildHelpsItem::new(UserEvent::Narrow, "Narrow selected column"),
+        BuildHelpsItem::new(UserEvent::HideColumn, "Hide selected column"),
+        BuildHelpsItem::new(UserEvent::ResetColumns, "Reset column widths and hidden columns"),
         BuildHelpsItem::new(UserEvent::Reload, "Reload table data"),
+        BuildHelpsItem::new(UserEvent::RefreshItem, "Refresh selected item"),
+        BuildHelpsItem::new(UserEvent::CheckKey, "Check if item with key exists"),
+        BuildHelpsItem::new(UserEvent::CreateItem, "Create new item"),
+        BuildHelpsItem::new(UserEvent::SetTtl, "Set TTL of selected item"),
+        BuildHelpsItem::new(UserEvent::BulkUpdate, "Set selected attribute of all visible items"),
+        BuildHelpsItem::new(UserEvent::DeleteItem, "Delete selected item"),
+        BuildHelpsItem::new(UserEvent::DeleteAllItems, "Delete all visible items"),
+        BuildHelpsItem::new(UserEvent::ReverseOrder, "Reverse item order"),
+        BuildHelpsItem::new(UserEvent::SortByColumn, "Sort by selected column (toggle asc/desc)"),
+        BuildHelpsItem::new(UserEvent::GroupByHashKey, "Toggle grouping by hash key"),
+        BuildHelpsItem::new(UserEvent::NextSamePartition, "Select next item with same hash key"),
+        BuildHelpsItem::new(UserEvent::PrevSamePartition, "Select previous item with same hash key"),
+        BuildHelpsItem::new(UserEvent::ExclusiveStartKey, "Resume from exclusive start key"),
         BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy selected item"),
+        BuildHelpsItem::new(UserEvent::CopyTable, "Copy visible items as TSV"),
+        BuildHelpsItem::new(UserEvent::CopyColumn, "Copy values of selected column"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save selected item to file"),
+        BuildHelpsItem::new(UserEvent::PrintAndQuit, "Quit and print selected item"),
+        BuildHelpsItem::new(UserEvent::PipeToCommand, "Pipe items to command as JSON Lines"),
+        BuildHelpsItem::new(UserEvent::OpenInConsole, "Open table in AWS console"),
+        BuildHelpsItem::new(UserEvent::OpenLink, "Open/copy links in selected attribute"),
+        BuildHelpsItem::new(UserEvent::PartiQL, "Open PartiQL editor"),
+        BuildHelpsItem::new(UserEvent::FilterBuilder, "Open filter expression builder"),
     ];
     #[rustfmt::skip]
     let attr_helps = vec![
@@ -372,8 +763,15 @@ fn build_helps(
         BuildHelpsItem::new(UserEvent::Left, "Scroll left"),
         BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
         BuildHelpsItem::new(UserEvent::ToggleNumber, "Toggle number"),
+        BuildHelpsItem::new(UserEvent::ToggleFullScreen, "Toggle full screen"),
+        BuildHelpsItem::new(UserEvent::OpenLink, "Open/copy links"),
         BuildHelpsItem::new(UserEvent::Reload, "Reload table data"),
+        BuildHelpsItem::new(UserEvent::ReverseOrder, "Reverse item order"),
+        BuildHelpsItem::new(UserEvent::ExclusiveStartKey, "Resume from exclusive start key"),
         BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy selected item"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save selected item to file"),
+        BuildHelpsItem::new(UserEvent::PrintAndQuit, "Quit and print selected item"),
+        BuildHelpsItem::new(UserEvent::PipeToCommand, "Pipe items to command as JSON Lines"),
     ];
     (
         build_help_spans(table_helps, mapper, theme),
@@ -416,6 +814,7 @@ fn build_short_helps(
         BuildShortHelpsItem::group(vec![UserEvent::ScrollDown, UserEvent::ScrollUp], "Scroll", 12),
         BuildShortHelpsItem::single(UserEvent::Confirm, "Open", 2),
         BuildShortHelpsItem::single(UserEvent::Reset, "Clear filter", 5),
+        BuildShortHelpsItem::single(UserEvent::SaveFilter, "Save filter", 13),
         BuildShortHelpsItem::single(UserEvent::Expand, "Expand", 4),
         BuildShortHelpsItem::single(UserEvent::Insight, "Insight", 3),
         BuildShortHelpsItem::single(UserEvent::CopyToClipboard, "Copy", 8),
@@ -442,6 +841,25 @@ fn build_short_helps(
 }
 
 impl TableView {
+    fn render_full_screen_expanded_item(&mut self, f: &mut Frame, area: Rect) {
+        let title = self
+            .table_state
+            .selected_col
+            .and_then(|col| self.attribute_keys().get(col).cloned())
+            .map(|key| format!(" {key} "))
+            .unwrap_or_default();
+        let scroll = ScrollLines::default()
+            .block(
+                Block::bordered()
+                    .border_set(border::DOUBLE)
+                    .title_top(Line::from(title).left_aligned())
+                    .fg(self.theme.fg)
+                    .bg(self.theme.bg),
+            )
+            .theme(&self.theme);
+        f.render_stateful_widget(scroll, area, &mut self.attr_scroll_lines_state);
+    }
+
     fn render_expanded_item(&mut self, f: &mut Frame, area: Rect) {
         if let Some((x, y)) = self.table_state.selected_item_position() {
             let x = area.left() + x;
@@ -494,17 +912,118 @@ impl TableView {
     }
 
     fn open_table_insight(&self) {
-        let insight = TableInsight::new(&self.table_description, &self.items);
-        self.tx.send(AppEvent::OpenTableInsight(insight));
+        let desc = self.table_description.clone();
+        self.tx
+            .send(AppEvent::OpenTableInsight(desc, self.items.clone()));
+    }
+
+    fn open_column_stats(&mut self) {
+        let Some(col) = self.table_state.selected_col else {
+            let msg = "Select a column to show the stats";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        };
+        let key = self.attribute_keys().swap_remove(col);
+        let items: Vec<&Item> = self.view_indices.iter().map(|&i| &self.items[i]).collect();
+        self.column_stats = Some(ColumnStats::new(&items, &key, COLUMN_STATS_SAMPLE_COUNT));
+    }
+
+    // The attribute name of the selected column and the value of the selected item
+    fn selected_cell(&self) -> Option<(String, Option<Attribute>)> {
+        let Some(col) = self.table_state.selected_col else {
+            let msg = "Select a column to use the value";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return None;
+        };
+        let item = self.current_selected_item()?;
+        let key = self.attribute_keys().swap_remove(col);
+        let value = item.attributes.get(&key).cloned();
+        Some((key, value))
+    }
+
+    fn filter_by_selected_value(&mut self) {
+        let Some((key, value)) = self.selected_cell() else {
+            return;
+        };
+        let selected_item_index = self.current_selected_item_index();
+        self.value_filter = Some((key, value));
+        self.filter_view_indices();
+        self.update_unused_columns();
+        if let Some(i) = selected_item_index {
+            self.select_item_row(i, 0);
+        }
+        self.table_state.update_table_state();
+    }
+
+    fn count_selected_value(&self) {
+        let Some((key, value)) = self.selected_cell() else {
+            return;
+        };
+        let count = self
+            .items
+            .iter()
+            .filter(|item| item.attributes.get(&key) == value.as_ref())
+            .count();
+        let msg = format!(
+            "{} of {} items have {}",
+            format_thousands(count),
+            format_thousands(self.items.len()),
+            value_condition_string(&key, value.as_ref()),
+        );
+        self.tx.send(AppEvent::NotifySuccess(msg));
+    }
+
+    fn compare_items(&self) {
+        let desc = self.table_description.clone();
+        let items = self.items.clone();
+        let on_submit =
+            Box::new(move |statement: String| compare_items_event(desc, items, statement));
+        let title = "Compare with PartiQL statement".into();
+        self.tx.send(AppEvent::OpenPrompt(title, on_submit));
+    }
+
+    fn open_side_by_side(&self) {
+        let desc = self.table_description.clone();
+        let loaded = LoadedItems {
+            items: self.items.clone(),
+            stats: self.stats,
+        };
+        let source = self.source.clone();
+        let on_submit = Box::new(move |environment: String| {
+            AppEvent::OpenSideBySide(desc, loaded, source, environment)
+        });
+        let title = "Open with profile@region (e.g. prod, prod@us-west-2, @eu-west-1)".into();
+        self.tx.send(AppEvent::OpenPrompt(title, on_submit));
+    }
+
+    fn jump_to_reference(&self) {
+        if let Some(col) = self.table_state.selected_col {
+            if let Some(selected_item) = self.current_selected_item() {
+                let key = &self.attribute_keys()[col];
+                if let Some(attr) = selected_item.attributes.get(key) {
+                    let table_name = self.table_description.table_name.clone();
+                    self.tx.send(AppEvent::JumpToReference(
+                        table_name,
+                        key.clone(),
+                        attr.clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn validate_items(&self) {
+        let desc = self.table_description.clone();
+        self.tx
+            .send(AppEvent::ValidateItems(desc, self.items.clone()));
     }
 
     fn open_expand_selected_attr(&mut self) {
         if let Some(col) = self.table_state.selected_col {
             if let Some(selected_item) = self.current_selected_item() {
-                let schema = &self.table_description.key_schema_type;
-                let key = &list_attribute_keys(&self.items, schema)[col];
+                let key = &self.attribute_keys()[col];
                 if let Some(attr) = selected_item.attributes.get(key) {
-                    let lines = get_raw_json_attribute_lines(attr, &self.theme);
+                    let lines = underline_links(get_raw_json_attribute_lines(attr, &self.theme));
                     let options = self.attr_scroll_lines_state.current_options();
                     self.attr_scroll_lines_state = ScrollLinesState::new(lines, options);
                     self.attr_expanded = true;
@@ -513,182 +1032,1077 @@ impl TableView {
         }
     }
 
+    fn open_selected_attr_links(&self) {
+        if let Some(col) = self.table_state.selected_col {
+            if let Some(selected_item) = self.current_selected_item() {
+                let key = &self.attribute_keys()[col];
+                if let Some(attr) = selected_item.attributes.get(key) {
+                    let lines = get_raw_json_attribute_lines(attr, &self.theme);
+                    open_links_dialog(links_in_lines(&lines), &self.tx);
+                }
+            }
+        }
+    }
+
     fn recalculate_cells(&mut self) {
         if let Some(col) = self.table_state.selected_col {
-            let attribute_keys =
-                list_attribute_keys(&self.items, &self.table_description.key_schema_type);
+            let attribute_keys = self.attribute_keys();
             let max_attribute_width = self.table_state.selected_col_width().unwrap();
             for (i, cell_items) in self.row_cell_items.iter_mut().enumerate() {
                 let item = &self.items[i];
                 let key = &attribute_keys[col];
+                let is_ttl = self.table_description.ttl_attribute_name.as_ref() == Some(key);
+                let expected_type = self.expected_types.get(key);
                 let (cell_item, _) = item
                     .attributes
                     .get(key)
-                    .map(|attr| attribute_to_cell_item(attr, max_attribute_width, &self.theme))
+                    .map(|attr| {
+                        let mismatched = is_type_mismatch(attr, expected_type);
+                        attribute_to_cell_item(
+                            attr,
+                            is_ttl,
+                            mismatched,
+                            max_attribute_width,
+                            &self.config,
+                            &self.theme,
+                        )
+                    })
                     .unwrap_or(undefined_cell_item(&self.theme));
                 cell_items[col] = cell_item;
             }
         }
     }
 
-    fn close_expand_selected_attr(&mut self) {
-        self.attr_expanded = false;
+    fn update_column_width_preference(&mut self) {
+        if let Some(col) = self.table_state.selected_col {
+            let key = self.attribute_keys().swap_remove(col);
+            let width = self.table_state.selected_col_width().unwrap();
+            self.preferences.column_widths.insert(key, width);
+            self.save_preferences();
+        }
     }
 
-    fn reload_table(&self) {
-        let desc = self.table_description.clone();
-        self.tx.send(AppEvent::LoadTableItems(desc));
+    fn hide_selected_column(&mut self) {
+        if let Some(col) = self.table_state.selected_col {
+            let key = self.attribute_keys().swap_remove(col);
+            let key_names = self.table_description.key_attribute_names(None);
+            if key_names.contains(&key) {
+                let msg = "Key attributes cannot be hidden";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+                return;
+            }
+            self.preferences.hidden_columns.insert(key);
+            self.rebuild_rows();
+            self.save_preferences();
+        }
     }
 
-    fn start_filtering(&mut self) {
-        match self.filter_state {
-            FilterState::None | FilterState::Filtered => {
-                self.filter_input.reset();
-                self.filter_state = FilterState::Filtering;
-                self.update_status_input();
-            }
-            FilterState::Filtering => {}
+    fn reset_columns(&mut self) {
+        if self.preferences.column_widths.is_empty() && self.preferences.hidden_columns.is_empty() {
+            return;
         }
+        self.preferences.column_widths.clear();
+        self.preferences.hidden_columns.clear();
+        self.rebuild_rows();
+        self.save_preferences();
     }
 
-    fn update_filter(&mut self, key_event: KeyEvent) {
-        let event = &ratatui::crossterm::event::Event::Key(key_event);
-        self.filter_input.handle_event(event);
+    // Rebuild all cells after the columns have been changed, keeping the same item selected
+    fn rebuild_rows(&mut self) {
+        let selected_item_index = self.current_selected_item_index();
+        self.rebuild_table_state();
         self.filter_view_indices();
-        self.update_status_input();
+        if let Some(i) = selected_item_index {
+            self.select_item_row(i, 0);
+        }
+        self.table_state.update_table_state();
     }
 
-    fn update_status_input(&mut self) {
-        let query = format!("/{}", self.filter_input.value());
-        let cursor_pos = self.filter_input.cursor() as u16 + 1; // "/"
-        self.tx
-            .send(AppEvent::UpdateStatusInput(query, Some(cursor_pos)));
+    fn rebuild_table_state(&mut self) {
+        self.expected_types = expected_attribute_types(&self.table_description, &self.items);
+        let (table_state, row_cell_items, header_row_cells) = new_table_state(
+            &self.table_description,
+            &self.items,
+            &self.expected_types,
+            &self.preferences,
+            &self.unused_columns,
+            &self.config,
+            self.theme,
+        );
+        self.table_state = table_state;
+        self.row_cell_items = row_cell_items;
+        self.header_row_cells = header_row_cells;
     }
 
-    fn apply_filter(&mut self) {
-        if self.filter_input.value().is_empty() {
-            self.filter_state = FilterState::None;
-        } else {
-            self.filter_state = FilterState::Filtered;
+    fn attribute_keys(&self) -> Vec<String> {
+        visible_attribute_keys(
+            &self.items,
+            &self.table_description.key_schema_type,
+            &self.preferences,
+            &self.unused_columns,
+        )
+    }
+
+    // Restore the preferences saved when the table was last opened,
+    // and save them whenever they are changed from now on
+    pub fn set_preferences(&mut self, preferences: TableViewPreferences) {
+        let reversed = preferences.reversed;
+        let filter = preferences.filter.clone();
+        self.preferences = preferences;
+        self.rebuild_table_state();
+        self.filter_view_indices();
+        if reversed {
+            self.reverse_items();
         }
-        if self.view_indices.is_empty() {
-            self.reset_filter();
-            return;
+        if let Some(filter) = filter {
+            self.apply_filter_query(filter);
         }
-        self.filter_view_indices();
-        self.tx.send(AppEvent::ClearStatus);
+        self.persist_preferences = true;
     }
 
-    fn reset_filter(&mut self) {
-        match self.filter_state {
-            FilterState::Filtering | FilterState::Filtered => {
-                self.filter_input.reset();
-                self.filter_state = FilterState::None;
-                let orig_idx = self
-                    .view_indices
-                    .get(self.table_state.selected_row)
-                    .cloned();
-                let before_offset_idx = self.table_state.selected_row_offset_index();
-                self.filter_view_indices();
-                if let Some(orig_idx) = orig_idx {
-                    self.table_state.select_index(orig_idx, before_offset_idx);
-                    self.table_state.update_table_state();
-                }
-                self.tx.send(AppEvent::ClearStatus);
-            }
-            FilterState::None => {}
+    fn save_preferences(&self) {
+        if self.persist_preferences {
+            let table_name = self.table_description.table_name.clone();
+            let preferences = self.preferences.clone();
+            self.tx
+                .send(AppEvent::SaveViewPreferences(table_name, preferences));
         }
     }
 
-    fn filter_view_indices(&mut self) {
-        let query = self.filter_input.value();
-        self.view_indices = if query.is_empty() {
-            (0..self.items.len()).collect()
-        } else {
-            self.row_cell_items
-                .iter()
-                .enumerate()
-                .filter(|(_, cell_items)| {
-                    cell_items
-                        .iter()
-                        .any(|cell_item| !cell_item.matched_indices(query).is_empty())
-                })
-                .map(|(i, _)| i)
-                .collect()
+    fn update_filter_preference(&mut self) {
+        let filter = match self.filter_state {
+            FilterState::Filtered => Some(self.filter_input.value().to_string()),
+            FilterState::None | FilterState::Filtering => None,
         };
-        self.table_state = self
-            .table_state
-            .with_new_total_rows(self.view_indices.len());
+        if self.preferences.filter != filter {
+            self.preferences.filter = filter;
+            self.save_preferences();
+        }
     }
 
-    fn current_selected_item(&self) -> Option<&Item> {
-        self.view_indices
-            .get(self.table_state.selected_row)
-            .and_then(|&idx| self.items.get(idx))
+    fn close_expand_selected_attr(&mut self) {
+        self.attr_expanded = false;
+        self.attr_full_screen = false;
     }
 
-    fn copy_to_clipboard(&self) {
-        if let Some(selected_item) = self.current_selected_item() {
-            let schema = &self.table_description.key_schema_type;
-
-            let (name, content) = if let Some(col) = self.table_state.selected_col {
-                let key = &list_attribute_keys(&self.items, schema)[col];
-                if let Some(attr) = selected_item.attributes.get(key) {
-                    if self.attr_expanded {
-                        ("selected attribute", get_raw_json_attribute_string(attr))
-                    } else {
-                        ("selected attribute", attr.to_simple_string())
-                    }
-                } else {
-                    return;
-                }
-            } else {
-                let raw_json_string = get_raw_json_string(selected_item, schema);
-                ("selected item", raw_json_string)
-            };
-
-            self.tx
-                .send(AppEvent::CopyToClipboard(name.into(), content));
+    fn reload_table(&self) {
+        let desc = self.table_description.clone();
+        match &self.source {
+            ItemsSource::Scan(options) => {
+                let options = ScanOptions {
+                    limit: None,
+                    ..options.clone()
+                };
+                self.tx.send(AppEvent::LoadTableItems(desc, options));
+            }
+            ItemsSource::Query(options) => {
+                self.tx
+                    .send(AppEvent::QueryTableItems(desc, options.clone()));
+            }
+            ItemsSource::Statement(statement) => {
+                self.tx
+                    .send(AppEvent::ExecuteStatement(desc, statement.clone()));
+            }
         }
     }
 
-    fn open_help(&self) {
-        if self.attr_expanded {
-            self.tx.send(AppEvent::OpenHelp(self.helps.attr.clone()))
-        } else {
-            match self.filter_state {
-                FilterState::None => {
-                    self.tx.send(AppEvent::OpenHelp(self.helps.table.clone()));
+    fn refresh_item(&self) {
+        if let Some(item) = self.current_selected_item() {
+            let schema = &self.table_description.key_schema_type;
+            match to_key_attributes(item, schema) {
+                Some(key) => {
+                    let desc = self.table_description.clone();
+                    self.tx.send(AppEvent::RefreshItem(desc, key));
                 }
-                FilterState::Filtering | FilterState::Filtered => {
-                    self.tx
-                        .send(AppEvent::OpenHelp(self.helps.table_filtered.clone()));
+                None => {
+                    let msg = "Selected item does not contain all key attributes";
+                    self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
                 }
             }
         }
     }
-}
-
-fn new_table_state(
-    table_description: &TableDescription,
-    items: &[Item],
-    config: &UiTableConfig,
-    theme: ColorTheme,
-) -> (TableState, Vec<Vec<CellItem<'static>>>, Vec<Cell<'static>>) {
-    let attribute_keys = list_attribute_keys(items, &table_description.key_schema_type);
-    let total_rows = items.len();
-    let total_cols = attribute_keys.len();
 
-    let mut max_width_vec: Vec<usize> = vec![0; total_cols];
+    fn create_item(&self) {
+        let insight = TableInsight::new(&self.table_description, &self.items);
+        let template = new_item_template(&self.table_description, &insight);
+        let schema = &self.table_description.key_schema_type;
+        let content = serde_json::to_string_pretty(&RawJsonItem::new(&template, schema)).unwrap();
+        let desc = self.table_description.clone();
+        self.tx.send(AppEvent::EditNewItem(desc, content));
+    }
 
-    let mut row_cell_items: Vec<Vec<CellItem>> = Vec::with_capacity(total_rows);
+    fn set_ttl(&self) {
+        let Some(item) = self.current_selected_item().cloned() else {
+            return;
+        };
+        if self.table_description.ttl_attribute_name.is_none() {
+            let msg = format!(
+                "TTL is not enabled on table {}",
+                self.table_description.table_name
+            );
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        let desc = self.table_description.clone();
+        let on_submit = Box::new(move |s: String| {
+            let request = parse_duration(&s).and_then(|d| {
+                let expires_at = Local::now().timestamp() + d.as_secs() as i64;
+                desc.set_ttl_request(&item, expires_at)
+            });
+            match request {
+                Ok(request) => AppEvent::WriteItems(desc, vec![request]),
+                Err(e) => AppEvent::NotifyWarning(e),
+            }
+        });
+        let title = "Expire in (e.g. 30m, 12h, 7d)".into();
+        self.tx.send(AppEvent::OpenPrompt(title, on_submit));
+    }
+
+    // A point GetItem for the typed key, cheaper than scanning to confirm that a write has been done
+    fn check_key(&self) {
+        let desc = self.table_description.clone();
+        let example = desc
+            .key_attribute_names(None)
+            .iter()
+            .map(|name| format!("{name}=..."))
+            .collect::<Vec<_>>()
+            .join(",");
+        let title = format!("Check key (e.g. {example})");
+        let on_submit = Box::new(move |s: String| match parse_key(&desc, &s) {
+            Ok(key) => AppEvent::CheckItemExists(desc, key),
+            Err(e) => AppEvent::NotifyWarning(e),
+        });
+        self.tx.send(AppEvent::OpenPrompt(title, on_submit));
+    }
+
+    fn set_attribute_of_visible_items(&self) {
+        let Some(col) = self.table_state.selected_col else {
+            return;
+        };
+        let name = self.attribute_keys().swap_remove(col);
+        if self
+            .table_description
+            .key_attribute_names(None)
+            .contains(&name)
+        {
+            let msg = "Key attributes cannot be updated";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        let items: Vec<Item> = self
+            .view_indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+        if items.is_empty() {
+            return;
+        }
+        let desc = self.table_description.clone();
+        let title = format!(
+            "Set {name} of {} items to (DynamoDB JSON, e.g. {{\"S\": \"value\"}})",
+            items.len()
+        );
+        let on_submit = Box::new(move |s: String| {
+            let requests = parse_raw_json_attribute_value(&s).and_then(|value| {
+                items
+                    .iter()
+                    .map(|item| desc.set_attribute_request(item, name.clone(), value.clone()))
+                    .collect::<AppResult<Vec<_>>>()
+            });
+            match requests {
+                Ok(requests) => AppEvent::WriteItems(desc, requests),
+                Err(e) => AppEvent::NotifyWarning(e),
+            }
+        });
+        self.tx.send(AppEvent::OpenPrompt(title, on_submit));
+    }
+
+    fn delete_selected_item(&self) {
+        if let Some(item) = self.current_selected_item() {
+            self.delete_items(vec![item]);
+        }
+    }
+
+    fn delete_visible_items(&self) {
+        let items = self.view_indices.iter().map(|&i| &self.items[i]).collect();
+        self.delete_items(items);
+    }
+
+    fn delete_items(&self, items: Vec<&Item>) {
+        if self.permissions.delete_item == Permission::Denied {
+            let msg = format!(
+                "You do not have permission to delete items from {}",
+                self.table_description.table_name
+            );
+            self.tx.send(AppEvent::NotifyError(AppError::msg(msg)));
+            return;
+        }
+        let schema = &self.table_description.key_schema_type;
+        let requests: Option<Vec<_>> = items
+            .into_iter()
+            .map(|item| {
+                to_key_attributes(item, schema).map(|key| WriteRequest::Delete {
+                    key,
+                    item: item.clone(),
+                })
+            })
+            .collect();
+        match requests {
+            Some(requests) => {
+                let desc = self.table_description.clone();
+                self.tx.send(AppEvent::WriteItems(desc, requests));
+            }
+            None => {
+                let msg = "Some items do not contain all key attributes";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+        }
+    }
+
+    pub fn set_permissions(&mut self, permissions: TablePermissions) {
+        self.permissions = permissions;
+    }
+
+    // Replace the items with the given keys (or remove them if they no longer exist)
+    // without reloading the whole table
+    pub fn update_items(&mut self, updates: Vec<(BTreeMap<String, Attribute>, Option<Item>)>) {
+        let schema = self.table_description.key_schema_type.clone();
+        let selected_key = self
+            .current_selected_item()
+            .and_then(|item| to_key_attributes(item, &schema));
+        let selected_item_index = self.current_selected_item_index();
+        let offset_index = self.table_state.selected_row_offset_index();
+        let old_attribute_keys = self.attribute_keys();
+
+        let mut updated_indices = Vec::new();
+        let mut rows_changed = false;
+        for (key, item) in updates {
+            let index = self
+                .items
+                .iter()
+                .position(|i| to_key_attributes(i, &schema).as_ref() == Some(&key));
+            match (index, item) {
+                (Some(index), Some(item)) => {
+                    self.items[index] = item;
+                    updated_indices.push(index);
+                }
+                (Some(index), None) => {
+                    self.items.remove(index);
+                    rows_changed = true;
+                }
+                (None, Some(item)) => {
+                    // newly created items are appended to the end
+                    self.items.push(item);
+                    rows_changed = true;
+                }
+                (None, None) => {}
+            }
+        }
+
+        let attribute_keys = self.attribute_keys();
+        let expected_types = expected_attribute_types(&self.table_description, &self.items);
+        if !rows_changed
+            && attribute_keys == old_attribute_keys
+            && expected_types == self.expected_types
+        {
+            for index in updated_indices {
+                let item = &self.items[index];
+                let cell_items = attribute_keys
+                    .iter()
+                    .map(|key| {
+                        let is_ttl =
+                            self.table_description.ttl_attribute_name.as_ref() == Some(key);
+                        let expected_type = self.expected_types.get(key);
+                        item.attributes
+                            .get(key)
+                            .map(|attr| {
+                                attribute_to_cell_item(
+                                    attr,
+                                    is_ttl,
+                                    is_type_mismatch(attr, expected_type),
+                                    self.config.max_attribute_width,
+                                    &self.config,
+                                    &self.theme,
+                                )
+                            })
+                            .unwrap_or(undefined_cell_item(&self.theme))
+                            .0
+                    })
+                    .collect();
+                self.row_cell_items[index] = cell_items;
+            }
+        } else {
+            self.rebuild_table_state();
+        }
+
+        self.filter_view_indices();
+
+        // keep the same item selected, or the item at the same position if it has been removed
+        let selected_item_index = selected_key
+            .and_then(|key| {
+                self.items
+                    .iter()
+                    .position(|i| to_key_attributes(i, &schema).as_ref() == Some(&key))
+            })
+            .or(selected_item_index.map(|i| i.min(self.items.len().saturating_sub(1))));
+        if let Some(i) = selected_item_index {
+            self.select_item_row(i, offset_index);
+        }
+    }
+
+    fn reverse_order(&mut self) {
+        self.reverse_items();
+        self.preferences.reversed = self.reversed;
+        self.save_preferences();
+    }
+
+    fn reverse_items(&mut self) {
+        self.items.reverse();
+        self.row_cell_items.reverse();
+        self.reversed = !self.reversed;
+
+        // keep the same item selected
+        let selected_item_index = self.current_selected_item_index();
+        self.filter_view_indices();
+        if let Some(i) = selected_item_index {
+            self.select_item_row(self.items.len() - 1 - i, 0);
+        }
+    }
+
+    // Sort by the selected column in ascending order, or toggle the direction if already sorted by it
+    fn sort_by_selected_column(&mut self) {
+        let Some(col) = self.table_state.selected_col else {
+            let msg = "Select a column to sort by";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        };
+        let key = self.attribute_keys().swap_remove(col);
+        let descending = matches!(&self.sort, Some((k, false)) if *k == key);
+        let order = sort_items_by_attribute(
+            &self.items,
+            &key,
+            descending,
+            self.config.sort_undefined_first,
+        );
+
+        let selected_item_index = self.current_selected_item_index();
+        self.items = reorder(std::mem::take(&mut self.items), &order);
+        self.row_cell_items = reorder(std::mem::take(&mut self.row_cell_items), &order);
+        self.sort = Some((key, descending));
+
+        // keep the same item selected
+        self.filter_view_indices();
+        if let Some(i) = selected_item_index.and_then(|i| order.iter().position(|&j| j == i)) {
+            self.select_item_row(i, 0);
+        }
+        self.table_state.update_table_state();
+    }
+
+    fn open_exclusive_start_key_dialog(&self) {
+        if let ItemsSource::Statement(_) = self.source {
+            let msg = "Exclusive start key is not available for PartiQL results";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+
+        let mut items = Vec::new();
+        if let Some(item) = self.current_selected_item() {
+            let index_name = match &self.source {
+                ItemsSource::Query(options) => options.index_name.as_deref(),
+                _ => None,
+            };
+            let key = self
+                .table_description
+                .key_attribute_names(index_name)
+                .into_iter()
+                .filter_map(|name| item.attributes.get(&name).map(|a| (name, a.clone())))
+                .collect();
+            let desc = self.table_description.clone();
+            let event = resume_from_key_event(desc, &self.source, key);
+            items.push(("Selected item".into(), event));
+        }
+
+        let desc = self.table_description.clone();
+        let source = self.source.clone();
+        let on_submit = Box::new(move |s: String| match parse_raw_json_attributes(&s) {
+            Ok(key) => resume_from_key_event(desc, &source, key),
+            Err(e) => AppEvent::NotifyWarning(e),
+        });
+        let title = "Exclusive start key (DynamoDB JSON)".into();
+        items.push((
+            "Enter key...".into(),
+            AppEvent::OpenPrompt(title, on_submit),
+        ));
+        items.push(("Cancel".into(), AppEvent::ClearStatus));
+
+        self.tx
+            .send(AppEvent::OpenSelectDialog("Resume from".into(), items));
+    }
+
+    fn start_filtering(&mut self) {
+        match self.filter_state {
+            FilterState::None | FilterState::Filtered => {
+                self.filter_input.reset();
+                self.filter_state = FilterState::Filtering;
+                self.update_unused_columns();
+                self.update_status_input();
+            }
+            FilterState::Filtering => {}
+        }
+    }
+
+    fn update_filter(&mut self, key_event: KeyEvent) {
+        let event = &ratatui::crossterm::event::Event::Key(key_event);
+        self.filter_input.handle_event(event);
+        self.filter_view_indices();
+        self.update_status_input();
+    }
+
+    fn update_status_input(&mut self) {
+        let query = format!("/{}", self.filter_input.value());
+        let cursor_pos = self.filter_input.cursor() as u16 + 1; // "/"
+        self.tx
+            .send(AppEvent::UpdateStatusInput(query, Some(cursor_pos)));
+    }
+
+    fn apply_filter(&mut self) {
+        if self.filter_input.value().is_empty() {
+            self.filter_state = FilterState::None;
+        } else {
+            self.filter_state = FilterState::Filtered;
+        }
+        if self.view_indices.is_empty() {
+            self.reset_filter();
+            return;
+        }
+        self.filter_view_indices();
+        self.update_unused_columns();
+        self.update_filter_preference();
+        self.tx.send(AppEvent::ClearStatus);
+    }
+
+    fn reset_filter(&mut self) {
+        let value_filtered = self.value_filter.take().is_some();
+        match self.filter_state {
+            FilterState::None if !value_filtered => {}
+            _ => {
+                self.filter_input.reset();
+                self.filter_state = FilterState::None;
+                let orig_idx = self.current_selected_item_index();
+                let before_offset_idx = self.table_state.selected_row_offset_index();
+                self.filter_view_indices();
+                self.update_unused_columns();
+                if let Some(orig_idx) = orig_idx {
+                    self.select_item_row(orig_idx, before_offset_idx);
+                    self.table_state.update_table_state();
+                }
+                self.update_filter_preference();
+                self.tx.send(AppEvent::ClearStatus);
+            }
+        }
+    }
+
+    pub fn apply_filter_query(&mut self, query: String) {
+        self.filter_input = Input::new(query.clone());
+        self.filter_state = FilterState::Filtered;
+        self.filter_view_indices();
+        if self.view_indices.is_empty() {
+            self.reset_filter();
+            let msg = format!("No items matched filter: {query}");
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        self.update_unused_columns();
+        self.update_filter_preference();
+    }
+
+    // While a filter is applied, only the attributes used by the filtered items are shown,
+    // so that filtering by an entity type in a single-table design shows the columns of that entity
+    fn update_unused_columns(&mut self) {
+        let filtered =
+            matches!(self.filter_state, FilterState::Filtered) || self.value_filter.is_some();
+        let unused_columns = if filtered && self.config.hide_unused_columns_when_filtered {
+            let used: HashSet<&String> = self
+                .view_indices
+                .iter()
+                .flat_map(|&i| self.items[i].attributes.keys())
+                .collect();
+            list_attribute_keys(&self.items, &self.table_description.key_schema_type)
+                .into_iter()
+                .filter(|key| !used.contains(key))
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+        if unused_columns != self.unused_columns {
+            self.unused_columns = unused_columns;
+            self.rebuild_rows();
+        }
+    }
+
+    fn save_filter(&self) {
+        if let FilterState::Filtered = self.filter_state {
+            let table_name = self.table_description.table_name.clone();
+            let query = self.filter_input.value().to_string();
+            let on_submit =
+                Box::new(move |name| AppEvent::SaveFilter(table_name, SavedFilter { name, query }));
+            self.tx
+                .send(AppEvent::OpenPrompt("Filter name".into(), on_submit));
+        }
+    }
+
+    fn open_saved_filters(&self) {
+        let desc = self.table_description.clone();
+        self.tx.send(AppEvent::OpenSavedFilters(desc));
+    }
+
+    fn open_key_templates(&self) {
+        let desc = self.table_description.clone();
+        self.tx.send(AppEvent::OpenKeyTemplates(desc));
+    }
+
+    fn filter_view_indices(&mut self) {
+        let query = self.filter_input.value();
+        let value_filter = self.value_filter.as_ref();
+        self.view_indices = self
+            .row_cell_items
+            .iter()
+            .enumerate()
+            .filter(|(_, cell_items)| {
+                query.is_empty()
+                    || cell_items
+                        .iter()
+                        .any(|cell_item| !cell_item.matched_indices(query).is_empty())
+            })
+            .filter(|(i, _)| {
+                value_filter
+                    .is_none_or(|(key, value)| self.items[*i].attributes.get(key) == value.as_ref())
+            })
+            .map(|(i, _)| i)
+            .collect();
+        self.update_rows();
+        self.table_state = self.table_state.with_new_total_rows(self.rows.len());
+    }
+
+    fn update_rows(&mut self) {
+        if !self.grouped {
+            self.groups.clear();
+            self.rows = self
+                .view_indices
+                .iter()
+                .map(|&i| TableRow::Item(i))
+                .collect();
+            return;
+        }
+
+        let hash_key = self.table_description.key_schema_type.hash_key_name();
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for &i in &self.view_indices {
+            let key = self.items[i]
+                .attributes
+                .get(hash_key)
+                .map(|attr| attr.to_simple_string())
+                .unwrap_or_else(|| "-".into());
+            match groups.last_mut() {
+                Some((last_key, indices)) if *last_key == key => indices.push(i),
+                _ => groups.push((key, vec![i])),
+            }
+        }
+
+        let total_cols = self.header_row_cells.len();
+        self.groups.clear();
+        self.rows.clear();
+        for (g, (key, indices)) in groups.into_iter().enumerate() {
+            let collapsed = self.collapsed_groups.contains(&key);
+            self.groups.push(ItemGroup::new(
+                key,
+                indices.len(),
+                collapsed,
+                total_cols,
+                &self.theme,
+            ));
+            self.rows.push(TableRow::Group(g));
+            if !collapsed {
+                self.rows.extend(indices.into_iter().map(TableRow::Item));
+            }
+        }
+    }
+
+    fn toggle_grouped(&mut self) {
+        if let KeySchemaType::Hash(_) = self.table_description.key_schema_type {
+            let msg = "Grouping is only available for tables with a sort key";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        let selected_item_index = self.current_selected_item_index();
+        self.grouped = !self.grouped;
+        self.collapsed_groups.clear();
+        self.update_rows();
+        self.table_state = self.table_state.with_new_total_rows(self.rows.len());
+        if let Some(i) = selected_item_index {
+            self.select_item_row(i, 0);
+        }
+        self.table_state.update_table_state();
+    }
+
+    fn toggle_selected_group(&mut self) {
+        if let Some(group) = self.selected_group() {
+            let key = group.key.clone();
+            if !self.collapsed_groups.remove(&key) {
+                self.collapsed_groups.insert(key);
+            }
+            // rows before the selected group are not changed, so keep the current position
+            let selected_row = self.table_state.selected_row;
+            let offset_index = self.table_state.selected_row_offset_index();
+            self.update_rows();
+            self.table_state = self.table_state.with_new_total_rows(self.rows.len());
+            self.table_state.select_index(selected_row, offset_index);
+            self.table_state.update_table_state();
+        }
+    }
+
+    fn select_same_partition_item(&mut self, forward: bool) {
+        let hash_key = self.table_description.key_schema_type.hash_key_name();
+        let key = match self
+            .current_selected_item()
+            .and_then(|item| item.attributes.get(hash_key))
+        {
+            Some(key) => key,
+            None => return,
+        };
+        let is_same_partition = |row: &TableRow| match row {
+            TableRow::Item(i) => self.items[*i].attributes.get(hash_key) == Some(key),
+            TableRow::Group(_) => false,
+        };
+        let selected_row = self.table_state.selected_row;
+        let found = if forward {
+            self.rows
+                .iter()
+                .enumerate()
+                .skip(selected_row + 1)
+                .find(|(_, row)| is_same_partition(row))
+        } else {
+            self.rows
+                .iter()
+                .enumerate()
+                .take(selected_row)
+                .rev()
+                .find(|(_, row)| is_same_partition(row))
+        };
+        match found {
+            Some((row, _)) => {
+                let offset_index = self.table_state.selected_row_offset_index();
+                self.table_state.select_index(row, offset_index);
+            }
+            None => {
+                let msg = "No more items with the same hash key";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+        }
+    }
+
+    // Select the next or previous item row, skipping the group rows,
+    // so that the item view can move through the items in the order of the table
+    pub fn select_adjacent_item(&mut self, forward: bool) -> Option<Item> {
+        let selected_row = self.table_state.selected_row;
+        let is_item = |row: &TableRow| matches!(row, TableRow::Item(_));
+        let found = if forward {
+            self.rows
+                .iter()
+                .enumerate()
+                .skip(selected_row + 1)
+                .find(|(_, row)| is_item(row))
+        } else {
+            self.rows
+                .iter()
+                .enumerate()
+                .take(selected_row)
+                .rev()
+                .find(|(_, row)| is_item(row))
+        };
+        let (row, _) = found?;
+        let offset_index = self.table_state.selected_row_offset_index();
+        self.table_state.select_index(row, offset_index);
+        self.table_state.update_table_state();
+        self.current_selected_item().cloned()
+    }
+
+    fn select_item_row(&mut self, item_index: usize, offset_index: usize) {
+        let row = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, TableRow::Item(i) if *i == item_index));
+        if let Some(row) = row {
+            self.table_state.select_index(row, offset_index);
+        }
+    }
+
+    fn selected_group(&self) -> Option<&ItemGroup> {
+        match self.rows.get(self.table_state.selected_row) {
+            Some(TableRow::Group(g)) => self.groups.get(*g),
+            _ => None,
+        }
+    }
+
+    fn current_selected_item_index(&self) -> Option<usize> {
+        match self.rows.get(self.table_state.selected_row) {
+            Some(TableRow::Item(i)) => Some(*i),
+            _ => None,
+        }
+    }
+
+    fn current_selected_item(&self) -> Option<&Item> {
+        self.current_selected_item_index()
+            .and_then(|idx| self.items.get(idx))
+    }
+
+    fn copy_to_clipboard(&self) {
+        if let Some(selected_item) = self.current_selected_item() {
+            let schema = &self.table_description.key_schema_type;
+
+            if let Some(col) = self.table_state.selected_col {
+                let key = &self.attribute_keys()[col];
+                if let Some(attr) = selected_item.attributes.get(key) {
+                    let items = attribute_copy_formats(attr)
+                        .into_iter()
+                        .map(|(format, content)| {
+                            let name = format!("selected attribute as {}", format.to_lowercase());
+                            (format.into(), AppEvent::CopyToClipboard(name, content))
+                        })
+                        .collect();
+                    self.tx
+                        .send(AppEvent::OpenSelectDialog("Copy as".into(), items));
+                }
+            } else {
+                let raw_json_string = get_raw_json_string(selected_item, schema);
+                self.tx.send(AppEvent::CopyToClipboard(
+                    "selected item".into(),
+                    raw_json_string,
+                ));
+            }
+        }
+    }
+
+    // The rows and columns as shown, so that they can be pasted into a spreadsheet
+    fn copy_visible_items_as_tsv(&self) {
+        let count = self.view_indices.len();
+        if count == 0 {
+            let msg = "No items to copy";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        let items: Vec<&Item> = self.view_indices.iter().map(|&i| &self.items[i]).collect();
+        let content = items_to_tsv(&self.attribute_keys(), &items);
+        let name = format!("{} items as TSV", format_thousands(count));
+        let copy = AppEvent::CopyToClipboard(name.clone(), content);
+        if count > self.config.copy_confirm_rows {
+            let title = format!("Copy {name}?");
+            let items = vec![
+                ("Copy".into(), copy),
+                ("Cancel".into(), AppEvent::ClearStatus),
+            ];
+            self.tx.send(AppEvent::OpenSelectDialog(title, items));
+        } else {
+            self.tx.send(copy);
+        }
+    }
+
+    // One value per line, e.g. to pass a list of IDs to another tool
+    fn copy_selected_column(&self) {
+        let Some(col) = self.table_state.selected_col else {
+            let msg = "No column is selected";
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        };
+        let key = self.attribute_keys().swap_remove(col);
+        let items: Vec<&Item> = self.view_indices.iter().map(|&i| &self.items[i]).collect();
+        let values = column_values(&key, &items);
+        if values.is_empty() {
+            let msg = format!("No visible items have {key}");
+            self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            return;
+        }
+        let name = format!("{} values of {key}", format_thousands(values.len()));
+        self.tx
+            .send(AppEvent::CopyToClipboard(name, values.join("\n")));
+    }
+
+    fn save_to_file(&self) {
+        if let Some(selected_item) = self.current_selected_item() {
+            let schema = &self.table_description.key_schema_type;
+            let raw_json_string = get_raw_json_string(selected_item, schema);
+            self.tx.send(AppEvent::SaveToFile(
+                "selected item".into(),
+                raw_json_string,
+            ));
+        }
+    }
+
+    fn print_and_quit(&self) {
+        if let Some(json) = self.selected_item_json() {
+            self.tx.send(AppEvent::QuitWithOutput(json));
+        }
+    }
+
+    fn pipe_to_command(&self) {
+        let schema = &self.table_description.key_schema_type;
+        let content: String = self
+            .view_indices
+            .iter()
+            .map(|&i| to_plain_json_string(&self.items[i], schema) + "\n")
+            .collect();
+        self.tx
+            .send(AppEvent::PipeToCommand("items".into(), content));
+    }
+
+    pub fn selected_item_json(&self) -> Option<String> {
+        let schema = &self.table_description.key_schema_type;
+        self.current_selected_item()
+            .map(|item| to_plain_json_string(item, schema))
+    }
+
+    fn open_partiql(&self) {
+        let desc = self.table_description.clone();
+        self.tx.send(AppEvent::OpenPartiQL(desc));
+    }
+
+    fn open_filter_builder(&self) {
+        let desc = self.table_description.clone();
+        self.tx.send(AppEvent::OpenFilterBuilder(desc));
+    }
+
+    fn open_query_builder(&self) {
+        let desc = self.table_description.clone();
+        self.tx.send(AppEvent::OpenQueryBuilder(desc));
+    }
+
+    fn open_table_in_console(&self) {
+        let table_name = self.table_description.table_name.clone();
+        self.tx.send(AppEvent::OpenTableInConsole(table_name));
+    }
+
+    fn open_help(&self) {
+        let helps = if self.attr_expanded {
+            &self.helps.attr
+        } else {
+            match self.filter_state {
+                FilterState::None => &self.helps.table,
+                FilterState::Filtering | FilterState::Filtered => &self.helps.table_filtered,
+            }
+        };
+        let mut disabled_events = Vec::new();
+        if self.permissions.delete_item == Permission::Denied {
+            disabled_events.extend([UserEvent::DeleteItem, UserEvent::DeleteAllItems]);
+        }
+        let helps = disable_help_spans(helps, &disabled_events, self.theme);
+        self.tx.send(AppEvent::OpenHelp(helps));
+    }
+}
+
+fn resume_from_key_event(
+    desc: TableDescription,
+    source: &ItemsSource,
+    key: BTreeMap<String, Attribute>,
+) -> AppEvent {
+    match source {
+        ItemsSource::Scan(options) => {
+            let options = ScanOptions {
+                limit: None,
+                exclusive_start_key: Some(key),
+                ..options.clone()
+            };
+            AppEvent::LoadTableItems(desc, options)
+        }
+        ItemsSource::Query(options) => {
+            let options = QueryOptions {
+                exclusive_start_key: Some(key),
+                ..options.clone()
+            };
+            AppEvent::QueryTableItems(desc, options)
+        }
+        ItemsSource::Statement(_) => {
+            let msg = "Exclusive start key is not available for PartiQL results";
+            AppEvent::NotifyWarning(AppError::msg(msg))
+        }
+    }
+}
+
+impl ItemGroup {
+    fn new(
+        key: String,
+        count: usize,
+        collapsed: bool,
+        total_cols: usize,
+        theme: &ColorTheme,
+    ) -> ItemGroup {
+        let mark = if collapsed { "▶" } else { "▼" };
+        let text = format!("{mark} {key} ({count})");
+        let content = vec![text.clone().bold().fg(theme.fg)];
+        let width = text_width(&text);
+        let mut cell_items = vec![CellItem::new(content, text, width)];
+        cell_items.extend((1..total_cols).map(|_| CellItem::new(vec![], "", 0)));
+        ItemGroup { key, cell_items }
+    }
+}
+
+fn visible_attribute_keys(
+    items: &[Item],
+    schema: &KeySchemaType,
+    preferences: &TableViewPreferences,
+    unused_columns: &BTreeSet<String>,
+) -> Vec<String> {
+    list_attribute_keys(items, schema)
+        .into_iter()
+        .filter(|key| !preferences.hidden_columns.contains(key) && !unused_columns.contains(key))
+        .collect()
+}
+
+fn new_table_state(
+    table_description: &TableDescription,
+    items: &[Item],
+    expected_types: &HashMap<String, AttributeType>,
+    preferences: &TableViewPreferences,
+    unused_columns: &BTreeSet<String>,
+    config: &UiTableConfig,
+    theme: ColorTheme,
+) -> (TableState, Vec<Vec<CellItem<'static>>>, Vec<Cell<'static>>) {
+    let attribute_keys = visible_attribute_keys(
+        items,
+        &table_description.key_schema_type,
+        preferences,
+        unused_columns,
+    );
+    let total_rows = items.len();
+    let total_cols = attribute_keys.len();
+
+    // columns resized by the user keep their width regardless of the contents
+    let saved_widths: Vec<Option<usize>> = attribute_keys
+        .iter()
+        .map(|key| preferences.column_widths.get(key).copied())
+        .collect();
+    let mut max_width_vec: Vec<usize> = vec![0; total_cols];
+
+    let mut row_cell_items: Vec<Vec<CellItem>> = Vec::with_capacity(total_rows);
     for item in items {
         let mut cell_items: Vec<CellItem> = Vec::new();
         for (i, key) in attribute_keys.iter().enumerate() {
+            let is_ttl = table_description.ttl_attribute_name.as_ref() == Some(key);
+            let expected_type = expected_types.get(key);
+            let max_attribute_width = saved_widths[i].unwrap_or(config.max_attribute_width);
             let (cell_item, width) = item
                 .attributes
                 .get(key)
-                .map(|attr| attribute_to_cell_item(attr, config.max_attribute_width, &theme))
+                .map(|attr| {
+                    let mismatched = is_type_mismatch(attr, expected_type);
+                    attribute_to_cell_item(
+                        attr,
+                        is_ttl,
+                        mismatched,
+                        max_attribute_width,
+                        config,
+                        &theme,
+                    )
+                })
                 .unwrap_or(undefined_cell_item(&theme));
             cell_items.push(cell_item);
 
@@ -708,33 +2122,132 @@ fn new_table_state(
         }
     }
 
+    for (width, saved_width) in max_width_vec.iter_mut().zip(saved_widths) {
+        if let Some(saved_width) = saved_width {
+            *width = saved_width;
+        }
+    }
+
     let table_state = TableState::new(total_rows, total_cols, max_width_vec);
 
     (table_state, row_cell_items, header_row_cells)
 }
 
+// NULL is not regarded as a mismatch since it is commonly used for optional values
+fn is_type_mismatch(attr: &Attribute, expected_type: Option<&AttributeType>) -> bool {
+    let attr_type = AttributeType::from(Some(attr));
+    attr_type != AttributeType::Null && expected_type.is_some_and(|t| *t != attr_type)
+}
+
 fn attribute_to_cell_item(
     attr: &Attribute,
+    is_ttl: bool,
+    mismatched: bool,
     max_attribute_width: usize,
+    config: &UiTableConfig,
     theme: &ColorTheme,
 ) -> (CellItem<'static>, usize) {
-    let spans = attribute_to_spans(attr, theme);
+    let mut spans = if is_ttl {
+        ttl_attribute_to_spans(attr, Local::now().timestamp(), theme)
+    } else if let Some(glyph) = cell_glyph(attr, config, theme) {
+        vec![glyph]
+    } else {
+        attribute_to_spans(attr, theme)
+    };
+    if mismatched {
+        spans = spans
+            .into_iter()
+            .map(|span| span.fg(theme.cell_type_mismatch_fg).underlined())
+            .collect();
+    }
     let plain = spans
         .iter()
         .map(|span| span.content.as_ref())
         .collect::<String>();
     let cut_spans = cut_spans_by_width(spans, max_attribute_width, ELLIPSIS, theme);
-    let width = cut_spans.iter().map(Span::width).sum();
-    let plain_width = console::measure_text_width(&plain);
+    let width = cut_spans.iter().map(|span| text_width(&span.content)).sum();
+    let plain_width = text_width(&plain);
     (CellItem::new(cut_spans, plain, plain_width), width)
 }
 
+fn cell_glyph(
+    attr: &Attribute,
+    config: &UiTableConfig,
+    theme: &ColorTheme,
+) -> Option<Span<'static>> {
+    if !config.use_glyphs {
+        return None;
+    }
+    match attr {
+        Attribute::BOOL(true) => Some(config.true_glyph.clone().fg(theme.cell_bool_fg)),
+        Attribute::BOOL(false) => Some(config.false_glyph.clone().fg(theme.cell_bool_fg)),
+        Attribute::NULL => Some(config.null_glyph.clone().fg(theme.cell_null_fg)),
+        _ => None,
+    }
+}
+
 fn key_to_cell(key: &str, config: &UiTableConfig, theme: &ColorTheme) -> (Cell<'static>, usize) {
     let span = key.to_string().bold();
     let spans = cut_spans_by_width(vec![span], config.max_attribute_width, ELLIPSIS, theme);
-    let line = Line::from(spans);
-    let width = line.width();
-    (Cell::new(line), width)
+    let width = spans.iter().map(|span| text_width(&span.content)).sum();
+    (Cell::new(Line::from(spans)), width)
+}
+
+fn reorder<T>(values: Vec<T>, order: &[usize]) -> Vec<T> {
+    let mut values: Vec<Option<T>> = values.into_iter().map(Some).collect();
+    order.iter().map(|&i| values[i].take().unwrap()).collect()
+}
+
+// e.g. `status = active`, the value is truncated to fit in the title and notifications
+fn value_condition_string(key: &str, value: Option<&Attribute>) -> String {
+    match value {
+        Some(attr) => {
+            let value = attr.to_simple_string();
+            let value = console::truncate_str(&value, VALUE_CONDITION_MAX_WIDTH, ELLIPSIS);
+            format!("{key} = {value}")
+        }
+        None => format!("{key} undefined"),
+    }
+}
+
+// The statement is executed as is, so writes are rejected as they would bypass the checks of write_items
+fn compare_items_event(desc: TableDescription, items: Vec<Item>, statement: String) -> AppEvent {
+    if statement.trim().is_empty() {
+        return AppEvent::NotifyWarning(AppError::msg("Statement is empty"));
+    }
+    if !is_select_statement(&statement) {
+        let msg = "Only SELECT statements can be compared";
+        return AppEvent::NotifyWarning(AppError::msg(msg));
+    }
+    AppEvent::CompareItems(desc, items, statement)
+}
+
+fn items_to_tsv(keys: &[String], items: &[&Item]) -> String {
+    let header: Vec<String> = keys.iter().map(|k| single_line(k)).collect();
+    let mut lines = vec![header.join("\t")];
+    for item in items {
+        let values: Vec<String> = keys
+            .iter()
+            .map(|k| item.attributes.get(k).map(csv_value).unwrap_or_default())
+            .map(|v| single_line(&v))
+            .collect();
+        lines.push(values.join("\t"));
+    }
+    lines.join("\n")
+}
+
+// The items without the attribute are skipped
+fn column_values(key: &str, items: &[&Item]) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|item| item.attributes.get(key))
+        .map(|attr| single_line(&csv_value(attr)))
+        .collect()
+}
+
+// Tabs and newlines in the values are replaced with spaces so that each value stays in one line
+fn single_line(s: &str) -> String {
+    s.replace(['\t', '\n', '\r'], " ")
 }
 
 fn undefined_cell_item(theme: &ColorTheme) -> (CellItem<'static>, usize) {
@@ -748,6 +2261,21 @@ fn get_raw_json_string(item: &Item, schema: &KeySchemaType) -> String {
     serde_json::to_string(&json_item).unwrap()
 }
 
+fn attribute_copy_formats(attr: &Attribute) -> Vec<(&'static str, String)> {
+    let mut formats = vec![
+        ("Plain string", attr.to_simple_string()),
+        ("JSON", serde_json::to_string(attr).unwrap()),
+        (
+            "Raw DynamoDB JSON",
+            serde_json::to_string(&RawAttributeJsonWrapper::new(attr)).unwrap(),
+        ),
+    ];
+    if let Attribute::B(b) = attr {
+        formats.push(("Base64", to_base64_str(b)));
+    }
+    formats
+}
+
 fn get_raw_json_attribute_string(attr: &Attribute) -> String {
     let wrapper = RawAttributeJsonWrapper::new(attr);
     serde_json::to_string_pretty(&wrapper).unwrap()
@@ -755,5 +2283,228 @@ fn get_raw_json_attribute_string(attr: &Attribute) -> String {
 
 fn get_raw_json_attribute_lines(attr: &Attribute, theme: &ColorTheme) -> Vec<Line<'static>> {
     let json_str = get_raw_json_attribute_string(attr);
-    to_highlighted_lines(&json_str, theme)
+    let mut lines = to_highlighted_lines(&json_str, theme);
+    if let Attribute::B(bytes) = attr {
+        lines.extend(get_decompressed_preview_lines(bytes, theme));
+    }
+    lines
+}
+
+fn get_decompressed_preview_lines(bytes: &[u8], theme: &ColorTheme) -> Vec<Line<'static>> {
+    let compression = match Compression::detect(bytes) {
+        Some(compression) => compression,
+        None => return Vec::new(),
+    };
+
+    let mut lines = vec![Line::raw("")];
+    match decompress(bytes, compression) {
+        Ok((decompressed, truncated)) => {
+            let size = |n: usize| humansize::format_size(n, humansize::DECIMAL);
+            let mut header = format!(
+                "Decompressed ({}, {} -> {}",
+                compression.as_str(),
+                size(bytes.len()),
+                size(decompressed.len())
+            );
+            if truncated {
+                header.push_str(", truncated");
+            }
+            header.push_str("):");
+            lines.push(Line::from(header.bold()));
+
+            match serde_json::from_slice::<serde_json::Value>(&decompressed) {
+                Ok(json) => {
+                    let json_str = serde_json::to_string_pretty(&json).unwrap();
+                    lines.extend(to_highlighted_lines(&json_str, theme));
+                }
+                Err(_) => {
+                    let text = String::from_utf8_lossy(&decompressed);
+                    lines.extend(text.lines().map(|l| Line::raw(l.to_string())));
+                }
+            }
+        }
+        Err(e) => {
+            lines.push(Line::from(e.msg.fg(theme.notification_error)));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rstest::*;
+    use rust_decimal::Decimal;
+
+    use crate::{
+        event::test_channel,
+        view::snapshot::{item, render_lines, table_description},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let (tx, _rx) = test_channel();
+        let mapper = UserEventMapper::new();
+        let items = vec![
+            item(vec![
+                ("id", Attribute::S("u1".into())),
+                ("age", Attribute::N(Decimal::from(30))),
+            ]),
+            item(vec![
+                ("id", Attribute::S("u2".into())),
+                ("name", Attribute::S("Bob".into())),
+            ]),
+        ];
+        let loaded = LoadedItems {
+            items,
+            stats: LoadStats {
+                count: 2,
+                pages: 1,
+                elapsed: Duration::from_millis(80),
+                memory_limited: false,
+            },
+        };
+        let mut view = TableView::new(
+            table_description("users", "id"),
+            loaded,
+            ItemsSource::Scan(ScanOptions::default()),
+            &mapper,
+            UiTableConfig::default(),
+            ColorTheme::default(),
+            tx,
+        );
+
+        let actual = render_lines(40, 6, |f, area| view.render(f, area));
+        let expected = vec![
+            "┌ users ──────────────────────── 1 / 2 ┐",
+            "│ id   age name                        │",
+            "│ \"u1\" 30  -                           │",
+            "│ \"u2\" -   \"Bob\"                       │",
+            "│                                      │",
+            "└──────────── 2 items in 80ms (1 page) ┘",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_value_condition_string() {
+        let s = |s: &str| Attribute::S(s.into());
+        assert_eq!(
+            value_condition_string("status", Some(&s("active"))),
+            "status = active"
+        );
+        assert_eq!(
+            value_condition_string("v", Some(&s(&"x".repeat(40)))),
+            format!("v = {}...", "x".repeat(27))
+        );
+        assert_eq!(
+            value_condition_string("v", Some(&Attribute::NULL)),
+            "v = null"
+        );
+        assert_eq!(value_condition_string("v", None), "v undefined");
+    }
+
+    #[rstest]
+    #[case("SELECT * FROM \"foo\"", true)]
+    #[case("  select * from foo", true)]
+    #[case("UPDATE foo SET a = 1 WHERE id = '1'", false)]
+    #[case("DELETE FROM foo WHERE id = '1'", false)]
+    #[case("INSERT INTO foo VALUE {'id': '1'}", false)]
+    #[case("", false)]
+    fn test_compare_items_event(#[case] statement: &str, #[case] accepted: bool) {
+        let desc = table_description("foo", "id");
+        let actual = compare_items_event(desc, vec![], statement.into());
+        assert_eq!(matches!(actual, AppEvent::CompareItems(..)), accepted);
+    }
+
+    #[test]
+    fn test_items_to_tsv() {
+        let s = |s: &str| Attribute::S(s.into());
+        let items = [
+            item(vec![("id", s("1")), ("name", s("foo\tbar"))]),
+            item(vec![("id", s("2")), ("memo", s("line1\nline2"))]),
+        ];
+        let items: Vec<&Item> = items.iter().collect();
+        let keys = vec!["id".to_string(), "name".into(), "memo".into()];
+
+        let actual = items_to_tsv(&keys, &items);
+        let expected = "id\tname\tmemo\n1\tfoo bar\t\n2\t\tline1 line2";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_column_values() {
+        let s = |s: &str| Attribute::S(s.into());
+        let items = [
+            item(vec![("id", s("1")), ("name", s("foo"))]),
+            item(vec![("name", s("bar"))]),
+            item(vec![("id", Attribute::N(Decimal::from(3)))]),
+            item(vec![("id", s("a\nb"))]),
+        ];
+        let items: Vec<&Item> = items.iter().collect();
+
+        let actual = column_values("id", &items);
+        assert_eq!(actual, vec!["1", "3", "a b"]);
+    }
+
+    #[test]
+    fn test_cell_glyph() {
+        let theme = ColorTheme::default();
+        let content = |attr: &Attribute, config: &UiTableConfig| {
+            cell_glyph(attr, config, &theme).map(|span| span.content.to_string())
+        };
+
+        let config = UiTableConfig::default();
+        assert_eq!(content(&Attribute::BOOL(true), &config), None);
+
+        let config = UiTableConfig {
+            use_glyphs: true,
+            ..Default::default()
+        };
+        assert_eq!(content(&Attribute::BOOL(true), &config), Some("✓".into()));
+        assert_eq!(content(&Attribute::BOOL(false), &config), Some("✗".into()));
+        assert_eq!(content(&Attribute::NULL, &config), Some("∅".into()));
+        assert_eq!(content(&Attribute::S("true".into()), &config), None);
+    }
+
+    #[test]
+    fn test_select_adjacent_item() {
+        let (tx, _rx) = test_channel();
+        let mapper = UserEventMapper::new();
+        let items = ["u1", "u2", "u3"]
+            .into_iter()
+            .map(|id| item(vec![("id", Attribute::S(id.into()))]))
+            .collect();
+        let loaded = LoadedItems {
+            items,
+            stats: LoadStats {
+                count: 3,
+                pages: 1,
+                elapsed: Duration::from_millis(80),
+                memory_limited: false,
+            },
+        };
+        let mut view = TableView::new(
+            table_description("users", "id"),
+            loaded,
+            ItemsSource::Scan(ScanOptions::default()),
+            &mapper,
+            UiTableConfig::default(),
+            ColorTheme::default(),
+            tx,
+        );
+        let mut select = |forward: bool| {
+            view.select_adjacent_item(forward)
+                .map(|item| item.attributes["id"].to_simple_string())
+        };
+
+        assert_eq!(select(false), None);
+        assert_eq!(select(true), Some("u2".into()));
+        assert_eq!(select(true), Some("u3".into()));
+        assert_eq!(select(true), None);
+        assert_eq!(select(false), Some("u2".into()));
+    }
 }
@@ -0,0 +1,244 @@
+use ratatui::{
+    crossterm::event::KeyEvent, layout::Rect, style::Stylize, text::Line, widgets::Block, Frame,
+};
+
+use crate::{
+    color::ColorTheme,
+    data::{diff_table_descriptions, TableDescription},
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    view::common::raw_string_from_scroll_lines_state,
+    widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState},
+};
+
+pub struct TableDiffView {
+    left: TableDescription,
+    right: TableDescription,
+
+    helps: TableDiffViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    scroll_lines_state: ScrollLinesState,
+}
+
+struct TableDiffViewHelps {
+    diff: Vec<Spans>,
+    diff_short: Vec<SpansWithPriority>,
+}
+
+impl TableDiffView {
+    pub fn new(
+        left: TableDescription,
+        right: TableDescription,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let lines = get_diff_lines(&left, &right, &theme);
+        let scroll_lines_state =
+            ScrollLinesState::new(lines, ScrollLinesOptions::new(false, false));
+        let helps = TableDiffViewHelps::new(mapper, theme);
+
+        TableDiffView {
+            left,
+            right,
+
+            helps,
+            theme,
+            tx,
+
+            scroll_lines_state,
+        }
+    }
+}
+
+impl TableDiffView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::Close => {
+                self.tx.send(AppEvent::BackToBeforeView);
+            }
+            UserEvent::Down => {
+                self.scroll_lines_state.scroll_forward();
+            }
+            UserEvent::Up => {
+                self.scroll_lines_state.scroll_backward();
+            }
+            UserEvent::PageDown => {
+                self.scroll_lines_state.scroll_page_forward();
+            }
+            UserEvent::PageUp => {
+                self.scroll_lines_state.scroll_page_backward();
+            }
+            UserEvent::GoToTop => {
+                self.scroll_lines_state.scroll_to_top();
+            }
+            UserEvent::GoToBottom => {
+                self.scroll_lines_state.scroll_to_end();
+            }
+            UserEvent::Right => {
+                self.scroll_lines_state.scroll_right();
+            }
+            UserEvent::Left => {
+                self.scroll_lines_state.scroll_left();
+            }
+            UserEvent::ToggleWrap => {
+                self.scroll_lines_state.toggle_wrap();
+            }
+            UserEvent::ToggleNumber => {
+                self.scroll_lines_state.toggle_number();
+            }
+            UserEvent::SaveToFile => {
+                self.save_to_file();
+            }
+            UserEvent::Help => {
+                self.open_help();
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let title = format!(
+            " {} <-> {} (Diff) ",
+            self.left.table_name, self.right.table_name
+        );
+        let scroll = ScrollLines::default()
+            .block(
+                Block::bordered()
+                    .title_top(Line::from(title).left_aligned())
+                    .fg(self.theme.fg)
+                    .bg(self.theme.bg),
+            )
+            .theme(&self.theme);
+
+        f.render_stateful_widget(scroll, area, &mut self.scroll_lines_state);
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        &self.helps.diff_short
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.left.table_name
+    }
+}
+
+impl TableDiffViewHelps {
+    fn new(mapper: &UserEventMapper, theme: ColorTheme) -> Self {
+        let diff = build_helps(mapper, theme);
+        let diff_short = build_short_helps(mapper);
+        Self { diff, diff_short }
+    }
+}
+
+fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> Vec<Spans> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Close, "Back to table list"),
+        BuildHelpsItem::new(UserEvent::Down, "Scroll down"),
+        BuildHelpsItem::new(UserEvent::Up, "Scroll up"),
+        BuildHelpsItem::new(UserEvent::Right, "Scroll right"),
+        BuildHelpsItem::new(UserEvent::Left, "Scroll left"),
+        BuildHelpsItem::new(UserEvent::PageDown, "Scroll page down"),
+        BuildHelpsItem::new(UserEvent::PageUp, "Scroll page up"),
+        BuildHelpsItem::new(UserEvent::GoToTop, "Scroll to top"),
+        BuildHelpsItem::new(UserEvent::GoToBottom, "Scroll to bottom"),
+        BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
+        BuildHelpsItem::new(UserEvent::ToggleNumber, "Toggle number"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save diff to file"),
+    ];
+    build_help_spans(helps, mapper, theme)
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Close, "Back", 1),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Scroll", 2),
+        BuildShortHelpsItem::group(vec![UserEvent::GoToTop, UserEvent::GoToBottom], "Top/Bottom", 3),
+        BuildShortHelpsItem::group(vec![UserEvent::ToggleWrap, UserEvent::ToggleNumber], "Toggle wrap/number", 4),
+        BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+impl TableDiffView {
+    fn save_to_file(&self) {
+        let content = raw_string_from_scroll_lines_state(&self.scroll_lines_state);
+        self.tx
+            .send(AppEvent::SaveToFile("table diff".into(), content));
+    }
+
+    fn open_help(&self) {
+        self.tx.send(AppEvent::OpenHelp(self.helps.diff.clone()))
+    }
+}
+
+fn get_diff_lines(
+    left: &TableDescription,
+    right: &TableDescription,
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
+    let diffs = diff_table_descriptions(left, right);
+    let mut lines = vec![];
+
+    lines.push(Line::from(vec![
+        "- ".fg(theme.diff_removed_fg),
+        left.table_name.clone().bold(),
+    ]));
+    lines.push(Line::from(vec![
+        "+ ".fg(theme.diff_added_fg),
+        right.table_name.clone().bold(),
+    ]));
+    let count = diffs.iter().filter(|d| !d.is_same()).count();
+    lines.push(Line::raw(format!("{count} differences")));
+    lines.push(Line::raw(""));
+
+    let max_width = diffs.iter().map(|d| d.property.len()).max().unwrap_or(0);
+    let property = |name: &str| format!("{name:>max_width$} : ").bold();
+
+    for diff in &diffs {
+        match (&diff.left, &diff.right) {
+            (Some(l), Some(r)) if l == r => {
+                lines.push(Line::from(vec![
+                    "  ".into(),
+                    property(&diff.property),
+                    l.clone().into(),
+                ]));
+            }
+            (Some(l), Some(r)) => {
+                lines.push(Line::from(vec![
+                    "~ ".fg(theme.diff_changed_fg),
+                    property(&diff.property),
+                    l.clone().fg(theme.diff_removed_fg),
+                    " -> ".into(),
+                    r.clone().fg(theme.diff_added_fg),
+                ]));
+            }
+            (Some(l), None) => {
+                lines.push(Line::from(vec![
+                    "- ".fg(theme.diff_removed_fg),
+                    property(&diff.property),
+                    l.clone().fg(theme.diff_removed_fg),
+                ]));
+            }
+            (None, Some(r)) => {
+                lines.push(Line::from(vec![
+                    "+ ".fg(theme.diff_added_fg),
+                    property(&diff.property),
+                    r.clone().fg(theme.diff_added_fg),
+                ]));
+            }
+            (None, None) => {}
+        }
+    }
+
+    lines
+}
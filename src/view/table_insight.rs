@@ -1,21 +1,25 @@
 use ratatui::{
     crossterm::event::KeyEvent, layout::Rect, style::Stylize, text::Line, widgets::Block, Frame,
 };
+use serde_json::json;
 
 use crate::{
     color::ColorTheme,
-    data::TableInsight,
+    config::UiInsightConfig,
+    data::{DuplicateGroup, TableInsight},
     event::{AppEvent, Sender, UserEvent, UserEventMapper},
     handle_user_events,
     help::{
         build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
         SpansWithPriority,
     },
+    view::common::raw_string_from_scroll_lines_state,
     widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState},
 };
 
 pub struct TableInsightView {
     table_insight: TableInsight,
+    sort_key_prefixes: Vec<(String, usize)>,
 
     helps: TableInsightViewHelps,
     theme: ColorTheme,
@@ -33,16 +37,20 @@ impl TableInsightView {
     pub fn new(
         table_insight: TableInsight,
         mapper: &UserEventMapper,
+        config: UiInsightConfig,
         theme: ColorTheme,
         tx: Sender,
     ) -> Self {
-        let lines = get_insight_lines(&table_insight, &theme);
+        let sort_key_prefixes = table_insight
+            .sort_key_prefixes(&config.sort_key_delimiter, config.sort_key_prefix_count);
+        let lines = get_insight_lines(&table_insight, &sort_key_prefixes, &config, &theme);
         let scroll_lines_state =
             ScrollLinesState::new(lines, ScrollLinesOptions::new(false, false));
         let helps = TableInsightViewHelps::new(mapper, theme);
 
         TableInsightView {
             table_insight,
+            sort_key_prefixes,
 
             helps,
             theme,
@@ -89,6 +97,12 @@ impl TableInsightView {
             UserEvent::ToggleNumber => {
                 self.scroll_lines_state.toggle_number();
             }
+            UserEvent::CopyToClipboard => {
+                self.copy_to_clipboard();
+            }
+            UserEvent::SaveToFile => {
+                self.save_to_file();
+            }
             UserEvent::Help => {
                 self.open_help();
             }
@@ -112,6 +126,10 @@ impl TableInsightView {
     pub fn short_helps(&self) -> &[SpansWithPriority] {
         &self.helps.insight_short
     }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_insight.table_name
+    }
 }
 
 impl TableInsightViewHelps {
@@ -140,6 +158,8 @@ fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> Vec<Spans> {
         BuildHelpsItem::new(UserEvent::GoToBottom, "Scroll to bottom"),
         BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
         BuildHelpsItem::new(UserEvent::ToggleNumber, "Toggle number"),
+        BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy insight report"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save insight report to file"),
     ];
     build_help_spans(helps, mapper, theme)
 }
@@ -152,18 +172,67 @@ fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
         BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Scroll", 2),
         BuildShortHelpsItem::group(vec![UserEvent::GoToTop, UserEvent::GoToBottom], "Top/Bottom", 3),
         BuildShortHelpsItem::group(vec![UserEvent::ToggleWrap, UserEvent::ToggleNumber], "Toggle wrap/number", 4),
+        BuildShortHelpsItem::single(UserEvent::CopyToClipboard, "Copy", 5),
         BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
     ];
     build_short_help_spans(helps, mapper)
 }
 
 impl TableInsightView {
+    fn copy_to_clipboard(&self) {
+        let items = self
+            .report_contents()
+            .into_iter()
+            .map(|(format, content)| {
+                let name = format!("insight report as {}", format.to_lowercase());
+                (format.into(), AppEvent::CopyToClipboard(name, content))
+            })
+            .collect();
+        self.tx
+            .send(AppEvent::OpenSelectDialog("Copy as".into(), items));
+    }
+
+    fn save_to_file(&self) {
+        let items = self
+            .report_contents()
+            .into_iter()
+            .map(|(format, content)| {
+                let name = format!("insight report as {}", format.to_lowercase());
+                (format.into(), AppEvent::SaveToFile(name, content))
+            })
+            .collect();
+        self.tx
+            .send(AppEvent::OpenSelectDialog("Save as".into(), items));
+    }
+
+    fn report_contents(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (
+                "Text",
+                raw_string_from_scroll_lines_state(&self.scroll_lines_state),
+            ),
+            (
+                "Markdown",
+                get_markdown_string(&self.table_insight, &self.sort_key_prefixes),
+            ),
+            (
+                "JSON",
+                get_json_string(&self.table_insight, &self.sort_key_prefixes),
+            ),
+        ]
+    }
+
     fn open_help(&self) {
         self.tx.send(AppEvent::OpenHelp(self.helps.insight.clone()))
     }
 }
 
-fn get_insight_lines(table_insight: &TableInsight, theme: &ColorTheme) -> Vec<Line<'static>> {
+fn get_insight_lines(
+    table_insight: &TableInsight,
+    sort_key_prefixes: &[(String, usize)],
+    config: &UiInsightConfig,
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
     let mut lines = vec![];
 
     lines.push(Line::from("Attribute Distribution:".bold()));
@@ -177,9 +246,18 @@ fn get_insight_lines(table_insight: &TableInsight, theme: &ColorTheme) -> Vec<Li
         .unwrap();
 
     for distribution in &table_insight.attribute_distributions {
+        let missing = distribution.missing_or_null_count();
+        let warning = config.missing_warning_percent > 0
+            && missing * 100 >= table_insight.total_items * config.missing_warning_percent as usize;
+
         let mut spans = vec![];
         spans.push("  ".into());
-        spans.push(format!("{:>width$}", distribution.attribute_name, width = max_width).bold());
+        let name = format!("{:>width$}", distribution.attribute_name, width = max_width).bold();
+        if warning {
+            spans.push(name.fg(theme.notification_warning));
+        } else {
+            spans.push(name);
+        }
         spans.push(" : ".bold());
         for (i, (at, n)) in distribution.distributions.iter().enumerate() {
             spans.push(at.as_str().to_string().fg(theme.insight_attribute_name_fg));
@@ -191,12 +269,312 @@ fn get_insight_lines(table_insight: &TableInsight, theme: &ColorTheme) -> Vec<Li
                 spans.push(" ".into());
             }
         }
+        if warning {
+            let ratio = format_ratio(missing, table_insight.total_items);
+            spans.push("  ".into());
+            spans.push(
+                format!("! {ratio} missing or NULL")
+                    .fg(theme.notification_warning)
+                    .bold(),
+            );
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::raw(""));
+    lines.extend(get_co_occurrence_lines(table_insight, max_width, theme));
+
+    if let Some((name, values)) = &table_insight.sort_key {
+        if !sort_key_prefixes.is_empty() {
+            lines.push(Line::raw(""));
+            lines.extend(get_sort_key_prefix_lines(
+                name,
+                values.len(),
+                sort_key_prefixes,
+                theme,
+            ));
+        }
+    }
+
+    if !table_insight.duplicate_keys.is_empty() {
+        lines.push(Line::raw(""));
+        lines.extend(get_duplicate_lines(
+            "Duplicate Keys (ignoring case and surrounding whitespace):",
+            &table_insight.duplicate_keys,
+            theme,
+        ));
+    }
+
+    if let Some((names, groups)) = &table_insight.near_duplicates {
+        lines.push(Line::raw(""));
+        lines.extend(get_duplicate_lines(
+            &format!("Near Duplicates ({}):", names.join(", ")),
+            groups,
+            theme,
+        ));
+    }
+
+    lines
+}
+
+fn get_duplicate_lines(
+    title: &str,
+    groups: &[DuplicateGroup],
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+
+    lines.push(Line::from(title.to_string().bold()));
+    lines.push(Line::raw(""));
+
+    if groups.is_empty() {
+        lines.push(Line::from(
+            "  No duplicates found".fg(theme.insight_attribute_value_fg),
+        ));
+    }
+    for group in groups {
+        let keys = group
+            .keys
+            .iter()
+            .map(|key| format!("\"{key}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(vec![
+            "  ".into(),
+            group.value.clone().bold(),
+            " : ".bold(),
+            format!("{} items", group.keys.len()).fg(theme.insight_attribute_name_fg),
+            " ".into(),
+            keys.fg(theme.insight_attribute_value_fg),
+        ]));
+    }
+
+    lines
+}
+
+fn get_sort_key_prefix_lines(
+    name: &str,
+    total: usize,
+    sort_key_prefixes: &[(String, usize)],
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+
+    lines.push(Line::from(format!("Sort Key Prefixes ({name}):").bold()));
+    lines.push(Line::raw(""));
+
+    let max_width = sort_key_prefixes
+        .iter()
+        .map(|(prefix, _)| prefix.len())
+        .max()
+        .unwrap_or_default();
+
+    for (prefix, n) in sort_key_prefixes {
+        lines.push(Line::from(vec![
+            "  ".into(),
+            format!("{prefix:>max_width$}").bold(),
+            " : ".bold(),
+            n.to_string().fg(theme.insight_attribute_name_fg),
+            " ".into(),
+            format_ratio(*n, total).fg(theme.insight_attribute_value_fg),
+        ]));
+    }
+
+    lines
+}
+
+// Each row shows the ratio of the items with the attribute that also have the attribute of each column
+fn get_co_occurrence_lines(
+    table_insight: &TableInsight,
+    max_width: usize,
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+
+    lines.push(Line::from("Attribute Co-occurrence:".bold()));
+    lines.push(Line::from(
+        "(% of the items with the attribute that also have the numbered attribute)"
+            .fg(theme.insight_attribute_value_fg),
+    ));
+    lines.push(Line::raw(""));
+
+    let count = table_insight.co_occurrences.len();
+    let index_width = count.to_string().len();
+
+    let mut header = vec![" ".repeat(2 + index_width + 2 + max_width + 2).into()];
+    for j in 0..count {
+        header.push(format!("{:>5}", j + 1).bold());
+    }
+    lines.push(Line::from(header));
+
+    for (i, distribution) in table_insight.attribute_distributions.iter().enumerate() {
+        let mut spans = vec![];
+        spans.push("  ".into());
+        spans.push(format!("{:>index_width$}. ", i + 1).bold());
+        spans.push(format!("{:>max_width$}", distribution.attribute_name).bold());
+        spans.push(" :".bold());
+        let row = &table_insight.co_occurrences[i];
+        let total = row[i].max(1);
+        for (j, n) in row.iter().enumerate() {
+            if i == j {
+                spans.push(format!("{:>5}", "-").fg(theme.insight_attribute_value_fg));
+            } else {
+                let ratio = format!("{:>5}", format!("{}%", n * 100 / total));
+                if *n == 0 {
+                    spans.push(ratio.fg(theme.insight_attribute_value_fg));
+                } else {
+                    spans.push(ratio.fg(theme.insight_attribute_name_fg));
+                }
+            }
+        }
         lines.push(Line::from(spans));
     }
 
     lines
 }
 
+fn get_markdown_string(
+    table_insight: &TableInsight,
+    sort_key_prefixes: &[(String, usize)],
+) -> String {
+    let mut md = format!("## {} (Insights)\n\n", table_insight.table_name);
+    md.push_str(&format!("Total items: {}\n\n", table_insight.total_items));
+
+    md.push_str("### Attribute Distribution\n\n");
+    md.push_str("| Attribute | Type | Count | Ratio |\n| --- | --- | ---: | ---: |\n");
+    for distribution in &table_insight.attribute_distributions {
+        for (at, n) in &distribution.distributions {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                distribution.attribute_name.replace('|', "\\|"),
+                at.as_str(),
+                n,
+                format_ratio(*n, table_insight.total_items)
+            ));
+        }
+    }
+
+    md.push_str("\n### Attribute Co-occurrence\n\n");
+    md.push_str("| Attribute | With | Count | Ratio |\n| --- | --- | ---: | ---: |\n");
+    let names: Vec<_> = table_insight
+        .attribute_distributions
+        .iter()
+        .map(|d| d.attribute_name.replace('|', "\\|"))
+        .collect();
+    for (i, row) in table_insight.co_occurrences.iter().enumerate() {
+        for (j, n) in row.iter().enumerate() {
+            if i != j && *n > 0 {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    names[i],
+                    names[j],
+                    n,
+                    format_ratio(*n, row[i])
+                ));
+            }
+        }
+    }
+
+    if let Some((name, values)) = &table_insight.sort_key {
+        if !sort_key_prefixes.is_empty() {
+            md.push_str(&format!("\n### Sort Key Prefixes ({name})\n\n"));
+            md.push_str("| Prefix | Count | Ratio |\n| --- | ---: | ---: |\n");
+            for (prefix, n) in sort_key_prefixes {
+                md.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    prefix.replace('|', "\\|"),
+                    n,
+                    format_ratio(*n, values.len())
+                ));
+            }
+        }
+    }
+
+    if !table_insight.duplicate_keys.is_empty() {
+        md.push_str("\n### Duplicate Keys\n\n");
+        md.push_str(&get_duplicate_markdown_table(&table_insight.duplicate_keys));
+    }
+
+    if let Some((names, groups)) = &table_insight.near_duplicates {
+        md.push_str(&format!("\n### Near Duplicates ({})\n\n", names.join(", ")));
+        md.push_str(&get_duplicate_markdown_table(groups));
+    }
+    md
+}
+
+fn get_duplicate_markdown_table(groups: &[DuplicateGroup]) -> String {
+    let mut md = "| Value | Count | Keys |\n| --- | ---: | --- |\n".to_string();
+    for group in groups {
+        md.push_str(&format!(
+            "| {} | {} | {} |\n",
+            group.value.replace('|', "\\|"),
+            group.keys.len(),
+            group.keys.join(", ").replace('|', "\\|")
+        ));
+    }
+    md
+}
+
+fn get_json_string(table_insight: &TableInsight, sort_key_prefixes: &[(String, usize)]) -> String {
+    let distributions: Vec<_> = table_insight
+        .attribute_distributions
+        .iter()
+        .map(|distribution| {
+            let types: Vec<_> = distribution
+                .distributions
+                .iter()
+                .map(|(at, n)| json!({ "type": at.as_str(), "count": n }))
+                .collect();
+            json!({ "attributeName": distribution.attribute_name, "types": types })
+        })
+        .collect();
+    let co_occurrences: Vec<_> = table_insight
+        .co_occurrences
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let with: Vec<_> = row
+                .iter()
+                .enumerate()
+                .filter(|(j, n)| i != *j && **n > 0)
+                .map(|(j, n)| {
+                    let name = &table_insight.attribute_distributions[j].attribute_name;
+                    json!({ "attributeName": name, "count": n })
+                })
+                .collect();
+            let name = &table_insight.attribute_distributions[i].attribute_name;
+            json!({ "attributeName": name, "with": with })
+        })
+        .collect();
+    let mut value = json!({
+        "tableName": table_insight.table_name,
+        "totalItems": table_insight.total_items,
+        "attributeDistributions": distributions,
+        "attributeCoOccurrences": co_occurrences,
+    });
+    if let Some((name, _)) = &table_insight.sort_key {
+        let prefixes: Vec<_> = sort_key_prefixes
+            .iter()
+            .map(|(prefix, n)| json!({ "prefix": prefix, "count": n }))
+            .collect();
+        value["sortKeyPrefixes"] = json!({ "attributeName": name, "prefixes": prefixes });
+    }
+    let duplicate_groups = |groups: &[DuplicateGroup]| -> Vec<_> {
+        groups
+            .iter()
+            .map(|group| json!({ "value": group.value, "keys": group.keys }))
+            .collect()
+    };
+    if !table_insight.duplicate_keys.is_empty() {
+        value["duplicateKeys"] = json!(duplicate_groups(&table_insight.duplicate_keys));
+    }
+    if let Some((names, groups)) = &table_insight.near_duplicates {
+        value["nearDuplicates"] =
+            json!({ "attributeNames": names, "groups": duplicate_groups(groups) });
+    }
+    serde_json::to_string_pretty(&value).unwrap()
+}
+
 fn format_ratio(n: usize, total: usize) -> String {
     let mut ratio = format!("{:.1}", (n as f64 / total as f64) * 100.0);
     if let Some(r) = ratio.strip_suffix(".0") {
@@ -204,3 +582,186 @@ fn format_ratio(n: usize, total: usize) -> String {
     };
     format!("{ratio}%")
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::{AttributeDistribution, AttributeType},
+        event::test_channel,
+        view::snapshot::render_lines,
+    };
+
+    use super::*;
+
+    fn insight() -> TableInsight {
+        TableInsight {
+            table_name: "foo".into(),
+            total_items: 4,
+            attribute_distributions: vec![
+                AttributeDistribution {
+                    attribute_name: "id".into(),
+                    distributions: vec![(AttributeType::String, 4)],
+                },
+                AttributeDistribution {
+                    attribute_name: "age".into(),
+                    distributions: vec![(AttributeType::Number, 3), (AttributeType::None, 1)],
+                },
+            ],
+            co_occurrences: vec![vec![4, 3], vec![3, 3]],
+            sort_key: None,
+            duplicate_keys: vec![],
+            near_duplicates: None,
+        }
+    }
+
+    #[test]
+    fn test_get_markdown_string() {
+        let expected = "## foo (Insights)
+
+Total items: 4
+
+### Attribute Distribution
+
+| Attribute | Type | Count | Ratio |
+| --- | --- | ---: | ---: |
+| id | S | 4 | 100% |
+| age | N | 3 | 75% |
+| age | undefined | 1 | 25% |
+
+### Attribute Co-occurrence
+
+| Attribute | With | Count | Ratio |
+| --- | --- | ---: | ---: |
+| id | age | 3 | 75% |
+| age | id | 3 | 100% |
+";
+        assert_eq!(get_markdown_string(&insight(), &[]), expected);
+    }
+
+    #[test]
+    fn test_get_json_string() {
+        let actual: serde_json::Value =
+            serde_json::from_str(&get_json_string(&insight(), &[])).unwrap();
+        let expected = json!({
+            "tableName": "foo",
+            "totalItems": 4,
+            "attributeDistributions": [
+                { "attributeName": "id", "types": [{ "type": "S", "count": 4 }] },
+                {
+                    "attributeName": "age",
+                    "types": [{ "type": "N", "count": 3 }, { "type": "undefined", "count": 1 }],
+                },
+            ],
+            "attributeCoOccurrences": [
+                { "attributeName": "id", "with": [{ "attributeName": "age", "count": 3 }] },
+                { "attributeName": "age", "with": [{ "attributeName": "id", "count": 3 }] },
+            ],
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render() {
+        let (tx, _rx) = test_channel();
+        let mapper = UserEventMapper::new();
+        let config = UiInsightConfig::default();
+        let mut view = TableInsightView::new(insight(), &mapper, config, ColorTheme::default(), tx);
+
+        let actual = render_lines(40, 13, |f, area| view.render(f, area));
+        let expected = vec![
+            "┌ foo (Insights) ──────────────────────┐",
+            "│ Attribute Distribution:              │",
+            "│                                      │",
+            "│    id : S 100%                       │",
+            "│   age : N 75% undefined 25%          │",
+            "│                                      │",
+            "│ Attribute Co-occurrence:             │",
+            "│ (% of the items with the attribute t │",
+            "│                                      │",
+            "│               1    2                 │",
+            "│   1.  id :    -  75%                 │",
+            "│   2. age : 100%    -                 │",
+            "└──────────────────────────────────────┘",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_render_missing_warning() {
+        let (tx, _rx) = test_channel();
+        let mapper = UserEventMapper::new();
+        let config = UiInsightConfig {
+            missing_warning_percent: 25,
+            ..Default::default()
+        };
+        let mut view = TableInsightView::new(insight(), &mapper, config, ColorTheme::default(), tx);
+
+        let actual = render_lines(60, 13, |f, area| view.render(f, area));
+        let expected = vec![
+            "┌ foo (Insights) ──────────────────────────────────────────┐",
+            "│ Attribute Distribution:                                  │",
+            "│                                                          │",
+            "│    id : S 100%                                           │",
+            "│   age : N 75% undefined 25%  ! 25% missing or NULL       │",
+            "│                                                          │",
+            "│ Attribute Co-occurrence:                                 │",
+            "│ (% of the items with the attribute that also have the nu │",
+            "│                                                          │",
+            "│               1    2                                     │",
+            "│   1.  id :    -  75%                                     │",
+            "│   2. age : 100%    -                                     │",
+            "└──────────────────────────────────────────────────────────┘",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_markdown_string_sort_key_prefixes() {
+        let insight = TableInsight {
+            sort_key: Some((
+                "sk".into(),
+                vec!["A#1".into(), "A#2".into(), "B".into(), "B".into()],
+            )),
+            ..insight()
+        };
+        let prefixes = insight.sort_key_prefixes("#", 10);
+
+        let actual = get_markdown_string(&insight, &prefixes);
+        let expected = "
+### Sort Key Prefixes (sk)
+
+| Prefix | Count | Ratio |
+| --- | ---: | ---: |
+| A# | 2 | 50% |
+| B | 2 | 50% |
+";
+        assert!(actual.ends_with(expected));
+    }
+
+    #[test]
+    fn test_get_markdown_string_duplicates() {
+        let insight = TableInsight {
+            duplicate_keys: vec![DuplicateGroup {
+                value: "user#1".into(),
+                keys: vec!["User#1".into(), "user#1 ".into()],
+            }],
+            near_duplicates: Some((vec!["email".into()], vec![])),
+            ..insight()
+        };
+
+        let actual = get_markdown_string(&insight, &[]);
+        let expected = "
+### Duplicate Keys
+
+| Value | Count | Keys |
+| --- | ---: | --- |
+| user#1 | 2 | User#1, user#1  |
+
+### Near Duplicates (email)
+
+| Value | Count | Keys |
+| --- | ---: | --- |
+";
+        assert!(actual.ends_with(expected));
+    }
+}
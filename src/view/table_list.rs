@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use itsuki::zero_indexed_enum;
 use laurier::highlight::highlight_matched_text;
@@ -15,23 +15,39 @@ use tui_input::{backend::crossterm::EventHandler, Input};
 use crate::{
     color::ColorTheme,
     config::UiTableListConfig,
-    data::{Table, TableDescription},
+    data::{
+        AutoScalingTarget, CostEstimate, Item, KeySchemaElement, PlainJsonItem, Projection,
+        ScanOptions, Table, TableDescription, TableStatus,
+    },
+    error::AppError,
     event::{AppEvent, Sender, UserEvent, UserEventMapper},
     handle_user_events, handle_user_events_with_default,
     help::{
         build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
         SpansWithPriority,
     },
-    view::common::{raw_string_from_scroll_lines_state, to_highlighted_lines},
+    state::TableSizeSnapshot,
+    util::{filtered_count_string, paste_to_input, DisplayTimeZone},
+    view::common::{
+        links_in_lines, open_links_dialog, raw_string_from_scroll_lines_state,
+        selected_raw_string_from_scroll_lines_state, to_highlighted_lines, to_yaml_lines,
+        underline_links,
+    },
     widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState, ScrollList, ScrollListState},
 };
 
 pub struct TableListView {
     tables: Vec<Table>,
     table_descriptions: HashMap<String, TableDescription>,
+    table_items_previews: HashMap<String, Vec<Item>>,
+    size_histories: HashMap<String, Vec<TableSizeSnapshot>>,
+    // table names whose description or items preview is being loaded
+    loading_descriptions: HashSet<String>,
+    loading_items_previews: HashSet<String>,
 
     helps: TableListViewHelps,
     config: UiTableListConfig,
+    timezone: DisplayTimeZone,
     theme: ColorTheme,
     tx: Sender,
 
@@ -42,6 +58,8 @@ pub struct TableListView {
     view_indices: Vec<usize>,
 
     focused: Focused,
+    maximized: bool,
+    full_screen: bool,
     preview_type: PreviewType,
 }
 
@@ -70,6 +88,8 @@ enum Focused {
 enum PreviewType {
     KeyValue,
     Json,
+    Yaml,
+    Items,
 }
 
 impl TableListView {
@@ -77,6 +97,7 @@ impl TableListView {
         tables: Vec<Table>,
         mapper: &UserEventMapper,
         config: UiTableListConfig,
+        timezone: DisplayTimeZone,
         theme: ColorTheme,
         tx: Sender,
     ) -> Self {
@@ -90,8 +111,13 @@ impl TableListView {
         let mut view = TableListView {
             tables,
             table_descriptions: HashMap::new(),
+            table_items_previews: HashMap::new(),
+            size_histories: HashMap::new(),
+            loading_descriptions: HashSet::new(),
+            loading_items_previews: HashSet::new(),
             helps,
             config,
+            timezone,
             theme,
             tx,
             filter_state: FilterState::None,
@@ -100,6 +126,8 @@ impl TableListView {
             list_state,
             scroll_lines_state,
             focused: Focused::List,
+            maximized: false,
+            full_screen: false,
             preview_type: PreviewType::KeyValue,
         };
         view.load_table_description();
@@ -169,6 +197,12 @@ impl TableListView {
                     UserEvent::NextPane => {
                         self.next_pane();
                     }
+                    UserEvent::ToggleMaximize => {
+                        self.toggle_maximize();
+                    }
+                    UserEvent::ToggleFullScreen => {
+                        self.toggle_full_screen();
+                    }
                     UserEvent::NextPreview => {
                         self.next_preview();
                         self.update_preview();
@@ -180,9 +214,42 @@ impl TableListView {
                     UserEvent::Confirm => {
                         self.load_table_items();
                     }
+                    UserEvent::OpenSavedFilters => {
+                        self.open_saved_filters();
+                    }
+                    UserEvent::KeyTemplates => {
+                        self.open_key_templates();
+                    }
                     UserEvent::CopyToClipboard => {
                         self.copy_table_name_to_clipboard();
                     }
+                    UserEvent::OpenInConsole => {
+                        self.open_table_in_console();
+                    }
+                    UserEvent::AuditLog => {
+                        self.tx.send(AppEvent::OpenAuditLog);
+                    }
+                    UserEvent::Compare => {
+                        self.open_diff_table_dialog();
+                    }
+                    UserEvent::ContributorInsights => {
+                        self.open_contributor_insights_dialog();
+                    }
+                    UserEvent::AccountLimits => {
+                        self.tx.send(AppEvent::LoadAccountLimits);
+                    }
+                    UserEvent::MultiRegion => {
+                        self.open_multi_region_prompt();
+                    }
+                    UserEvent::PartiQL => {
+                        self.open_partiql();
+                    }
+                    UserEvent::FilterBuilder => {
+                        self.open_filter_builder();
+                    }
+                    UserEvent::QueryBuilder => {
+                        self.open_query_builder();
+                    }
                     UserEvent::Reload => {
                         self.reload_table_list();
                     }
@@ -220,6 +287,12 @@ impl TableListView {
                     UserEvent::NextPane => {
                         self.next_pane();
                     }
+                    UserEvent::ToggleMaximize => {
+                        self.toggle_maximize();
+                    }
+                    UserEvent::ToggleFullScreen => {
+                        self.toggle_full_screen();
+                    }
                     UserEvent::NextPreview => {
                         self.next_preview();
                         self.update_preview();
@@ -234,9 +307,24 @@ impl TableListView {
                     UserEvent::ToggleNumber => {
                         self.scroll_lines_state.toggle_number();
                     }
+                    UserEvent::ToggleSelection => {
+                        self.scroll_lines_state.toggle_selection();
+                    }
+                    UserEvent::Yank => {
+                        self.yank_selected_lines();
+                    }
+                    UserEvent::OpenLink => {
+                        self.open_links();
+                    }
+                    UserEvent::Reset => {
+                        self.scroll_lines_state.end_selection();
+                    }
                     UserEvent::CopyToClipboard => {
                         self.copy_table_descriptions_to_clipboard();
                     }
+                    UserEvent::SaveToFile => {
+                        self.save_table_descriptions_to_file();
+                    }
                     UserEvent::Reload => {
                         self.reload_table_description();
                     }
@@ -248,9 +336,27 @@ impl TableListView {
         }
     }
 
+    pub fn handle_paste(&mut self, text: &str) {
+        if let FilterState::Filtering = self.filter_state {
+            paste_to_input(&mut self.filter_input, text);
+            self.filter_view_indices();
+            self.update_status_input();
+            self.load_table_description();
+            self.update_preview();
+        }
+    }
+
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        if self.maximized || self.full_screen {
+            match self.focused {
+                Focused::List => self.render_list(f, area),
+                Focused::Detail => self.render_detail(f, area),
+            }
+            return;
+        }
+
         let [list_area, detail_area] = Layout::horizontal([
-            Constraint::Length(self.config.list_width),
+            Constraint::Length(list_width(&self.config, area.width)),
             Constraint::Min(0),
         ])
         .areas(area);
@@ -259,6 +365,10 @@ impl TableListView {
         self.render_detail(f, detail_area);
     }
 
+    pub fn is_full_screen(&self) -> bool {
+        self.full_screen
+    }
+
     pub fn short_helps(&self) -> &[SpansWithPriority] {
         match self.focused {
             Focused::List => match self.filter_state {
@@ -297,11 +407,23 @@ fn build_helps(
         BuildHelpsItem::new(UserEvent::GoToTop, "Select first item"),
         BuildHelpsItem::new(UserEvent::GoToBottom, "Select last item"),
         BuildHelpsItem::new(UserEvent::Confirm, "Open table"),
+        BuildHelpsItem::new(UserEvent::OpenSavedFilters, "Open table with saved filter or query"),
+        BuildHelpsItem::new(UserEvent::KeyTemplates, "Query table with key template"),
         BuildHelpsItem::new(UserEvent::QuickFilter, "Filter tables"),
         BuildHelpsItem::new(UserEvent::NextPane, "Switch to next pane"),
+        BuildHelpsItem::new(UserEvent::ToggleMaximize, "Maximize/restore pane"),
+        BuildHelpsItem::new(UserEvent::ToggleFullScreen, "Toggle full screen"),
         BuildHelpsItem::new(UserEvent::NextPreview, "Switch to next preview"),
         BuildHelpsItem::new(UserEvent::PrevPreview, "Switch to prev preview"),
         BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy table name"),
+        BuildHelpsItem::new(UserEvent::OpenInConsole, "Open table in AWS console"),
+        BuildHelpsItem::new(UserEvent::AuditLog, "Open audit log"),
+        BuildHelpsItem::new(UserEvent::Compare, "Compare schema with another table"),
+        BuildHelpsItem::new(UserEvent::ContributorInsights, "Enable/disable Contributor Insights"),
+        BuildHelpsItem::new(UserEvent::AccountLimits, "Show account capacity limits"),
+        BuildHelpsItem::new(UserEvent::MultiRegion, "List tables across regions"),
+        BuildHelpsItem::new(UserEvent::PartiQL, "Open PartiQL editor"),
+        BuildHelpsItem::new(UserEvent::FilterBuilder, "Open filter expression builder"),
         BuildHelpsItem::new(UserEvent::Reload, "Reload table list"),
     ];
     #[rustfmt::skip]
@@ -312,11 +434,23 @@ fn build_helps(
         BuildHelpsItem::new(UserEvent::GoToTop, "Select first item"),
         BuildHelpsItem::new(UserEvent::GoToBottom, "Select last item"),
         BuildHelpsItem::new(UserEvent::Confirm, "Open table"),
+        BuildHelpsItem::new(UserEvent::OpenSavedFilters, "Open table with saved filter or query"),
+        BuildHelpsItem::new(UserEvent::KeyTemplates, "Query table with key template"),
         BuildHelpsItem::new(UserEvent::Reset, "Clear filter"),
         BuildHelpsItem::new(UserEvent::NextPane, "Switch to next pane"),
+        BuildHelpsItem::new(UserEvent::ToggleMaximize, "Maximize/restore pane"),
+        BuildHelpsItem::new(UserEvent::ToggleFullScreen, "Toggle full screen"),
         BuildHelpsItem::new(UserEvent::NextPreview, "Switch to next preview"),
         BuildHelpsItem::new(UserEvent::PrevPreview, "Switch to prev preview"),
         BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy table name"),
+        BuildHelpsItem::new(UserEvent::OpenInConsole, "Open table in AWS console"),
+        BuildHelpsItem::new(UserEvent::AuditLog, "Open audit log"),
+        BuildHelpsItem::new(UserEvent::Compare, "Compare schema with another table"),
+        BuildHelpsItem::new(UserEvent::ContributorInsights, "Enable/disable Contributor Insights"),
+        BuildHelpsItem::new(UserEvent::AccountLimits, "Show account capacity limits"),
+        BuildHelpsItem::new(UserEvent::MultiRegion, "List tables across regions"),
+        BuildHelpsItem::new(UserEvent::PartiQL, "Open PartiQL editor"),
+        BuildHelpsItem::new(UserEvent::FilterBuilder, "Open filter expression builder"),
         BuildHelpsItem::new(UserEvent::Reload, "Reload table list"),
     ];
     #[rustfmt::skip]
@@ -331,11 +465,18 @@ fn build_helps(
         BuildHelpsItem::new(UserEvent::Right, "Scroll right"),
         BuildHelpsItem::new(UserEvent::Left, "Scroll left"),
         BuildHelpsItem::new(UserEvent::NextPane, "Switch to next pane"),
+        BuildHelpsItem::new(UserEvent::ToggleMaximize, "Maximize/restore pane"),
+        BuildHelpsItem::new(UserEvent::ToggleFullScreen, "Toggle full screen"),
         BuildHelpsItem::new(UserEvent::NextPreview, "Switch to next preview"),
         BuildHelpsItem::new(UserEvent::PrevPreview, "Switch to previous preview"),
         BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
         BuildHelpsItem::new(UserEvent::ToggleNumber, "Toggle number"),
+        BuildHelpsItem::new(UserEvent::ToggleSelection, "Start/end line selection"),
+        BuildHelpsItem::new(UserEvent::Yank, "Copy selected lines"),
+        BuildHelpsItem::new(UserEvent::Reset, "Cancel line selection"),
+        BuildHelpsItem::new(UserEvent::OpenLink, "Open/copy links (at cursor)"),
         BuildHelpsItem::new(UserEvent::CopyToClipboard, "Copy table descriptions"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save table descriptions to file"),
         BuildHelpsItem::new(UserEvent::Reload, "Reload table details"),
     ];
     (
@@ -400,7 +541,8 @@ fn build_short_helps(
 impl TableListView {
     fn render_list(&mut self, f: &mut Frame, area: Rect) {
         let show_items_count = area.height as usize - 2 /* border */;
-        let item_width = area.width as usize - 2 /* border */ - 2 /* padding (list) */ - 2 /* padding (item) */;
+        let padding = 2 /* border */ + 2 /* padding (list) */ + 2 /* padding (item) */;
+        let item_width = (area.width as usize).saturating_sub(padding);
         let query = self.filter_input.value().to_lowercase();
         let items: Vec<_> = self
             .filtered_tables()
@@ -409,13 +551,16 @@ impl TableListView {
             .take(show_items_count)
             .enumerate()
             .map(|(i, t)| {
-                let line = if query.is_empty() {
-                    let name = console::truncate_str(&t.name, item_width, "..");
-                    Line::raw(format!(" {name:item_width$} "))
+                let badge = self.table_status_badge(&t.name);
+                let badge_width = badge.as_ref().map(|b| b.width() + 1).unwrap_or_default();
+                let name_width = item_width.saturating_sub(badge_width);
+                let mut line = if query.is_empty() {
+                    let name = console::truncate_str(&t.name, name_width, "..");
+                    Line::raw(format!(" {name:name_width$} "))
                 } else {
                     let i = t.name.to_lowercase().find(&query).unwrap();
                     let mut hm = highlight_matched_text(vec![Span::raw(&t.name)]);
-                    if t.name.len() > item_width {
+                    if t.name.len() > name_width {
                         hm = hm.ellipsis("..");
                     }
                     let mut spans = hm
@@ -426,10 +571,15 @@ impl TableListView {
                                 .bg(self.theme.quick_filter_matched_bg),
                         )
                         .into_spans();
+                    let width: usize = spans.iter().map(Span::width).sum();
                     spans.insert(0, " ".into());
-                    spans.push(" ".into());
+                    spans.push(" ".repeat(name_width.saturating_sub(width) + 1).into());
                     Line::from(spans)
                 };
+                if let Some(badge) = badge {
+                    line.spans.push(badge);
+                    line.spans.push(" ".into());
+                }
                 let mut style = Style::default();
                 if i + self.list_state.offset == self.list_state.selected {
                     style = style.fg(self.theme.selected_fg);
@@ -443,13 +593,48 @@ impl TableListView {
             })
             .collect();
         let list = ScrollList::new(items)
+            .footer(filtered_count_string(
+                self.view_indices.len(),
+                self.tables.len(),
+            ))
             .theme(&self.theme)
             .focused(self.focused == Focused::List);
         f.render_stateful_widget(list, area, &mut self.list_state);
     }
 
+    fn table_status_badge(&self, table_name: &str) -> Option<Span<'static>> {
+        let desc = self.table_descriptions.get(table_name)?;
+        let color = match desc.table_status {
+            TableStatus::Active => return None,
+            TableStatus::Creating => self.theme.table_status_creating_fg,
+            TableStatus::Updating => self.theme.table_status_updating_fg,
+            TableStatus::Deleting => self.theme.table_status_deleting_fg,
+            TableStatus::Archiving
+            | TableStatus::Archived
+            | TableStatus::InaccessibleEncryptionCredentials
+            | TableStatus::Other(_) => self.theme.table_status_inactive_fg,
+        };
+        let status = match desc.table_status {
+            TableStatus::InaccessibleEncryptionCredentials => "INACCESSIBLE",
+            ref status => status.as_str(),
+        };
+        Some(Span::raw(status.to_string()).fg(color).bold())
+    }
+
     fn render_detail(&mut self, f: &mut Frame, area: Rect) {
         let mut block = Block::bordered().fg(self.theme.fg).bg(self.theme.bg);
+        if self.is_loading_selected_table() {
+            block = block.title_top(Line::from(" Loading... ").bold().left_aligned());
+        }
+        if let Some(desc) = self.current_selected_table_description() {
+            if !desc.missing_fields.is_empty() {
+                let banner = Line::from(" Incomplete description ")
+                    .fg(self.theme.notification_warning)
+                    .bold()
+                    .right_aligned();
+                block = block.title_top(banner);
+            }
+        }
         if self.focused != Focused::Detail {
             block = block.border_style(Style::default().fg(self.theme.disabled));
         }
@@ -460,36 +645,183 @@ impl TableListView {
 }
 
 impl TableListView {
-    fn load_table_description(&self) {
+    fn load_table_description(&mut self) {
         if let Some(name) = self.current_selected_table_name() {
-            if self.table_descriptions.contains_key(name) {
+            if self.table_descriptions.contains_key(name)
+                || self.loading_descriptions.contains(name)
+            {
                 return;
             }
 
-            self.tx.send(AppEvent::LoadTableDescription(name.into()));
+            let name = name.to_string();
+            self.loading_descriptions.insert(name.clone());
+            self.tx.send(AppEvent::LoadTableDescription(name));
         }
     }
 
-    fn reload_table_description(&self) {
+    fn reload_table_description(&mut self) {
         if let Some(name) = self.current_selected_table_name() {
-            self.tx.send(AppEvent::LoadTableDescription(name.into()));
+            let name = name.to_string();
+            self.table_items_previews.remove(&name);
+            self.loading_descriptions.insert(name.clone());
+            self.tx.send(AppEvent::LoadTableDescription(name));
         }
     }
 
+    pub fn set_size_history(&mut self, table_name: String, history: Vec<TableSizeSnapshot>) {
+        self.size_histories.insert(table_name, history);
+    }
+
     pub fn set_table_description(&mut self, desc: TableDescription) {
         let name = desc.table_name.clone();
-        self.table_descriptions.insert(name, desc);
+        self.loading_descriptions.remove(&name);
+        if !self.tables.iter().any(|t| t.name == name) {
+            return;
+        }
+        self.table_descriptions.insert(name.clone(), desc);
+
+        // the cursor may have moved on while loading, rebuilding the preview would reset its scroll
+        if self.current_selected_table_name() == Some(name.as_str()) {
+            self.update_preview();
+        }
+    }
+
+    pub fn cancel_loading_table_description(&mut self, table_name: &str) {
+        self.loading_descriptions.remove(table_name);
+    }
+
+    pub fn select_table(&mut self, table_name: &str) {
+        if let Some(i) = self.tables.iter().position(|t| t.name == table_name) {
+            self.filter_input.reset();
+            self.filter_state = FilterState::None;
+            self.filter_view_indices();
+            self.list_state.select_index(i);
+            self.focused = Focused::List;
+            self.load_table_description();
+            self.update_preview();
+        }
+    }
+
+    fn load_table_items_preview(&mut self) {
+        if let Some(desc) = self.current_selected_table_description() {
+            let name = &desc.table_name;
+            if self.table_items_previews.contains_key(name)
+                || self.loading_items_previews.contains(name)
+            {
+                return;
+            }
+
+            let desc = desc.clone();
+            let count = self.config.preview_item_count;
+            self.loading_items_previews.insert(desc.table_name.clone());
+            self.tx.send(AppEvent::LoadTableItemsPreview(desc, count));
+        }
+    }
 
-        self.update_preview();
+    pub fn set_table_items_preview(&mut self, table_name: String, items: Vec<Item>) {
+        self.loading_items_previews.remove(&table_name);
+        let selected = self.current_selected_table_name() == Some(table_name.as_str());
+        self.table_items_previews.insert(table_name, items);
+
+        if selected {
+            self.update_preview();
+        }
+    }
+
+    pub fn cancel_loading_table_items_preview(&mut self, table_name: &str) {
+        self.loading_items_previews.remove(table_name);
+    }
+
+    fn is_loading_selected_table(&self) -> bool {
+        self.current_selected_table_name().is_some_and(|name| {
+            self.loading_descriptions.contains(name)
+                || (self.preview_type == PreviewType::Items
+                    && self.loading_items_previews.contains(name))
+        })
     }
 
     fn load_table_items(&self) {
         if let Some(desc) = self.current_selected_table_description() {
-            self.tx.send(AppEvent::LoadTableItems(desc.clone()));
+            let options = ScanOptions::default();
+            self.tx
+                .send(AppEvent::LoadTableItems(desc.clone(), options));
+        }
+    }
+
+    fn open_partiql(&self) {
+        if let Some(desc) = self.current_selected_table_description() {
+            self.tx.send(AppEvent::OpenPartiQL(desc.clone()));
+        }
+    }
+
+    fn open_filter_builder(&self) {
+        if let Some(desc) = self.current_selected_table_description() {
+            self.tx.send(AppEvent::OpenFilterBuilder(desc.clone()));
+        }
+    }
+
+    fn open_query_builder(&self) {
+        if let Some(desc) = self.current_selected_table_description() {
+            self.tx.send(AppEvent::OpenQueryBuilder(desc.clone()));
+        }
+    }
+
+    fn open_saved_filters(&self) {
+        if let Some(desc) = self.current_selected_table_description() {
+            self.tx.send(AppEvent::OpenSavedFilters(desc.clone()));
+        }
+    }
+
+    fn open_key_templates(&self) {
+        if let Some(desc) = self.current_selected_table_description() {
+            self.tx.send(AppEvent::OpenKeyTemplates(desc.clone()));
+        }
+    }
+
+    fn open_diff_table_dialog(&self) {
+        if let Some(name) = self.current_selected_table_name() {
+            let items = self
+                .tables
+                .iter()
+                .filter(|t| t.name != name)
+                .map(|t| {
+                    let event = AppEvent::DiffTables(name.into(), t.name.clone());
+                    (t.name.clone(), event)
+                })
+                .collect();
+            let title = format!("Compare {name} with");
+            self.tx.send(AppEvent::OpenSelectDialog(title, items));
+        }
+    }
+
+    fn open_contributor_insights_dialog(&self) {
+        if let Some(desc) = self.current_selected_table_description() {
+            let table_name = &desc.table_name;
+            // Contributor Insights is available for the table and its global secondary indexes
+            let gsis = desc.global_secondary_indexes.iter().flatten();
+            let targets = std::iter::once(None).chain(gsis.map(|gsi| Some(&gsi.index_name)));
+            let items = targets
+                .map(|index_name| {
+                    let enabled = desc.contributor_insights_enabled(index_name.map(|s| s.as_str()));
+                    let action = if enabled { "Disable" } else { "Enable" };
+                    let label = match index_name {
+                        Some(index_name) => format!("{action} for index {index_name}"),
+                        None => format!("{action} for table {table_name}"),
+                    };
+                    let event = AppEvent::UpdateContributorInsights(
+                        table_name.clone(),
+                        index_name.cloned(),
+                        !enabled,
+                    );
+                    (label, event)
+                })
+                .collect();
+            let title = "Contributor Insights".into();
+            self.tx.send(AppEvent::OpenSelectDialog(title, items));
         }
     }
 
-    fn current_selected_table_name(&self) -> Option<&str> {
+    pub fn current_selected_table_name(&self) -> Option<&str> {
         self.filtered_tables()
             .get(self.list_state.selected)
             .map(|t| t.name.as_str())
@@ -504,6 +836,14 @@ impl TableListView {
         self.focused = self.focused.next();
     }
 
+    fn toggle_maximize(&mut self) {
+        self.maximized = !self.maximized;
+    }
+
+    fn toggle_full_screen(&mut self) {
+        self.full_screen = !self.full_screen;
+    }
+
     fn next_preview(&mut self) {
         self.preview_type = self.preview_type.next();
     }
@@ -517,10 +857,25 @@ impl TableListView {
 
         if let Some(desc) = self.current_selected_table_description() {
             let lines = match self.preview_type {
-                PreviewType::KeyValue => get_key_value_lines(desc),
+                PreviewType::KeyValue => {
+                    let history = self
+                        .size_histories
+                        .get(&desc.table_name)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+                    get_key_value_lines(desc, history, self.timezone)
+                }
                 PreviewType::Json => get_json_lines(desc, &self.theme),
+                PreviewType::Yaml => get_yaml_lines(desc, &self.theme),
+                PreviewType::Items => match self.table_items_previews.get(&desc.table_name) {
+                    Some(items) => get_items_lines(desc, items, &self.theme),
+                    None => vec![],
+                },
             };
-            self.scroll_lines_state = ScrollLinesState::new(lines, options);
+            self.scroll_lines_state = ScrollLinesState::new(underline_links(lines), options);
+            if self.preview_type == PreviewType::Items {
+                self.load_table_items_preview();
+            }
         } else {
             self.scroll_lines_state = ScrollLinesState::new(vec![], options);
         }
@@ -600,6 +955,18 @@ impl TableListView {
         self.view_indices.iter().map(|&i| &self.tables[i]).collect()
     }
 
+    fn open_multi_region_prompt(&self) {
+        let on_submit = Box::new(|input: String| {
+            let regions = parse_regions(&input);
+            if regions.is_empty() {
+                return AppEvent::NotifyWarning(AppError::msg("No regions specified"));
+            }
+            AppEvent::ListMultiRegionTables(regions)
+        });
+        let title = "Regions (e.g. us-east-1,eu-west-1)".into();
+        self.tx.send(AppEvent::OpenPrompt(title, on_submit));
+    }
+
     fn copy_table_name_to_clipboard(&self) {
         if let Some(name) = self.current_selected_table_name() {
             self.tx
@@ -607,12 +974,56 @@ impl TableListView {
         }
     }
 
+    fn open_table_in_console(&self) {
+        if let Some(name) = self.current_selected_table_name() {
+            self.tx.send(AppEvent::OpenTableInConsole(name.into()));
+        }
+    }
+
+    fn open_links(&self) {
+        let lines = self.scroll_lines_state.lines();
+        let links = match self.scroll_lines_state.cursor() {
+            Some(cursor) => links_in_lines(&lines[cursor..=cursor]),
+            None => links_in_lines(lines),
+        };
+        open_links_dialog(links, &self.tx);
+    }
+
+    fn yank_selected_lines(&mut self) {
+        match selected_raw_string_from_scroll_lines_state(&self.scroll_lines_state) {
+            Some(content) => {
+                self.tx
+                    .send(AppEvent::CopyToClipboard("selected lines".into(), content));
+                self.scroll_lines_state.end_selection();
+            }
+            None => {
+                let msg = "Start the line selection to select the lines to copy";
+                self.tx.send(AppEvent::NotifyWarning(AppError::msg(msg)));
+            }
+        }
+    }
+
     fn copy_table_descriptions_to_clipboard(&self) {
         let content = raw_string_from_scroll_lines_state(&self.scroll_lines_state);
-        self.tx.send(AppEvent::CopyToClipboard(
-            "table descriptions".into(),
-            content,
-        ));
+        let mut items = vec![(
+            "Current preview".into(),
+            AppEvent::CopyToClipboard("table descriptions".into(), content),
+        )];
+        if let Some(desc) = self.current_selected_table_description() {
+            let markdown = get_markdown_string(desc, self.timezone);
+            items.push((
+                "Markdown".into(),
+                AppEvent::CopyToClipboard("table descriptions as markdown".into(), markdown),
+            ));
+        }
+        self.tx
+            .send(AppEvent::OpenSelectDialog("Copy as".into(), items));
+    }
+
+    fn save_table_descriptions_to_file(&self) {
+        let content = raw_string_from_scroll_lines_state(&self.scroll_lines_state);
+        self.tx
+            .send(AppEvent::SaveToFile("table descriptions".into(), content));
     }
 
     fn reload_table_list(&self) {
@@ -635,7 +1046,80 @@ impl TableListView {
     }
 }
 
-fn get_key_value_lines(desc: &TableDescription) -> Vec<Line<'static>> {
+fn list_width(config: &UiTableListConfig, area_width: u16) -> u16 {
+    let width = if config.list_width_percent > 0 {
+        let percent = config.list_width_percent.min(100) as u32;
+        (area_width as u32 * percent / 100) as u16
+    } else {
+        config.list_width
+    };
+    width.min(area_width.saturating_sub(config.min_detail_width))
+}
+
+// The first string is the name and keys of the index, followed by its details
+fn index_detail_strings(
+    index_name: &str,
+    key_schema: &[KeySchemaElement],
+    projection: &Projection,
+    index_size_bytes: u64,
+    item_count: u64,
+) -> Vec<String> {
+    let keys = key_schema
+        .iter()
+        .map(|key| key.attribute_name.clone())
+        .collect::<Vec<String>>()
+        .join(" / ");
+    let mut projection_string = format!("Projection: {}", projection.projection_type.as_str());
+    if let Some(attrs) = projection
+        .non_key_attributes
+        .as_ref()
+        .filter(|a| !a.is_empty())
+    {
+        projection_string.push_str(&format!(" ({})", attrs.join(", ")));
+    }
+    vec![
+        format!("{index_name} ({keys})"),
+        projection_string,
+        format!(
+            "Item Count: {item_count} / Size: {}",
+            format_size(index_size_bytes)
+        ),
+    ]
+}
+
+// The details are indented under the name of each index
+fn push_index_lines(
+    lines: &mut Vec<Line<'static>>,
+    label: &str,
+    indexes: impl Iterator<Item = Vec<String>>,
+    key_max_width: usize,
+    separator: &str,
+) {
+    let blank = " ".repeat(key_max_width + separator.len());
+    for (i, details) in indexes.enumerate() {
+        for (j, detail) in details.into_iter().enumerate() {
+            let mut spans = vec![];
+            if i == 0 && j == 0 {
+                spans.push(format!("{label:>key_max_width$}").bold());
+                spans.push(separator.to_string().into());
+            } else {
+                spans.push(blank.clone().into());
+            }
+            if j == 0 {
+                spans.push(detail.into());
+            } else {
+                spans.push(format!("  {detail}").dim());
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+}
+
+fn get_key_value_lines(
+    desc: &TableDescription,
+    size_history: &[TableSizeSnapshot],
+    timezone: DisplayTimeZone,
+) -> Vec<Line<'static>> {
     let key_max_width = 22;
     let separator = " : ";
     let mut lines = vec![];
@@ -681,7 +1165,12 @@ fn get_key_value_lines(desc: &TableDescription) -> Vec<Line<'static>> {
     let mut spans = vec![];
     spans.push(format!("{:>key_max_width$}", "Creation Date").bold());
     spans.push(separator.into());
-    spans.push(desc.creation_date_time.to_string().into());
+    spans.push(
+        timezone
+            .convert(&desc.creation_date_time)
+            .to_string()
+            .into(),
+    );
     lines.push(Line::from(spans));
 
     if let Some(pt) = &desc.provisioned_throughput {
@@ -698,6 +1187,20 @@ fn get_key_value_lines(desc: &TableDescription) -> Vec<Line<'static>> {
         lines.push(Line::from(spans));
     }
 
+    let mut spans = vec![];
+    for (i, target) in desc.auto_scaling_targets.iter().enumerate() {
+        if i == 0 {
+            spans.push(format!("{:>key_max_width$}", "Auto Scaling").bold());
+            spans.push(separator.into());
+        } else {
+            spans.push(" ".repeat(key_max_width + separator.len()).into());
+        }
+        spans.push(auto_scaling_target_string(desc, target).into());
+        lines.push(Line::from(spans));
+        spans = vec![];
+    }
+    push_unavailable_line(&mut lines, desc, "Auto Scaling", key_max_width, separator);
+
     let mut spans = vec![];
     spans.push(format!("{:>key_max_width$}", "Item Count").bold());
     spans.push(separator.into());
@@ -710,6 +1213,37 @@ fn get_key_value_lines(desc: &TableDescription) -> Vec<Line<'static>> {
     spans.push(format_size(desc.total_size_bytes).into());
     lines.push(Line::from(spans));
 
+    let mut spans = vec![];
+    spans.push(format!("{:>key_max_width$}", "Estimated Cost").bold());
+    spans.push(separator.into());
+    spans.push(cost_estimate_string(&CostEstimate::new(desc)).into());
+    spans.push(" (us-east-1 Standard pricing)".dim());
+    lines.push(Line::from(spans));
+
+    if let [first, .., last] = size_history {
+        let since = format!(
+            " ({} - {})",
+            timezone.convert(&first.recorded_at).format("%Y-%m-%d"),
+            timezone.convert(&last.recorded_at).format("%Y-%m-%d")
+        );
+
+        let mut spans = vec![];
+        spans.push(format!("{:>key_max_width$}", "Item Count Trend").bold());
+        spans.push(separator.into());
+        let counts: Vec<u64> = size_history.iter().map(|s| s.item_count).collect();
+        spans.push(sparkline(&counts).into());
+        spans.push(since.clone().dim());
+        lines.push(Line::from(spans));
+
+        let mut spans = vec![];
+        spans.push(format!("{:>key_max_width$}", "Total Size Trend").bold());
+        spans.push(separator.into());
+        let sizes: Vec<u64> = size_history.iter().map(|s| s.total_size_bytes).collect();
+        spans.push(sparkline(&sizes).into());
+        spans.push(since.dim());
+        lines.push(Line::from(spans));
+    }
+
     let mut spans = vec![];
     spans.push(format!("{:>key_max_width$}", "Table ARN").bold());
     spans.push(separator.into());
@@ -717,65 +1251,308 @@ fn get_key_value_lines(desc: &TableDescription) -> Vec<Line<'static>> {
     lines.push(Line::from(spans));
 
     if let Some(lsis) = &desc.local_secondary_indexes {
-        let mut spans = vec![];
-        for (i, lsi) in lsis.iter().enumerate() {
-            if i == 0 {
-                spans.push(format!("{:>key_max_width$}", "LSI").bold());
-                spans.push(separator.into());
-            } else {
-                spans.push(" ".repeat(key_max_width + separator.len()).into());
-            }
-            spans.push(
-                format!(
-                    "{} ({})",
-                    lsi.index_name,
-                    lsi.key_schema
-                        .iter()
-                        .map(|key| key.attribute_name.clone())
-                        .collect::<Vec<String>>()
-                        .join(" / "),
-                )
-                .into(),
-            );
-            lines.push(Line::from(spans));
-            spans = vec![];
-        }
+        let details = lsis.iter().map(|lsi| {
+            index_detail_strings(
+                &lsi.index_name,
+                &lsi.key_schema,
+                &lsi.projection,
+                lsi.index_size_bytes,
+                lsi.item_count,
+            )
+        });
+        push_index_lines(&mut lines, "LSI", details, key_max_width, separator);
     }
 
     if let Some(gsis) = &desc.global_secondary_indexes {
-        let mut spans = vec![];
-        for (i, gsi) in gsis.iter().enumerate() {
-            if i == 0 {
-                spans.push(format!("{:>key_max_width$}", "GSI").bold());
-                spans.push(separator.into());
-            } else {
-                spans.push(" ".repeat(key_max_width + separator.len()).into());
-            }
-            spans.push(
-                format!(
-                    "{} ({})",
-                    gsi.index_name,
-                    gsi.key_schema
-                        .iter()
-                        .map(|key| key.attribute_name.clone())
-                        .collect::<Vec<String>>()
-                        .join(" / "),
-                )
-                .into(),
+        let details = gsis.iter().map(|gsi| {
+            let mut details = index_detail_strings(
+                &gsi.index_name,
+                &gsi.key_schema,
+                &gsi.projection,
+                gsi.index_size_bytes,
+                gsi.item_count,
             );
-            lines.push(Line::from(spans));
-            spans = vec![];
+            let mut status = vec![];
+            if let Some(index_status) = &gsi.index_status {
+                status.push(format!("Status: {}", index_status.as_str()));
+            }
+            if let Some(pt) = &gsi.provisioned_throughput {
+                status.push(format!(
+                    "Read: {} / Write: {}",
+                    pt.read_capacity_units, pt.write_capacity_units
+                ));
+            }
+            if !status.is_empty() {
+                details.push(status.join(" / "));
+            }
+            details
+        });
+        push_index_lines(&mut lines, "GSI", details, key_max_width, separator);
+    }
+
+    let mut spans = vec![];
+    for (i, dest) in desc.kinesis_streaming_destinations.iter().enumerate() {
+        if i == 0 {
+            spans.push(format!("{:>key_max_width$}", "Kinesis Destinations").bold());
+            spans.push(separator.into());
+        } else {
+            spans.push(" ".repeat(key_max_width + separator.len()).into());
+        }
+        spans.push(format!("{} ({})", dest.stream_arn, dest.destination_status).into());
+        if let Some(description) = &dest.destination_status_description {
+            spans.push(format!(" {description}").dim());
+        }
+        lines.push(Line::from(spans));
+        spans = vec![];
+    }
+    push_unavailable_line(
+        &mut lines,
+        desc,
+        "Kinesis Destinations",
+        key_max_width,
+        separator,
+    );
+
+    for (i, insight) in desc.contributor_insights.iter().enumerate() {
+        if i == 0 {
+            spans.push(format!("{:>key_max_width$}", "Contributor Insights").bold());
+            spans.push(separator.into());
+        } else {
+            spans.push(" ".repeat(key_max_width + separator.len()).into());
         }
+        let name = insight.index_name.as_ref().unwrap_or(&desc.table_name);
+        spans.push(format!("{name} ({})", insight.status).into());
+        if !insight.rules.is_empty() {
+            spans.push(format!(" {}", insight.rules.join(", ")).dim());
+        }
+        lines.push(Line::from(spans));
+        spans = vec![];
     }
+    push_unavailable_line(
+        &mut lines,
+        desc,
+        "Contributor Insights",
+        key_max_width,
+        separator,
+    );
 
     lines
 }
 
+// Distinguish a detail that failed to load from one that is just not configured
+fn push_unavailable_line(
+    lines: &mut Vec<Line<'static>>,
+    desc: &TableDescription,
+    label: &'static str,
+    key_max_width: usize,
+    separator: &str,
+) {
+    if desc.unavailable_details.contains(&label) {
+        lines.push(Line::from(vec![
+            format!("{label:>key_max_width$}").bold(),
+            separator.to_string().into(),
+            "unavailable".dim(),
+        ]));
+    }
+}
+
+fn get_markdown_string(desc: &TableDescription, timezone: DisplayTimeZone) -> String {
+    let key_schema = |keys: &[KeySchemaElement]| {
+        keys.iter()
+            .map(|key| format!("{} ({})", key.attribute_name, key.key_type.as_str()))
+            .collect::<Vec<String>>()
+            .join(" / ")
+    };
+
+    let mut md = format!("## {}\n\n", escape_markdown(&desc.table_name));
+
+    md.push_str("| Property | Value |\n| --- | --- |\n");
+    let throughput = match &desc.provisioned_throughput {
+        Some(pt) => format!(
+            "Read: {} / Write: {}",
+            pt.read_capacity_units, pt.write_capacity_units
+        ),
+        None => "-".into(),
+    };
+    let properties = [
+        ("Key Schema", key_schema(&desc.key_schema)),
+        ("Table Status", desc.table_status.as_str().into()),
+        (
+            "Creation Date",
+            timezone.convert(&desc.creation_date_time).to_string(),
+        ),
+        ("Provisioned Throughput", throughput),
+        ("Item Count", desc.item_count.to_string()),
+        ("Total Size", format_size(desc.total_size_bytes)),
+        ("Table ARN", desc.table_arn.clone()),
+    ];
+    for (name, value) in properties {
+        md.push_str(&format!("| {name} | {} |\n", escape_markdown(&value)));
+    }
+
+    md.push_str("\n### Attribute Definitions\n\n");
+    md.push_str("| Attribute | Type |\n| --- | --- |\n");
+    for attr in &desc.attribute_definitions {
+        md.push_str(&format!(
+            "| {} | {} |\n",
+            escape_markdown(&attr.attribute_name),
+            attr.attribute_type.as_str()
+        ));
+    }
+
+    let lsis = desc.local_secondary_indexes.iter().flatten();
+    let lsis = lsis.map(|i| ("LSI", &i.index_name, &i.key_schema, &i.projection));
+    let gsis = desc.global_secondary_indexes.iter().flatten();
+    let gsis = gsis.map(|i| ("GSI", &i.index_name, &i.key_schema, &i.projection));
+    let indexes: Vec<_> = lsis.chain(gsis).collect();
+    if !indexes.is_empty() {
+        md.push_str("\n### Indexes\n\n");
+        md.push_str("| Index | Type | Key Schema | Projection |\n| --- | --- | --- | --- |\n");
+        for (index_type, name, keys, projection) in indexes {
+            md.push_str(&format!(
+                "| {} | {index_type} | {} | {} |\n",
+                escape_markdown(name),
+                escape_markdown(&key_schema(keys)),
+                projection.projection_type.as_str()
+            ));
+        }
+    }
+
+    if !desc.kinesis_streaming_destinations.is_empty() {
+        md.push_str("\n### Kinesis Streaming Destinations\n\n");
+        md.push_str("| Stream ARN | Status |\n| --- | --- |\n");
+        for dest in &desc.kinesis_streaming_destinations {
+            md.push_str(&format!(
+                "| {} | {} |\n",
+                escape_markdown(&dest.stream_arn),
+                dest.destination_status
+            ));
+        }
+    }
+
+    if !desc.auto_scaling_targets.is_empty() {
+        md.push_str("\n### Auto Scaling\n\n");
+        md.push_str("| Target | Capacity | Min | Max | Target Utilization |\n");
+        md.push_str("| --- | --- | --- | --- | --- |\n");
+        for target in &desc.auto_scaling_targets {
+            let name = target.index_name.as_ref().unwrap_or(&desc.table_name);
+            let utilization = match target.target_utilization {
+                Some(utilization) => format!("{utilization}%"),
+                None => "-".into(),
+            };
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | {utilization} |\n",
+                escape_markdown(name),
+                target.dimension,
+                target.min_capacity,
+                target.max_capacity
+            ));
+        }
+    }
+
+    if !desc.contributor_insights.is_empty() {
+        md.push_str("\n### Contributor Insights\n\n");
+        md.push_str("| Target | Status | Rules |\n| --- | --- | --- |\n");
+        for insight in &desc.contributor_insights {
+            let name = insight.index_name.as_ref().unwrap_or(&desc.table_name);
+            md.push_str(&format!(
+                "| {} | {} | {} |\n",
+                escape_markdown(name),
+                insight.status,
+                escape_markdown(&insight.rules.join(", "))
+            ));
+        }
+    }
+
+    for label in &desc.unavailable_details {
+        md.push_str(&format!("\n### {label}\n\nunavailable\n"));
+    }
+
+    md
+}
+
+fn auto_scaling_target_string(desc: &TableDescription, target: &AutoScalingTarget) -> String {
+    let name = target.index_name.as_ref().unwrap_or(&desc.table_name);
+    let mut s = format!(
+        "{name} {}: {} - {}",
+        target.dimension, target.min_capacity, target.max_capacity
+    );
+    if let Some(utilization) = target.target_utilization {
+        s.push_str(&format!(" (target {utilization}%)"));
+    }
+    s
+}
+
+fn parse_regions(s: &str) -> Vec<String> {
+    let mut regions: Vec<String> = Vec::new();
+    for region in s.split([',', ' ']).map(str::trim).filter(|r| !r.is_empty()) {
+        if !regions.iter().any(|r| r == region) {
+            regions.push(region.into());
+        }
+    }
+    regions
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
 fn get_json_lines(desc: &TableDescription, theme: &ColorTheme) -> Vec<Line<'static>> {
     let json_str = serde_json::to_string_pretty(&desc).unwrap();
     to_highlighted_lines(&json_str, theme)
 }
 
+fn get_yaml_lines(desc: &TableDescription, theme: &ColorTheme) -> Vec<Line<'static>> {
+    let value = serde_json::to_value(desc).unwrap();
+    to_yaml_lines(&value, theme)
+}
+
+fn get_items_lines(
+    desc: &TableDescription,
+    items: &[Item],
+    theme: &ColorTheme,
+) -> Vec<Line<'static>> {
+    if items.is_empty() {
+        return vec![Line::raw("No items").fg(theme.disabled)];
+    }
+    let json_str = items
+        .iter()
+        .map(|item| {
+            let json_item = PlainJsonItem::new(item, &desc.key_schema_type);
+            serde_json::to_string(&json_item).unwrap()
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    to_highlighted_lines(&json_str, theme)
+}
+
+fn cost_estimate_string(estimate: &CostEstimate) -> String {
+    let storage = format!("~${:.2}/month storage", estimate.monthly_storage_cost);
+    match estimate.monthly_capacity_cost {
+        Some(capacity) => format!("{storage} + ~${capacity:.2}/month provisioned capacity"),
+        None => format!("{storage} + on-demand requests"),
+    }
+}
+
+const SPARKLINE_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+fn sparkline(values: &[u64]) -> String {
+    let min = values.iter().min().copied().unwrap_or_default();
+    let max = values.iter().max().copied().unwrap_or_default();
+    let top = (SPARKLINE_CHARS.len() - 1) as u128;
+    values
+        .iter()
+        .map(|v| {
+            let i = if max == min {
+                0
+            } else {
+                (v - min) as u128 * top / (max - min) as u128
+            };
+            SPARKLINE_CHARS[i as usize]
+        })
+        .collect()
+}
+
 fn format_size(size_byte: u64) -> String {
     format!(
         "{} ({} bytes)",
@@ -783,3 +1560,258 @@ fn format_size(size_byte: u64) -> String {
         size_byte
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeZone, Utc};
+
+    use crate::{
+        data::{
+            AttributeDefinition, AutoScalingTarget, ContributorInsights,
+            GlobalSecondaryIndexDescription, KeySchemaType, KeyType, KinesisStreamingDestination,
+            ProjectionType, ScalarAttributeType,
+        },
+        event::test_channel,
+        view::snapshot::{render_lines, table_description},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_markdown_string() {
+        let key = |name: &str, key_type| KeySchemaElement {
+            attribute_name: name.into(),
+            key_type,
+        };
+        let desc = TableDescription {
+            attribute_definitions: vec![
+                AttributeDefinition::new("pk".into(), ScalarAttributeType::S),
+                AttributeDefinition::new("sk".into(), ScalarAttributeType::N),
+            ],
+            key_schema: vec![key("pk", KeyType::Hash), key("sk", KeyType::Range)],
+            creation_date_time: Utc
+                .with_ymd_and_hms(2024, 1, 2, 3, 4, 5)
+                .unwrap()
+                .with_timezone(&Local),
+            total_size_bytes: 1234,
+            item_count: 10,
+            table_arn: "arn:aws:dynamodb:us-east-1:000000000000:table/foo".into(),
+            global_secondary_indexes: Some(vec![GlobalSecondaryIndexDescription {
+                index_name: "gsi|1".into(),
+                key_schema: vec![key("sk", KeyType::Hash)],
+                projection: Projection {
+                    projection_type: ProjectionType::KeysOnly,
+                    non_key_attributes: None,
+                },
+                index_status: None,
+                provisioned_throughput: None,
+                index_size_bytes: 0,
+                item_count: 0,
+                index_arn: "".into(),
+            }]),
+            key_schema_type: KeySchemaType::HashRange("pk".into(), "sk".into()),
+            kinesis_streaming_destinations: vec![KinesisStreamingDestination {
+                stream_arn: "arn:aws:kinesis:us-east-1:000000000000:stream/bar".into(),
+                destination_status: "ACTIVE".into(),
+                destination_status_description: None,
+            }],
+            contributor_insights: vec![
+                ContributorInsights {
+                    index_name: None,
+                    status: "ENABLED".into(),
+                    rules: vec!["rule-1".into(), "rule-2".into()],
+                },
+                ContributorInsights {
+                    index_name: Some("gsi|1".into()),
+                    status: "DISABLED".into(),
+                    rules: vec![],
+                },
+            ],
+            auto_scaling_targets: vec![AutoScalingTarget {
+                index_name: None,
+                dimension: "Read".into(),
+                min_capacity: 5,
+                max_capacity: 100,
+                target_utilization: Some(70.0),
+            }],
+            ..table_description("foo", "pk")
+        };
+
+        let actual = get_markdown_string(&desc, DisplayTimeZone::Utc);
+        let expected = "## foo
+
+| Property | Value |
+| --- | --- |
+| Key Schema | pk (HASH) / sk (RANGE) |
+| Table Status | ACTIVE |
+| Creation Date | 2024-01-02 03:04:05 +00:00 |
+| Provisioned Throughput | - |
+| Item Count | 10 |
+| Total Size | 1.23 kB (1234 bytes) |
+| Table ARN | arn:aws:dynamodb:us-east-1:000000000000:table/foo |
+
+### Attribute Definitions
+
+| Attribute | Type |
+| --- | --- |
+| pk | S |
+| sk | N |
+
+### Indexes
+
+| Index | Type | Key Schema | Projection |
+| --- | --- | --- | --- |
+| gsi\\|1 | GSI | sk (HASH) | KEYS_ONLY |
+
+### Kinesis Streaming Destinations
+
+| Stream ARN | Status |
+| --- | --- |
+| arn:aws:kinesis:us-east-1:000000000000:stream/bar | ACTIVE |
+
+### Auto Scaling
+
+| Target | Capacity | Min | Max | Target Utilization |
+| --- | --- | --- | --- | --- |
+| foo | Read | 5 | 100 | 70% |
+
+### Contributor Insights
+
+| Target | Status | Rules |
+| --- | --- | --- |
+| foo | ENABLED | rule-1, rule-2 |
+| gsi\\|1 | DISABLED |  |
+";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_regions() {
+        assert_eq!(
+            parse_regions("us-east-1, eu-west-1 ap-northeast-1,,us-east-1"),
+            vec!["us-east-1", "eu-west-1", "ap-northeast-1"]
+        );
+        assert!(parse_regions(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_list_width() {
+        let config = |list_width, list_width_percent, min_detail_width| UiTableListConfig {
+            list_width,
+            list_width_percent,
+            min_detail_width,
+            ..Default::default()
+        };
+        assert_eq!(list_width(&config(30, 0, 0), 200), 30);
+        assert_eq!(list_width(&config(30, 25, 0), 200), 50);
+        assert_eq!(list_width(&config(30, 150, 0), 200), 200);
+        assert_eq!(list_width(&config(30, 0, 60), 80), 20);
+        assert_eq!(list_width(&config(30, 50, 60), 100), 40);
+        assert_eq!(list_width(&config(30, 0, 100), 80), 0);
+    }
+
+    #[test]
+    fn test_index_detail_strings() {
+        let key_schema = vec![
+            KeySchemaElement {
+                attribute_name: "gpk".into(),
+                key_type: KeyType::Hash,
+            },
+            KeySchemaElement {
+                attribute_name: "gsk".into(),
+                key_type: KeyType::Range,
+            },
+        ];
+        let projection = Projection {
+            projection_type: ProjectionType::Include,
+            non_key_attributes: Some(vec!["name".into(), "email".into()]),
+        };
+
+        let actual = index_detail_strings("gsi1", &key_schema, &projection, 1234, 10);
+        let expected = vec![
+            "gsi1 (gpk / gsk)",
+            "Projection: INCLUDE (name, email)",
+            "Item Count: 10 / Size: 1.23 kB (1234 bytes)",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_get_key_value_lines_unavailable_details() {
+        let desc = TableDescription {
+            unavailable_details: vec!["Auto Scaling"],
+            ..table_description("foo", "pk")
+        };
+
+        let lines = get_key_value_lines(&desc, &[], DisplayTimeZone::Utc);
+
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        assert!(lines.contains(&"          Auto Scaling : unavailable".to_string()));
+        assert!(!lines
+            .iter()
+            .any(|line| line.contains("Contributor Insights")));
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[5, 5, 5]), "▁▁▁");
+        assert_eq!(sparkline(&[0, 1, 2, 3, 4, 5, 6, 7]), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[100, 200, 150]), "▁█▄");
+    }
+
+    #[test]
+    fn test_cost_estimate_string() {
+        let estimate = CostEstimate {
+            monthly_storage_cost: 1.234,
+            monthly_capacity_cost: Some(3.3215),
+        };
+        assert_eq!(
+            cost_estimate_string(&estimate),
+            "~$1.23/month storage + ~$3.32/month provisioned capacity"
+        );
+
+        let estimate = CostEstimate {
+            monthly_storage_cost: 0.0,
+            monthly_capacity_cost: None,
+        };
+        assert_eq!(
+            cost_estimate_string(&estimate),
+            "~$0.00/month storage + on-demand requests"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let (tx, _rx) = test_channel();
+        let mapper = UserEventMapper::new();
+        let tables = vec![
+            Table {
+                name: "orders".into(),
+            },
+            Table {
+                name: "users".into(),
+            },
+        ];
+        let mut view = TableListView::new(
+            tables,
+            &mapper,
+            UiTableListConfig::default(),
+            DisplayTimeZone::default(),
+            ColorTheme::default(),
+            tx,
+        );
+
+        // the description of the selected table is requested on creation and not yet loaded
+        let actual = render_lines(50, 6, |f, area| view.render(f, area));
+        let expected = vec![
+            "┌────────────────────────────┐┌ Loading... ──────┐",
+            "│  orders                    ││                  │",
+            "│  users                     ││                  │",
+            "│                            ││                  │",
+            "│                            ││                  │",
+            "└────────────────────────────┘└──────────────────┘",
+        ];
+        assert_eq!(actual, expected);
+    }
+}
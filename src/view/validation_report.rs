@@ -0,0 +1,207 @@
+use ratatui::{
+    crossterm::event::KeyEvent, layout::Rect, style::Stylize, text::Line, widgets::Block, Frame,
+};
+
+use crate::{
+    color::ColorTheme,
+    event::{AppEvent, Sender, UserEvent, UserEventMapper},
+    handle_user_events,
+    help::{
+        build_help_spans, build_short_help_spans, BuildHelpsItem, BuildShortHelpsItem, Spans,
+        SpansWithPriority,
+    },
+    validation::ValidationReport,
+    view::common::raw_string_from_scroll_lines_state,
+    widget::{ScrollLines, ScrollLinesOptions, ScrollLinesState},
+};
+
+pub struct ValidationReportView {
+    report: ValidationReport,
+
+    helps: ValidationReportViewHelps,
+    theme: ColorTheme,
+    tx: Sender,
+
+    scroll_lines_state: ScrollLinesState,
+}
+
+struct ValidationReportViewHelps {
+    report: Vec<Spans>,
+    report_short: Vec<SpansWithPriority>,
+}
+
+impl ValidationReportView {
+    pub fn new(
+        report: ValidationReport,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let lines = get_report_lines(&report, &theme);
+        let scroll_lines_state =
+            ScrollLinesState::new(lines, ScrollLinesOptions::new(false, false));
+        let helps = ValidationReportViewHelps::new(mapper, theme);
+
+        ValidationReportView {
+            report,
+
+            helps,
+            theme,
+            tx,
+
+            scroll_lines_state,
+        }
+    }
+}
+
+impl ValidationReportView {
+    pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, _key_event: KeyEvent) {
+        handle_user_events! { user_events =>
+            UserEvent::Close => {
+                self.tx.send(AppEvent::BackToBeforeView);
+            }
+            UserEvent::Down => {
+                self.scroll_lines_state.scroll_forward();
+            }
+            UserEvent::Up => {
+                self.scroll_lines_state.scroll_backward();
+            }
+            UserEvent::PageDown => {
+                self.scroll_lines_state.scroll_page_forward();
+            }
+            UserEvent::PageUp => {
+                self.scroll_lines_state.scroll_page_backward();
+            }
+            UserEvent::GoToTop => {
+                self.scroll_lines_state.scroll_to_top();
+            }
+            UserEvent::GoToBottom => {
+                self.scroll_lines_state.scroll_to_end();
+            }
+            UserEvent::Right => {
+                self.scroll_lines_state.scroll_right();
+            }
+            UserEvent::Left => {
+                self.scroll_lines_state.scroll_left();
+            }
+            UserEvent::ToggleWrap => {
+                self.scroll_lines_state.toggle_wrap();
+            }
+            UserEvent::ToggleNumber => {
+                self.scroll_lines_state.toggle_number();
+            }
+            UserEvent::SaveToFile => {
+                self.save_to_file();
+            }
+            UserEvent::Help => {
+                self.open_help();
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let title = format!(" {} (Validation) ", self.report.table_name);
+        let scroll = ScrollLines::default()
+            .block(
+                Block::bordered()
+                    .title_top(Line::from(title).left_aligned())
+                    .fg(self.theme.fg)
+                    .bg(self.theme.bg),
+            )
+            .theme(&self.theme);
+
+        f.render_stateful_widget(scroll, area, &mut self.scroll_lines_state);
+    }
+
+    pub fn short_helps(&self) -> &[SpansWithPriority] {
+        &self.helps.report_short
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.report.table_name
+    }
+}
+
+impl ValidationReportViewHelps {
+    fn new(mapper: &UserEventMapper, theme: ColorTheme) -> Self {
+        let report = build_helps(mapper, theme);
+        let report_short = build_short_helps(mapper);
+        Self {
+            report,
+            report_short,
+        }
+    }
+}
+
+fn build_helps(mapper: &UserEventMapper, theme: ColorTheme) -> Vec<Spans> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildHelpsItem::new(UserEvent::Quit, "Quit app"),
+        BuildHelpsItem::new(UserEvent::Close, "Back to table"),
+        BuildHelpsItem::new(UserEvent::Down, "Scroll down"),
+        BuildHelpsItem::new(UserEvent::Up, "Scroll up"),
+        BuildHelpsItem::new(UserEvent::Right, "Scroll right"),
+        BuildHelpsItem::new(UserEvent::Left, "Scroll left"),
+        BuildHelpsItem::new(UserEvent::PageDown, "Scroll page down"),
+        BuildHelpsItem::new(UserEvent::PageUp, "Scroll page up"),
+        BuildHelpsItem::new(UserEvent::GoToTop, "Scroll to top"),
+        BuildHelpsItem::new(UserEvent::GoToBottom, "Scroll to bottom"),
+        BuildHelpsItem::new(UserEvent::ToggleWrap, "Toggle wrap"),
+        BuildHelpsItem::new(UserEvent::ToggleNumber, "Toggle number"),
+        BuildHelpsItem::new(UserEvent::SaveToFile, "Save validation report to file"),
+    ];
+    build_help_spans(helps, mapper, theme)
+}
+
+fn build_short_helps(mapper: &UserEventMapper) -> Vec<SpansWithPriority> {
+    #[rustfmt::skip]
+    let helps = vec![
+        BuildShortHelpsItem::single(UserEvent::Quit, "Quit", 0),
+        BuildShortHelpsItem::single(UserEvent::Close, "Back", 1),
+        BuildShortHelpsItem::group(vec![UserEvent::Down, UserEvent::Up], "Scroll", 2),
+        BuildShortHelpsItem::group(vec![UserEvent::GoToTop, UserEvent::GoToBottom], "Top/Bottom", 3),
+        BuildShortHelpsItem::group(vec![UserEvent::ToggleWrap, UserEvent::ToggleNumber], "Toggle wrap/number", 4),
+        BuildShortHelpsItem::single(UserEvent::Help, "Help", 0),
+    ];
+    build_short_help_spans(helps, mapper)
+}
+
+impl ValidationReportView {
+    fn save_to_file(&self) {
+        let content = raw_string_from_scroll_lines_state(&self.scroll_lines_state);
+        self.tx
+            .send(AppEvent::SaveToFile("validation report".into(), content));
+    }
+
+    fn open_help(&self) {
+        self.tx.send(AppEvent::OpenHelp(self.helps.report.clone()))
+    }
+}
+
+fn get_report_lines(report: &ValidationReport, theme: &ColorTheme) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+
+    lines.push(Line::from(vec![
+        "Schema: ".bold(),
+        report.schema_path.clone().into(),
+    ]));
+    lines.push(Line::from(vec![
+        "Invalid items: ".bold(),
+        format!("{} / {}", report.invalid_items.len(), report.total_items).into(),
+    ]));
+
+    for invalid_item in &report.invalid_items {
+        lines.push(Line::raw(""));
+        lines.push(Line::from(invalid_item.key.to_string().bold()));
+        for violation in &invalid_item.violations {
+            lines.push(Line::from(vec![
+                "  ".into(),
+                violation.path.clone().fg(theme.insight_attribute_name_fg),
+                " : ".into(),
+                violation.message.clone().into(),
+            ]));
+        }
+    }
+
+    lines
+}
@@ -1,23 +1,57 @@
 use ratatui::{crossterm::event::KeyEvent, layout::Rect, Frame};
 
 use crate::{
+    audit::AuditEntry,
     color::ColorTheme,
-    config::{UiTableConfig, UiTableListConfig},
-    data::{Item, Table, TableDescription, TableInsight},
+    config::{UiInsightConfig, UiTableConfig, UiTableListConfig},
+    data::{
+        AccountLimits, ConnectionDiagnostics, Item, ItemsComparison, ItemsSource, LoadedItems,
+        RegionTable, Table, TableDescription, TableInsight, TablePermissions,
+    },
     event::{Sender, UserEvent, UserEventMapper},
     help::{Spans, SpansWithPriority},
+    profile::Profile,
+    state::TableViewPreferences,
+    util::DisplayTimeZone,
+    validation::ValidationReport,
     view::{
-        help::HelpView, init::InitView, item::ItemView, table::TableView,
-        table_insight::TableInsightView, table_list::TableListView,
+        account_limits::AccountLimitsView,
+        audit_log::AuditLogView,
+        connection_diagnostics::ConnectionDiagnosticsView,
+        filter_builder::FilterBuilderView,
+        help::HelpView,
+        init::InitView,
+        item::ItemView,
+        items_comparison::ItemsComparisonView,
+        multi_region_table_list::MultiRegionTableListView,
+        partiql::PartiQLView,
+        query_builder::QueryBuilderView,
+        side_by_side::{SideBySidePane, SideBySideView},
+        table::TableView,
+        table_diff::TableDiffView,
+        table_insight::TableInsightView,
+        table_list::TableListView,
+        validation_report::ValidationReportView,
     },
 };
 
 pub enum View {
     Init(Box<InitView>),
+    ConnectionDiagnostics(Box<ConnectionDiagnosticsView>),
     TableList(Box<TableListView>),
+    MultiRegionTableList(Box<MultiRegionTableListView>),
     Table(Box<TableView>),
     Item(Box<ItemView>),
     TableInsight(Box<TableInsightView>),
+    TableDiff(Box<TableDiffView>),
+    AccountLimits(Box<AccountLimitsView>),
+    ItemsComparison(Box<ItemsComparisonView>),
+    SideBySide(Box<SideBySideView>),
+    ValidationReport(Box<ValidationReportView>),
+    PartiQL(Box<PartiQLView>),
+    FilterBuilder(Box<FilterBuilderView>),
+    QueryBuilder(Box<QueryBuilderView>),
+    AuditLog(Box<AuditLogView>),
     Help(Box<HelpView>),
 }
 
@@ -25,21 +59,54 @@ impl View {
     pub fn handle_user_key_event(&mut self, user_events: Vec<UserEvent>, key_event: KeyEvent) {
         match self {
             View::Init(view) => view.handle_user_key_event(user_events, key_event),
+            View::ConnectionDiagnostics(view) => view.handle_user_key_event(user_events, key_event),
             View::TableList(view) => view.handle_user_key_event(user_events, key_event),
+            View::MultiRegionTableList(view) => view.handle_user_key_event(user_events, key_event),
             View::Table(view) => view.handle_user_key_event(user_events, key_event),
             View::Item(view) => view.handle_user_key_event(user_events, key_event),
             View::TableInsight(view) => view.handle_user_key_event(user_events, key_event),
+            View::TableDiff(view) => view.handle_user_key_event(user_events, key_event),
+            View::AccountLimits(view) => view.handle_user_key_event(user_events, key_event),
+            View::ItemsComparison(view) => view.handle_user_key_event(user_events, key_event),
+            View::SideBySide(view) => view.handle_user_key_event(user_events, key_event),
+            View::ValidationReport(view) => view.handle_user_key_event(user_events, key_event),
+            View::PartiQL(view) => view.handle_user_key_event(user_events, key_event),
+            View::FilterBuilder(view) => view.handle_user_key_event(user_events, key_event),
+            View::QueryBuilder(view) => view.handle_user_key_event(user_events, key_event),
+            View::AuditLog(view) => view.handle_user_key_event(user_events, key_event),
             View::Help(view) => view.handle_user_key_event(user_events, key_event),
         }
     }
 
+    pub fn handle_paste(&mut self, text: &str) {
+        match self {
+            View::TableList(view) => view.handle_paste(text),
+            View::Table(view) => view.handle_paste(text),
+            View::PartiQL(view) => view.handle_paste(text),
+            View::FilterBuilder(view) => view.handle_paste(text),
+            View::QueryBuilder(view) => view.handle_paste(text),
+            _ => {}
+        }
+    }
+
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
         match self {
             View::Init(view) => view.render(f, area),
+            View::ConnectionDiagnostics(view) => view.render(f, area),
             View::TableList(view) => view.render(f, area),
+            View::MultiRegionTableList(view) => view.render(f, area),
             View::Table(view) => view.render(f, area),
             View::Item(view) => view.render(f, area),
             View::TableInsight(view) => view.render(f, area),
+            View::TableDiff(view) => view.render(f, area),
+            View::AccountLimits(view) => view.render(f, area),
+            View::ItemsComparison(view) => view.render(f, area),
+            View::SideBySide(view) => view.render(f, area),
+            View::ValidationReport(view) => view.render(f, area),
+            View::PartiQL(view) => view.render(f, area),
+            View::FilterBuilder(view) => view.render(f, area),
+            View::QueryBuilder(view) => view.render(f, area),
+            View::AuditLog(view) => view.render(f, area),
             View::Help(view) => view.render(f, area),
         }
     }
@@ -47,42 +114,154 @@ impl View {
     pub fn short_helps(&self) -> &[SpansWithPriority] {
         match self {
             View::Init(view) => view.short_helps(),
+            View::ConnectionDiagnostics(view) => view.short_helps(),
             View::TableList(view) => view.short_helps(),
+            View::MultiRegionTableList(view) => view.short_helps(),
             View::Table(view) => view.short_helps(),
             View::Item(view) => view.short_helps(),
             View::TableInsight(view) => view.short_helps(),
+            View::TableDiff(view) => view.short_helps(),
+            View::AccountLimits(view) => view.short_helps(),
+            View::ItemsComparison(view) => view.short_helps(),
+            View::SideBySide(view) => view.short_helps(),
+            View::ValidationReport(view) => view.short_helps(),
+            View::PartiQL(view) => view.short_helps(),
+            View::FilterBuilder(view) => view.short_helps(),
+            View::QueryBuilder(view) => view.short_helps(),
+            View::AuditLog(view) => view.short_helps(),
             View::Help(view) => view.short_helps(),
         }
     }
 }
 
+impl View {
+    pub fn table_name(&self) -> Option<&str> {
+        match self {
+            View::TableList(view) => view.current_selected_table_name(),
+            View::MultiRegionTableList(view) => view.current_selected_table_name(),
+            View::Table(view) => Some(view.table_name()),
+            View::Item(view) => Some(view.table_name()),
+            View::TableInsight(view) => Some(view.table_name()),
+            View::TableDiff(view) => Some(view.table_name()),
+            View::ItemsComparison(view) => Some(view.table_name()),
+            View::SideBySide(view) => Some(view.table_name()),
+            View::ValidationReport(view) => Some(view.table_name()),
+            View::PartiQL(view) => Some(view.table_name()),
+            View::FilterBuilder(view) => Some(view.table_name()),
+            View::QueryBuilder(view) => Some(view.table_name()),
+            View::Init(_)
+            | View::ConnectionDiagnostics(_)
+            | View::AccountLimits(_)
+            | View::AuditLog(_)
+            | View::Help(_) => None,
+        }
+    }
+
+    pub fn is_full_screen(&self) -> bool {
+        match self {
+            View::TableList(view) => view.is_full_screen(),
+            View::Table(view) => view.is_full_screen(),
+            _ => false,
+        }
+    }
+
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            View::Table(view) => Some(view.position()),
+            _ => None,
+        }
+    }
+
+    pub fn set_preferences(&mut self, preferences: TableViewPreferences) {
+        if let View::Table(view) = self {
+            view.set_preferences(preferences);
+        }
+    }
+
+    pub fn set_permissions(&mut self, permissions: TablePermissions) {
+        match self {
+            View::Table(view) => view.set_permissions(permissions),
+            View::Item(view) => view.set_permissions(permissions),
+            _ => {}
+        }
+    }
+
+    pub fn selected_item_json(&self) -> Option<String> {
+        match self {
+            View::Table(view) => view.selected_item_json(),
+            View::Item(view) => Some(view.item_json()),
+            _ => None,
+        }
+    }
+}
+
 impl View {
     pub fn of_init(theme: ColorTheme, tx: Sender) -> Self {
         View::Init(Box::new(InitView::new(theme, tx)))
     }
 
+    pub fn of_profile_picker(
+        profiles: Vec<Profile>,
+        region: Option<String>,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::Init(Box::new(InitView::with_profiles(
+            profiles, region, mapper, theme, tx,
+        )))
+    }
+
+    pub fn of_connection_diagnostics(
+        diagnostics: ConnectionDiagnostics,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::ConnectionDiagnostics(Box::new(ConnectionDiagnosticsView::new(
+            diagnostics,
+            mapper,
+            theme,
+            tx,
+        )))
+    }
+
     pub fn of_table_list(
         tables: Vec<Table>,
         mapper: &UserEventMapper,
         config: UiTableListConfig,
+        timezone: DisplayTimeZone,
         theme: ColorTheme,
         tx: Sender,
     ) -> Self {
         View::TableList(Box::new(TableListView::new(
-            tables, mapper, config, theme, tx,
+            tables, mapper, config, timezone, theme, tx,
+        )))
+    }
+
+    pub fn of_multi_region_table_list(
+        tables: Vec<RegionTable>,
+        regions: Vec<String>,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::MultiRegionTableList(Box::new(MultiRegionTableListView::new(
+            tables, regions, mapper, theme, tx,
         )))
     }
 
     pub fn of_table(
         desc: TableDescription,
-        items: Vec<Item>,
+        loaded: LoadedItems,
+        source: ItemsSource,
         mapper: &UserEventMapper,
         config: UiTableConfig,
         theme: ColorTheme,
         tx: Sender,
     ) -> Self {
         View::Table(Box::new(TableView::new(
-            desc, items, mapper, config, theme, tx,
+            desc, loaded, source, mapper, config, theme, tx,
         )))
     }
 
@@ -99,10 +278,143 @@ impl View {
     pub fn of_table_insight(
         insight: TableInsight,
         mapper: &UserEventMapper,
+        config: UiInsightConfig,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::TableInsight(Box::new(TableInsightView::new(
+            insight, mapper, config, theme, tx,
+        )))
+    }
+
+    pub fn of_table_diff(
+        left: TableDescription,
+        right: TableDescription,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::TableDiff(Box::new(TableDiffView::new(left, right, mapper, theme, tx)))
+    }
+
+    pub fn of_account_limits(
+        account_limits: AccountLimits,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::AccountLimits(Box::new(AccountLimitsView::new(
+            account_limits,
+            mapper,
+            theme,
+            tx,
+        )))
+    }
+
+    pub fn of_items_comparison(
+        table_name: String,
+        statement: String,
+        comparison: ItemsComparison,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::ItemsComparison(Box::new(ItemsComparisonView::new(
+            table_name, statement, comparison, mapper, theme, tx,
+        )))
+    }
+
+    pub fn of_side_by_side(
+        left: SideBySidePane,
+        right: SideBySidePane,
+        mapper: &UserEventMapper,
+        config: UiTableConfig,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::SideBySide(Box::new(SideBySideView::new(
+            left, right, mapper, config, theme, tx,
+        )))
+    }
+
+    pub fn of_validation_report(
+        report: ValidationReport,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::ValidationReport(Box::new(ValidationReportView::new(
+            report, mapper, theme, tx,
+        )))
+    }
+
+    pub fn of_partiql(
+        desc: TableDescription,
+        table_names: Vec<String>,
+        attribute_names: Vec<String>,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::PartiQL(Box::new(PartiQLView::new(
+            desc,
+            table_names,
+            attribute_names,
+            mapper,
+            theme,
+            tx,
+        )))
+    }
+
+    pub fn of_filter_builder(
+        desc: TableDescription,
+        attribute_names: Vec<String>,
+        mapper: &UserEventMapper,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        View::FilterBuilder(Box::new(FilterBuilderView::new(
+            desc,
+            attribute_names,
+            mapper,
+            theme,
+            tx,
+        )))
+    }
+
+    pub fn of_query_builder(
+        desc: TableDescription,
+        mapper: &UserEventMapper,
         theme: ColorTheme,
         tx: Sender,
     ) -> Self {
-        View::TableInsight(Box::new(TableInsightView::new(insight, mapper, theme, tx)))
+        View::QueryBuilder(Box::new(QueryBuilderView::new(desc, mapper, theme, tx)))
+    }
+
+    pub fn of_audit_log(
+        entries: Vec<AuditEntry>,
+        mapper: &UserEventMapper,
+        timezone: DisplayTimeZone,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let title = "Audit log";
+        View::AuditLog(Box::new(AuditLogView::new(
+            title, entries, mapper, timezone, theme, tx,
+        )))
+    }
+
+    pub fn of_dry_run(
+        entries: Vec<AuditEntry>,
+        mapper: &UserEventMapper,
+        timezone: DisplayTimeZone,
+        theme: ColorTheme,
+        tx: Sender,
+    ) -> Self {
+        let title = "Dry run";
+        View::AuditLog(Box::new(AuditLogView::new(
+            title, entries, mapper, timezone, theme, tx,
+        )))
     }
 
     pub fn of_help(
@@ -129,9 +441,12 @@ impl ViewStack {
         ViewStack { stack: vec![view] }
     }
 
-    pub fn pop(&mut self) {
+    pub fn pop(&mut self) -> bool {
         if self.stack.len() > 1 {
             self.stack.pop();
+            true
+        } else {
+            false
         }
     }
 
@@ -146,4 +461,14 @@ impl ViewStack {
     pub fn current_view_mut(&mut self) -> &mut View {
         self.stack.last_mut().unwrap()
     }
+
+    pub fn views_mut(&mut self) -> impl Iterator<Item = &mut View> {
+        self.stack.iter_mut()
+    }
+
+    // The view to go back to from the current view
+    pub fn parent_view_mut(&mut self) -> Option<&mut View> {
+        let len = self.stack.len();
+        len.checked_sub(2).map(|i| &mut self.stack[i])
+    }
 }
@@ -1,14 +1,22 @@
+mod column_stats_dialog;
 mod divider;
+mod inspector_panel;
 mod loading_dialog;
+mod progress_dialog;
 mod scroll;
 mod scroll_lines;
 mod scroll_list;
+mod select_dialog;
 mod table;
 
 use scroll::*;
 
+pub use column_stats_dialog::*;
 pub use divider::*;
+pub use inspector_panel::*;
 pub use loading_dialog::*;
+pub use progress_dialog::*;
 pub use scroll_lines::*;
 pub use scroll_list::*;
+pub use select_dialog::*;
 pub use table::*;
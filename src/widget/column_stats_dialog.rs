@@ -0,0 +1,146 @@
+use laurier::{dialog::Dialog, layout::calc_centered_area};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Padding, Paragraph, Widget},
+};
+
+use crate::{color::ColorTheme, data::ColumnStats};
+
+#[derive(Debug, Default)]
+struct ColumnStatsDialogColor {
+    bg: Color,
+    fg: Color,
+    dim: Color,
+}
+
+impl ColumnStatsDialogColor {
+    fn new(theme: ColorTheme) -> Self {
+        ColumnStatsDialogColor {
+            bg: theme.bg,
+            fg: theme.fg,
+            dim: theme.disabled,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ColumnStatsDialog<'a> {
+    stats: &'a ColumnStats,
+    color: ColumnStatsDialogColor,
+}
+
+impl<'a> ColumnStatsDialog<'a> {
+    pub fn new(stats: &'a ColumnStats) -> Self {
+        ColumnStatsDialog {
+            stats,
+            color: ColumnStatsDialogColor::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: ColorTheme) -> Self {
+        self.color = ColumnStatsDialogColor::new(theme);
+        self
+    }
+}
+
+impl Widget for ColumnStatsDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = stats_rows(self.stats);
+        let label_width = rows.iter().map(|(l, _)| l.len()).max().unwrap_or_default();
+        let max_width = (area.width as usize).saturating_sub(Self::MARGIN_WIDTH + 4);
+        let value_width = max_width.saturating_sub(label_width + 3);
+
+        let mut lines: Vec<Line> = rows
+            .into_iter()
+            .map(|(label, value)| {
+                let value = console::truncate_str(&value, value_width, "...").to_string();
+                Line::from(vec![
+                    format!("{label:>label_width$} : ").fg(self.color.fg).bold(),
+                    value.fg(self.color.fg),
+                ])
+            })
+            .collect();
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Self::CLOSE_MSG.fg(self.color.dim)));
+
+        let content_width = lines.iter().map(Line::width).max().unwrap_or_default();
+        let title = format!(" {} ", self.stats.attribute_name);
+        let width = content_width.max(console::measure_text_width(&title)) + Self::MARGIN_WIDTH;
+        let height = lines.len() + 4; // border + padding
+        let area = calc_centered_area(area, width as u16, height as u16);
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title_top(Line::from(title).left_aligned())
+                .padding(Padding::uniform(1))
+                .fg(self.color.fg),
+        );
+
+        let dialog = Dialog::new(paragraph)
+            .margin(Margin::new(1, 0))
+            .bg(self.color.bg);
+        dialog.render(area, buf);
+    }
+}
+
+impl ColumnStatsDialog<'_> {
+    const CLOSE_MSG: &'static str = "Press any key to close";
+    const MARGIN_WIDTH: usize = 6; // border + padding + margin
+}
+
+fn stats_rows(stats: &ColumnStats) -> Vec<(&'static str, String)> {
+    let mut rows = vec![
+        ("Items", stats.total_items.to_string()),
+        ("Distinct", stats.distinct_count.to_string()),
+        ("Missing", stats.missing_count.to_string()),
+        ("Null", stats.null_count.to_string()),
+    ];
+    if let Some((min, max)) = &stats.number_range {
+        rows.push(("Min", min.to_string()));
+        rows.push(("Max", max.to_string()));
+    }
+    if stats.samples.is_empty() {
+        rows.push(("Samples", "-".into()));
+    }
+    for (i, sample) in stats.samples.iter().enumerate() {
+        let label = if i == 0 { "Samples" } else { "" };
+        rows.push((label, sample.clone()));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    #[test]
+    fn test_stats_rows() {
+        let stats = ColumnStats {
+            attribute_name: "v".into(),
+            total_items: 7,
+            missing_count: 1,
+            null_count: 1,
+            distinct_count: 4,
+            number_range: Some((Decimal::from(-1), Decimal::from(10))),
+            samples: vec!["3".into(), "a".into()],
+        };
+        let actual = stats_rows(&stats);
+        let expected = vec![
+            ("Items", "7".to_string()),
+            ("Distinct", "4".into()),
+            ("Missing", "1".into()),
+            ("Null", "1".into()),
+            ("Min", "-1".into()),
+            ("Max", "10".into()),
+            ("Samples", "3".into()),
+            ("", "a".into()),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
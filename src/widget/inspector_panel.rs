@@ -0,0 +1,181 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use crate::{color::ColorTheme, inspector::OperationRecord};
+
+#[derive(Debug, Default)]
+struct InspectorPanelColor {
+    bg: Color,
+    fg: Color,
+    block: Color,
+    dim: Color,
+    warning: Color,
+    error: Color,
+}
+
+impl InspectorPanelColor {
+    fn new(theme: &ColorTheme) -> Self {
+        InspectorPanelColor {
+            bg: theme.bg,
+            fg: theme.fg,
+            block: theme.fg,
+            dim: theme.disabled,
+            warning: theme.notification_warning,
+            error: theme.notification_error,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InspectorPanel<'a> {
+    records: &'a [OperationRecord],
+    color: InspectorPanelColor,
+}
+
+impl<'a> InspectorPanel<'a> {
+    pub fn new(records: &'a [OperationRecord]) -> Self {
+        Self {
+            records,
+            color: InspectorPanelColor::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: &ColorTheme) -> Self {
+        self.color = InspectorPanelColor::new(theme);
+        self
+    }
+}
+
+impl Widget for InspectorPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let operation_width = self
+            .records
+            .iter()
+            .map(|r| r.operation.len())
+            .max()
+            .unwrap_or_default();
+
+        let lines: Vec<Line> = self
+            .records
+            .iter()
+            .take(area.height.saturating_sub(2) as usize)
+            .map(|r| self.record_line(r, operation_width))
+            .collect();
+
+        let title = format!(" Requests ({}) ", self.records.len());
+        let paragraph = Paragraph::new(lines).block(
+            Block::bordered()
+                .title_top(Line::from(title).left_aligned())
+                .fg(self.color.block)
+                .bg(self.color.bg),
+        );
+        paragraph.render(area, buf);
+    }
+}
+
+impl InspectorPanel<'_> {
+    fn record_line(&self, record: &OperationRecord, operation_width: usize) -> Line<'static> {
+        let status = match record.status {
+            Some(status) => status.to_string(),
+            None => "---".into(),
+        };
+        let status = if record.is_failed() {
+            status.fg(self.color.error)
+        } else {
+            status.fg(self.color.fg)
+        };
+        let retries = format!("retry {}", record.retries);
+        let retries = if record.retries > 0 {
+            retries.fg(self.color.warning)
+        } else {
+            retries.fg(self.color.dim)
+        };
+        let request_id = record.request_id.as_deref().unwrap_or("-").to_string();
+
+        let mut spans: Vec<Span> = vec![
+            " ".into(),
+            record
+                .started_at
+                .format("%H:%M:%S")
+                .to_string()
+                .fg(self.color.dim),
+            " ".into(),
+            format!("{:operation_width$}", record.operation)
+                .fg(self.color.fg)
+                .bold(),
+            " ".into(),
+            status,
+            " ".into(),
+            format!("{:>6}ms", record.duration.as_millis()).fg(self.color.fg),
+            " ".into(),
+            retries,
+            " ".into(),
+            request_id.fg(self.color.dim),
+            " ".into(),
+        ];
+        match &record.error {
+            Some(error) => spans.push(error.clone().fg(self.color.error)),
+            None => spans.push(record.parameters.clone().fg(self.color.dim)),
+        }
+        Line::from(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{Local, TimeZone};
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let records = vec![
+            OperationRecord {
+                started_at: Local.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+                operation: "Scan".into(),
+                parameters: r#"{"TableName":"foo"}"#.into(),
+                duration: Duration::from_millis(42),
+                status: Some(200),
+                request_id: Some("REQ1".into()),
+                retries: 0,
+                error: None,
+            },
+            OperationRecord {
+                started_at: Local.with_ymd_and_hms(2024, 1, 2, 3, 4, 6).unwrap(),
+                operation: "GetItem".into(),
+                parameters: "{}".into(),
+                duration: Duration::from_millis(1200),
+                status: Some(400),
+                request_id: Some("REQ2".into()),
+                retries: 2,
+                error: Some("Throttled".into()),
+            },
+        ];
+        let panel = InspectorPanel::new(&records);
+        let area = Rect::new(0, 0, 80, 4);
+        let mut buf = Buffer::empty(area);
+        panel.render(area, &mut buf);
+
+        let actual: Vec<String> = (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect();
+        let expected = vec![
+            "┌ Requests (2) ────────────────────────────────────────────────────────────────┐",
+            "│ 03:04:05 Scan    200     42ms retry 0 REQ1 {\"TableName\":\"foo\"}               │",
+            "│ 03:04:06 GetItem 400   1200ms retry 2 REQ2 Throttled                         │",
+            "└──────────────────────────────────────────────────────────────────────────────┘",
+        ];
+        assert_eq!(actual, expected);
+    }
+}
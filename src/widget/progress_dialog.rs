@@ -0,0 +1,131 @@
+use laurier::{dialog::Dialog, layout::calc_centered_area};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Margin, Rect},
+    style::{Color, Modifier, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Padding, Paragraph, Widget},
+};
+
+use crate::color::ColorTheme;
+
+#[derive(Debug, Default)]
+struct ProgressDialogColor {
+    bg: Color,
+    fg: Color,
+}
+
+impl ProgressDialogColor {
+    fn new(theme: ColorTheme) -> Self {
+        ProgressDialogColor {
+            bg: theme.bg,
+            fg: theme.fg,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ProgressDialog {
+    current: usize,
+    total: usize,
+    percent_only: bool,
+    abort_key: Option<String>,
+    color: ProgressDialogColor,
+}
+
+impl ProgressDialog {
+    pub fn new(current: usize, total: usize) -> Self {
+        ProgressDialog {
+            current,
+            total,
+            ..Default::default()
+        }
+    }
+
+    pub fn percent_only(mut self, percent_only: bool) -> Self {
+        self.percent_only = percent_only;
+        self
+    }
+
+    pub fn abort_key(mut self, key: impl Into<String>) -> Self {
+        self.abort_key = Some(key.into());
+        self
+    }
+
+    pub fn theme(mut self, theme: ColorTheme) -> Self {
+        self.color = ProgressDialogColor::new(theme);
+        self
+    }
+}
+
+impl Widget for ProgressDialog {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let msg = if self.percent_only {
+            Self::MSG.to_string()
+        } else {
+            format!("{} {} / {}", Self::MSG, self.current, self.total)
+        };
+        let mut lines = vec![
+            Line::from(msg.fg(self.color.fg).add_modifier(Modifier::BOLD)),
+            Line::from(progress_bar(self.current, self.total, Self::BAR_WIDTH).fg(self.color.fg)),
+        ];
+        if let Some(key) = self.abort_key {
+            lines.push(Line::from(format!("{key}: Abort").fg(self.color.fg)));
+        }
+
+        let width = Self::BAR_WIDTH as u16 + 10; // border + padding + margin
+        let height = lines.len() as u16 + 4; // border + padding
+        let area = calc_centered_area(area, width, height);
+
+        let paragraph = Paragraph::new(lines).alignment(Alignment::Center).block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .padding(Padding::vertical(1))
+                .fg(self.color.fg),
+        );
+
+        let dialog = Dialog::new(paragraph)
+            .margin(Margin::new(1, 0))
+            .bg(self.color.bg);
+        dialog.render(area, buf);
+    }
+}
+
+impl ProgressDialog {
+    const MSG: &'static str = "Loading...";
+    const BAR_WIDTH: usize = 30;
+}
+
+// The current may exceed the total if the total is an estimate
+fn progress_bar(current: usize, total: usize, width: usize) -> String {
+    let ratio = if total == 0 {
+        1.0
+    } else {
+        (current as f64 / total as f64).min(1.0)
+    };
+    let percent = format!("{:>3}%", (ratio * 100.0).floor() as usize);
+    let bar_width = width.saturating_sub(percent.len() + 1);
+    let filled = (bar_width as f64 * ratio).floor() as usize;
+    format!(
+        "{}{} {percent}",
+        "█".repeat(filled),
+        "░".repeat(bar_width - filled)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, 10, "░░░░░░░░░░   0%")]
+    #[case(5, 10, "█████░░░░░  50%")]
+    #[case(10, 10, "██████████ 100%")]
+    #[case(15, 10, "██████████ 100%")]
+    #[case(0, 0, "██████████ 100%")]
+    fn test_progress_bar(#[case] current: usize, #[case] total: usize, #[case] expected: &str) {
+        assert_eq!(progress_bar(current, total, 15), expected);
+    }
+}
@@ -6,7 +6,7 @@ use ratatui::{
     widgets::{Block, BlockExt, Borders, Padding, Paragraph, StatefulWidget, Widget, Wrap},
 };
 
-use crate::color::ColorTheme;
+use crate::{color::ColorTheme, util::format_thousands, widget::ScrollBar};
 
 #[derive(Debug, Default)]
 enum ScrollEvent {
@@ -48,9 +48,18 @@ pub struct ScrollLinesState {
     v_offset: usize,
     h_offset: usize,
     options: ScrollLinesOptions,
+    cursor: Option<usize>,
+    selection: Option<Selection>,
     scroll_event: ScrollEvent,
 }
 
+#[derive(Debug)]
+struct Selection {
+    anchor: usize,
+    // whether the cursor was enabled to start the selection and should be disabled with it
+    cursor_enabled: bool,
+}
+
 impl ScrollLinesState {
     pub fn new(lines: Vec<Line<'static>>, options: ScrollLinesOptions) -> Self {
         let max_digits = digits(lines.len());
@@ -106,6 +115,46 @@ impl ScrollLinesState {
         self.options.number = !self.options.number;
     }
 
+    pub fn toggle_cursor(&mut self) {
+        self.cursor = match self.cursor {
+            Some(_) => None,
+            None => Some(self.v_offset),
+        };
+        self.selection = None;
+    }
+
+    pub fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    pub fn toggle_selection(&mut self) {
+        if self.selection.is_some() {
+            self.end_selection();
+            return;
+        }
+        let cursor_enabled = self.cursor.is_none();
+        let anchor = *self.cursor.get_or_insert(self.v_offset);
+        self.selection = Some(Selection {
+            anchor,
+            cursor_enabled,
+        });
+    }
+
+    pub fn end_selection(&mut self) {
+        if let Some(selection) = self.selection.take() {
+            if selection.cursor_enabled {
+                self.cursor = None;
+            }
+        }
+    }
+
+    // Returns the range of the selected lines (inclusive)
+    pub fn selected_range(&self) -> Option<(usize, usize)> {
+        let anchor = self.selection.as_ref()?.anchor;
+        let cursor = self.cursor?;
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
     pub fn current_options(&self) -> ScrollLinesOptions {
         self.options
     }
@@ -123,6 +172,7 @@ impl ScrollLinesState {
 struct ScrollLinesColor {
     block: Color,
     line_number: Color,
+    cursor_bg: Color,
 }
 
 impl ScrollLinesColor {
@@ -130,6 +180,7 @@ impl ScrollLinesColor {
         Self {
             block: theme.fg,
             line_number: theme.line_number_fg,
+            cursor_bg: theme.selected_axis_bg,
         }
     }
 }
@@ -138,6 +189,8 @@ impl ScrollLinesColor {
 #[derive(Debug, Default)]
 pub struct ScrollLines {
     block: Option<Block<'static>>,
+    // pinned above the lines, not affected by scrolling
+    header: Option<Line<'static>>,
     color: ScrollLinesColor,
 }
 
@@ -147,6 +200,11 @@ impl ScrollLines {
         self
     }
 
+    pub fn header(mut self, header: Line<'static>) -> Self {
+        self.header = Some(header);
+        self
+    }
+
     pub fn theme(mut self, theme: &ColorTheme) -> Self {
         self.color = ScrollLinesColor::new(theme);
         self
@@ -157,7 +215,13 @@ impl StatefulWidget for ScrollLines {
     type State = ScrollLinesState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let content_area = self.block.inner_if_some(area);
+        let mut content_area = self.block.inner_if_some(area);
+        let header_area = self.header.as_ref().map(|_| {
+            let [header_area, lines_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+            content_area = lines_area;
+            header_area
+        });
 
         let line_numbers_width = if state.options.number {
             state.max_digits as u16 + 1
@@ -181,14 +245,68 @@ impl StatefulWidget for ScrollLines {
             show_lines_count,
             self.color.line_number,
         );
-        let lines_paragraph = build_lines_paragraph(state, show_lines_count, self.color.block);
+        let lines_paragraph = build_lines_paragraph(
+            state,
+            show_lines_count,
+            self.color.block,
+            self.color.cursor_bg,
+        );
 
-        self.block.map(|b| b.fg(self.color.block)).render(area, buf);
+        let lines_count = state.lines.len();
+        let scrollable = lines_count > show_lines_count;
+        let block = self.block.map(|b| {
+            let b = b.fg(self.color.block);
+            if scrollable {
+                let indicator = position_indicator(state, show_lines_count);
+                b.title_bottom(Line::from(format!(" {indicator} ")).right_aligned())
+            } else {
+                b
+            }
+        });
+
+        block.render(area, buf);
+        if let (Some(header), Some(header_area)) = (self.header, header_area) {
+            let header_paragraph = Paragraph::new(header).block(
+                Block::default()
+                    .padding(Padding::horizontal(1))
+                    .fg(self.color.block),
+            );
+            header_paragraph.render(header_area, buf);
+        }
         line_numbers_paragraph.render(chunks[0], buf);
         lines_paragraph.render(chunks[1], buf);
+
+        if scrollable {
+            // render on the right padding of the text area
+            let scrollbar_area = Rect::new(
+                chunks[1].right().saturating_sub(1),
+                chunks[1].top(),
+                1,
+                chunks[1].height,
+            );
+            let offset = state.v_offset.min(lines_count - show_lines_count);
+            let scroll_bar = ScrollBar::new(lines_count, offset).color(self.color.block);
+            Widget::render(scroll_bar, scrollbar_area, buf);
+        }
     }
 }
 
+fn position_indicator(state: &ScrollLinesState, show_lines_count: usize) -> String {
+    let total = state.lines.len();
+    let current = state.cursor.unwrap_or(state.v_offset) + 1;
+    let max_offset = total.saturating_sub(show_lines_count);
+    let percent = match state.cursor {
+        Some(cursor) => cursor * 100 / total.saturating_sub(1).max(1),
+        None if max_offset == 0 => 100,
+        None => state.v_offset.min(max_offset) * 100 / max_offset,
+    };
+    format!(
+        "{}/{} · {percent}%",
+        format_thousands(current),
+        format_thousands(total)
+    )
+}
+
 fn build_line_numbers_paragraph(
     state: &ScrollLinesState,
     text_area_width: usize,
@@ -230,13 +348,24 @@ fn build_lines_paragraph(
     state: &ScrollLinesState,
     show_lines_count: usize,
     block_color: Color,
+    cursor_bg_color: Color,
 ) -> Paragraph<'_> {
     let lines_content: Vec<Line> = state
         .lines
         .iter()
+        .enumerate()
         .skip(state.v_offset)
         .take(show_lines_count)
-        .cloned()
+        .map(|(i, line)| {
+            let selected = state
+                .selected_range()
+                .is_some_and(|(start, end)| start <= i && i <= end);
+            if selected || state.cursor == Some(i) {
+                line.clone().bg(cursor_bg_color)
+            } else {
+                line.clone()
+            }
+        })
         .collect();
 
     let lines_paragraph = Paragraph::new(lines_content).block(
@@ -254,6 +383,10 @@ fn build_lines_paragraph(
 }
 
 fn handle_scroll_events(state: &mut ScrollLinesState, width: usize, height: usize) {
+    if let Some(cursor) = state.cursor {
+        handle_cursor_events(state, cursor, height);
+        return;
+    }
     match state.scroll_event {
         ScrollEvent::None => {}
         ScrollEvent::Forward => {
@@ -341,6 +474,37 @@ fn handle_scroll_events(state: &mut ScrollLinesState, width: usize, height: usiz
     state.scroll_event = ScrollEvent::None;
 }
 
+fn handle_cursor_events(state: &mut ScrollLinesState, cursor: usize, height: usize) {
+    let last = state.lines.len().saturating_sub(1);
+    let cursor = match state.scroll_event {
+        ScrollEvent::None => cursor,
+        ScrollEvent::Forward => (cursor + 1).min(last),
+        ScrollEvent::Backward => cursor.saturating_sub(1),
+        ScrollEvent::PageForward => (cursor + height).min(last),
+        ScrollEvent::PageBackward => cursor.saturating_sub(height),
+        ScrollEvent::Top => 0,
+        ScrollEvent::End => last,
+        ScrollEvent::Right => {
+            if !state.options.wrap && state.h_offset < state.max_line_width.saturating_sub(1) {
+                state.h_offset = state.h_offset.saturating_add(1);
+            }
+            cursor
+        }
+        ScrollEvent::Left => {
+            state.h_offset = state.h_offset.saturating_sub(1);
+            cursor
+        }
+    };
+    // keep the cursor line visible (may not be exact if lines are wrapped)
+    if cursor < state.v_offset {
+        state.v_offset = cursor;
+    } else if cursor >= state.v_offset + height {
+        state.v_offset = cursor + 1 - height;
+    }
+    state.cursor = Some(cursor);
+    state.scroll_event = ScrollEvent::None;
+}
+
 fn wrapped_line_width_iter<'a>(
     lines: &'a [Line],
     offset: usize,
@@ -401,3 +565,51 @@ pub fn digits(n: usize) -> usize {
     }
     c
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, None, "1/200 · 0%")]
+    #[case(90, None, "91/200 · 50%")]
+    #[case(199, None, "200/200 · 100%")]
+    #[case(0, Some(199), "200/200 · 100%")]
+    fn test_position_indicator(
+        #[case] v_offset: usize,
+        #[case] cursor: Option<usize>,
+        #[case] expected: &str,
+    ) {
+        let lines = (0..200).map(|i| Line::raw(i.to_string())).collect();
+        let mut state = ScrollLinesState::new(lines, ScrollLinesOptions::default());
+        state.v_offset = v_offset;
+        state.cursor = cursor;
+        assert_eq!(position_indicator(&state, 20), expected);
+    }
+
+    #[test]
+    fn test_selection() {
+        let lines = (0..10).map(|i| Line::raw(i.to_string())).collect();
+        let mut state = ScrollLinesState::new(lines, ScrollLinesOptions::default());
+        state.v_offset = 5;
+        assert_eq!(state.selected_range(), None);
+
+        state.toggle_selection();
+        assert_eq!(state.cursor(), Some(5));
+        assert_eq!(state.selected_range(), Some((5, 5)));
+
+        state.cursor = Some(2);
+        assert_eq!(state.selected_range(), Some((2, 5)));
+
+        state.toggle_selection();
+        assert_eq!(state.selected_range(), None);
+        assert_eq!(state.cursor(), None);
+
+        state.toggle_cursor();
+        state.toggle_selection();
+        state.end_selection();
+        assert_eq!(state.cursor(), Some(5));
+    }
+}
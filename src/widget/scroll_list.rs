@@ -2,6 +2,7 @@ use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Margin, Rect},
     style::{Color, Style, Stylize},
+    text::Line,
     widgets::{Block, List, ListItem, Padding, StatefulWidget, Widget},
 };
 
@@ -158,6 +159,7 @@ impl ScrollListColor {
 pub struct ScrollList<'a> {
     items: Vec<ListItem<'a>>,
     title: Option<String>,
+    footer: Option<String>,
     color: ScrollListColor,
     focused: bool,
 }
@@ -167,11 +169,22 @@ impl ScrollList<'_> {
         ScrollList {
             items,
             title: None,
+            footer: None,
             color: Default::default(),
             focused: false,
         }
     }
 
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn footer(mut self, footer: impl Into<String>) -> Self {
+        self.footer = Some(footer.into());
+        self
+    }
+
     pub fn theme(mut self, theme: &ColorTheme) -> Self {
         self.color = ScrollListColor::new(theme);
         self
@@ -196,6 +209,9 @@ impl StatefulWidget for ScrollList<'_> {
         if let Some(title) = self.title {
             block = block.title(title).title_alignment(Alignment::Left);
         }
+        if let Some(footer) = self.footer {
+            block = block.title_bottom(Line::from(footer).left_aligned());
+        }
         if !self.focused {
             block = block.border_style(Style::default().fg(self.color.disabled_fg));
         }
@@ -216,3 +232,65 @@ impl StatefulWidget for ScrollList<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Next,
+        Prev,
+        NextPage,
+        PrevPage,
+        First,
+        Last,
+        Index(usize),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            Just(Op::Next),
+            Just(Op::Prev),
+            Just(Op::NextPage),
+            Just(Op::PrevPage),
+            Just(Op::First),
+            Just(Op::Last),
+            (0..60usize).prop_map(Op::Index),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_navigation_invariants(
+            total in 0..50usize,
+            height in 1..20usize,
+            ops in prop::collection::vec(op(), 0..100),
+        ) {
+            let mut state = ScrollListState::new(total);
+            state.height = height;
+
+            for op in ops {
+                match op {
+                    Op::Next => state.select_next(),
+                    Op::Prev => state.select_prev(),
+                    Op::NextPage => state.select_next_page(),
+                    Op::PrevPage => state.select_prev_page(),
+                    Op::First => state.select_first(),
+                    Op::Last => state.select_last(),
+                    Op::Index(i) => state.select_index(i),
+                }
+
+                if total == 0 {
+                    prop_assert_eq!((state.selected, state.offset), (0, 0));
+                } else {
+                    prop_assert!(state.selected < total);
+                    prop_assert!(state.offset <= state.selected);
+                    prop_assert!(state.selected < state.offset + height);
+                }
+            }
+        }
+    }
+}
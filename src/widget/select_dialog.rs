@@ -0,0 +1,171 @@
+use laurier::layout::calc_centered_area;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, ListItem, Padding, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::{
+    color::ColorTheme,
+    widget::{ScrollList, ScrollListState},
+};
+
+#[derive(Debug)]
+pub struct SelectDialogState {
+    title: String,
+    message: Vec<String>,
+    labels: Vec<String>,
+    list_state: ScrollListState,
+}
+
+impl SelectDialogState {
+    pub fn new(title: impl Into<String>, labels: Vec<String>) -> SelectDialogState {
+        let list_state = ScrollListState::new(labels.len());
+        SelectDialogState {
+            title: title.into(),
+            message: Vec::new(),
+            labels,
+            list_state,
+        }
+    }
+
+    // lines shown above the options, e.g. details of an error
+    pub fn with_message(mut self, message: Vec<String>) -> SelectDialogState {
+        self.message = message;
+        self
+    }
+
+    pub fn select_next(&mut self) {
+        self.list_state.select_next();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.list_state.select_prev();
+    }
+
+    pub fn selected(&self) -> usize {
+        self.list_state.selected
+    }
+}
+
+#[derive(Default)]
+pub struct SelectDialog {
+    theme: ColorTheme,
+}
+
+impl SelectDialog {
+    pub fn theme(mut self, theme: ColorTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl StatefulWidget for SelectDialog {
+    type State = SelectDialogState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let max_label_width = state
+            .labels
+            .iter()
+            .map(|l| console::measure_text_width(l))
+            .max()
+            .unwrap_or_default()
+            .max(console::measure_text_width(&state.title));
+        let max_message_width = state
+            .message
+            .iter()
+            .map(|l| console::measure_text_width(l))
+            .max()
+            .unwrap_or_default();
+        let max_label_width = max_label_width.max(max_message_width);
+        let message_height = if state.message.is_empty() {
+            0
+        } else {
+            state.message.len() as u16 + 1 // blank line between the message and the options
+        };
+        let w = (max_label_width as u16 + 6).min(area.width.saturating_sub(4)); // border + padding
+        let h = (message_height + state.labels.len() as u16 + 2).min(area.height.saturating_sub(4)); // border
+        let dialog_area = calc_centered_area(area, w, h);
+
+        if message_height > 0 {
+            self.render_with_message(dialog_area, buf, state, message_height);
+            return;
+        }
+
+        let show_items_count = (dialog_area.height as usize).saturating_sub(2); // border
+        let item_width = (dialog_area.width as usize).saturating_sub(6); // border + padding
+        let items: Vec<ListItem> = state
+            .labels
+            .iter()
+            .enumerate()
+            .skip(state.list_state.offset)
+            .take(show_items_count)
+            .map(|(i, label)| {
+                let label = console::truncate_str(label, item_width, "..");
+                let line = Line::raw(format!(" {label:item_width$} "));
+                let mut style = Style::default();
+                if i == state.list_state.selected {
+                    style = style.fg(self.theme.selected_fg).bg(self.theme.selected_bg);
+                }
+                ListItem::new(line).style(style)
+            })
+            .collect();
+
+        let list = ScrollList::new(items)
+            .title(format!(" {} ", state.title))
+            .theme(&self.theme)
+            .focused(true);
+
+        Clear.render(dialog_area, buf);
+        list.render(dialog_area, buf, &mut state.list_state);
+    }
+}
+
+impl SelectDialog {
+    fn render_with_message(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut SelectDialogState,
+        message_height: u16,
+    ) {
+        let block = Block::bordered()
+            .title(format!(" {} ", state.title))
+            .padding(Padding::horizontal(1))
+            .bg(self.theme.bg)
+            .fg(self.theme.fg);
+        let inner_area = block.inner(area);
+        let [message_area, labels_area] =
+            Layout::vertical([Constraint::Length(message_height), Constraint::Min(0)])
+                .areas(inner_area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let message: Vec<Line> = state.message.iter().map(Line::raw).collect();
+        Paragraph::new(message).render(message_area, buf);
+
+        // the options are expected to be few, so they are not scrolled
+        let item_width = labels_area.width as usize;
+        let lines: Vec<Line> = state
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let label = console::truncate_str(label, item_width.saturating_sub(2), "..");
+                let mut line = Line::raw(format!(" {label:w$} ", w = item_width.saturating_sub(2)));
+                if i == state.list_state.selected {
+                    line = line.style(
+                        Style::default()
+                            .fg(self.theme.selected_fg)
+                            .bg(self.theme.selected_bg),
+                    );
+                }
+                line
+            })
+            .collect();
+        Paragraph::new(lines).render(labels_area, buf);
+    }
+}
@@ -1,7 +1,7 @@
 use laurier::highlight::highlight_matched_text;
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Flex, Rect},
+    layout::{Constraint, Flex, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Cell, Row, StatefulWidget, Table as RatatuiTable, TableState as RatatuiTableState},
@@ -21,6 +21,8 @@ pub struct TableState {
     width: usize,
     height: usize,
     col_widths: Vec<usize>,
+    // x positions of the visible columns as actually laid out in the last render
+    rendered_col_xs: Vec<u16>,
 
     ratatui_table_state: RatatuiTableState,
 }
@@ -41,6 +43,7 @@ impl TableState {
             width: 0,
             height: 0,
             col_widths,
+            rendered_col_xs: Vec::new(),
 
             ratatui_table_state,
         }
@@ -57,6 +60,7 @@ impl TableState {
             width: self.width,
             height: self.height,
             col_widths: self.col_widths.clone(),
+            rendered_col_xs: self.rendered_col_xs.clone(),
 
             ratatui_table_state: self.ratatui_table_state.with_selected(Some(0)),
         }
@@ -153,10 +157,9 @@ impl TableState {
             return;
         }
         if let Some(selected_col) = self.selected_col {
-            let updated_selected_col = selected_col + 1;
-            if selected_col < self.total_cols - 1 {
-                self.selected_col = Some(updated_selected_col);
-            }
+            // on the last column, only scroll until the column itself is the first visible one
+            let updated_selected_col = (selected_col + 1).min(self.total_cols - 1);
+            self.selected_col = Some(updated_selected_col);
             loop {
                 if updated_selected_col == self.offset_col {
                     break;
@@ -217,7 +220,8 @@ impl TableState {
             }
             count += 1;
         }
-        self.offset_col = self.total_cols - count;
+        // keep the last column visible even if it is wider than the table
+        self.offset_col = self.total_cols - count.max(1);
     }
 
     pub fn scroll_down(&mut self) {
@@ -272,15 +276,18 @@ impl TableState {
 
     pub fn selected_item_position(&self) -> Option<(u16, u16)> {
         self.selected_col.map(|col| {
-            let x = self
-                .col_widths
-                .iter()
-                .skip(self.offset_col)
-                .take(col - self.offset_col)
-                .map(|w| *w + 1)
-                .sum::<usize>();
+            let x = match self.rendered_col_xs.get(col - self.offset_col) {
+                Some(x) => *x,
+                None => self
+                    .col_widths
+                    .iter()
+                    .skip(self.offset_col)
+                    .take(col - self.offset_col)
+                    .map(|w| *w as u16 + 1)
+                    .sum(),
+            };
             let y = self.selected_row - self.offset_row;
-            (x as u16, y as u16)
+            (x, y as u16)
         })
     }
 
@@ -393,12 +400,23 @@ impl StatefulWidget for Table<'_> {
                         }),
                 )
             });
-        let widths = state
+        let widths: Vec<Constraint> = state
             .col_widths
             .iter()
             .skip(state.offset_col)
             .take(count)
-            .map(|w| Constraint::Length(*w as u16));
+            .map(|w| Constraint::Length(*w as u16))
+            .collect();
+
+        // compute the layout in the same way as ratatui table to know where each column is rendered
+        let row_area = Rect::new(0, 0, area.width, 1);
+        state.rendered_col_xs = Layout::horizontal(widths.clone())
+            .flex(Flex::Legacy)
+            .spacing(1)
+            .split(row_area)
+            .iter()
+            .map(|r| r.x)
+            .collect();
         let header_row = Row::new(
             self.header_row_cells
                 .iter()
@@ -477,27 +495,31 @@ fn matched_indices(query: &str, plain: &str) -> Vec<usize> {
         }
         let lower_query = q.to_lowercase();
         let l = lower_query.len();
-        if let Some(i) = lower_plain.find(&lower_query) {
-            for idx in i..i + l {
-                indices.push(idx);
-            }
+        // highlight all occurrences, not only the first one
+        for (i, _) in lower_plain.match_indices(&lower_query) {
+            indices.extend(i..i + l);
         }
     }
+    indices.sort_unstable();
+    indices.dedup();
     indices
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use rstest::*;
 
     use super::*;
 
     #[rstest]
     #[case("", vec![])]
-    #[case("o", vec![4])]
+    #[case("o", vec![4, 7])]
+    #[case("L", vec![2, 3, 9])]
     #[case("lo", vec![3, 4])]
     #[case("WOR", vec![6, 7, 8])]
     #[case("el|or", vec![1, 2, 7, 8])]
+    #[case("ll|lo", vec![2, 3, 4])]
     #[case("ll|", vec![2, 3])]
     #[case("|rl", vec![8, 9])]
     fn test_matched_indices(#[case] query: &str, #[case] expected: Vec<usize>) {
@@ -505,4 +527,118 @@ mod tests {
         let actual = matched_indices(query, plain);
         assert_eq!(actual, expected);
     }
+
+    #[derive(Debug, Clone)]
+    enum RowOp {
+        Next,
+        Prev,
+        NextPage,
+        PrevPage,
+        First,
+        Last,
+        ScrollDown,
+        ScrollUp,
+        Index(usize, usize),
+    }
+
+    fn row_op() -> impl Strategy<Value = RowOp> {
+        prop_oneof![
+            Just(RowOp::Next),
+            Just(RowOp::Prev),
+            Just(RowOp::NextPage),
+            Just(RowOp::PrevPage),
+            Just(RowOp::First),
+            Just(RowOp::Last),
+            Just(RowOp::ScrollDown),
+            Just(RowOp::ScrollUp),
+            (0..60usize, 0..20usize).prop_map(|(i, o)| RowOp::Index(i, o)),
+        ]
+    }
+
+    #[derive(Debug, Clone)]
+    enum ColOp {
+        Next,
+        Prev,
+        First,
+        Last,
+        Widen,
+        Narrow,
+    }
+
+    fn col_op() -> impl Strategy<Value = ColOp> {
+        prop_oneof![
+            Just(ColOp::Next),
+            Just(ColOp::Prev),
+            Just(ColOp::First),
+            Just(ColOp::Last),
+            Just(ColOp::Widen),
+            Just(ColOp::Narrow),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_row_navigation_invariants(
+            total_rows in 0..50usize,
+            height in 1..20usize,
+            ops in prop::collection::vec(row_op(), 0..100),
+        ) {
+            let mut state = TableState::new(total_rows, 1, vec![10]);
+            state.height = height;
+
+            for op in ops {
+                match op {
+                    RowOp::Next => state.select_next_row(),
+                    RowOp::Prev => state.select_prev_row(),
+                    RowOp::NextPage => state.select_next_row_page(),
+                    RowOp::PrevPage => state.select_prev_row_page(),
+                    RowOp::First => state.select_first_row(),
+                    RowOp::Last => state.select_last_row(),
+                    RowOp::ScrollDown => state.scroll_down(),
+                    RowOp::ScrollUp => state.scroll_up(),
+                    // the offset index is always taken from a rendered position
+                    RowOp::Index(i, o) => state.select_index(i, o % height),
+                }
+
+                if total_rows == 0 {
+                    prop_assert_eq!((state.selected_row, state.offset_row), (0, 0));
+                } else {
+                    prop_assert!(state.selected_row < total_rows);
+                    prop_assert!(state.offset_row <= state.selected_row);
+                    prop_assert!(state.selected_row < state.offset_row + height);
+                }
+                state.update_table_state();
+            }
+        }
+
+        #[test]
+        fn test_col_navigation_invariants(
+            col_widths in prop::collection::vec(1..30usize, 0..20),
+            width in 1..60usize,
+            ops in prop::collection::vec(col_op(), 0..100),
+        ) {
+            let total_cols = col_widths.len();
+            let mut state = TableState::new(1, total_cols, col_widths);
+            state.width = width;
+            state.height = 1;
+
+            for op in ops {
+                match op {
+                    ColOp::Next => state.select_next_col(),
+                    ColOp::Prev => state.select_prev_col(),
+                    ColOp::First => state.select_first_col(),
+                    ColOp::Last => state.select_last_col(),
+                    ColOp::Widen => state.widen_col(),
+                    ColOp::Narrow => state.narrow_col(),
+                }
+
+                if let Some(col) = state.selected_col {
+                    prop_assert!(col < total_cols);
+                    prop_assert!(state.offset_col <= col);
+                    prop_assert!(state.selected_item_position().is_some());
+                }
+                state.update_table_state();
+            }
+        }
+    }
 }